@@ -1,4 +1,6 @@
 use crate::completions::CompletionOptions;
+use crate::man::ManOptions;
+use crate::output_format::OutputFormat;
 use clap::Parser;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -14,14 +16,29 @@ pub enum Command {
         count: u32,
     },
     /// Describes the current database state, the synced schema version, collections, document couts, etc.
-    Describe,
+    Describe {
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "table")]
+        output: OutputFormat,
+    },
     /// Describes a database collection current indexes.
     DescribeIndexes {
         /// Database collection name
         collection_name: String,
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "table")]
+        output: OutputFormat,
     },
     /// Loads migrations and check their format.
     Check,
+    /// Applies the migrations up, down and up again on a temporary database to check both directions work.
+    Verify {
+        /// Uses this empty database instead of a temporary one, which is kept afterwards
+        #[clap(long)]
+        scratch_database: Option<String>,
+    },
+    /// Removes the migration lock left by an interrupted command.
+    Unlock,
     /// Truncates the database, removes all collections, graphs, indexes and documents.
     Truncate,
     /// Generates and apply a migration for collections, indexes and graphs missing from the schema.
@@ -33,6 +50,8 @@ pub enum Command {
     },
     /// Generates tab-completion script for your shell
     Completions(CompletionOptions),
+    /// Generates the man page, or the markdown help
+    Man(ManOptions),
 }
 
 #[derive(Debug, Parser)]
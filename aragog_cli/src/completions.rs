@@ -1,13 +1,22 @@
 use crate::app::AragogCliApp;
+use crate::config::Config;
+use crate::migration_manager::SCHEMA_NAME;
+use aragog::schema::DatabaseSchema;
 use clap::{ArgEnum, Command, IntoApp, Parser};
 use clap_complete::Shell::{Bash, Elvish, Fish, PowerShell, Zsh};
 use clap_complete::{generate, Generator};
 
+/// The subcommand arguments completed with the schema collection names
+const COLLECTION_ARGS: &[(&str, &str)] = &[("describe-indexes", "collection-name")];
+
 #[derive(Parser, Debug)]
 pub struct CompletionOptions {
     /// target shell type
     #[clap(arg_enum, default_value = "bash")]
     pub shell_type: ShellType,
+    /// Doesn't read the schema to complete the collection names
+    #[clap(long)]
+    pub no_schema: bool,
 }
 #[derive(Parser, ArgEnum, Debug, Copy, Clone)]
 pub enum ShellType {
@@ -24,9 +33,47 @@ fn print_completions<G: Generator>(generator: G, command: &mut Command) {
     generate(generator, command, name, &mut std::io::stdout());
 }
 
+/// Loads the collection names of the schema, empty if the schema can't be read.
+///
+/// Nothing is logged as the completion script is printed on the standard output
+fn schema_collection_names(opts: &AragogCliApp) -> Vec<String> {
+    let path = format!("{}/{}", Config::schema_path(opts), SCHEMA_NAME);
+    DatabaseSchema::load(&path)
+        .map(|schema| {
+            let mut names: Vec<String> = schema
+                .collections
+                .into_iter()
+                .map(|collection| collection.name)
+                .collect();
+            names.sort();
+            names
+        })
+        .unwrap_or_default()
+}
+
+/// Adds the collection names as possible values of the collection arguments
+fn with_collection_names<'a>(mut command: Command<'a>, names: &'a [String]) -> Command<'a> {
+    if names.is_empty() {
+        return command;
+    }
+    let values: Vec<&str> = names.iter().map(String::as_str).collect();
+    for &(subcommand, arg) in COLLECTION_ARGS {
+        let values = values.clone();
+        command = command.mut_subcommand(subcommand, |subcommand| {
+            subcommand.mut_arg(arg, |arg| arg.possible_values(values))
+        });
+    }
+    command
+}
+
 impl CompletionOptions {
-    pub fn generate(&self) {
-        let mut command = AragogCliApp::command();
+    pub fn generate(&self, opts: &AragogCliApp) {
+        let names = if self.no_schema {
+            Vec::new()
+        } else {
+            schema_collection_names(opts)
+        };
+        let mut command = with_collection_names(AragogCliApp::command(), &names);
         match self.shell_type {
             ShellType::Bash => print_completions(Bash, &mut command),
             ShellType::Elvish => print_completions(Elvish, &mut command),
@@ -36,3 +83,32 @@ impl CompletionOptions {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_names_are_completed() {
+        let names = vec![String::from("Dish"), String::from("User")];
+        let mut command = with_collection_names(AragogCliApp::command(), &names);
+        command.build();
+        let subcommand = command
+            .get_subcommands()
+            .find(|subcommand| subcommand.get_name() == "describe-indexes")
+            .unwrap();
+        let arg = subcommand
+            .get_arguments()
+            .filter(|arg| arg.is_positional())
+            .find(|arg| arg.get_id() == "collection-name")
+            .unwrap();
+        let values: Vec<&str> = arg
+            .get_possible_values()
+            .unwrap()
+            .iter()
+            .map(|value| value.get_name())
+            .collect();
+        assert_eq!(values, ["Dish", "User"]);
+        assert_eq!(subcommand.get_positionals().count(), 1);
+    }
+}
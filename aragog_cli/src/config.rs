@@ -5,9 +5,7 @@ use std::fmt::{self, Display, Formatter};
 
 static mut LOG_LEVEL: LogLevel = LogLevel::Info;
 
-const ARAGOG_DEFAULT_COLLECTION: &str = "AragogConfiguration";
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Config {
     pub schema_collection_name: String,
     pub schema_path: String,
@@ -36,13 +34,8 @@ impl Config {
             schema_collection_name: opts
                 .schema_collection_name
                 .clone()
-                .unwrap_or_else(|| ARAGOG_DEFAULT_COLLECTION.to_string()),
-            schema_path: {
-                match Self::load_str(&opts.schema_path, "schema_path", "SCHEMA_PATH", "path") {
-                    Ok(val) => val,
-                    Err(_err) => String::from(aragog::schema::SCHEMA_DEFAULT_PATH),
-                }
-            },
+                .unwrap_or_else(|| aragog::schema::SCHEMA_DEFAULT_COLLECTION_NAME.to_string()),
+            schema_path: Self::schema_path(opts),
             db_host: Self::load_str(&opts.db_host, "db_host", "DB_HOST", "db-host")?,
             db_name: Self::load_str(&opts.db_name, "db_name", "DB_NAME", "db-name")?,
             db_user: Self::load_str(&opts.db_user, "db_user", "DB_USER", "db-user")?,
@@ -52,6 +45,14 @@ impl Config {
         Ok(res)
     }
 
+    /// The schema and migrations folder path, which doesn't require the database options
+    pub fn schema_path(opts: &AragogCliApp) -> String {
+        match Self::load_str(&opts.schema_path, "schema_path", "SCHEMA_PATH", "path") {
+            Ok(val) => val,
+            Err(_err) => String::from(aragog::schema::SCHEMA_DEFAULT_PATH),
+        }
+    }
+
     pub fn load_str(
         value: &Option<String>,
         value_name: &str,
@@ -1,17 +1,72 @@
 use arangors_lite::collection::response::Properties;
+use arangors_lite::index::Index;
+use serde::Serialize;
 
 use crate::config::Config;
 use crate::error::AragogCliError;
+use crate::migration::MigrationVersion;
+use crate::output_format::OutputFormat;
 use crate::versioned_database::VersionedDatabase;
 
-pub fn describe_db(config: &Config) -> Result<(), AragogCliError> {
+#[derive(Debug, Serialize)]
+struct DatabaseDescription {
+    name: String,
+    schema_version: Option<MigrationVersion>,
+    graph_count: usize,
+    collections: Vec<CollectionDescription>,
+}
+
+#[derive(Debug, Serialize)]
+struct CollectionDescription {
+    name: String,
+    collection_type: String,
+    document_count: u32,
+    index_count: usize,
+    wait_for_sync: bool,
+    in_schema: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct IndexesDescription {
+    database: String,
+    collection: String,
+    indexes: Vec<Index>,
+}
+
+pub fn describe_db(config: &Config, output: OutputFormat) -> Result<(), AragogCliError> {
     let db = VersionedDatabase::init(config)?;
-    println!("\nDescription of {}: \n", db.name());
-    match db.schema.version {
+    let mut collections = Vec::new();
+    for info in db.accessible_collections()?.iter() {
+        if info.is_system {
+            continue;
+        }
+        let collection = db.collection(&info.name)?;
+        let properties: Properties = collection.document_count()?;
+        collections.push(CollectionDescription {
+            name: info.name.clone(),
+            collection_type: format!("{:?}", &info.collection_type),
+            document_count: properties.info.count.unwrap_or(0),
+            index_count: db.indexes(&info.name)?.indexes.len(),
+            wait_for_sync: properties.detail.wait_for_sync,
+            in_schema: db.schema.collections.iter().any(|a| a.name == info.name),
+        });
+    }
+    let description = DatabaseDescription {
+        name: db.name().to_string(),
+        schema_version: db.schema.version,
+        graph_count: db.graphs()?.graphs.len(),
+        collections,
+    };
+    if let Some(serialized) = output.serialize(&description)? {
+        println!("{}", serialized);
+        return Ok(());
+    }
+    println!("\nDescription of {}: \n", description.name);
+    match description.schema_version {
         Some(version) => println!("- Database Schema version: {}", version),
         None => println!("- Database Schema is not versioned yet (use migrate)"),
     };
-    println!("- Database Graph count: {}", db.graphs()?.graphs.len());
+    println!("- Database Graph count: {}", description.graph_count);
     let mut table = table!([
         "Name",
         "Type",
@@ -20,21 +75,14 @@ pub fn describe_db(config: &Config) -> Result<(), AragogCliError> {
         "Wait for Sync",
         "In Schema"
     ]);
-    for info in db.accessible_collections()?.iter() {
-        if info.is_system {
-            continue;
-        }
-        let collection = db.collection(&info.name)?;
-        let properties: Properties = collection.document_count()?;
-        let synced = db.schema.collections.iter().any(|a| a.name == info.name);
-        let index_count = db.indexes(&info.name)?.indexes.len();
+    for collection in &description.collections {
         table.add_row(row![
-            &info.name,
-            format!("{:?}", &info.collection_type),
-            &properties.info.count.unwrap_or(0),
-            index_count,
-            &properties.detail.wait_for_sync,
-            synced
+            &collection.name,
+            &collection.collection_type,
+            &collection.document_count,
+            collection.index_count,
+            &collection.wait_for_sync,
+            collection.in_schema
         ]);
     }
     table.printstd();
@@ -44,16 +92,25 @@ pub fn describe_db(config: &Config) -> Result<(), AragogCliError> {
 pub fn describe_collection_indexes(
     config: &Config,
     collection_name: &str,
+    output: OutputFormat,
 ) -> Result<(), AragogCliError> {
     let db = VersionedDatabase::init(config)?;
     db.collection(collection_name)?;
+    let description = IndexesDescription {
+        database: db.name().to_string(),
+        collection: collection_name.to_string(),
+        indexes: db.indexes(collection_name)?.indexes,
+    };
+    if let Some(serialized) = output.serialize(&description)? {
+        println!("{}", serialized);
+        return Ok(());
+    }
     println!(
         "\nDescription of {} collection {} indexes: \n",
-        db.name(),
-        collection_name
+        description.database, description.collection
     );
     let mut table = table!(["Name", "id", "Fields", "Settings"]);
-    for index in db.indexes(collection_name)?.indexes.iter() {
+    for index in &description.indexes {
         table.add_row(row![
             index.name,
             index.id,
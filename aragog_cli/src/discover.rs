@@ -29,12 +29,22 @@ pub fn discover_migration(
                 up.push(MigrationOperation::CreateEdgeCollection {
                     name: name.clone(),
                     wait_for_sync: None,
+                    number_of_shards: None,
+                    shard_keys: None,
+                    replication_factor: None,
+                    write_concern: None,
+                    distribute_shards_like: None,
                 });
                 down.push(MigrationOperation::DeleteEdgeCollection { name: name.clone() });
             } else {
                 up.push(MigrationOperation::CreateCollection {
                     name: name.clone(),
                     wait_for_sync: None,
+                    number_of_shards: None,
+                    shard_keys: None,
+                    replication_factor: None,
+                    write_concern: None,
+                    distribute_shards_like: None,
                 });
                 down.push(MigrationOperation::DeleteCollection { name: name.clone() });
             }
@@ -67,7 +77,7 @@ pub fn discover_migration(
                 orphan_collections: Some(graph.orphan_collections),
                 is_smart: graph.is_smart,
                 is_disjoint: graph.is_disjoint,
-                options: graph.options,
+                options: graph.options.map(Into::into),
             });
             operations_down.push(MigrationOperation::DeleteGraph { name: graph.name })
         }
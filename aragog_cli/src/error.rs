@@ -13,10 +13,16 @@ pub enum AragogCliError {
     MissingEdgeCollection { name: String },
     #[error("Duplicate Edge Collection: {name}")]
     DuplicateEdgeCollection { name: String },
+    #[error("Collection {name} is used by graph {graph}")]
+    CollectionInGraph { name: String, graph: String },
     #[error("Missing Index: {name} on collection {collection}")]
     MissingIndex { name: String, collection: String },
     #[error("Duplicate Index: {name} on collection {collection}")]
     DuplicateIndex { name: String, collection: String },
+    #[error("Missing Computed Value: {name} on collection {collection}")]
+    MissingComputedValue { name: String, collection: String },
+    #[error("Duplicate Computed Value: {name} on collection {collection}")]
+    DuplicateComputedValue { name: String, collection: String },
     #[error("Missing Graph: {name}")]
     MissingGraph { name: String },
     #[error("Duplicate Graph: {name}")]
@@ -29,6 +35,10 @@ pub enum AragogCliError {
     ParsingError { message: String },
     #[error("Failed to initialize {item} ({message})")]
     InitError { item: String, message: String },
+    #[error("Migration verification failed: {message}")]
+    VerificationFailed { message: String },
+    #[error("Migrations are locked by {holder} until {expires_at}, use `aragog unlock` if it was abandoned")]
+    MigrationLocked { holder: String, expires_at: String },
     #[error("Arango Error: {0}")]
     ClientError(ClientError),
 }
@@ -60,6 +70,7 @@ impl AragogCliError {
         match self {
             Self::InvalidFileName { .. } | Self::ParsingError { .. } => exitcode::DATAERR,
             Self::IOError { .. } => exitcode::IOERR,
+            Self::MigrationLocked { .. } => exitcode::TEMPFAIL,
             _ => exitcode::USAGE,
         }
     }
@@ -0,0 +1,315 @@
+use arangors_lite::document::options::{InsertOptions, RemoveOptions, ReplaceOptions};
+use arangors_lite::document::response::DocumentResponse;
+use arangors_lite::{ClientError, Collection};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::error::AragogCliError;
+use crate::log;
+use crate::log_level::LogLevel;
+use crate::versioned_database::VersionedDatabase;
+
+/// Key of the lock document in the schema collection
+pub const LOCK_DOCUMENT_KEY: &str = "migration_lock";
+/// Duration in seconds after which a lock that wasn't renewed is considered abandoned and can be
+/// taken over
+pub const LOCK_TTL: i64 = 600;
+/// Duration in seconds between the renewals of a held lock
+pub const LOCK_RENEWAL_INTERVAL: u64 = 60;
+
+/// ArangoDB `unique constraint violated` error number
+const UNIQUE_CONSTRAINT_VIOLATED: u16 = 1210;
+/// ArangoDB `conflict` error number, returned on a revision mismatch
+const CONFLICT: u16 = 1200;
+/// ArangoDB `document not found` error number
+const DOCUMENT_NOT_FOUND: u16 = 1202;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockDocument {
+    pub _key: String,
+    /// User, host and process holding the lock
+    pub holder: String,
+    /// Acquisition timestamp, in seconds
+    pub acquired_at: i64,
+    /// Expiration timestamp, in seconds
+    pub expires_at: i64,
+}
+
+impl LockDocument {
+    fn new(now: i64) -> Self {
+        Self {
+            _key: LOCK_DOCUMENT_KEY.to_string(),
+            holder: holder(),
+            acquired_at: now,
+            expires_at: now + LOCK_TTL,
+        }
+    }
+
+    const fn is_expired(&self, now: i64) -> bool {
+        self.expires_at <= now
+    }
+
+    /// The lock extended for [`LOCK_TTL`] seconds from `now`
+    fn renewed(&self, now: i64) -> Self {
+        Self {
+            expires_at: now + LOCK_TTL,
+            ..self.clone()
+        }
+    }
+
+    fn locked_error(&self) -> AragogCliError {
+        AragogCliError::MigrationLocked {
+            holder: self.holder.clone(),
+            expires_at: format_timestamp(self.expires_at),
+        }
+    }
+}
+
+/// Identifies the current process as `user@host (pid ...)`
+fn holder() -> String {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| String::from("unknown"));
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| String::from("unknown"));
+    format!("{}@{} (pid {})", user, host, std::process::id())
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map_or_else(|| timestamp.to_string(), |date| date.to_rfc3339())
+}
+
+const fn is_arango_error(error: &ClientError, error_num: u16) -> bool {
+    matches!(error, ClientError::Arango(error) if error.error_num() == error_num)
+}
+
+fn revision<T>(response: &DocumentResponse<T>) -> Option<String> {
+    match response {
+        DocumentResponse::Response { header, .. } => Some(header._rev.clone()),
+        DocumentResponse::Silent => None,
+    }
+}
+
+/// Migration lock held in the schema collection, preventing concurrent commands from
+/// interleaving their operations on the same database.
+///
+/// The lock is renewed every [`LOCK_RENEWAL_INTERVAL`] seconds until its release, so long
+/// migrations keep it while the lock of an interrupted command expires after [`LOCK_TTL`] seconds.
+#[derive(Debug)]
+pub struct MigrationLock {
+    collection: Collection,
+    /// The revision of the lock document, updated by the renewals
+    revision: Arc<Mutex<Option<String>>>,
+    /// Stops the renewal thread
+    heartbeat: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+impl MigrationLock {
+    /// Acquires the lock, taking over an expired one.
+    ///
+    /// Fails with [`AragogCliError::MigrationLocked`] if another process holds the lock
+    pub fn acquire(db: &VersionedDatabase) -> Result<Self, AragogCliError> {
+        let collection = db.schema_collection.clone();
+        let now = Utc::now().timestamp();
+        let lock = LockDocument::new(now);
+        log(
+            format!("Acquiring migration lock for {}", lock.holder),
+            LogLevel::Verbose,
+        );
+        let response = match collection.create_document(
+            lock.clone(),
+            InsertOptions::builder().wait_for_sync(true).build(),
+        ) {
+            Ok(response) => response,
+            Err(error) if is_arango_error(&error, UNIQUE_CONSTRAINT_VIOLATED) => {
+                let current = collection.document::<LockDocument>(LOCK_DOCUMENT_KEY)?;
+                if !current.document.is_expired(now) {
+                    return Err(current.document.locked_error());
+                }
+                log(
+                    format!(
+                        "Taking over the migration lock of {}, expired since {}",
+                        current.document.holder,
+                        format_timestamp(current.document.expires_at)
+                    ),
+                    LogLevel::Info,
+                );
+                match collection.replace_document(
+                    LOCK_DOCUMENT_KEY,
+                    lock.clone(),
+                    ReplaceOptions::builder().wait_for_sync(true).build(),
+                    Some(current.header._rev),
+                ) {
+                    Ok(response) => response,
+                    // Another process took over the lock first
+                    Err(error) if is_arango_error(&error, CONFLICT) => {
+                        let current = collection.document::<LockDocument>(LOCK_DOCUMENT_KEY)?;
+                        return Err(current.document.locked_error());
+                    }
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            Err(error) => return Err(error.into()),
+        };
+        let revision = Arc::new(Mutex::new(revision(&response)));
+        let (stop, stopped) = mpsc::channel();
+        let heartbeat = {
+            let collection = collection.clone();
+            let revision = Arc::clone(&revision);
+            std::thread::spawn(move || {
+                let interval = Duration::from_secs(LOCK_RENEWAL_INTERVAL);
+                while stopped.recv_timeout(interval) == Err(RecvTimeoutError::Timeout) {
+                    if !Self::renew(&collection, &lock, &revision) {
+                        break;
+                    }
+                }
+            })
+        };
+        Ok(Self {
+            collection,
+            revision,
+            heartbeat: Some((stop, heartbeat)),
+        })
+    }
+
+    /// Extends the `lock` expiration, returns `false` if it was taken over by another process
+    fn renew(
+        collection: &Collection,
+        lock: &LockDocument,
+        revision: &Mutex<Option<String>>,
+    ) -> bool {
+        let current_revision = revision.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let lock = lock.renewed(Utc::now().timestamp());
+        log(
+            format!(
+                "Renewing migration lock until {}",
+                format_timestamp(lock.expires_at)
+            ),
+            LogLevel::Verbose,
+        );
+        match collection.replace_document(
+            LOCK_DOCUMENT_KEY,
+            lock,
+            ReplaceOptions::builder().wait_for_sync(true).build(),
+            current_revision,
+        ) {
+            Ok(response) => {
+                *revision.lock().unwrap_or_else(|e| e.into_inner()) = self::revision(&response);
+                true
+            }
+            Err(error) if is_arango_error(&error, CONFLICT) => {
+                log(
+                    "The migration lock expired and was taken over by another process",
+                    LogLevel::Info,
+                );
+                false
+            }
+            // The renewal is retried on the next interval, before the lock expires
+            Err(error) => {
+                log(
+                    format!("Failed to renew the migration lock: {}", error),
+                    LogLevel::Info,
+                );
+                true
+            }
+        }
+    }
+
+    /// Releases the lock, unless it expired and was taken over by another process
+    pub fn release(mut self) -> Result<(), AragogCliError> {
+        log("Releasing migration lock", LogLevel::Verbose);
+        if let Some((stop, heartbeat)) = self.heartbeat.take() {
+            // The thread may have stopped already if the lock was taken over
+            let _ = stop.send(());
+            let _ = heartbeat.join();
+        }
+        let revision = self
+            .revision
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        match self.collection.remove_document::<LockDocument>(
+            LOCK_DOCUMENT_KEY,
+            RemoveOptions::builder().wait_for_sync(true).build(),
+            revision,
+        ) {
+            Ok(_) => Ok(()),
+            Err(error) if is_arango_error(&error, CONFLICT) => {
+                log(
+                    "The migration lock expired and was taken over by another process",
+                    LogLevel::Info,
+                );
+                Ok(())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Runs `operation` while holding the lock, releasing it even if `operation` fails
+    pub fn with_lock<T>(
+        db: &mut VersionedDatabase,
+        operation: impl FnOnce(&mut VersionedDatabase) -> Result<T, AragogCliError>,
+    ) -> Result<T, AragogCliError> {
+        let lock = Self::acquire(db)?;
+        let result = operation(db);
+        let released = lock.release();
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+
+    /// Forcefully removes the lock, whoever holds it.
+    ///
+    /// Returns the previous holder, if any
+    pub fn force_release(db: &VersionedDatabase) -> Result<Option<String>, AragogCliError> {
+        let current = match db
+            .schema_collection
+            .document::<LockDocument>(LOCK_DOCUMENT_KEY)
+        {
+            Ok(current) => current,
+            Err(error) if is_arango_error(&error, DOCUMENT_NOT_FOUND) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        db.schema_collection.remove_document::<LockDocument>(
+            LOCK_DOCUMENT_KEY,
+            RemoveOptions::builder().wait_for_sync(true).build(),
+            None,
+        )?;
+        Ok(Some(current.document.holder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_expires_after_ttl() {
+        let lock = LockDocument::new(1_000);
+        assert_eq!(lock._key, LOCK_DOCUMENT_KEY);
+        assert_eq!(lock.expires_at, 1_000 + LOCK_TTL);
+        assert!(!lock.is_expired(1_000));
+        assert!(!lock.is_expired(1_000 + LOCK_TTL - 1));
+        assert!(lock.is_expired(1_000 + LOCK_TTL));
+        assert!(lock
+            .holder
+            .contains(&format!("(pid {})", std::process::id())));
+    }
+
+    #[test]
+    fn renewal_extends_the_lock() {
+        let lock = LockDocument::new(1_000);
+        let renewed = lock.renewed(1_000 + LOCK_TTL - 1);
+        assert_eq!(renewed.holder, lock.holder);
+        assert_eq!(renewed.acquired_at, 1_000);
+        assert!(!renewed.is_expired(1_000 + LOCK_TTL));
+        assert!(renewed.is_expired(1_000 + 2 * LOCK_TTL - 1));
+        // The lock is renewed well before its expiration
+        assert!(LOCK_RENEWAL_INTERVAL * 3 <= LOCK_TTL as u64);
+    }
+}
@@ -15,9 +15,11 @@ use crate::config::Config;
 use crate::describe::{describe_collection_indexes, describe_db};
 use crate::discover::discover_migration;
 use crate::error::AragogCliError;
+use crate::lock::MigrationLock;
 use crate::log_level::LogLevel;
 use crate::migration::Migration;
 use crate::migration_manager::MigrationManager;
+use crate::verify::verify_migrations;
 use crate::versioned_database::VersionedDatabase;
 
 mod app;
@@ -26,11 +28,15 @@ mod config;
 mod describe;
 mod discover;
 mod error;
+mod lock;
 mod log_level;
+mod man;
 mod migration;
 mod migration_data;
 mod migration_manager;
 mod migration_operation;
+mod output_format;
+mod verify;
 mod versioned_database;
 
 #[derive(Debug)]
@@ -55,6 +61,36 @@ fn migrate(
     Ok(())
 }
 
+fn discover(
+    db: &mut VersionedDatabase,
+    config: &Config,
+    manager: &MigrationManager,
+) -> Result<(), AragogCliError> {
+    let migration = discover_migration(db, config)?;
+    if migration.data.is_empty() {
+        log(
+            "Your schema and database are synchronized, no discovery required",
+            LogLevel::Info,
+        );
+        return Ok(());
+    }
+    log(
+        format!("Created discover migration {}", migration.path),
+        LogLevel::Info,
+    );
+    migration.apply_up(db, true)?;
+    db.save()?;
+    MigrationManager::write_schema(&db.schema, &manager.schema_file_path)?;
+    log(
+        format!(
+            "Applied discover migration to schema, new version: {}",
+            db.schema.version.unwrap()
+        ),
+        LogLevel::Info,
+    );
+    Ok(())
+}
+
 fn handle_commands() -> Result<(), AragogCliError> {
     let opts: AragogCliApp = AragogCliApp::parse();
 
@@ -63,19 +99,36 @@ fn handle_commands() -> Result<(), AragogCliError> {
             let config = Config::new(&opts)?;
             MigrationManager::new(&config.schema_path)?;
         }
+        Command::Verify { scratch_database } => {
+            let config = Config::new(&opts)?;
+            verify_migrations(&config, scratch_database.as_deref())?;
+        }
         Command::Migrate => {
             let config = Config::new(&opts)?;
             let schema_path = config.schema_path.clone();
             let manager = MigrationManager::new(&schema_path)?;
             let mut db = VersionedDatabase::init(&config)?;
-            migrate(MigrationDirection::Up, &mut db, manager)?;
+            MigrationLock::with_lock(&mut db, |db| migrate(MigrationDirection::Up, db, manager))?;
         }
         Command::Rollback { count } => {
             let config = Config::new(&opts)?;
             let schema_path = config.schema_path.clone();
             let manager = MigrationManager::new(&schema_path)?;
             let mut db = VersionedDatabase::init(&config)?;
-            migrate(MigrationDirection::Down(*count), &mut db, manager)?;
+            MigrationLock::with_lock(&mut db, |db| {
+                migrate(MigrationDirection::Down(*count), db, manager)
+            })?;
+        }
+        Command::Unlock => {
+            let config = Config::new(&opts)?;
+            let db = VersionedDatabase::init(&config)?;
+            match MigrationLock::force_release(&db)? {
+                Some(holder) => log(
+                    format!("Removed the migration lock held by {}", holder),
+                    LogLevel::Info,
+                ),
+                None => log("Migrations are not locked", LogLevel::Info),
+            }
         }
         Command::CreateMigration { migration_name } => {
             let config = Config::new(&opts)?;
@@ -105,39 +158,24 @@ fn handle_commands() -> Result<(), AragogCliError> {
             let schema_path = config.schema_path.clone();
             let mut db = VersionedDatabase::init(&config)?;
             let manager = MigrationManager::new(&schema_path)?;
-            let migration = discover_migration(&mut db, &config)?;
-            if migration.data.is_empty() {
-                log(
-                    "Your schema and database are synchronized, no discovery required",
-                    LogLevel::Info,
-                );
-                return Ok(());
-            }
-            log(
-                format!("Created discover migration {}", migration.path),
-                LogLevel::Info,
-            );
-            migration.apply_up(&mut db, true)?;
-            db.save()?;
-            MigrationManager::write_schema(&db.schema, &manager.schema_file_path)?;
-            log(
-                format!(
-                    "Applied discover migration to schema, new version: {}",
-                    db.schema.version.unwrap()
-                ),
-                LogLevel::Info,
-            );
+            MigrationLock::with_lock(&mut db, |db| discover(db, &config, &manager))?;
         }
-        Command::Describe => {
+        Command::Describe { output } => {
             let config = Config::new(&opts)?;
-            describe_db(&config)?;
+            describe_db(&config, *output)?;
         }
-        Command::DescribeIndexes { collection_name } => {
+        Command::DescribeIndexes {
+            collection_name,
+            output,
+        } => {
             let config = Config::new(&opts)?;
-            describe_collection_indexes(&config, collection_name)?;
+            describe_collection_indexes(&config, collection_name, *output)?;
+        }
+        Command::Completions(completion_opts) => {
+            completion_opts.generate(&opts);
         }
-        Command::Completions(opts) => {
-            opts.generate();
+        Command::Man(man_opts) => {
+            man_opts.generate();
         }
     };
     Ok(())
@@ -0,0 +1,225 @@
+use crate::app::AragogCliApp;
+use clap::{Arg, Command, IntoApp, Parser};
+use std::fmt::Write;
+
+#[derive(Parser, Debug)]
+pub struct ManOptions {
+    /// Generates the help as markdown instead of a man page
+    #[clap(long)]
+    pub markdown_help: bool,
+}
+
+impl ManOptions {
+    pub fn generate(&self) {
+        let mut command = AragogCliApp::command();
+        command.build();
+        let output = if self.markdown_help {
+            render_markdown(&command)
+        } else {
+            render_man(&command)
+        };
+        print!("{}", output);
+    }
+}
+
+/// The documented arguments of a command, without the hidden ones
+fn visible_args<'a, 'help>(command: &'a Command<'help>) -> impl Iterator<Item = &'a Arg<'help>> {
+    command.get_arguments().filter(|arg| !arg.is_hide_set())
+}
+
+/// The documented subcommands of a command, without the generated `help`
+fn visible_subcommands<'a, 'help>(
+    command: &'a Command<'help>,
+) -> impl Iterator<Item = &'a Command<'help>> {
+    command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set() && subcommand.get_name() != "help")
+}
+
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map_or_else(|| arg.get_id().to_uppercase(), |name| (*name).to_string())
+}
+
+/// The argument as typed on the command line, like `-c, --aragog-collection <NAME>`
+fn arg_label(arg: &Arg) -> String {
+    if arg.is_positional() {
+        return if arg.is_required_set() {
+            format!("<{}>", value_name(arg))
+        } else {
+            format!("[{}]", value_name(arg))
+        };
+    }
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("-{}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("--{}", long));
+    }
+    let mut label = names.join(", ");
+    if arg.is_takes_value_set() {
+        let _ = write!(label, " <{}>", value_name(arg));
+    }
+    label
+}
+
+/// The argument help, with its default value if any
+fn arg_help(arg: &Arg) -> String {
+    let mut help = arg.get_help().unwrap_or_default().to_string();
+    let defaults: Vec<_> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy())
+        .collect();
+    if !defaults.is_empty() {
+        if !help.is_empty() {
+            help.push(' ');
+        }
+        let _ = write!(help, "[default: {}]", defaults.join(", "));
+    }
+    help
+}
+
+fn usage(command: &Command, path: &str) -> String {
+    let mut usage = path.to_string();
+    if visible_args(command).any(|arg| !arg.is_positional()) {
+        usage.push_str(" [OPTIONS]");
+    }
+    for arg in visible_args(command).filter(|arg| arg.is_positional()) {
+        usage.push(' ');
+        usage.push_str(&arg_label(arg));
+    }
+    if visible_subcommands(command).next().is_some() {
+        usage.push_str(" <SUBCOMMAND>");
+    }
+    usage
+}
+
+fn roff_escape(text: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace('-', "\\-");
+    if text.starts_with('.') || text.starts_with('\'') {
+        format!("\\&{}", text)
+    } else {
+        text
+    }
+}
+
+fn render_man_args(output: &mut String, command: &Command) {
+    for arg in visible_args(command) {
+        let _ = writeln!(output, ".TP\n\\fB{}\\fR", roff_escape(&arg_label(arg)));
+        let _ = writeln!(output, "{}", roff_escape(&arg_help(arg)));
+    }
+}
+
+/// Renders the man page of the command and its subcommands, in `roff` format
+pub fn render_man(command: &Command) -> String {
+    let name = command.get_name();
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        ".TH {} 1 \"{} {}\"",
+        name.to_uppercase(),
+        name,
+        command.get_version().unwrap_or_default()
+    );
+    let _ = writeln!(output, ".SH NAME");
+    let _ = writeln!(
+        output,
+        "{} \\- {}",
+        name,
+        roff_escape(command.get_about().unwrap_or_default())
+    );
+    let _ = writeln!(output, ".SH SYNOPSIS");
+    let _ = writeln!(output, "\\fB{}\\fR", roff_escape(&usage(command, name)));
+    let _ = writeln!(output, ".SH OPTIONS");
+    render_man_args(&mut output, command);
+    let _ = writeln!(output, ".SH SUBCOMMANDS");
+    for subcommand in visible_subcommands(command) {
+        let path = format!("{} {}", name, subcommand.get_name());
+        let _ = writeln!(output, ".SS {}", roff_escape(&path));
+        let _ = writeln!(
+            output,
+            "{}",
+            roff_escape(subcommand.get_about().unwrap_or_default())
+        );
+        let _ = writeln!(
+            output,
+            ".PP\n\\fB{}\\fR",
+            roff_escape(&usage(subcommand, &path))
+        );
+        render_man_args(&mut output, subcommand);
+    }
+    if let Some(author) = command.get_author() {
+        let _ = writeln!(output, ".SH AUTHORS\n{}", roff_escape(author.trim()));
+    }
+    output
+}
+
+fn render_markdown_args(output: &mut String, command: &Command) {
+    for arg in visible_args(command) {
+        let help = arg_help(arg);
+        if help.is_empty() {
+            let _ = writeln!(output, "* `{}`", arg_label(arg));
+        } else {
+            let _ = writeln!(output, "* `{}`: {}", arg_label(arg), help);
+        }
+    }
+}
+
+/// Renders the help of the command and its subcommands as markdown
+pub fn render_markdown(command: &Command) -> String {
+    let name = command.get_name();
+    let mut output = String::new();
+    let _ = writeln!(output, "# `{}`\n", name);
+    if let Some(about) = command.get_about() {
+        let _ = writeln!(output, "{}\n", about);
+    }
+    let _ = writeln!(output, "**Usage:** `{}`\n", usage(command, name));
+    let _ = writeln!(output, "## Options\n");
+    render_markdown_args(&mut output, command);
+    let _ = writeln!(output, "\n## Subcommands");
+    for subcommand in visible_subcommands(command) {
+        let path = format!("{} {}", name, subcommand.get_name());
+        let _ = writeln!(output, "\n### `{}`\n", path);
+        if let Some(about) = subcommand.get_about() {
+            let _ = writeln!(output, "{}\n", about);
+        }
+        let _ = writeln!(output, "**Usage:** `{}`", usage(subcommand, &path));
+        if visible_args(subcommand).next().is_some() {
+            output.push('\n');
+            render_markdown_args(&mut output, subcommand);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command<'static> {
+        let mut command = AragogCliApp::command();
+        command.build();
+        command
+    }
+
+    #[test]
+    fn man_page() {
+        let man = render_man(&command());
+        assert!(man.starts_with(".TH ARAGOG 1"));
+        assert!(man.contains("\\fB\\-c, \\-\\-aragog\\-collection <SCHEMA_COLLECTION_NAME>\\fR"));
+        assert!(man.contains(".SS aragog describe\\-indexes"));
+        assert!(man.contains("\\fBaragog rollback [OPTIONS] [COUNT]\\fR"));
+    }
+
+    #[test]
+    fn markdown_help() {
+        let markdown = render_markdown(&command());
+        assert!(markdown.starts_with("# `aragog`\n"));
+        assert!(markdown.contains("### `aragog describe-indexes`"));
+        assert!(markdown.contains("* `[COUNT]`: Number of migrations to rollback [default: 1]"));
+        assert!(!markdown.contains("### `aragog help`"));
+    }
+}
@@ -34,6 +34,11 @@ impl Default for MigrationData {
             up: vec![MigrationOperation::CreateCollection {
                 name: String::from("MyCollection"),
                 wait_for_sync: None,
+                number_of_shards: None,
+                shard_keys: None,
+                replication_factor: None,
+                write_concern: None,
+                distribute_shards_like: None,
             }],
             down: Some(vec![MigrationOperation::DeleteCollection {
                 name: String::from("MyCollection"),
@@ -44,7 +49,8 @@ impl Default for MigrationData {
 
 #[cfg(test)]
 mod tests {
-    use arangors_lite::graph::{EdgeDefinition, GraphOptions};
+    use aragog::schema::{ComputedValue, GraphSchemaOptions, ReplicationFactor};
+    use arangors_lite::graph::EdgeDefinition;
     use arangors_lite::index::IndexSettings;
 
     use super::*;
@@ -56,10 +62,20 @@ mod tests {
                 MigrationOperation::CreateCollection {
                     name: "Collection1".to_string(),
                     wait_for_sync: None,
+                    number_of_shards: None,
+                    shard_keys: None,
+                    replication_factor: None,
+                    write_concern: None,
+                    distribute_shards_like: None,
                 },
                 MigrationOperation::CreateCollection {
                     name: "Collection2".to_string(),
                     wait_for_sync: Some(true),
+                    number_of_shards: Some(3),
+                    shard_keys: Some(vec!["name".to_string()]),
+                    replication_factor: Some(ReplicationFactor::Factor(2)),
+                    write_concern: Some(2),
+                    distribute_shards_like: None,
                 },
                 MigrationOperation::CreateIndex {
                     name: "OnNameAndEmail".to_string(),
@@ -71,10 +87,28 @@ mod tests {
                         deduplicate: false,
                     },
                 },
+                MigrationOperation::AddComputedValue {
+                    collection: "Collection1".to_string(),
+                    computed_value: ComputedValue::new("nameLength", "RETURN LENGTH(@doc.name)"),
+                },
                 MigrationOperation::Aql("This is a query".to_string()),
+                MigrationOperation::CopyCollection {
+                    from: "Collection1".to_string(),
+                    to: "Collection2".to_string(),
+                    transform: Some("MERGE(doc, { copied: true })".to_string()),
+                },
+                MigrationOperation::RenameCollection {
+                    name: "Collection2".to_string(),
+                    new_name: "Collection3".to_string(),
+                },
                 MigrationOperation::CreateEdgeCollection {
                     name: "Edge".to_string(),
                     wait_for_sync: None,
+                    number_of_shards: None,
+                    shard_keys: None,
+                    replication_factor: None,
+                    write_concern: None,
+                    distribute_shards_like: None,
                 },
                 MigrationOperation::CreateGraph {
                     name: "Named Graph".to_string(),
@@ -86,15 +120,20 @@ mod tests {
                     orphan_collections: None,
                     is_smart: Some(false),
                     is_disjoint: Some(true),
-                    options: Some(GraphOptions {
+                    options: Some(GraphSchemaOptions {
                         smart_graph_attribute: None,
                         number_of_shards: Some(10),
                         replication_factor: None,
                         write_concern: Some(2),
+                        satellites: vec![],
                     }),
                 },
             ],
             down: Some(vec![
+                MigrationOperation::RemoveComputedValue {
+                    name: "nameLength".to_string(),
+                    collection: "Collection1".to_string(),
+                },
                 MigrationOperation::DeleteGraph {
                     name: "Named Graph".to_string(),
                 },
@@ -137,6 +176,22 @@ mod tests {
                     sparse: false
                     deduplicate: false
               - aql: This is a query
+              - add_unique_index:
+                  collection: Collection1
+                  fields:
+                    - email
+              - add_ttl_index:
+                  collection: Collection2
+                  field: created_at
+                  expiry: 3600
+                  name: Expiry
+              - copy_collection:
+                  from: Collection1
+                  to: Collection2
+                  transform: \"MERGE(doc, { copied: true })\"
+              - rename_collection:
+                  name: Collection2
+                  new_name: Collection3
               - create_edge_collection:
                   name: Edge
               - create_graph:
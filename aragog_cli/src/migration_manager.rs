@@ -10,7 +10,7 @@ use crate::log_level::LogLevel;
 use crate::migration::Migration;
 use crate::VersionedDatabase;
 
-const SCHEMA_NAME: &str = "schema.yaml";
+pub const SCHEMA_NAME: &str = "schema.yaml";
 
 const HELP_MESSAGE: &str = "# \n\
                             # This schema file is auto generated and synchronized with the database.\n\
@@ -1,9 +1,12 @@
-use arangors_lite::graph::{EdgeDefinition, Graph, GraphOptions};
+use arangors_lite::graph::EdgeDefinition;
 use arangors_lite::index::IndexSettings;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use aragog::schema::{CollectionSchema, GraphSchema, IndexSchema, SchemaDatabaseOperation};
+use aragog::schema::{
+    CollectionSchema, ComputedValue, GraphSchema, GraphSchemaOptions, IndexSchema,
+    ReplicationFactor, SchemaDatabaseOperation,
+};
 
 use crate::error::AragogCliError;
 use crate::log;
@@ -17,6 +20,16 @@ pub enum MigrationOperation {
         name: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         wait_for_sync: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        number_of_shards: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        shard_keys: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        replication_factor: Option<ReplicationFactor>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        write_concern: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        distribute_shards_like: Option<String>,
     },
     DeleteCollection {
         name: String,
@@ -25,10 +38,38 @@ pub enum MigrationOperation {
         name: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         wait_for_sync: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        number_of_shards: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        shard_keys: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        replication_factor: Option<ReplicationFactor>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        write_concern: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        distribute_shards_like: Option<String>,
     },
     DeleteEdgeCollection {
         name: String,
     },
+    RenameCollection {
+        name: String,
+        new_name: String,
+    },
+    CopyCollection {
+        from: String,
+        to: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+    },
+    AddComputedValue {
+        collection: String,
+        computed_value: ComputedValue,
+    },
+    RemoveComputedValue {
+        name: String,
+        collection: String,
+    },
     CreateIndex {
         name: String,
         collection: String,
@@ -39,6 +80,21 @@ pub enum MigrationOperation {
         name: String,
         collection: String,
     },
+    AddUniqueIndex {
+        collection: String,
+        fields: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sparse: Option<bool>,
+    },
+    AddTtlIndex {
+        collection: String,
+        field: String,
+        expiry: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
     CreateGraph {
         name: String,
         edge_definitions: Vec<EdgeDefinition>,
@@ -49,7 +105,7 @@ pub enum MigrationOperation {
         #[serde(skip_serializing_if = "Option::is_none")]
         is_disjoint: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
-        options: Option<GraphOptions>,
+        options: Option<GraphSchemaOptions>,
     },
     DeleteGraph {
         name: String,
@@ -58,11 +114,50 @@ pub enum MigrationOperation {
 }
 
 impl MigrationOperation {
+    /// Expands an `add_unique_index` operation to the matching `create_index` operation, on a
+    /// unique and deduplicated persistent index
+    pub fn unique_index(
+        collection: String,
+        fields: Vec<String>,
+        name: Option<String>,
+        sparse: Option<bool>,
+    ) -> Self {
+        Self::CreateIndex {
+            name: name.unwrap_or_else(|| default_index_name("unique", &fields)),
+            collection,
+            fields,
+            settings: IndexSettings::Persistent {
+                unique: true,
+                sparse: sparse.unwrap_or(false),
+                deduplicate: true,
+            },
+        }
+    }
+
+    /// Expands an `add_ttl_index` operation to the matching `create_index` operation, removing
+    /// the documents `expiry` seconds after the date stored in `field`
+    pub fn ttl_index(collection: String, field: String, expiry: u32, name: Option<String>) -> Self {
+        let fields = vec![field];
+        Self::CreateIndex {
+            name: name.unwrap_or_else(|| default_index_name("ttl", &fields)),
+            collection,
+            fields,
+            settings: IndexSettings::Ttl {
+                expire_after: expiry,
+            },
+        }
+    }
+
     pub fn apply(self, db: &mut VersionedDatabase, silent: bool) -> Result<(), AragogCliError> {
         match self {
             Self::CreateCollection {
                 name,
                 wait_for_sync,
+                number_of_shards,
+                shard_keys,
+                replication_factor,
+                write_concern,
+                distribute_shards_like,
             } => {
                 log(
                     format!("Executing create_collection `{}` operation", name),
@@ -70,7 +165,14 @@ impl MigrationOperation {
                 );
                 let item = match db.schema.collection(&name) {
                     Some(_) => return Err(AragogCliError::DuplicateCollection { name }),
-                    None => CollectionSchema::new(&name, false, wait_for_sync),
+                    None => CollectionSchema {
+                        number_of_shards,
+                        shard_keys,
+                        replication_factor,
+                        write_concern,
+                        distribute_shards_like,
+                        ..CollectionSchema::new(&name, false, wait_for_sync)
+                    },
                 };
                 item.apply_to_database(db, silent)?;
                 db.schema.collections.push(item);
@@ -78,6 +180,11 @@ impl MigrationOperation {
             Self::CreateEdgeCollection {
                 name,
                 wait_for_sync,
+                number_of_shards,
+                shard_keys,
+                replication_factor,
+                write_concern,
+                distribute_shards_like,
             } => {
                 log(
                     format!("Executing create_edge_collection `{}` operation", name),
@@ -85,7 +192,14 @@ impl MigrationOperation {
                 );
                 let item = match db.schema.collection(&name) {
                     Some(_) => return Err(AragogCliError::DuplicateEdgeCollection { name }),
-                    None => CollectionSchema::new(&name, true, wait_for_sync),
+                    None => CollectionSchema {
+                        number_of_shards,
+                        shard_keys,
+                        replication_factor,
+                        write_concern,
+                        distribute_shards_like,
+                        ..CollectionSchema::new(&name, true, wait_for_sync)
+                    },
                 };
                 item.apply_to_database(db, silent)?;
                 db.schema.collections.push(item);
@@ -116,6 +230,119 @@ impl MigrationOperation {
                     }
                 }
             }
+            Self::RenameCollection { name, new_name } => {
+                log(
+                    format!(
+                        "Executing rename_collection `{}` to `{}` operation",
+                        name, new_name
+                    ),
+                    LogLevel::Verbose,
+                );
+                if db.schema.collection(&new_name).is_some() {
+                    return Err(AragogCliError::DuplicateCollection { name: new_name });
+                }
+                let index = match db.schema.collection_index(&name) {
+                    None => return Err(AragogCliError::MissingCollection { name }),
+                    Some(index) => index,
+                };
+                if let Some(graph) = db.schema.graphs.iter().find(|graph| {
+                    graph.orphan_collections.contains(&name)
+                        || graph.edge_definitions.iter().any(|definition| {
+                            definition.collection == name
+                                || definition.from.contains(&name)
+                                || definition.to.contains(&name)
+                        })
+                }) {
+                    return Err(AragogCliError::CollectionInGraph {
+                        name,
+                        graph: graph.name.clone(),
+                    });
+                }
+                db.collection(&name)?.rename(&new_name)?;
+                db.schema.collections[index].name = new_name.clone();
+                for item in db
+                    .schema
+                    .indexes
+                    .iter_mut()
+                    .filter(|item| item.collection == name)
+                {
+                    item.collection = new_name.clone();
+                }
+            }
+            Self::CopyCollection {
+                from,
+                to,
+                transform,
+            } => {
+                log(
+                    format!("Executing copy_collection `{}` to `{}` operation", from, to),
+                    LogLevel::Verbose,
+                );
+                for name in [&from, &to] {
+                    if db.schema.collection(name).is_none() {
+                        return Err(AragogCliError::MissingCollection { name: name.clone() });
+                    }
+                }
+                let aql = copy_collection_query(&from, &to, transform.as_deref());
+                log(format!("Copy query: {}", aql), LogLevel::Debug);
+                let res: Vec<Value> = db.aql_str(aql.as_str())?;
+                log(format!("{:?}", res), LogLevel::Verbose);
+            }
+            Self::AddComputedValue {
+                collection,
+                computed_value,
+            } => {
+                log(
+                    format!(
+                        "Executing add_computed_value `{}` operation",
+                        computed_value.name
+                    ),
+                    LogLevel::Verbose,
+                );
+                let mut item = match db.schema.collection(&collection) {
+                    None => return Err(AragogCliError::MissingCollection { name: collection }),
+                    Some(item) => item.clone(),
+                };
+                if item
+                    .computed_values
+                    .iter()
+                    .any(|value| value.name == computed_value.name)
+                {
+                    return Err(AragogCliError::DuplicateComputedValue {
+                        name: computed_value.name,
+                        collection,
+                    });
+                }
+                item.computed_values.push(computed_value);
+                item.update_computed_values(db)?;
+                if let Some(index) = db.schema.collection_index(&collection) {
+                    db.schema.collections[index] = item;
+                }
+            }
+            Self::RemoveComputedValue { name, collection } => {
+                log(
+                    format!("Executing remove_computed_value `{}` operation", name),
+                    LogLevel::Verbose,
+                );
+                let mut item = match db.schema.collection(&collection) {
+                    None => return Err(AragogCliError::MissingCollection { name: collection }),
+                    Some(item) => item.clone(),
+                };
+                match item
+                    .computed_values
+                    .iter()
+                    .position(|value| value.name == name)
+                {
+                    None => return Err(AragogCliError::MissingComputedValue { name, collection }),
+                    Some(index) => {
+                        item.computed_values.remove(index);
+                    }
+                }
+                item.update_computed_values(db)?;
+                if let Some(index) = db.schema.collection_index(&collection) {
+                    db.schema.collections[index] = item;
+                }
+            }
             Self::CreateIndex {
                 collection,
                 name,
@@ -151,6 +378,24 @@ impl MigrationOperation {
                     }
                 }
             }
+            Self::AddUniqueIndex {
+                collection,
+                fields,
+                name,
+                sparse,
+            } => {
+                log("Executing add_unique_index operation", LogLevel::Verbose);
+                Self::unique_index(collection, fields, name, sparse).apply(db, silent)?;
+            }
+            Self::AddTtlIndex {
+                collection,
+                field,
+                expiry,
+                name,
+            } => {
+                log("Executing add_ttl_index operation", LogLevel::Verbose);
+                Self::ttl_index(collection, field, expiry, name).apply(db, silent)?;
+            }
             Self::CreateGraph {
                 name,
                 edge_definitions,
@@ -165,14 +410,14 @@ impl MigrationOperation {
                 );
                 let item = match db.schema.graph(&name) {
                     Some(_) => return Err(AragogCliError::DuplicateGraph { name }),
-                    None => GraphSchema(Graph {
+                    None => GraphSchema {
                         name,
                         edge_definitions,
                         orphan_collections: orphan_collections.unwrap_or_default(),
                         is_smart,
                         is_disjoint,
                         options,
-                    }),
+                    },
                 };
                 item.apply_to_database(db, silent)?;
                 db.schema.graphs.push(item);
@@ -199,3 +444,93 @@ impl MigrationOperation {
         Ok(())
     }
 }
+
+/// Names the index after its kind and fields, like `unique_name_email`
+fn default_index_name(kind: &str, fields: &[String]) -> String {
+    let mut name = String::from(kind);
+    for field in fields {
+        name.push('_');
+        name.push_str(&field.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+    }
+    name
+}
+
+/// Builds the query inserting every document of `from` into `to`, the optional `transform` AQL
+/// expression computing the copied document from the source `doc`
+fn copy_collection_query(from: &str, to: &str, transform: Option<&str>) -> String {
+    format!(
+        "FOR doc IN `{}` LET document = {} INSERT UNSET(document, \"_id\", \"_rev\") INTO `{}`",
+        from,
+        transform.unwrap_or("doc"),
+        to
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_templates_expand_to_create_index() {
+        match MigrationOperation::unique_index(
+            "User".to_string(),
+            vec!["email".to_string(), "profile.phone".to_string()],
+            None,
+            None,
+        ) {
+            MigrationOperation::CreateIndex {
+                name,
+                collection,
+                fields,
+                settings:
+                    IndexSettings::Persistent {
+                        unique,
+                        sparse,
+                        deduplicate,
+                    },
+            } => {
+                assert_eq!(name, "unique_email_profile_phone");
+                assert_eq!(collection, "User");
+                assert_eq!(fields, ["email", "profile.phone"]);
+                assert!(unique && deduplicate && !sparse);
+            }
+            operation => panic!("Unexpected operation {:?}", operation),
+        }
+        match MigrationOperation::ttl_index(
+            "Session".to_string(),
+            "created_at".to_string(),
+            3600,
+            Some("SessionExpiry".to_string()),
+        ) {
+            MigrationOperation::CreateIndex {
+                name,
+                fields,
+                settings: IndexSettings::Ttl { expire_after },
+                ..
+            } => {
+                assert_eq!(name, "SessionExpiry");
+                assert_eq!(fields, ["created_at"]);
+                assert_eq!(expire_after, 3600);
+            }
+            operation => panic!("Unexpected operation {:?}", operation),
+        }
+    }
+
+    #[test]
+    fn copy_collection_query_applies_transform() {
+        assert_eq!(
+            copy_collection_query("Dish", "DishArchive", None),
+            "FOR doc IN `Dish` LET document = doc \
+             INSERT UNSET(document, \"_id\", \"_rev\") INTO `DishArchive`"
+        );
+        assert_eq!(
+            copy_collection_query(
+                "Dish",
+                "DishArchive",
+                Some("MERGE(doc, { archived: true })")
+            ),
+            "FOR doc IN `Dish` LET document = MERGE(doc, { archived: true }) \
+             INSERT UNSET(document, \"_id\", \"_rev\") INTO `DishArchive`"
+        );
+    }
+}
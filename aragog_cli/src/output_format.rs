@@ -0,0 +1,57 @@
+use clap::ArgEnum;
+use serde::Serialize;
+
+use crate::error::AragogCliError;
+
+/// Output format of the describe commands
+#[derive(ArgEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable tables
+    Table,
+    /// JSON, for scripts and monitoring
+    Json,
+    /// YAML, for scripts and monitoring
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Serializes `value` in the JSON or YAML format, `None` for the table format
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<Option<String>, AragogCliError> {
+        let output = match self {
+            Self::Table => return Ok(None),
+            Self::Json => serde_json::to_string_pretty(value).map_err(|error| {
+                AragogCliError::ParsingError {
+                    message: error.to_string(),
+                }
+            })?,
+            Self::Yaml => serde_yaml::to_string(value)?,
+        };
+        Ok(Some(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Item {
+        name: &'static str,
+        count: u32,
+    }
+
+    #[test]
+    fn serialized_formats() {
+        let item = Item {
+            name: "Dish",
+            count: 2,
+        };
+        assert!(OutputFormat::Table.serialize(&item).unwrap().is_none());
+        assert_eq!(
+            OutputFormat::Json.serialize(&item).unwrap().unwrap(),
+            "{\n  \"name\": \"Dish\",\n  \"count\": 2\n}"
+        );
+        let yaml = OutputFormat::Yaml.serialize(&item).unwrap().unwrap();
+        assert!(yaml.contains("name: Dish\ncount: 2"));
+    }
+}
@@ -0,0 +1,118 @@
+use arangors_lite::{Connection, Database};
+
+use crate::config::Config;
+use crate::error::AragogCliError;
+use crate::log;
+use crate::log_level::LogLevel;
+use crate::migration_manager::MigrationManager;
+use crate::versioned_database::VersionedDatabase;
+
+/// Lists the collections and graphs remaining in `db`, ignoring the system and schema collections
+fn remaining_elements(db: &Database, config: &Config) -> Result<Vec<String>, AragogCliError> {
+    let mut elements: Vec<String> = db
+        .accessible_collections()?
+        .into_iter()
+        .filter(|info| !info.is_system && info.name != config.schema_collection_name)
+        .map(|info| format!("collection {}", info.name))
+        .collect();
+    elements.extend(
+        db.graphs()?
+            .graphs
+            .into_iter()
+            .map(|graph| format!("graph {}", graph.name)),
+    );
+    Ok(elements)
+}
+
+/// Applies every migration up, then every migration down and up again on the `config` database.
+///
+/// Fails on the first failing operation, on migrations without `down` operations and if the
+/// database isn't empty once every migration is rolled back.
+fn run_migrations(config: &Config) -> Result<usize, AragogCliError> {
+    let mut db = VersionedDatabase::init(config)?;
+    let remaining = remaining_elements(&db, config)?;
+    if db.schema_version() != 0 || !remaining.is_empty() {
+        return Err(AragogCliError::VerificationFailed {
+            message: format!("the scratch database {} is not empty", config.db_name),
+        });
+    }
+    let manager = MigrationManager::new(&config.schema_path)?;
+    if let Some(migration) = manager
+        .migrations
+        .iter()
+        .find(|migration| migration.data.down.is_none())
+    {
+        return Err(AragogCliError::VerificationFailed {
+            message: format!("migration {} can't be rolled back", migration.path),
+        });
+    }
+    let count = manager.migrations.len();
+    log("Verifying migrations up...", LogLevel::Info);
+    for migration in manager.migrations {
+        migration.apply_up(&mut db, false)?;
+        db.save()?;
+    }
+    log("Verifying migrations down...", LogLevel::Info);
+    let mut migrations = MigrationManager::new(&config.schema_path)?.migrations;
+    migrations.reverse();
+    for migration in migrations {
+        migration.apply_down(&mut db)?;
+        db.save()?;
+    }
+    let remaining = remaining_elements(&db, config)?;
+    if !remaining.is_empty() {
+        return Err(AragogCliError::VerificationFailed {
+            message: format!("rolling back every migration left {}", remaining.join(", ")),
+        });
+    }
+    log("Verifying migrations up again...", LogLevel::Info);
+    for migration in MigrationManager::new(&config.schema_path)?.migrations {
+        migration.apply_up(&mut db, false)?;
+        db.save()?;
+    }
+    Ok(count)
+}
+
+/// Verifies that the migrations can be applied and rolled back, on the `scratch_database` or on a
+/// temporary database dropped afterwards. The schema file is not modified.
+pub fn verify_migrations(
+    config: &Config,
+    scratch_database: Option<&str>,
+) -> Result<(), AragogCliError> {
+    let mut scratch_config = config.clone();
+    scratch_config.db_name = scratch_database.map_or_else(
+        || format!("aragog_verify_{}", chrono::Utc::now().timestamp_millis()),
+        String::from,
+    );
+    log(
+        format!(
+            "Verifying migrations on database {}",
+            scratch_config.db_name
+        ),
+        LogLevel::Info,
+    );
+    let result = run_migrations(&scratch_config);
+    if scratch_database.is_none() {
+        log(
+            format!("Dropping database {}", scratch_config.db_name),
+            LogLevel::Verbose,
+        );
+        let connection =
+            Connection::establish_basic_auth(&config.db_host, &config.db_user, &config.db_pwd)?;
+        if let Err(error) = connection.drop_database(&scratch_config.db_name) {
+            log(
+                format!(
+                    "Failed to drop database {}: {}",
+                    scratch_config.db_name, error
+                ),
+                LogLevel::Info,
+            );
+        }
+    }
+    let count = result?;
+    log(
+        format!("Verified {} migrations up and down", count),
+        LogLevel::Info,
+    );
+    Ok(())
+}
@@ -5,7 +5,7 @@ use arangors_lite::Connection;
 use arangors_lite::{Collection, Database};
 use serde::{Deserialize, Serialize};
 
-use aragog::schema::DatabaseSchema;
+use aragog::schema::{DatabaseSchema, SCHEMA_DOCUMENT_KEY};
 
 use crate::config::Config;
 use crate::error::AragogCliError;
@@ -13,8 +13,6 @@ use crate::log;
 use crate::log_level::LogLevel;
 use crate::migration::MigrationVersion;
 
-const SCHEMA_DOC_KEY: &str = "DatabaseSchema";
-
 #[derive(Serialize, Deserialize)]
 struct SchemaWithKey {
     pub _key: String,
@@ -85,7 +83,7 @@ impl VersionedDatabase {
             }
         };
         log("Retrieving Schema document", LogLevel::Verbose);
-        let schema = match schema_collection.document(SCHEMA_DOC_KEY) {
+        let schema = match schema_collection.document(SCHEMA_DOCUMENT_KEY) {
             Ok(doc) => doc.document,
             Err(_err) => {
                 log(
@@ -94,7 +92,7 @@ impl VersionedDatabase {
                 );
                 let schema = DatabaseSchema::default();
                 let doc = SchemaWithKey {
-                    _key: SCHEMA_DOC_KEY.to_string(),
+                    _key: SCHEMA_DOCUMENT_KEY.to_string(),
                     version: schema.version,
                     collections: vec![],
                 };
@@ -119,7 +117,7 @@ impl VersionedDatabase {
             LogLevel::Verbose,
         );
         self.schema_collection.replace_document(
-            SCHEMA_DOC_KEY,
+            SCHEMA_DOCUMENT_KEY,
             self.schema.clone(),
             ReplaceOptions::builder().wait_for_sync(true).build(),
             None,
@@ -0,0 +1,236 @@
+use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Expr, Ident, LitStr, Token};
+
+/// The keywords an AQL query can start with
+const STATEMENT_KEYWORDS: [&str; 9] = [
+    "FOR", "LET", "RETURN", "INSERT", "UPDATE", "REPLACE", "REMOVE", "UPSERT", "WITH",
+];
+/// The keywords an AQL query must contain to produce a result
+const FINAL_KEYWORDS: [&str; 6] = ["RETURN", "INSERT", "UPDATE", "REPLACE", "REMOVE", "UPSERT"];
+
+/// A bind parameter argument: `name = value` for `@name` or `@name = value` for `@@name`
+struct BindArgument {
+    is_collection: bool,
+    name: Ident,
+    value: Expr,
+}
+
+impl BindArgument {
+    fn key(&self) -> String {
+        if self.is_collection {
+            format!("@{}", self.name)
+        } else {
+            self.name.to_string()
+        }
+    }
+}
+
+impl Parse for BindArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let is_collection = input.parse::<Option<Token![@]>>()?.is_some();
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self {
+            is_collection,
+            name,
+            value,
+        })
+    }
+}
+
+/// The `aql!` macro input: the query literal followed by the bind parameters arguments
+pub struct AqlInput {
+    query: LitStr,
+    arguments: Vec<BindArgument>,
+}
+
+impl Parse for AqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let query = input.parse()?;
+        if input.is_empty() {
+            return Ok(Self {
+                query,
+                arguments: Vec::new(),
+            });
+        }
+        input.parse::<Token![,]>()?;
+        let arguments = Punctuated::<BindArgument, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+        Ok(Self { query, arguments })
+    }
+}
+
+/// The words and bind parameters of an AQL query, outside of its strings and comments
+#[derive(Default)]
+struct ScannedQuery {
+    words: Vec<String>,
+    bind_parameters: Vec<String>,
+}
+
+const fn closing_delimiter(opening: char) -> char {
+    match opening {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Scans the AQL query, checking its strings, comments and delimiters
+fn scan(query: &str) -> Result<ScannedQuery, String> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut scanned = ScannedQuery::default();
+    let mut delimiters = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' | '`' | '´' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(format!("unterminated `{}` quoted string", c));
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                if i + 1 >= chars.len() {
+                    return Err("unterminated comment".to_string());
+                }
+                i += 1;
+            }
+            '(' | '[' | '{' => delimiters.push(c),
+            ')' | ']' | '}' => match delimiters.pop() {
+                Some(opening) if closing_delimiter(opening) == c => (),
+                _ => return Err(format!("unexpected `{}`", c)),
+            },
+            '@' => {
+                let prefix = if chars.get(i + 1) == Some(&'@') {
+                    i += 1;
+                    "@"
+                } else {
+                    ""
+                };
+                let start = i + 1;
+                while i + 1 < chars.len() && (chars[i + 1].is_alphanumeric() || chars[i + 1] == '_')
+                {
+                    i += 1;
+                }
+                if start > i {
+                    return Err("missing bind parameter name after `@`".to_string());
+                }
+                let name: String = chars[start..=i].iter().collect();
+                let parameter = format!("{}{}", prefix, name);
+                if !scanned.bind_parameters.contains(&parameter) {
+                    scanned.bind_parameters.push(parameter);
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i + 1 < chars.len() && (chars[i + 1].is_alphanumeric() || chars[i + 1] == '_')
+                {
+                    i += 1;
+                }
+                scanned.words.push(chars[start..=i].iter().collect());
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+    if let Some(opening) = delimiters.pop() {
+        return Err(format!("unclosed `{}`", opening));
+    }
+    Ok(scanned)
+}
+
+/// Checks the basic AQL syntax, returning the bind parameters
+fn check_query(query: &str) -> Result<Vec<String>, String> {
+    let scanned = scan(query)?;
+    let is_keyword = |keywords: &[&str], word: &String| {
+        keywords
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(word))
+    };
+    match scanned.words.first() {
+        Some(word) if is_keyword(&STATEMENT_KEYWORDS, word) => (),
+        _ => {
+            return Err(format!(
+                "the query must start with one of {}",
+                STATEMENT_KEYWORDS.join(", ")
+            ))
+        }
+    }
+    if !scanned
+        .words
+        .iter()
+        .any(|word| is_keyword(&FINAL_KEYWORDS, word))
+    {
+        return Err(
+            "the query must contain a `RETURN` or a data modification operation".to_string(),
+        );
+    }
+    Ok(scanned.bind_parameters)
+}
+
+/// Builds the `AqlQuery` expression. The errors are gathered in a block as several
+/// `compile_error!` invocations are not a valid expression
+pub fn impl_aql_macro(input: &AqlInput) -> TokenStream {
+    let query = &input.query;
+    let bind_parameters = match check_query(&query.value()) {
+        Ok(parameters) => parameters,
+        Err(error) => abort!(query.span(), "Invalid AQL query: {}", error),
+    };
+    let mut errors = Vec::new();
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+    for argument in &input.arguments {
+        let key = argument.key();
+        if keys.contains(&key) {
+            errors.push(Error::new(
+                argument.name.span(),
+                format!("Duplicate bind parameter `@{}`", key),
+            ));
+        } else if !bind_parameters.contains(&key) {
+            errors.push(Error::new(
+                argument.name.span(),
+                format!("Unused bind parameter `@{}`, not found in the query", key),
+            ));
+        }
+        keys.push(key);
+        values.push(&argument.value);
+    }
+    for parameter in &bind_parameters {
+        if !keys.contains(parameter) {
+            errors.push(Error::new(
+                query.span(),
+                format!(
+                    "Missing bind parameter `@{}`, add a `{} = ...` argument",
+                    parameter, parameter
+                ),
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        let errors = errors.iter().map(Error::to_compile_error);
+        return quote! {{ #(#errors)* }};
+    }
+    quote! {
+        aragog::AqlQuery::new(#query) #(.bind_var(#keys, #values))*
+    }
+}
@@ -0,0 +1,126 @@
+use crate::derives::json_schema::serde_attributes::{rename, ContainerAttributes, FieldAttributes};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::spanned::Spanned;
+use syn::{Data, DataEnum, DeriveInput, Fields, FieldsNamed, GenericParam};
+
+pub mod serde_attributes;
+
+pub fn impl_json_schema_macro(ast: &DeriveInput) -> TokenStream {
+    let target_name = &ast.ident;
+    let container = ContainerAttributes::parse(&ast.attrs);
+    let schema = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => named_fields_schema(fields, &container),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field = fields.unnamed.first().unwrap();
+                let ty = FieldAttributes::parse(&field.attrs)
+                    .schema_type
+                    .unwrap_or_else(|| field.ty.clone());
+                quote! { <#ty as aragog::JsonSchema>::json_schema() }
+            }
+            _ => {
+                emit_call_site_error!(
+                    "`JsonSchema` only supports structs with named fields and newtype structs"
+                );
+                quote! { aragog::serde_json::json!({}) }
+            }
+        },
+        Data::Enum(data) => unit_enum_schema(data, &container),
+        Data::Union(_) => {
+            emit_call_site_error!("`JsonSchema` doesn't support unions");
+            quote! { aragog::serde_json::json!({}) }
+        }
+    };
+    let mut generics = ast.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(syn::parse_quote!(aragog::JsonSchema));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let gen = quote! {
+        impl #impl_generics aragog::JsonSchema for #target_name #ty_generics #where_clause {
+            fn json_schema() -> aragog::serde_json::Value {
+                #schema
+            }
+        }
+    };
+    gen.into()
+}
+
+/// Builds an object schema, the `Option` fields and the fields with a default value are not
+/// required
+fn named_fields_schema(fields: &FieldsNamed, container: &ContainerAttributes) -> TokenStream2 {
+    let mut statements = Vec::new();
+    for field in &fields.named {
+        let attributes = FieldAttributes::parse(&field.attrs);
+        if attributes.skip {
+            continue;
+        }
+        let ty = attributes.schema_type.unwrap_or_else(|| field.ty.clone());
+        if attributes.flatten {
+            statements.push(quote! {
+                let flattened = <#ty as aragog::JsonSchema>::json_schema();
+                if let Some(aragog::serde_json::Value::Object(flattened)) = flattened.get("properties") {
+                    properties.extend(flattened.clone());
+                }
+                if let Some(aragog::serde_json::Value::Array(flattened)) = flattened.get("required") {
+                    required.extend(flattened.iter().cloned());
+                }
+            });
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap().to_string();
+        let name = attributes
+            .rename
+            .unwrap_or_else(|| rename(&ident, container.rename_all.as_deref(), false));
+        let optional = attributes.optional || container.default;
+        statements.push(quote! {
+            properties.insert(String::from(#name), <#ty as aragog::JsonSchema>::json_schema());
+            if !#optional && !<#ty as aragog::JsonSchema>::OPTIONAL {
+                required.push(aragog::serde_json::Value::from(#name));
+            }
+        });
+    }
+    quote! {
+        let mut properties = aragog::serde_json::Map::new();
+        let mut required: Vec<aragog::serde_json::Value> = Vec::new();
+        #(#statements)*
+        aragog::serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+/// Builds a string enumeration schema, only unit variants are supported
+fn unit_enum_schema(data: &DataEnum, container: &ContainerAttributes) -> TokenStream2 {
+    let mut values = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            emit_error!(
+                variant.span(),
+                "`JsonSchema` only supports enums with unit variants"
+            );
+            continue;
+        }
+        let attributes = FieldAttributes::parse(&variant.attrs);
+        if attributes.skip {
+            continue;
+        }
+        let ident = variant.ident.to_string();
+        values.push(
+            attributes
+                .rename
+                .unwrap_or_else(|| rename(&ident, container.rename_all.as_deref(), true)),
+        );
+    }
+    quote! {
+        aragog::serde_json::json!({
+            "type": "string",
+            "enum": [#(#values),*],
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use syn::spanned::Spanned;
+use syn::{Attribute, Lit, Meta, NestedMeta, Type};
+
+/// Lists the nested items of the `#[serde(...)]` attributes, ignoring the other attributes
+fn serde_items(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+fn str_value(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Str(value) => Some(value.value()),
+        _ => None,
+    }
+}
+
+/// The `serde` container attributes changing the schema
+#[derive(Default)]
+pub struct ContainerAttributes {
+    pub rename_all: Option<String>,
+    pub default: bool,
+}
+
+impl ContainerAttributes {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut res = Self::default();
+        for item in serde_items(attrs) {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("rename_all") => {
+                    res.rename_all = str_value(&value.lit);
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => {
+                    res.default = true;
+                }
+                _ => (),
+            }
+        }
+        res
+    }
+}
+
+/// The `serde` field and variant attributes changing the schema, and the
+/// `#[json_schema(as = "Type")]` schema type override
+#[derive(Default)]
+pub struct FieldAttributes {
+    pub rename: Option<String>,
+    pub skip: bool,
+    pub flatten: bool,
+    /// The field may be missing from the serialized document
+    pub optional: bool,
+    pub schema_type: Option<Type>,
+}
+
+impl FieldAttributes {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut res = Self::default();
+        for item in serde_items(attrs) {
+            let path = match &item {
+                NestedMeta::Meta(meta) => meta.path(),
+                NestedMeta::Lit(_) => continue,
+            };
+            let name = match path.get_ident() {
+                Some(ident) => ident.to_string(),
+                None => continue,
+            };
+            match (name.as_str(), &item) {
+                ("rename", NestedMeta::Meta(Meta::NameValue(value))) => {
+                    res.rename = str_value(&value.lit);
+                }
+                ("skip" | "skip_serializing", _) => res.skip = true,
+                ("flatten", _) => res.flatten = true,
+                ("default" | "skip_serializing_if", _) => res.optional = true,
+                _ => (),
+            }
+        }
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("json_schema"))
+        {
+            res.schema_type = parse_schema_type(attr);
+        }
+        res
+    }
+}
+
+/// Parses the `#[json_schema(as = "Type")]` attribute
+fn parse_schema_type(attr: &Attribute) -> Option<Type> {
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        Ok(meta) => {
+            emit_error!(
+                meta.span(),
+                "Expected a schema type: `#[json_schema(as = \"String\")]`"
+            );
+            return None;
+        }
+        Err(error) => {
+            emit_error!(
+                error.span(),
+                format!("Failed to parse attribute: {}", error)
+            );
+            return None;
+        }
+    };
+    let mut res = None;
+    for nest in &list.nested {
+        match nest {
+            NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("as") => {
+                match str_value(&value.lit).map(|name| syn::parse_str::<Type>(&name)) {
+                    Some(Ok(ty)) => res = Some(ty),
+                    _ => emit_error!(value.lit.span(), "Expected a type name string"),
+                }
+            }
+            _ => emit_error!(nest.span(), "Can't find a valid option, expected `as`"),
+        }
+    }
+    res
+}
+
+/// Applies the `serde` `rename_all` rule to a field, or to a variant if `is_variant` is set
+pub fn rename(ident: &str, rule: Option<&str>, is_variant: bool) -> String {
+    let rule = match rule {
+        Some(rule) => rule,
+        None => return ident.to_string(),
+    };
+    let snake = if is_variant {
+        let mut snake = String::new();
+        for (i, c) in ident.char_indices() {
+            if i > 0 && c.is_uppercase() {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        }
+        snake
+    } else {
+        ident.to_string()
+    };
+    let pascal: String = snake
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect();
+    match rule {
+        "lowercase" => ident.to_ascii_lowercase(),
+        "UPPERCASE" => ident.to_ascii_uppercase(),
+        "PascalCase" if is_variant => ident.to_string(),
+        "PascalCase" => pascal,
+        "camelCase" => {
+            let source = if is_variant { ident } else { pascal.as_str() };
+            let mut chars = source.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_lowercase().to_string() + chars.as_str()
+            })
+        }
+        "snake_case" => snake,
+        "SCREAMING_SNAKE_CASE" => snake.to_ascii_uppercase(),
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.replace('_', "-").to_ascii_uppercase(),
+        _ => ident.to_string(),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, GenericParam, Ident, Meta, NestedMeta, Path};
+
+/// The field merge strategies, functions of the `aragog::merge` module
+const STRATEGIES: [&str; 6] = ["local", "stored", "max", "min", "union", "merge"];
+
+pub fn impl_merge_macro(ast: &DeriveInput) -> TokenStream {
+    let target_name = &ast.ident;
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => {
+                emit_call_site_error!("`Merge` only supports structs with named fields");
+                return quote! {}.into();
+            }
+        },
+        _ => {
+            emit_call_site_error!("`Merge` only supports structs with named fields");
+            return quote! {}.into();
+        }
+    };
+    let merged_fields = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let function = field_merge_function(&field.attrs);
+        quote! { #ident: #function(&local.#ident, &stored.#ident) }
+    });
+    let mut generics = ast.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(syn::parse_quote!(Clone));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let gen = quote! {
+        impl #impl_generics aragog::Merge for #target_name #ty_generics #where_clause {
+            fn merge(local: &Self, stored: &Self) -> Self {
+                Self {
+                    #(#merged_fields),*
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+/// The merge function of a field, from its `#[merge(strategy = "max")]` or
+/// `#[merge(with = "path::to::function")]` attribute, `local` by default
+fn field_merge_function(attrs: &[Attribute]) -> TokenStream2 {
+    let mut function = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("merge")) {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected a strategy: `#[merge(strategy = \"max\")]`"
+                );
+                continue;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                continue;
+            }
+        };
+        for nest in &list.nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            if function.is_some() {
+                emit_error!(named_value.span(), "Only one merge strategy is allowed");
+                continue;
+            }
+            let value = match expect_str_lit(&named_value.lit) {
+                Some(value) => value,
+                None => continue,
+            };
+            match get_ident(&named_value.path).as_deref() {
+                Some("strategy") if STRATEGIES.contains(&value.as_str()) => {
+                    let strategy = Ident::new(&value, Span::call_site());
+                    function = Some(quote! { aragog::merge::#strategy });
+                }
+                Some("strategy") => emit_error!(
+                    named_value.lit.span(),
+                    "Unknown merge strategy `{}`, expected one of: {}",
+                    value,
+                    STRATEGIES.join(", ")
+                ),
+                Some("with") => match syn::parse_str::<Path>(&value) {
+                    Ok(path) => function = Some(quote! { #path }),
+                    Err(error) => {
+                        emit_error!(named_value.lit.span(), "Invalid function path: {}", error)
+                    }
+                },
+                Some(_) => emit_error!(named_value.path.span(), "Can't find a valid option"),
+                None => (),
+            }
+        }
+    }
+    function.unwrap_or_else(|| quote! { aragog::merge::local })
+}
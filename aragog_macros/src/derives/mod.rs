@@ -1,4 +1,11 @@
-pub use {record::impl_record_macro, validate::impl_validate_macro};
+pub use {
+    json_schema::impl_json_schema_macro,
+    merge::impl_merge_macro,
+    record::{impl_record_macro, impl_register_record_macro},
+    validate::impl_validate_macro,
+};
 
+mod json_schema;
+mod merge;
 mod record;
 mod validate;
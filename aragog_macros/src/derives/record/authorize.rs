@@ -0,0 +1,76 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Meta, NestedMeta, Type};
+
+/// The `#[authorize(actor = "User")]` attribute
+pub struct AuthorizeAttribute {
+    actor: Type,
+}
+
+impl AuthorizeAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("authorize") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected an actor type: `#[authorize(actor = \"User\")]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut actor = None;
+        for nest in &list.nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            match get_ident(&named_value.path).as_deref() {
+                Some("actor") => {
+                    let name = match expect_str_lit(&named_value.lit) {
+                        Some(name) => name,
+                        None => continue,
+                    };
+                    match syn::parse_str::<Type>(&name) {
+                        Ok(ty) => actor = Some(ty),
+                        Err(_) => emit_error!(named_value.lit.span(), "Invalid actor type"),
+                    }
+                }
+                Some(_) => emit_error!(named_value.path.span(), "Can't find a valid option"),
+                None => (),
+            }
+        }
+        if actor.is_none() {
+            emit_error!(list.span(), "Missing `actor` option");
+        }
+        actor.map(|actor| Self { actor })
+    }
+
+    /// Builds the `Record::authorize_write` implementation delegating to the actor
+    /// `AuthorizeAction` implementation
+    pub fn token_stream(&self) -> TokenStream {
+        let actor = &self.actor;
+        quote! {
+            fn authorize_write(
+                actor: &aragog::serde_json::Value,
+                action: aragog::RecordAction,
+                target: Option<&aragog::DatabaseRecord<Self>>,
+            ) -> Result<(), aragog::Error> {
+                <#actor as aragog::AuthorizeAction<Self>>::authorize_actor(actor, action, target)
+            }
+        }
+    }
+}
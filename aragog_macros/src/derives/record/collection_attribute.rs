@@ -1,7 +1,142 @@
+use proc_macro2::Span;
 use syn::spanned::Spanned;
-use syn::{Attribute, Lit, Meta, Path};
+use syn::{Attribute, Lit, Meta, MetaList, NestedMeta, Path};
 
-pub struct CollectionNameAttribute(pub Lit);
+/// Maximum length in bytes of an `ArangoDB` collection name
+const MAX_COLLECTION_NAME_LENGTH: usize = 256;
+
+/// Checks `name` against the `ArangoDB` collection naming rules: 1 to 256 bytes, starting with a
+/// letter and only made of ASCII letters, digits, underscores and dashes.
+fn collection_name_error(name: &str) -> Option<String> {
+    match name.chars().next() {
+        None => return Some(String::from("The collection name can't be empty")),
+        Some('_') => return Some(String::from(
+            "The collection name can't start with an underscore, reserved for system collections",
+        )),
+        Some(c) if !c.is_ascii_alphabetic() => {
+            return Some(String::from("The collection name must start with a letter"))
+        }
+        Some(_) => (),
+    }
+    if name.len() > MAX_COLLECTION_NAME_LENGTH {
+        return Some(format!(
+            "The collection name can't be longer than {} characters",
+            MAX_COLLECTION_NAME_LENGTH
+        ));
+    }
+    name.chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '-'))
+        .map(|c| {
+            format!(
+                "Invalid character `{}` in collection name, only letters, digits, `_` and `-` are allowed",
+                c
+            )
+        })
+}
+
+/// Emits a compilation error if `name` is not a valid `ArangoDB` collection name
+pub fn validate_collection_name(name: &str, span: Span) {
+    if let Some(error) = collection_name_error(name) {
+        emit_error!(span, error);
+    }
+}
+
+/// The case of a collection name derived from the type name
+#[derive(Clone, Copy)]
+pub enum NameCase {
+    /// `OrderItem` -> `order_item`
+    Snake,
+    /// `OrderItem` -> `orderItem`
+    Camel,
+}
+
+/// The `#[collection_name(...)]` naming strategy, applied to the type name
+#[derive(Default)]
+pub struct NamingStrategy {
+    pub case: Option<NameCase>,
+    pub pluralize: bool,
+}
+
+impl NamingStrategy {
+    /// Splits a `PascalCase` type name into its words: `HTTPRequestLog` -> `HTTP`, `Request`, `Log`
+    fn words(name: &str) -> Vec<String> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut words: Vec<String> = Vec::new();
+        for (i, c) in chars.iter().enumerate() {
+            let boundary = i > 0
+                && c.is_ascii_uppercase()
+                && (!chars[i - 1].is_ascii_uppercase()
+                    || chars.get(i + 1).map_or(false, char::is_ascii_lowercase));
+            match words.last_mut() {
+                Some(word) if !boundary && chars[i - 1] != '_' => word.push(*c),
+                _ if *c == '_' => (),
+                _ => words.push(c.to_string()),
+            }
+        }
+        words
+    }
+
+    /// Returns the english plural of `word`
+    fn pluralize(word: &str) -> String {
+        let lowercase = word.to_ascii_lowercase();
+        if ["s", "x", "z", "ch", "sh"]
+            .iter()
+            .any(|suffix| lowercase.ends_with(suffix))
+        {
+            format!("{}es", word)
+        } else if lowercase.ends_with('y')
+            && !lowercase
+                .chars()
+                .rev()
+                .nth(1)
+                .map_or(true, |c| "aeiou".contains(c))
+        {
+            format!("{}ies", &word[..word.len() - 1])
+        } else {
+            format!("{}s", word)
+        }
+    }
+
+    /// Builds the collection name of the `type_name` type
+    pub fn apply(&self, type_name: &str) -> String {
+        let mut words = Self::words(type_name);
+        if self.pluralize {
+            if let Some(last) = words.last_mut() {
+                *last = Self::pluralize(last);
+            }
+        }
+        match self.case {
+            None => words.concat(),
+            Some(NameCase::Snake) => words
+                .iter()
+                .map(|word| word.to_ascii_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Some(NameCase::Camel) => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_ascii_lowercase()
+                    } else {
+                        let mut chars = word.chars();
+                        chars.next().map_or_else(String::new, |first| {
+                            first.to_ascii_uppercase().to_string()
+                                + &chars.as_str().to_ascii_lowercase()
+                        })
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+pub enum CollectionNameAttribute {
+    /// `#[collection_name = "Name"]`
+    Literal(Lit),
+    /// `#[collection_name(snake_case, pluralize)]`
+    Strategy(NamingStrategy),
+}
 
 impl CollectionNameAttribute {
     fn correct_path(path: &Path) -> Option<()> {
@@ -13,14 +148,47 @@ impl CollectionNameAttribute {
         }
     }
 
+    fn parse_strategy(list: &MetaList) -> NamingStrategy {
+        let mut strategy = NamingStrategy::default();
+        for nested in &list.nested {
+            let ident = match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.get_ident().map(ToString::to_string),
+                _ => None,
+            };
+            match ident.as_deref() {
+                Some("pluralize") => strategy.pluralize = true,
+                Some("snake_case") if strategy.case.is_none() => {
+                    strategy.case = Some(NameCase::Snake);
+                }
+                Some("camel_case") if strategy.case.is_none() => {
+                    strategy.case = Some(NameCase::Camel);
+                }
+                Some("snake_case" | "camel_case") => {
+                    emit_error!(nested.span(), "Only one naming case is allowed");
+                }
+                _ => emit_error!(
+                    nested.span(),
+                    "Expected a naming option: `pluralize`, `snake_case` or `camel_case`"
+                ),
+            }
+        }
+        strategy
+    }
+
     pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
         Self::correct_path(&attr.path)?;
         match attr.parse_meta() {
             Ok(meta) => match meta {
                 Meta::NameValue(named_value) => {
-                    return Some(Self(named_value.lit));
+                    if let Lit::Str(name) = &named_value.lit {
+                        validate_collection_name(&name.value(), name.span());
+                    } else {
+                        emit_error!(named_value.lit.span(), "Expected a string collection name");
+                    }
+                    return Some(Self::Literal(named_value.lit));
                 }
-                _ => {
+                Meta::List(list) => return Some(Self::Strategy(Self::parse_strategy(&list))),
+                Meta::Path(_) => {
                     emit_error!(
                         meta.span(),
                         "Expected Named Value, add a correct collection name"
@@ -35,3 +203,27 @@ impl CollectionNameAttribute {
         None
     }
 }
+
+/// Parses the `#[edge_collection]` marker attribute, returns `true` if `attr` is a valid marker
+pub fn is_edge_collection_attribute(attr: &Attribute) -> bool {
+    if !attr.path.is_ident("edge_collection") {
+        return false;
+    }
+    match attr.parse_meta() {
+        Ok(Meta::Path(_)) => true,
+        Ok(meta) => {
+            emit_error!(
+                meta.span(),
+                "Expected a marker attribute without arguments: `#[edge_collection]`"
+            );
+            false
+        }
+        Err(error) => {
+            emit_error!(
+                error.span(),
+                format!("Failed to parse attribute: {}", error)
+            );
+            false
+        }
+    }
+}
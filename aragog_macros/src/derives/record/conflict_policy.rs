@@ -0,0 +1,120 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Ident, Lit, Meta, NestedMeta};
+
+/// The resolution of a `conflict_policy` attribute
+enum Resolution {
+    Fail,
+    RetryWithReload,
+    MergeFields,
+    Merge(String),
+}
+
+/// The `#[conflict_policy(merge = "merge_fn", max_retries = 5)]` attribute, or with `fail`,
+/// `retry_with_reload` or `merge_fields` policies
+pub struct ConflictPolicyAttribute {
+    resolution: Resolution,
+    max_retries: Option<u32>,
+}
+
+impl ConflictPolicyAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("conflict_policy") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected a policy: `#[conflict_policy(retry_with_reload)]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut resolution = None;
+        let mut max_retries = None;
+        for nest in &list.nested {
+            match nest {
+                NestedMeta::Meta(Meta::Path(path)) => match get_ident(path).as_deref() {
+                    Some("fail") => resolution = Some(Resolution::Fail),
+                    Some("retry_with_reload") => resolution = Some(Resolution::RetryWithReload),
+                    Some("merge_fields") => resolution = Some(Resolution::MergeFields),
+                    Some(_) => emit_error!(
+                        path.span(),
+                        "Expected `fail`, `retry_with_reload`, `merge_fields` or `merge = \"function\"`"
+                    ),
+                    None => (),
+                },
+                NestedMeta::Meta(Meta::NameValue(named_value)) => {
+                    match get_ident(&named_value.path).as_deref() {
+                        Some("merge") => {
+                            resolution = expect_str_lit(&named_value.lit).map(Resolution::Merge);
+                        }
+                        Some("max_retries") => match &named_value.lit {
+                            Lit::Int(lit) => match lit.base10_parse::<u32>() {
+                                Ok(value) => max_retries = Some(value),
+                                Err(error) => {
+                                    emit_error!(lit.span(), "Invalid retries count: {}", error);
+                                }
+                            },
+                            lit => emit_error!(lit.span(), "Expected a retries count"),
+                        },
+                        Some(_) => {
+                            emit_error!(named_value.path.span(), "Can't find a valid option")
+                        }
+                        None => (),
+                    }
+                }
+                NestedMeta::Lit(lit) => emit_error!(lit.span(), "Expected a policy"),
+                NestedMeta::Meta(meta) => emit_error!(meta.span(), "Expected a policy"),
+            }
+        }
+        let resolution = match resolution {
+            Some(resolution) => resolution,
+            None => {
+                emit_error!(
+                    list.span(),
+                    "Expected `fail`, `retry_with_reload`, `merge_fields` or `merge = \"function\"`"
+                );
+                return None;
+            }
+        };
+        if max_retries.is_some() && matches!(resolution, Resolution::Fail) {
+            emit_error!(list.span(), "The `fail` policy doesn't retry");
+        }
+        Some(Self {
+            resolution,
+            max_retries,
+        })
+    }
+
+    /// Builds the `Record::conflict_policy` implementation
+    pub fn token_stream(&self) -> TokenStream {
+        let policy = match &self.resolution {
+            Resolution::Fail => quote! { aragog::ConflictPolicy::Fail },
+            Resolution::RetryWithReload => quote! { aragog::ConflictPolicy::retry_with_reload() },
+            Resolution::MergeFields => quote! { aragog::ConflictPolicy::merge_fields() },
+            Resolution::Merge(func) => {
+                let func = Ident::new(func, Span::call_site());
+                quote! { aragog::ConflictPolicy::merge(Self::#func) }
+            }
+        };
+        let max_retries = self
+            .max_retries
+            .map(|max_retries| quote! { .max_retries(#max_retries) });
+        quote! {
+            fn conflict_policy() -> aragog::ConflictPolicy<Self> {
+                #policy #max_retries
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Meta, NestedMeta};
+
+/// The `#[counter_cache(edge = "Likes", field = "likes_count", vertex = "to")]` attribute
+pub struct CounterCacheAttribute {
+    edge: String,
+    field: String,
+    on_from_vertex: bool,
+}
+
+impl CounterCacheAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("counter_cache") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected a meta list: `#[counter_cache(edge = \"...\", field = \"...\")]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut edge = None;
+        let mut field = None;
+        let mut on_from_vertex = false;
+        for nest in &list.nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            let value = match expect_str_lit(&named_value.lit) {
+                Some(value) => value,
+                None => continue,
+            };
+            match get_ident(&named_value.path).as_deref() {
+                Some("edge") => edge = Some(value),
+                Some("field") => field = Some(value),
+                Some("vertex") => match value.as_str() {
+                    "from" => on_from_vertex = true,
+                    "to" => on_from_vertex = false,
+                    _ => emit_error!(
+                        named_value.lit.span(),
+                        "Expected `from` or `to` as counter cache vertex"
+                    ),
+                },
+                Some(_) => emit_error!(named_value.path.span(), "Can't find a valid option"),
+                None => (),
+            }
+        }
+        if edge.is_none() {
+            emit_error!(list.span(), "Missing `edge` option for counter cache");
+        }
+        if field.is_none() {
+            emit_error!(list.span(), "Missing `field` option for counter cache");
+        }
+        Some(Self {
+            edge: edge?,
+            field: field?,
+            on_from_vertex,
+        })
+    }
+}
+
+/// Builds the `Record::COUNTER_CACHES` constant, if any counter cache is declared
+pub fn counter_caches_token_stream(counter_caches: &[CounterCacheAttribute]) -> TokenStream {
+    if counter_caches.is_empty() {
+        return quote! {};
+    }
+    let items = counter_caches.iter().map(|counter_cache| {
+        let edge = &counter_cache.edge;
+        let field = &counter_cache.field;
+        if counter_cache.on_from_vertex {
+            quote! { aragog::CounterCache::new(#edge, #field).on_from_vertex() }
+        } else {
+            quote! { aragog::CounterCache::new(#edge, #field) }
+        }
+    });
+    quote! {
+        const COUNTER_CACHES: &'static [aragog::CounterCache] = &[#(#items),*];
+    }
+}
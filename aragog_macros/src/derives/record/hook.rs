@@ -52,6 +52,8 @@ impl ParseAttribute for Hook {
                 func: None,
                 database_access: None,
                 is_async: None,
+                is_sync: None,
+                priority: None,
             },
         })
     }
@@ -64,7 +66,9 @@ impl ParseAttribute for Hook {
         match operation {
             HookOperation::Func(func) => self.hook_data.edit_func(span, &func),
             HookOperation::IsAsync(v) => self.hook_data.edit_is_async(span, v),
+            HookOperation::IsSync(v) => self.hook_data.edit_is_sync(span, v),
             HookOperation::DbAccess(v) => self.hook_data.edit_db_access(span, v),
+            HookOperation::Priority(v) => self.hook_data.edit_priority(span, v),
         }
     }
 
@@ -72,6 +76,13 @@ impl ParseAttribute for Hook {
         if self.hook_data.func.is_none() {
             emit_error!(span, "Missing function for {:?} hook", self.hook_type);
             false
+        } else if self.hook_data.is_async == Some(true) && self.hook_data.is_sync == Some(true) {
+            emit_error!(
+                span,
+                "A {:?} hook can't be both `is_async` and `sync`",
+                self.hook_type
+            );
+            false
         } else {
             true
         }
@@ -8,6 +8,8 @@ pub struct HookData {
     pub func: Option<String>,
     pub database_access: Option<bool>,
     pub is_async: Option<bool>,
+    pub is_sync: Option<bool>,
+    pub priority: Option<i64>,
 }
 
 impl HookData {
@@ -32,6 +34,20 @@ impl HookData {
         }
         self.is_async = Some(value);
     }
+
+    pub fn edit_is_sync(&mut self, span: Span, value: bool) {
+        if self.is_sync.is_some() {
+            emit_error!(span, "Can't have multiple `sync` identifiers");
+        }
+        self.is_sync = Some(value);
+    }
+
+    pub fn edit_priority(&mut self, span: Span, value: i64) {
+        if self.priority.is_some() {
+            emit_error!(span, "Can't have multiple `priority` identifiers");
+        }
+        self.priority = Some(value);
+    }
 }
 
 impl ToTokenStream for HookData {
@@ -42,10 +58,9 @@ impl ToTokenStream for HookData {
             emit_call_site_error!("Missing function for hook");
             return TokenStream::new();
         };
-        #[cfg(feature = "blocking")]
-        let is_async = false;
-        #[cfg(not(feature = "blocking"))]
-        let is_async = self.is_async.unwrap_or(false);
+        // Sync hooks are called directly in both modes, async hooks are awaited in async mode and
+        // resolved by `aragog::blocking_hook` in blocking mode
+        let is_async = self.is_async.unwrap_or(false) && !self.is_sync.unwrap_or(false);
         let db_access = self.database_access.unwrap_or(false);
         let func_ident = Ident::new(&func, Span::call_site());
         let func = if db_access {
@@ -57,14 +72,23 @@ impl ToTokenStream for HookData {
                 self.#func_ident()
             }
         };
-        if is_async {
-            quote! {
-                #func.await?;
-            }
-        } else {
-            quote! {
+        if !is_async {
+            return quote! {
               #func?;
-            }
+            };
         }
+        #[cfg(feature = "blocking")]
+        let res = quote! {
+            {
+                #[allow(unused_imports)]
+                use aragog::blocking_hook::{BlockOnHook as _, ReadyHook as _};
+                (&aragog::blocking_hook::BlockingHook::new(#func)).resolve()?;
+            }
+        };
+        #[cfg(not(feature = "blocking"))]
+        let res = quote! {
+            #func.await?;
+        };
+        res
     }
 }
@@ -2,6 +2,7 @@ use crate::derives::record::hook::{Hook, HookType};
 use crate::derives::record::hook_data::HookData;
 use crate::to_tokenstream::ToTokenStream;
 use proc_macro2::TokenStream;
+use std::cmp::Reverse;
 
 #[derive(Clone, Default)]
 pub struct HooksContainer {
@@ -14,8 +15,10 @@ pub struct HooksContainer {
 }
 
 impl From<Vec<Hook>> for HooksContainer {
-    fn from(vec: Vec<Hook>) -> Self {
+    fn from(mut vec: Vec<Hook>) -> Self {
         let mut res = Self::default();
+        // Stable sort: hooks with equal priority keep their declaration order
+        vec.sort_by_key(|hook| Reverse(hook.hook_data.priority.unwrap_or(0)));
         for hook in vec {
             let data = hook.hook_data;
             match hook.hook_type {
@@ -0,0 +1,154 @@
+use crate::toolbox::{expect_int_lit, expect_str_lit, get_ident};
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Meta, NestedMeta};
+
+/// The `#[index(fields("name"), unique, sparse, name = "MyIndex", ttl = 3600)]` attribute
+pub struct IndexAttribute {
+    name: Option<String>,
+    fields: Vec<String>,
+    unique: bool,
+    sparse: bool,
+    ttl: Option<u32>,
+}
+
+impl IndexAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("index") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected index fields: `#[index(fields(\"name\"), unique)]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut res = Self {
+            name: None,
+            fields: Vec::new(),
+            unique: false,
+            sparse: false,
+            ttl: None,
+        };
+        for nest in &list.nested {
+            let meta = if let NestedMeta::Meta(meta) = nest {
+                meta
+            } else {
+                emit_error!(nest.span(), "Expected an index option");
+                continue;
+            };
+            match (get_ident(meta.path()).as_deref(), meta) {
+                (Some("fields"), Meta::List(fields)) => {
+                    for field in &fields.nested {
+                        match field {
+                            NestedMeta::Lit(lit) => res.fields.extend(expect_str_lit(lit)),
+                            NestedMeta::Meta(meta) => {
+                                emit_error!(meta.span(), "Expected a string value");
+                            }
+                        }
+                    }
+                }
+                (Some("unique"), Meta::Path(_)) => res.unique = true,
+                (Some("sparse"), Meta::Path(_)) => res.sparse = true,
+                (Some("name"), Meta::NameValue(value)) => res.name = expect_str_lit(&value.lit),
+                (Some("ttl"), Meta::NameValue(value)) => {
+                    res.ttl = expect_int_lit(&value.lit).and_then(|ttl| {
+                        let ttl = u32::try_from(ttl).ok();
+                        if ttl.is_none() {
+                            emit_error!(value.lit.span(), "Expected a positive number of seconds");
+                        }
+                        ttl
+                    });
+                }
+                (Some(_), _) => emit_error!(
+                    meta.span(),
+                    "Can't find a valid option, expected `fields`, `unique`, `sparse`, `name` or `ttl`"
+                ),
+                (None, _) => (),
+            }
+        }
+        if res.fields.is_empty() {
+            emit_error!(list.span(), "Missing `fields` option");
+            return None;
+        }
+        if res.ttl.is_some() && (res.unique || res.sparse || res.fields.len() > 1) {
+            emit_error!(
+                list.span(),
+                "TTL indexes have a single field and no `unique` or `sparse` option"
+            );
+        }
+        Some(res)
+    }
+
+    /// Names the index after its kind and fields, like `unique_name_email`
+    fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let kind = match (self.ttl, self.unique) {
+                (Some(_), _) => "ttl",
+                (None, true) => "unique",
+                (None, false) => "index",
+            };
+            let mut name = String::from(kind);
+            for field in &self.fields {
+                name.push('_');
+                name.push_str(&field.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+            }
+            name
+        })
+    }
+
+    fn token_stream(&self) -> TokenStream {
+        let name = self.name();
+        let fields = &self.fields;
+        let unique = self.unique;
+        let sparse = self.sparse;
+        let settings = self.ttl.map_or_else(
+            || {
+                quote! {
+                    aragog::schema::IndexSettings::Persistent {
+                        unique: #unique,
+                        sparse: #sparse,
+                        deduplicate: true,
+                    }
+                }
+            },
+            |expire_after| {
+                quote! {
+                    aragog::schema::IndexSettings::Ttl { expire_after: #expire_after }
+                }
+            },
+        );
+        quote! {
+            aragog::schema::IndexSchema {
+                name: String::from(#name),
+                collection: String::from(Self::COLLECTION_NAME),
+                fields: vec![#(String::from(#fields)),*],
+                settings: #settings,
+            }
+        }
+    }
+}
+
+/// Builds the `Record::indexes` implementation, `None` without index attributes
+pub fn indexes_token_stream(indexes: &[IndexAttribute]) -> Option<TokenStream> {
+    if indexes.is_empty() {
+        return None;
+    }
+    let indexes = indexes.iter().map(IndexAttribute::token_stream);
+    Some(quote! {
+        fn indexes() -> Vec<aragog::schema::IndexSchema> {
+            vec![#(#indexes),*]
+        }
+    })
+}
@@ -1,53 +1,186 @@
-use crate::derives::record::collection_attribute::CollectionNameAttribute;
+use crate::derives::record::authorize::AuthorizeAttribute;
+use crate::derives::record::collection_attribute::{
+    is_edge_collection_attribute, validate_collection_name, CollectionNameAttribute,
+};
+use crate::derives::record::conflict_policy::ConflictPolicyAttribute;
+use crate::derives::record::counter_cache::{counter_caches_token_stream, CounterCacheAttribute};
 use crate::derives::record::hook::Hook;
 use crate::derives::record::hooks_container::HooksContainer;
+use crate::derives::record::index::{indexes_token_stream, IndexAttribute};
+use crate::derives::record::model_version::ModelVersionAttribute;
+use crate::derives::record::scope::{scopes_token_stream, DefaultScopeAttribute, ScopeAttribute};
+use crate::derives::record::tenant_scoped::TenantScopedAttribute;
 use crate::parse_attribute::ParseAttribute;
 use crate::to_tokenstream::ToTokenStream;
 use proc_macro::TokenStream;
-use syn::Data;
+use syn::spanned::Spanned;
+use syn::{Data, Generics, WhereClause};
 
+mod authorize;
 mod collection_attribute;
+mod conflict_policy;
+mod counter_cache;
 mod hook;
 mod hook_data;
 mod hooks_container;
+mod index;
+mod model_version;
 mod operation;
+mod query_fields;
+mod redact;
+mod scope;
+mod tenant_scoped;
 
 pub fn impl_record_macro(ast: &syn::DeriveInput) -> TokenStream {
     let target_name = &ast.ident;
 
-    if let Data::Enum(_) = ast.data {
-        emit_call_site_error!("`Record` doesn't support enums");
+    if let Data::Union(_) = ast.data {
+        emit_call_site_error!("`Record` doesn't support unions");
     }
     let mut hooks = Vec::new();
     let mut collection_names = Vec::new();
+    let mut counter_caches = Vec::new();
+    let mut model_versions = Vec::new();
+    let mut scopes = Vec::new();
+    let mut default_scopes = Vec::new();
+    let mut authorizations = Vec::new();
+    let mut tenant_fields = Vec::new();
+    let mut indexes = Vec::new();
+    let mut conflict_policies = Vec::new();
+    let mut is_edge_collection = false;
     for attr in &ast.attrs {
+        is_edge_collection |= is_edge_collection_attribute(attr);
         Hook::parse_attribute(attr, None, &mut hooks);
         if let Some(cn) = CollectionNameAttribute::parse_attribute(attr) {
             collection_names.push(cn);
         }
+        if let Some(counter_cache) = CounterCacheAttribute::parse_attribute(attr) {
+            counter_caches.push(counter_cache);
+        }
+        if let Some(model_version) = ModelVersionAttribute::parse_attribute(attr) {
+            model_versions.push(model_version);
+        }
+        ScopeAttribute::parse_attribute(attr, &mut scopes);
+        if let Some(default_scope) = DefaultScopeAttribute::parse_attribute(attr) {
+            default_scopes.push(default_scope);
+        }
+        if let Some(authorize) = AuthorizeAttribute::parse_attribute(attr) {
+            authorizations.push(authorize);
+        }
+        if let Some(tenant_field) = TenantScopedAttribute::parse_attribute(attr) {
+            tenant_fields.push(tenant_field);
+        }
+        if let Some(index) = IndexAttribute::parse_attribute(attr) {
+            indexes.push(index);
+        }
+        if let Some(conflict_policy) = ConflictPolicyAttribute::parse_attribute(attr) {
+            conflict_policies.push(conflict_policy);
+        }
     }
     if collection_names.len() > 1 {
         emit_call_site_error!("Only one collection_name attribute is allowed");
     }
     let collection_name = match collection_names.first() {
-        None => quote! { stringify!(#target_name) },
-        Some(CollectionNameAttribute(lit)) => quote! { #lit },
+        None => {
+            validate_collection_name(&target_name.to_string(), target_name.span());
+            quote! { stringify!(#target_name) }
+        }
+        Some(CollectionNameAttribute::Literal(lit)) => quote! { #lit },
+        Some(CollectionNameAttribute::Strategy(strategy)) => {
+            let name = strategy.apply(&target_name.to_string());
+            validate_collection_name(&name, target_name.span());
+            quote! { #name }
+        }
+    };
+    let edge_collection_quote = if is_edge_collection {
+        quote! { const IS_EDGE_COLLECTION: bool = true; }
+    } else {
+        quote! {}
+    };
+    if model_versions.len() > 1 {
+        emit_call_site_error!("Only one model_version attribute is allowed");
+    }
+    let model_version_quote = model_versions
+        .first()
+        .map(ModelVersionAttribute::token_stream);
+    let counter_caches_quote = counter_caches_token_stream(&counter_caches);
+    if default_scopes.len() > 1 {
+        emit_call_site_error!("Only one default_scope attribute is allowed");
+    }
+    let default_scope_quote = default_scopes
+        .first()
+        .map(DefaultScopeAttribute::token_stream);
+    if authorizations.len() > 1 {
+        emit_call_site_error!("Only one authorize attribute is allowed");
+    }
+    let authorize_quote = authorizations.first().map(AuthorizeAttribute::token_stream);
+    if tenant_fields.len() > 1 {
+        emit_call_site_error!("Only one tenant_scoped attribute is allowed");
+    }
+    let tenant_field_quote = tenant_fields
+        .first()
+        .map(TenantScopedAttribute::token_stream);
+    let indexes_quote = indexes_token_stream(&indexes);
+    if conflict_policies.len() > 1 {
+        emit_call_site_error!("Only one conflict_policy attribute is allowed");
+    }
+    let conflict_policy_quote = conflict_policies
+        .first()
+        .map(ConflictPolicyAttribute::token_stream);
+    let scopes_quote = if scopes.is_empty() {
+        quote! {}
+    } else {
+        let functions = scopes_token_stream(&scopes);
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+        quote! {
+            impl #impl_generics #target_name #ty_generics #where_clause {
+                #functions
+            }
+        }
     };
     let container = HooksContainer::from(hooks);
     let container_quote = container.token_stream();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let where_clause = record_where_clause(&ast.generics, where_clause);
+    let fmt_impls = redact::redacted_fmt(ast);
+    let query_fields_quote = query_fields::allowed_query_fields(ast);
     #[cfg(feature = "blocking")]
     let gen = quote! {
-        impl Record for #target_name {
+        #fmt_impls
+        #scopes_quote
+
+        impl #impl_generics Record for #target_name #ty_generics #where_clause {
              const COLLECTION_NAME :&'static str = #collection_name;
+            #edge_collection_quote
+            #counter_caches_quote
+            #model_version_quote
+            #tenant_field_quote
+            #default_scope_quote
+            #authorize_quote
+            #indexes_quote
+            #conflict_policy_quote
+            #query_fields_quote
 
             #container_quote
         }
     };
     #[cfg(not(feature = "blocking"))]
     let gen = quote! {
+        #fmt_impls
+        #scopes_quote
+
         #[aragog::async_trait::async_trait]
-        impl Record for #target_name {
+        impl #impl_generics Record for #target_name #ty_generics #where_clause {
             const COLLECTION_NAME :&'static str = #collection_name;
+            #edge_collection_quote
+            #counter_caches_quote
+            #model_version_quote
+            #tenant_field_quote
+            #default_scope_quote
+            #authorize_quote
+            #indexes_quote
+            #conflict_policy_quote
+            #query_fields_quote
 
             #container_quote
         }
@@ -56,3 +189,63 @@ pub fn impl_record_macro(ast: &syn::DeriveInput) -> TokenStream {
     // println!("{}", gen);
     gen.into()
 }
+
+/// Keeps the `#[register_record]` item and submits it to the model registry
+pub fn impl_register_record_macro(
+    attr: proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+) -> proc_macro2::TokenStream {
+    if !attr.is_empty() {
+        emit_error!(attr.span(), "`register_record` doesn't take options");
+    }
+    if !ast.generics.params.is_empty() {
+        emit_error!(
+            ast.generics.span(),
+            "`register_record` doesn't support generic types"
+        );
+        return quote! { #ast };
+    }
+    let target_name = &ast.ident;
+    quote! {
+        #ast
+
+        aragog::inventory::submit! {
+            aragog::RegisteredRecord {
+                collection_name: <#target_name as aragog::Record>::COLLECTION_NAME,
+                add_to_schema: aragog::schema::DatabaseSchema::add_record::<#target_name>,
+            }
+        }
+    }
+}
+
+/// Builds the `where` clause of the `Record` implementation.
+/// For generic types, the `Record` super traits bounds are added to the existing clause since they
+/// depend on the type parameters.
+fn record_where_clause(
+    generics: &Generics,
+    where_clause: Option<&WhereClause>,
+) -> proc_macro2::TokenStream {
+    if generics.type_params().next().is_none() {
+        return quote! { #where_clause };
+    }
+    let predicates = where_clause.map(|clause| &clause.predicates);
+    #[cfg(feature = "blocking")]
+    let bounds = quote! {
+        Self: aragog::serde::Serialize + aragog::serde::de::DeserializeOwned + Clone
+    };
+    #[cfg(not(feature = "blocking"))]
+    let bounds = quote! {
+        Self: aragog::serde::Serialize + aragog::serde::de::DeserializeOwned + Clone + Send
+    };
+    match predicates {
+        Some(predicates) if !predicates.is_empty() => {
+            let separator = if predicates.trailing_punct() {
+                quote! {}
+            } else {
+                quote! { , }
+            };
+            quote! { where #predicates #separator #bounds }
+        }
+        _ => quote! { where #bounds },
+    }
+}
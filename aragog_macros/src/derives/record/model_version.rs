@@ -0,0 +1,89 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Ident, Lit, Meta, NestedMeta};
+
+/// The `#[model_version(3, migrate_from = "upgrade")]` attribute
+pub struct ModelVersionAttribute {
+    version: u32,
+    migrate_from: Option<String>,
+}
+
+impl ModelVersionAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("model_version") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(meta.span(), "Expected a version: `#[model_version(1)]`");
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut nested = list.nested.iter();
+        let version = match nested.next() {
+            Some(NestedMeta::Lit(Lit::Int(lit))) => match lit.base10_parse::<u32>() {
+                Ok(0) => {
+                    emit_error!(lit.span(), "The model version must be greater than 0");
+                    return None;
+                }
+                Ok(version) => version,
+                Err(error) => {
+                    emit_error!(lit.span(), "Invalid model version: {}", error);
+                    return None;
+                }
+            },
+            _ => {
+                emit_error!(list.span(), "Expected the version number as first argument");
+                return None;
+            }
+        };
+        let mut migrate_from = None;
+        for nest in nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            match get_ident(&named_value.path).as_deref() {
+                Some("migrate_from") => migrate_from = expect_str_lit(&named_value.lit),
+                Some(_) => emit_error!(named_value.path.span(), "Can't find a valid option"),
+                None => (),
+            }
+        }
+        Some(Self {
+            version,
+            migrate_from,
+        })
+    }
+
+    /// Builds the `Record::MODEL_VERSION` constant and, if a function is set, the
+    /// `Record::migrate_from` implementation calling it
+    pub fn token_stream(&self) -> TokenStream {
+        let version = self.version;
+        let migrate_from = self.migrate_from.as_ref().map(|func| {
+            let func = Ident::new(func, Span::call_site());
+            quote! {
+                fn migrate_from(
+                    version: u32,
+                    value: aragog::serde_json::Value,
+                ) -> Result<Self, aragog::Error> {
+                    Self::#func(version, value)
+                }
+            }
+        });
+        quote! {
+            const MODEL_VERSION: u32 = #version;
+            #migrate_from
+        }
+    }
+}
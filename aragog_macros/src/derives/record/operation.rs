@@ -1,5 +1,5 @@
 use crate::parse_operation::{OperationValue, ParseOperation};
-use crate::toolbox::{expect_bool_lit, expect_str_lit, get_ident};
+use crate::toolbox::{expect_bool_lit, expect_int_lit, expect_str_lit, get_ident};
 use syn::{spanned::Spanned, Path};
 
 const FORBIDDEN_FUNCTIONS: [&str; 6] = [
@@ -15,16 +15,28 @@ const FORBIDDEN_FUNCTIONS: [&str; 6] = [
 pub enum HookOperation {
     Func(String),
     IsAsync(bool),
+    IsSync(bool),
     DbAccess(bool),
+    Priority(i64),
+}
+
+impl HookOperation {
+    /// Boolean options can be used as flags (`sync`) or with an explicit value (`sync = true`)
+    fn flag_value(path: &Path, value: Option<OperationValue>) -> Option<bool> {
+        match value {
+            None => Some(true),
+            Some(_) => expect_bool_lit(&Self::expect_literal_value(path, value)?),
+        }
+    }
 }
 
 impl ParseOperation for HookOperation {
     fn parse(path: &Path, value: Option<OperationValue>, field: Option<String>) -> Option<Self> {
         Self::expect_no_field(path, field)?;
         let ident = get_ident(path)?;
-        let lit = Self::expect_literal_value(path, value)?;
         let res = match ident.as_str() {
             "func" => {
+                let lit = Self::expect_literal_value(path, value)?;
                 let func = expect_str_lit(&lit)?;
                 if FORBIDDEN_FUNCTIONS.contains(&func.as_str()) {
                     emit_error!(
@@ -37,8 +49,12 @@ impl ParseOperation for HookOperation {
                 }
                 Self::Func(func)
             }
-            "db_access" => Self::DbAccess(expect_bool_lit(&lit)?),
-            "is_async" => Self::IsAsync(expect_bool_lit(&lit)?),
+            "db_access" => Self::DbAccess(Self::flag_value(path, value)?),
+            "is_async" => Self::IsAsync(Self::flag_value(path, value)?),
+            "sync" => Self::IsSync(Self::flag_value(path, value)?),
+            "priority" => {
+                Self::Priority(expect_int_lit(&Self::expect_literal_value(path, value)?)?)
+            }
             _ => {
                 emit_error!(path.span(), "Can't find a valid operation");
                 return None;
@@ -0,0 +1,41 @@
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::derives::json_schema::serde_attributes::{rename, ContainerAttributes, FieldAttributes};
+use crate::derives::record::redact::is_redacted;
+
+/// Builds the `allowed_query_fields` implementation listing the serialized fields of a struct,
+/// without the skipped and redacted fields.
+/// Returns `None` for enums, tuple structs and structs with flattened fields, whose fields can't
+/// be listed.
+pub fn allowed_query_fields(ast: &DeriveInput) -> Option<TokenStream> {
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let container = ContainerAttributes::parse(&ast.attrs);
+    let mut names = Vec::new();
+    for field in &fields.named {
+        let attributes = FieldAttributes::parse(&field.attrs);
+        if attributes.flatten {
+            return None;
+        }
+        if attributes.skip || is_redacted(&field.attrs) {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap().to_string();
+        names.push(
+            attributes
+                .rename
+                .unwrap_or_else(|| rename(&ident, container.rename_all.as_deref(), false)),
+        );
+    }
+    Some(quote! {
+        fn allowed_query_fields() -> Option<&'static [&'static str]> {
+            Some(&[#(#names),*])
+        }
+    })
+}
@@ -0,0 +1,148 @@
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, Meta, NestedMeta};
+
+const REDACTED_VALUE: &str = "[REDACTED]";
+const FIELD_OPTIONS: [&str; 1] = ["redact"];
+const CONTAINER_OPTIONS: [&str; 2] = ["redacted_debug", "redacted_display"];
+
+fn is_db_attribute(attr: &Attribute) -> bool {
+    attr.path.get_ident().map_or(false, |ident| ident == "db")
+}
+
+/// Parses the `#[db(...)]` attributes, returns the set options among `expected`
+fn db_options(attrs: &[Attribute], expected: &[&str]) -> Vec<String> {
+    let mut res = Vec::new();
+    for attr in attrs.iter().filter(|attr| is_db_attribute(attr)) {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => {
+                for nested in &list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path))
+                            if expected.iter().any(|option| path.is_ident(option)) =>
+                        {
+                            res.push(path.get_ident().unwrap().to_string());
+                        }
+                        _ => emit_error!(
+                            nested.span(),
+                            format!(
+                                "Unknown `db` option, expected one of `{}`",
+                                expected.join("`, `")
+                            )
+                        ),
+                    }
+                }
+            }
+            Ok(meta) => emit_error!(
+                meta.span(),
+                format!("Expected a meta list like `#[db({})]`", expected[0])
+            ),
+            Err(error) => emit_error!(
+                error.span(),
+                format!("Failed to parse attribute: {}", error)
+            ),
+        }
+    }
+    res
+}
+
+/// Parses the `#[db(...)]` field attributes, returns `true` if the field must be redacted
+pub fn is_redacted(attrs: &[Attribute]) -> bool {
+    !db_options(attrs, &FIELD_OPTIONS).is_empty()
+}
+
+/// Builds the `Debug` and `Display` implementations masking the fields marked with
+/// `#[db(redact)]`, opted in by the `#[db(redacted_debug)]` and `#[db(redacted_display)]`
+/// container attributes. Without them the user implements or derives the traits.
+pub fn redacted_fmt(ast: &DeriveInput) -> TokenStream {
+    let options = db_options(&ast.attrs, &CONTAINER_OPTIONS);
+    let fields = match &ast.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            if let Some(attr) = ast.attrs.iter().find(|attr| is_db_attribute(attr)) {
+                emit_error!(attr.span(), "`db` attributes are only supported on structs");
+            }
+            for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
+                if field.attrs.iter().any(is_db_attribute) {
+                    emit_error!(
+                        field.span(),
+                        "`db` attributes are only supported on structs"
+                    );
+                }
+            }
+            return quote! {};
+        }
+        Data::Union(_) => return quote! {},
+    };
+    let redacted: Vec<bool> = fields.iter().map(|f| is_redacted(&f.attrs)).collect();
+    if options.is_empty() {
+        return quote! {};
+    }
+    let target_name = &ast.ident;
+    let name = target_name.to_string();
+    let mut calls = Vec::new();
+    let mut bounds = Vec::new();
+    for (i, (field, redact)) in fields.iter().zip(redacted).enumerate() {
+        let member = field.ident.as_ref().map_or_else(
+            || {
+                let index = syn::Index::from(i);
+                quote! { #index }
+            },
+            |ident| quote! { #ident },
+        );
+        let value = if redact {
+            quote! { &#REDACTED_VALUE }
+        } else {
+            let ty = &field.ty;
+            bounds.push(quote! { #ty: ::std::fmt::Debug });
+            quote! { &self.#member }
+        };
+        calls.push(field.ident.as_ref().map_or_else(
+            || quote! { .field(#value) },
+            |ident| {
+                let field_name = ident.to_string();
+                quote! { .field(#field_name, #value) }
+            },
+        ));
+    }
+    let builder = match fields {
+        Fields::Named(_) => quote! { f.debug_struct(#name) },
+        Fields::Unnamed(_) | Fields::Unit => quote! { f.debug_tuple(#name) },
+    };
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    // Type parameters must be debuggable when used in non redacted fields
+    let where_clause = if ast.generics.type_params().next().is_none() {
+        quote! { #where_clause }
+    } else {
+        let predicates = where_clause
+            .map(|clause| &clause.predicates)
+            .filter(|predicates| !predicates.is_empty())
+            .map(|predicates| {
+                if predicates.trailing_punct() {
+                    quote! { #predicates }
+                } else {
+                    quote! { #predicates, }
+                }
+            });
+        quote! { where #predicates #(#bounds),* }
+    };
+    let body = quote! { #builder #(#calls)* .finish() };
+    CONTAINER_OPTIONS
+        .iter()
+        .filter(|option| options.iter().any(|o| o == *option))
+        .map(|option| {
+            let fmt_trait = if *option == "redacted_debug" {
+                quote! { ::std::fmt::Debug }
+            } else {
+                quote! { ::std::fmt::Display }
+            };
+            quote! {
+                impl #impl_generics #fmt_trait for #target_name #ty_generics #where_clause {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        #body
+                    }
+                }
+            }
+        })
+        .collect()
+}
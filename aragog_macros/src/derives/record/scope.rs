@@ -0,0 +1,307 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::{Literal, Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Ident, Meta, NestedMeta};
+
+/// The comparison operators of the scope conditions, the longest first
+const OPERATORS: [&str; 12] = [
+    "NOT LIKE", "NOT IN", "LIKE", "IN", "==", "!=", "<=", ">=", "=~", "!~", "<", ">",
+];
+
+/// A `field operator value` comparison of a scope condition
+struct ScopeComparison {
+    field: String,
+    operator: &'static str,
+    value: String,
+}
+
+/// A scope condition: comparisons chained with `&&` or `||`
+struct ScopeCondition {
+    first: ScopeComparison,
+    chained: Vec<(bool, ScopeComparison)>,
+}
+
+/// A `#[scope(active = "active == true", adults = "age >= 18")]` named scope
+pub struct ScopeAttribute {
+    name: String,
+    condition_str: String,
+    condition: ScopeCondition,
+}
+
+impl ScopeAttribute {
+    pub fn parse_attribute(attr: &Attribute, scopes: &mut Vec<Self>) {
+        if !attr.path.is_ident("scope") {
+            return;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected named scopes: `#[scope(active = \"active == true\")]`"
+                );
+                return;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return;
+            }
+        };
+        for nest in &list.nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            let (name, condition_str) = match (
+                get_ident(&named_value.path),
+                expect_str_lit(&named_value.lit),
+            ) {
+                (Some(name), Some(condition)) => (name, condition),
+                _ => continue,
+            };
+            if scopes.iter().any(|scope| scope.name == name) {
+                emit_error!(named_value.path.span(), "Duplicate scope `{}`", name);
+                continue;
+            }
+            match parse_condition(&condition_str) {
+                Ok(condition) => scopes.push(Self {
+                    name,
+                    condition_str,
+                    condition,
+                }),
+                Err(message) => emit_error!(named_value.lit.span(), "Invalid scope: {}", message),
+            }
+        }
+    }
+
+    /// Builds the scope function returning the condition as a `Filter`
+    fn token_stream(&self) -> TokenStream {
+        let name = Ident::new(&self.name, Span::call_site());
+        let doc = format!("The `{}` scope: `{}`", self.name, self.condition_str);
+        let filter = self.condition.token_stream();
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            pub fn #name() -> aragog::query::Filter {
+                #filter
+            }
+        }
+    }
+}
+
+/// The `#[default_scope("deleted == false")]` attribute, with a scope condition
+pub struct DefaultScopeAttribute(ScopeCondition);
+
+impl DefaultScopeAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("default_scope") {
+            return None;
+        }
+        let lit = match attr.parse_meta() {
+            Ok(Meta::List(list)) if list.nested.len() == 1 => match list.nested.first() {
+                Some(NestedMeta::Lit(lit)) => lit.clone(),
+                _ => {
+                    emit_error!(list.span(), "Expected a condition string");
+                    return None;
+                }
+            },
+            Ok(Meta::NameValue(named_value)) => named_value.lit,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected a condition: `#[default_scope(\"deleted == false\")]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let condition = expect_str_lit(&lit)?;
+        match parse_condition(&condition) {
+            Ok(condition) => Some(Self(condition)),
+            Err(message) => {
+                emit_error!(lit.span(), "Invalid default scope: {}", message);
+                None
+            }
+        }
+    }
+
+    /// Builds the `Record::default_scope` implementation
+    pub fn token_stream(&self) -> TokenStream {
+        let filter = self.0.token_stream();
+        quote! {
+            fn default_scope() -> Option<aragog::query::Filter> {
+                Some(#filter)
+            }
+        }
+    }
+}
+
+impl ScopeCondition {
+    fn token_stream(&self) -> TokenStream {
+        let first = self.first.token_stream();
+        let chained = self.chained.iter().map(|(is_and, comparison)| {
+            let comparison = comparison.token_stream();
+            if *is_and {
+                quote! { .and(#comparison) }
+            } else {
+                quote! { .or(#comparison) }
+            }
+        });
+        quote! { aragog::query::Filter::new(#first)#(#chained)* }
+    }
+}
+
+impl ScopeComparison {
+    fn token_stream(&self) -> TokenStream {
+        let field = &self.field;
+        let value = &self.value;
+        let builder = quote! { aragog::query::Comparison::field(#field) };
+        match self.operator {
+            "==" => quote! { #builder.equals(#value) },
+            "!=" => quote! { #builder.different_than(#value) },
+            "<" | "<=" | ">" | ">=" => {
+                let number = number_literal(value).unwrap();
+                match self.operator {
+                    "<" => quote! { #builder.lesser_than(#number) },
+                    "<=" => quote! { #builder.lesser_or_equal(#number) },
+                    ">" => quote! { #builder.greater_than(#number) },
+                    _ => quote! { #builder.greater_or_equal(#number) },
+                }
+            }
+            "LIKE" => quote! { #builder.like(#value) },
+            "NOT LIKE" => quote! { #builder.not_like(#value) },
+            "=~" => quote! { #builder.matches(#value) },
+            "!~" => quote! { #builder.does_not_match(#value) },
+            operator => {
+                let values = split_top_level(value, ",");
+                let values = values.iter().map(|(_, value)| value.trim());
+                if operator == "IN" {
+                    quote! { #builder.in_array(&[#(#values),*]) }
+                } else {
+                    quote! { #builder.not_in_array(&[#(#values),*]) }
+                }
+            }
+        }
+    }
+}
+
+/// Builds the scope functions, to be placed in an inherent implementation of the record
+pub fn scopes_token_stream(scopes: &[ScopeAttribute]) -> TokenStream {
+    let functions = scopes.iter().map(ScopeAttribute::token_stream);
+    quote! { #(#functions)* }
+}
+
+fn parse_condition(condition: &str) -> Result<ScopeCondition, String> {
+    let mut comparisons = split_top_level(condition, "&&|");
+    let (_, first) = comparisons.remove(0);
+    let first = parse_comparison(&first)?;
+    let chained = comparisons
+        .into_iter()
+        .map(|(is_and, comparison)| Ok((is_and, parse_comparison(&comparison)?)))
+        .collect::<Result<_, String>>()?;
+    Ok(ScopeCondition { first, chained })
+}
+
+fn parse_comparison(comparison: &str) -> Result<ScopeComparison, String> {
+    let comparison = comparison.trim();
+    let field_end = comparison
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(comparison.len());
+    let field = &comparison[..field_end];
+    if field.is_empty() || field.starts_with(|c: char| c.is_numeric()) {
+        return Err(format!("expected a field name in `{}`", comparison));
+    }
+    let rest = comparison[field_end..].trim_start();
+    let operator = OPERATORS
+        .iter()
+        .find(|operator| rest.starts_with(*operator))
+        .ok_or_else(|| {
+            format!(
+                "expected an operator ({}) after `{}`",
+                OPERATORS.join(", "),
+                field
+            )
+        })?;
+    let value = rest[operator.len()..].trim();
+    if value.is_empty() {
+        return Err(format!("expected a value in `{}`", comparison));
+    }
+    let value = match *operator {
+        "LIKE" | "NOT LIKE" | "=~" | "!~" => value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .ok_or_else(|| format!("expected a string pattern in `{}`", comparison))?,
+        "IN" | "NOT IN" => value
+            .strip_prefix('[')
+            .and_then(|value| value.strip_suffix(']'))
+            .ok_or_else(|| format!("expected an array in `{}`", comparison))?,
+        "<" | "<=" | ">" | ">=" if number_literal(value).is_none() => {
+            return Err(format!("expected a number in `{}`", comparison));
+        }
+        _ => value,
+    };
+    Ok(ScopeComparison {
+        field: field.to_string(),
+        operator,
+        value: value.to_string(),
+    })
+}
+
+/// The integer or float literal of a numeric `value`
+fn number_literal(value: &str) -> Option<Literal> {
+    match (value.parse::<i64>(), value.parse::<f64>()) {
+        (Ok(integer), _) => Some(Literal::i64_unsuffixed(integer)),
+        (_, Ok(float)) if float.is_finite() => Some(Literal::f64_unsuffixed(float)),
+        _ => None,
+    }
+}
+
+/// Splits `value` on the `separators` (`,` or `&&|`), ignoring the separators in strings and
+/// arrays. Every part is returned with `true` if it follows `&&`.
+fn split_top_level(value: &str, separators: &str) -> Vec<(bool, String)> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut is_and = true;
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+            current.push(c);
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 && separators == "," => {
+                parts.push((is_and, std::mem::take(&mut current)));
+                continue;
+            }
+            '&' | '|' if depth == 0 && separators != "," && chars.peek() == Some(&c) => {
+                chars.next();
+                parts.push((is_and, std::mem::take(&mut current)));
+                is_and = c == '&';
+                continue;
+            }
+            _ => (),
+        }
+        current.push(c);
+    }
+    parts.push((is_and, current));
+    parts
+}
@@ -0,0 +1,66 @@
+use crate::toolbox::{expect_str_lit, get_ident};
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Meta, NestedMeta};
+
+/// The `#[tenant_scoped(field = "org_id")]` attribute
+pub struct TenantScopedAttribute {
+    field: String,
+}
+
+impl TenantScopedAttribute {
+    pub fn parse_attribute(attr: &Attribute) -> Option<Self> {
+        if !attr.path.is_ident("tenant_scoped") {
+            return None;
+        }
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => {
+                emit_error!(
+                    meta.span(),
+                    "Expected a tenant field: `#[tenant_scoped(field = \"org_id\")]`"
+                );
+                return None;
+            }
+            Err(error) => {
+                emit_error!(
+                    error.span(),
+                    format!("Failed to parse attribute: {}", error)
+                );
+                return None;
+            }
+        };
+        let mut field = None;
+        for nest in &list.nested {
+            let named_value = if let NestedMeta::Meta(Meta::NameValue(named_value)) = nest {
+                named_value
+            } else {
+                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                continue;
+            };
+            match get_ident(&named_value.path).as_deref() {
+                Some("field") => match expect_str_lit(&named_value.lit) {
+                    Some(name) if name.is_empty() => {
+                        emit_error!(named_value.lit.span(), "The tenant field can't be empty");
+                    }
+                    Some(name) => field = Some(name),
+                    None => (),
+                },
+                Some(_) => emit_error!(named_value.path.span(), "Can't find a valid option"),
+                None => (),
+            }
+        }
+        if field.is_none() {
+            emit_error!(list.span(), "Missing `field` option");
+        }
+        field.map(|field| Self { field })
+    }
+
+    /// Builds the `Record::TENANT_FIELD` constant
+    pub fn token_stream(&self) -> TokenStream {
+        let field = &self.field;
+        quote! {
+            const TENANT_FIELD: Option<&'static str> = Some(#field);
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
-use syn::{spanned::Spanned, Field, Ident, Path};
+use syn::{spanned::Spanned, Field, Ident, Meta, Path};
 
 use crate::derives::validate::operation::Operation;
 use crate::parse_attribute::ParseAttribute;
 use crate::to_tokenstream::ToTokenStream;
-use crate::toolbox::expect_field_name;
+use crate::toolbox::{expect_field_name, expect_str_lit};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone)]
@@ -19,11 +19,13 @@ pub enum ValidateCommandType {
 pub struct ValidateCommand {
     operations: Vec<Operation>,
     command_type: ValidateCommandType,
+    message: Option<String>,
 }
 
 impl ParseAttribute for ValidateCommand {
     type AttributeOperation = Operation;
 
+    #[allow(clippy::option_if_let_else)]
     fn init(path: &Path, field: Option<&Field>) -> Option<Self> {
         let ident = path.get_ident()?;
         let command_type = match ident.to_string().as_str() {
@@ -41,6 +43,7 @@ impl ParseAttribute for ValidateCommand {
         Some(Self {
             command_type,
             operations: vec![],
+            message: None,
         })
     }
 
@@ -56,6 +59,19 @@ impl ParseAttribute for ValidateCommand {
         self.operations.push(operation);
     }
 
+    fn parse_option(&mut self, meta: &Meta) -> bool {
+        match meta {
+            Meta::NameValue(named_value) if named_value.path.is_ident("message") => {
+                if self.message.is_some() {
+                    emit_error!(named_value.span(), "Only one message is allowed");
+                }
+                self.message = expect_str_lit(&named_value.lit);
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn validate(&self, span: Span) -> bool {
         if self.operations.is_empty() {
             emit_error!(span, "Validation attribute requires at least one operation");
@@ -103,6 +119,21 @@ impl ToTokenStream for ValidateCommand {
                }
             };
         }
+        if let Some(message) = self.message {
+            // The rules errors are replaced by the custom message
+            quote = quote! {
+                {
+                    let mut rule_errors = Vec::new();
+                    {
+                        let errors = &mut rule_errors;
+                        #quote
+                    }
+                    if !rule_errors.is_empty() {
+                        errors.push(String::from(#message));
+                    }
+                }
+            };
+        }
         quote
     }
 }
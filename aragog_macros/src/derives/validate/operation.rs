@@ -2,7 +2,7 @@ use crate::parse_operation::{OperationValue, ParseOperation};
 use crate::toolbox::{expect_str_lit, expect_usize_lit, get_ident};
 use proc_macro2::{Span, TokenStream};
 use std::fmt::{self, Display, Formatter};
-use syn::{spanned::Spanned, Ident, Path};
+use syn::{spanned::Spanned, Ident, Lit, Path};
 
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -64,6 +64,43 @@ pub enum Operation {
     IsNone {
         field: String,
     },
+    GreaterThanField {
+        other: String,
+        field: String,
+    },
+    LesserThanField {
+        other: String,
+        field: String,
+    },
+    GreaterOrEqualField {
+        other: String,
+        field: String,
+    },
+    LesserOrEqualField {
+        other: String,
+        field: String,
+    },
+    EqualsField {
+        other: String,
+        field: String,
+    },
+    FieldsEqual {
+        left: String,
+        right: String,
+    },
+    Range {
+        min: Lit,
+        max: Lit,
+        field: String,
+    },
+    OneOf {
+        values: Vec<OperationValue>,
+        field: String,
+    },
+    NotOneOf {
+        values: Vec<OperationValue>,
+        field: String,
+    },
 }
 
 impl ParseOperation for Operation {
@@ -140,6 +177,61 @@ impl ParseOperation for Operation {
                 let field = Self::expect_field(path, field)?;
                 Self::IsNone { field }
             }
+            "greater_than_field" => {
+                let other = expect_str_lit(&Self::expect_literal_value(path, value)?)?;
+                let field = Self::expect_field(path, field)?;
+                Self::GreaterThanField { other, field }
+            }
+            "lesser_than_field" => {
+                let other = expect_str_lit(&Self::expect_literal_value(path, value)?)?;
+                let field = Self::expect_field(path, field)?;
+                Self::LesserThanField { other, field }
+            }
+            "greater_or_equal_field" => {
+                let other = expect_str_lit(&Self::expect_literal_value(path, value)?)?;
+                let field = Self::expect_field(path, field)?;
+                Self::GreaterOrEqualField { other, field }
+            }
+            "lesser_or_equal_field" => {
+                let other = expect_str_lit(&Self::expect_literal_value(path, value)?)?;
+                let field = Self::expect_field(path, field)?;
+                Self::LesserOrEqualField { other, field }
+            }
+            "equals_field" => {
+                let other = expect_str_lit(&Self::expect_literal_value(path, value)?)?;
+                let field = Self::expect_field(path, field)?;
+                Self::EqualsField { other, field }
+            }
+            "fields_equal" => {
+                emit_error!(
+                    path.span(),
+                    "Operation requires two field names: `fields_equal(\"a\", \"b\")`"
+                );
+                return None;
+            }
+            "range" => {
+                emit_error!(
+                    path.span(),
+                    "Operation requires named bounds: `range(min = 1, max = 10)`"
+                );
+                return None;
+            }
+            "one_of" => {
+                let value = Self::expect_value(path, value)?;
+                let field = Self::expect_field(path, field)?;
+                Self::OneOf {
+                    values: vec![value],
+                    field,
+                }
+            }
+            "not_one_of" => {
+                let value = Self::expect_value(path, value)?;
+                let field = Self::expect_field(path, field)?;
+                Self::NotOneOf {
+                    values: vec![value],
+                    field,
+                }
+            }
             "func" => {
                 let lit = Self::expect_literal_value(path, value)?;
                 let func = expect_str_lit(&lit)?;
@@ -163,6 +255,91 @@ impl ParseOperation for Operation {
         };
         Some(res)
     }
+
+    fn parse_values(
+        path: &Path,
+        values: Vec<OperationValue>,
+        field: Option<String>,
+    ) -> Option<Self> {
+        let str = get_ident(path)?;
+        let res = match str.as_str() {
+            "fields_equal" => {
+                Self::expect_no_field(path, field)?;
+                if values.len() != 2 {
+                    emit_error!(path.span(), "Operation requires exactly two field names");
+                    return None;
+                }
+                let mut names = Vec::with_capacity(2);
+                for value in values {
+                    names.push(expect_str_lit(&Self::expect_literal_value(
+                        path,
+                        Some(value),
+                    )?)?);
+                }
+                let right = names.pop()?;
+                let left = names.pop()?;
+                Self::FieldsEqual { left, right }
+            }
+            "one_of" => {
+                let field = Self::expect_field(path, field)?;
+                Self::OneOf { values, field }
+            }
+            "not_one_of" => {
+                let field = Self::expect_field(path, field)?;
+                Self::NotOneOf { values, field }
+            }
+            _ => {
+                emit_error!(
+                    path.span(),
+                    "Wrong format, only one value per operation allowed"
+                );
+                return None;
+            }
+        };
+        Some(res)
+    }
+
+    fn parse_named_values(
+        path: &Path,
+        values: Vec<(Path, Lit)>,
+        field: Option<String>,
+    ) -> Option<Self> {
+        let str = get_ident(path)?;
+        if str != "range" {
+            emit_error!(
+                path.span(),
+                "Wrong value, expected literal value or custom type got name value"
+            );
+            return None;
+        }
+        let field = Self::expect_field(path, field)?;
+        let (mut min, mut max) = (None, None);
+        for (name, lit) in values {
+            let bound = match get_ident(&name)?.as_str() {
+                "min" => &mut min,
+                "max" => &mut max,
+                _ => {
+                    emit_error!(name.span(), "Unknown range bound, expected `min` or `max`");
+                    return None;
+                }
+            };
+            if bound.replace(lit).is_some() {
+                emit_error!(name.span(), "Range bound defined twice");
+                return None;
+            }
+        }
+        match (min, max) {
+            (Some(min), Some(max)) => Some(Self::Range { min, max, field }),
+            _ => {
+                emit_error!(
+                    path.span(),
+                    "Operation requires both `min` and `max` bounds, \
+                    use `greater_or_equal` or `lesser_or_equal` for a single bound"
+                );
+                None
+            }
+        }
+    }
 }
 
 impl Display for Operation {
@@ -186,6 +363,15 @@ impl Display for Operation {
                 Self::CallValidations { .. } => "call_validations",
                 Self::IsSome { .. } => "is_some",
                 Self::IsNone { .. } => "is_none",
+                Self::GreaterThanField { .. } => "greater_than_field",
+                Self::LesserThanField { .. } => "lesser_than_field",
+                Self::GreaterOrEqualField { .. } => "greater_or_equal_field",
+                Self::LesserOrEqualField { .. } => "lesser_or_equal_field",
+                Self::EqualsField { .. } => "equals_field",
+                Self::FieldsEqual { .. } => "fields_equal",
+                Self::Range { .. } => "range",
+                Self::OneOf { .. } => "one_of",
+                Self::NotOneOf { .. } => "not_one_of",
             }
         )
     }
@@ -193,6 +379,7 @@ impl Display for Operation {
 
 impl Operation {
     //noinspection RsTypeCheck
+    #[allow(clippy::option_if_let_else)]
     fn field_token(
         field: &str,
         custom_token_stream: Option<TokenStream>,
@@ -215,6 +402,7 @@ impl Operation {
     }
 
     //noinspection RsTypeCheck
+    #[allow(clippy::or_fun_call)]
     pub(crate) fn token_stream(self, custom_token: Option<TokenStream>) -> TokenStream {
         match self {
             Self::MinLength { value, field } => {
@@ -301,6 +489,66 @@ impl Operation {
                     Self::validate_field_absence(#field, &#field_token, errors);
                 }
             }
+            Self::GreaterThanField { other, field } => {
+                let field_token = Self::field_token(&field, custom_token, false);
+                let other_token = Self::field_token(&other, None, false);
+                quote! {
+                    Self::validate_greater_than_field(#field, &#field_token, #other, &#other_token, errors);
+                }
+            }
+            Self::LesserThanField { other, field } => {
+                let field_token = Self::field_token(&field, custom_token, false);
+                let other_token = Self::field_token(&other, None, false);
+                quote! {
+                    Self::validate_lesser_than_field(#field, &#field_token, #other, &#other_token, errors);
+                }
+            }
+            Self::GreaterOrEqualField { other, field } => {
+                let field_token = Self::field_token(&field, custom_token, false);
+                let other_token = Self::field_token(&other, None, false);
+                quote! {
+                    Self::validate_greater_or_equal_to_field(#field, &#field_token, #other, &#other_token, errors);
+                }
+            }
+            Self::LesserOrEqualField { other, field } => {
+                let field_token = Self::field_token(&field, custom_token, false);
+                let other_token = Self::field_token(&other, None, false);
+                quote! {
+                    Self::validate_lesser_or_equal_to_field(#field, &#field_token, #other, &#other_token, errors);
+                }
+            }
+            Self::EqualsField { other, field } => {
+                let field_token = Self::field_token(&field, custom_token, false);
+                let other_token = Self::field_token(&other, None, false);
+                quote! {
+                    Self::validate_fields_equal(#field, &#field_token, #other, &#other_token, errors);
+                }
+            }
+            Self::FieldsEqual { left, right } => {
+                let left_token = Self::field_token(&left, None, false);
+                let right_token = Self::field_token(&right, None, false);
+                quote! {
+                    Self::validate_fields_equal(#left, &#left_token, #right, &#right_token, errors);
+                }
+            }
+            Self::Range { min, max, field } => {
+                let field_token = Self::field_token(&field, custom_token, true);
+                quote! {
+                    Self::validate_range(#field, #field_token, #min, #max, errors);
+                }
+            }
+            Self::OneOf { values, field } => {
+                let field_token = Self::field_token(&field, custom_token, true);
+                quote! {
+                    Self::validate_one_of(#field, &#field_token, &[#(#values),*], errors);
+                }
+            }
+            Self::NotOneOf { values, field } => {
+                let field_token = Self::field_token(&field, custom_token, true);
+                quote! {
+                    Self::validate_not_one_of(#field, &#field_token, &[#(#values),*], errors);
+                }
+            }
             Self::Function { func, field } => {
                 let func_ident = Ident::new(&func, Span::call_site());
                 field.map_or(
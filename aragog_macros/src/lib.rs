@@ -4,14 +4,21 @@
 extern crate proc_macro;
 extern crate proc_macro2;
 #[macro_use]
+extern crate quote;
+#[macro_use]
 extern crate proc_macro_error;
 
 use proc_macro::TokenStream;
 
 use syn::{self, DeriveInput};
 
-use crate::derives::{impl_record_macro, impl_validate_macro};
+use crate::aql::{impl_aql_macro, AqlInput};
+use crate::derives::{
+    impl_json_schema_macro, impl_merge_macro, impl_record_macro, impl_register_record_macro,
+    impl_validate_macro,
+};
 
+mod aql;
 mod derives;
 mod parse_attribute;
 mod parse_operation;
@@ -23,6 +30,15 @@ mod toolbox;
     Record,
     attributes(
         collection_name,
+        edge_collection,
+        counter_cache,
+        model_version,
+        scope,
+        default_scope,
+        authorize,
+        tenant_scoped,
+        index,
+        conflict_policy,
         before_create,
         before_save,
         before_write,
@@ -33,6 +49,7 @@ mod toolbox;
         after_delete,
         after_write,
         after_all,
+        db,
     )
 )]
 pub fn record_macro_derive(attr: TokenStream) -> TokenStream {
@@ -54,3 +71,56 @@ pub fn validate_macro_derive(attr: TokenStream) -> TokenStream {
     // Build the trait implementation
     impl_validate_macro(&ast)
 }
+
+/// Registers a `Record` type in the compile time model registry of the `registry` feature, used by
+/// `DatabaseSchema::from_registered_models`.
+///
+/// ```rust ignore
+/// #[register_record]
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// pub struct User {
+///     pub name: String,
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn register_record(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(item).unwrap();
+
+    impl_register_record_macro(attr.into(), &ast).into()
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(JsonSchema, attributes(json_schema))]
+pub fn json_schema_macro_derive(attr: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(attr).unwrap();
+
+    impl_json_schema_macro(&ast)
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(Merge, attributes(merge))]
+pub fn merge_macro_derive(attr: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(attr).unwrap();
+
+    impl_merge_macro(&ast)
+}
+
+/// Builds an `AqlQuery` from an AQL string literal checked at compile time: the query syntax is
+/// validated and every `@name` bind parameter must be provided as a `name = value` argument,
+/// `@@name` collection parameters as `@name = value` arguments.
+///
+/// ```rust ignore
+/// let query = aql!(
+///     "FOR u IN @@collection FILTER u.age >= @min RETURN u",
+///     @collection = "User",
+///     min = 18
+/// );
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn aql(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as AqlInput);
+
+    impl_aql_macro(&input).into()
+}
@@ -1,6 +1,6 @@
 use crate::parse_operation::{OperationValue, ParseOperation};
 use proc_macro2::Span;
-use syn::{spanned::Spanned, Attribute, Field, Meta, NestedMeta, Path};
+use syn::{spanned::Spanned, Attribute, Field, Lit, Meta, NestedMeta, Path};
 
 pub trait ParseAttribute: Sized {
     type AttributeOperation: ParseOperation;
@@ -13,6 +13,11 @@ pub trait ParseAttribute: Sized {
 
     fn validate(&self, span: Span) -> bool;
 
+    /// Parses an attribute level option (not an operation), returns `true` if `meta` was consumed
+    fn parse_option(&mut self, _meta: &Meta) -> bool {
+        false
+    }
+
     fn parse_operation(&self, meta: &Meta) -> Option<Self::AttributeOperation> {
         let operation = match meta {
             Meta::NameValue(named_value) => Self::AttributeOperation::parse(
@@ -21,12 +26,41 @@ pub trait ParseAttribute: Sized {
                 self.field(),
             )?,
             Meta::List(list) => {
+                if list
+                    .nested
+                    .iter()
+                    .any(|n| matches!(n, NestedMeta::Meta(Meta::NameValue(_))))
+                {
+                    let values = list
+                        .nested
+                        .iter()
+                        .map(|nest| match nest {
+                            NestedMeta::Meta(Meta::NameValue(named_value)) => {
+                                Some((named_value.path.clone(), named_value.lit.clone()))
+                            }
+                            _ => {
+                                emit_error!(nest.span(), "Expected a named value (`name = value`)");
+                                None
+                            }
+                        })
+                        .collect::<Option<Vec<(Path, Lit)>>>()?;
+                    return Self::AttributeOperation::parse_named_values(
+                        &list.path,
+                        values,
+                        self.field(),
+                    );
+                }
                 if list.nested.len() > 1 {
-                    emit_error!(
-                        list.span(),
-                        "Wrong format, only one value per operation allowed"
+                    let values = list
+                        .nested
+                        .iter()
+                        .map(OperationValue::parse)
+                        .collect::<Option<Vec<OperationValue>>>()?;
+                    return Self::AttributeOperation::parse_values(
+                        &list.path,
+                        values,
+                        self.field(),
                     );
-                    return None;
                 }
                 let nested_meta = if let Some(m) = list.nested.first() {
                     m
@@ -53,6 +87,9 @@ pub trait ParseAttribute: Sized {
                     for nest in list.nested.iter() {
                         match nest {
                             NestedMeta::Meta(meta) => {
+                                if cmd.parse_option(meta) {
+                                    continue;
+                                }
                                 if let Some(op) = cmd.parse_operation(meta) {
                                     cmd.add_operation(meta.span(), op);
                                 }
@@ -39,6 +39,32 @@ impl ToTokens for OperationValue {
 pub trait ParseOperation: Sized {
     fn parse(path: &Path, lit: Option<OperationValue>, field: Option<String>) -> Option<Self>;
 
+    /// Parses operations with multiple values, which are not allowed by default
+    fn parse_values(
+        path: &Path,
+        _values: Vec<OperationValue>,
+        _field: Option<String>,
+    ) -> Option<Self> {
+        emit_error!(
+            path.span(),
+            "Wrong format, only one value per operation allowed"
+        );
+        None
+    }
+
+    /// Parses operations with named values (`op(a = 1, b = 2)`), which are not allowed by default
+    fn parse_named_values(
+        path: &Path,
+        _values: Vec<(Path, Lit)>,
+        _field: Option<String>,
+    ) -> Option<Self> {
+        emit_error!(
+            path.span(),
+            "Wrong value, expected literal value or custom type got name value"
+        );
+        None
+    }
+
     fn expect_no_field(path: &Path, field: Option<String>) -> Option<()> {
         if field.is_none() {
             Some(())
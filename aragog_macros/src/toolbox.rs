@@ -37,6 +37,21 @@ pub fn expect_usize_lit(lit: &Lit) -> Option<usize> {
     }
 }
 
+pub fn expect_int_lit(lit: &Lit) -> Option<i64> {
+    if let Lit::Int(val) = lit {
+        val.base10_parse().map_or_else(
+            |error| {
+                emit_error!(lit.span(), "Invalid integer value: {}", error);
+                None
+            },
+            Some,
+        )
+    } else {
+        emit_error!(lit.span(), "Expected an integer value");
+        None
+    }
+}
+
 pub fn expect_str_lit(lit: &Lit) -> Option<String> {
     if let Lit::Str(val) = lit {
         Some(val.value())
@@ -33,7 +33,7 @@ async fn main() {
         .unwrap();
 
     // Testing purposes
-    db_connection.truncate().await;
+    db_connection.unsafe_truncate_all().await.unwrap();
 
     let boxed_connection = BoxedConnection {
         connection: Box::new(db_connection),
@@ -45,7 +45,7 @@ async fn main() {
         last_name: String::from("Surcouf"),
     };
 
-    DatabaseRecord::create(user, boxed_connection.connection())
+    DatabaseRecord::create(user, &boxed_connection.connection)
         .await
         .unwrap();
 
@@ -36,7 +36,7 @@ async fn main() {
         .await
         .unwrap();
     // Testing purposes
-    db_connection.truncate().await;
+    db_connection.unsafe_truncate_all().await.unwrap();
 
     // Character creation
 
@@ -34,7 +34,7 @@ async fn main() {
         .unwrap();
 
     // Testing purposes
-    db_connection.truncate().await;
+    db_connection.unsafe_truncate_all().await.unwrap();
 
     // Instantiate a new dish
     let dish = Dish::new(DishDTO {
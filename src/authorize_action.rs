@@ -1,4 +1,4 @@
-use crate::{DatabaseRecord, Error, Record};
+use crate::{DatabaseRecord, Error, Record, RecordAction};
 
 /// The `AuthorizeAction` trait of the Aragog library.
 /// This traits allows provides the ability to authorize a [`Record`] to execute a custom action on
@@ -64,6 +64,38 @@ pub trait AuthorizeAction<T: Record> {
         Err(Error::Forbidden(None))
     }
 
+    /// Authorizes the operation `actor` document, set with [`OperationOptions::actor`], to do
+    /// `action` on `target`.
+    ///
+    /// Used by the `#[authorize(actor = "...")]` attribute of the `Record` derive macro to
+    /// implement [`Record::authorize_write`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`]::[`Forbidden`] if `actor` is not a document of `Self` or if the action is
+    /// not authorized.
+    ///
+    /// [`OperationOptions::actor`]: crate::OperationOptions::actor
+    /// [`Record::authorize_write`]: crate::Record::authorize_write
+    /// [`Error`]: crate::Error
+    /// [`Forbidden`]: crate::Error::Forbidden
+    fn authorize_actor(
+        actor: &serde_json::Value,
+        action: RecordAction,
+        target: Option<&DatabaseRecord<T>>,
+    ) -> Result<(), Error>
+    where
+        Self: Record,
+        Self::Action: From<RecordAction>,
+    {
+        let actor: DatabaseRecord<Self> =
+            serde_json::from_value(actor.clone()).map_err(|_| Error::Forbidden(None))?;
+        if actor.id().split('/').next() != Some(Self::COLLECTION_NAME) {
+            return Err(Error::Forbidden(None));
+        }
+        actor.record.authorize_action(action.into(), target)
+    }
+
     /// Returns true if the object is authorized to do `action` on `target`
     fn is_action_authorized(
         &self,
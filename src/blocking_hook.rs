@@ -0,0 +1,88 @@
+//! Calls of the `is_async` derive hooks in blocking mode.
+//!
+//! The hook functions declared with `maybe_async` are synchronous in blocking mode and return
+//! their result directly, the plain `async fn` hooks return a future which is run to completion.
+//! The derive macro picks the right call through method resolution:
+//!
+//! ```ignore
+//! use aragog::blocking_hook::{BlockOnHook as _, ReadyHook as _};
+//! (&aragog::blocking_hook::BlockingHook::new(self.my_hook())).resolve()?;
+//! ```
+use std::cell::Cell;
+use std::future::Future;
+
+use crate::Error;
+
+/// The value returned by an `is_async` hook function, a result or a future
+pub struct BlockingHook<T>(Cell<Option<T>>);
+
+impl<T> BlockingHook<T> {
+    /// Wraps the returned `value`
+    pub const fn new(value: T) -> Self {
+        Self(Cell::new(Some(value)))
+    }
+
+    fn take(&self) -> T {
+        self.0.take().expect("hook value can only be resolved once")
+    }
+}
+
+/// Resolves the result of a synchronous hook, preferred by method resolution
+pub trait ReadyHook {
+    /// Returns the hook result
+    ///
+    /// # Errors
+    ///
+    /// Returns the hook error
+    fn resolve(&self) -> Result<(), Error>;
+}
+
+impl ReadyHook for BlockingHook<Result<(), Error>> {
+    fn resolve(&self) -> Result<(), Error> {
+        self.take()
+    }
+}
+
+/// Resolves the future of an `async fn` hook by blocking the current thread
+pub trait BlockOnHook {
+    /// Runs the hook future to completion and returns its result
+    ///
+    /// # Errors
+    ///
+    /// Returns the hook error
+    fn resolve(&self) -> Result<(), Error>;
+}
+
+impl<F> BlockOnHook for &BlockingHook<F>
+where
+    F: Future<Output = Result<(), Error>>,
+{
+    fn resolve(&self) -> Result<(), Error> {
+        futures_executor::block_on(self.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn async_hook(called: &mut bool) -> Result<(), Error> {
+        *called = true;
+        Ok(())
+    }
+
+    fn sync_hook() -> Result<(), Error> {
+        Err(Error::ValidationError(String::from("invalid")))
+    }
+
+    #[test]
+    fn hooks_are_resolved() {
+        let mut called = false;
+        (&BlockingHook::new(async_hook(&mut called)))
+            .resolve()
+            .unwrap();
+        assert!(called);
+        let res = (&BlockingHook::new(sync_hook())).resolve();
+        assert!(matches!(res, Err(Error::ValidationError(_))));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{AqlQuery, DatabaseAccess, Error, Record};
+
+/// A count of the edges linked to a vertex, stored in a numeric field of the vertex and maintained
+/// by the hooks of the edges, like a Rails counter cache.
+///
+/// The counter caches are declared on the counted record (see [`Record::COUNTER_CACHES`]) and
+/// registered on the connection with [`DatabaseConnectionBuilder::with_counter_caches`]. The
+/// counter is then incremented when an edge of the `edge` collection is created through
+/// [`DatabaseRecord`] and decremented when it's deleted, including by
+/// [`DatabaseRecord::detach_delete`], whether the hooks run or not. Edges written through raw AQL
+/// don't update the counters.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{Record, Validate};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// // Creating or deleting `Likes` edges to a post updates its `likes_count` field
+/// #[derive(Clone, Serialize, Deserialize, Record, Validate)]
+/// #[counter_cache(edge = "Likes", field = "likes_count")]
+/// pub struct Post {
+///     pub title: String,
+///     #[serde(default)]
+///     pub likes_count: u64,
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize, Record, Validate)]
+/// pub struct Likes {}
+/// ```
+///
+/// [`DatabaseRecord`]: crate::DatabaseRecord
+/// [`DatabaseRecord::detach_delete`]: crate::DatabaseRecord::detach_delete
+/// [`Record::COUNTER_CACHES`]: crate::Record::COUNTER_CACHES
+/// [`DatabaseConnectionBuilder::with_counter_caches`]: crate::DatabaseConnectionBuilder::with_counter_caches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterCache {
+    /// The edge collection of the counted edges
+    pub edge: &'static str,
+    /// The numeric vertex field storing the count
+    pub field: &'static str,
+    /// The edge vertex holding the count
+    pub vertex: CounterCacheVertex,
+}
+
+/// The edge vertex storing a [`CounterCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterCacheVertex {
+    /// The `_from` vertex, counting its outbound edges
+    From,
+    /// The `_to` vertex, counting its inbound edges
+    To,
+}
+
+impl CounterCache {
+    /// Initializes a counter cache of the `edge` collection edges stored in the `field` of their
+    /// `_to` vertex
+    #[must_use]
+    #[inline]
+    pub const fn new(edge: &'static str, field: &'static str) -> Self {
+        Self {
+            edge,
+            field,
+            vertex: CounterCacheVertex::To,
+        }
+    }
+
+    /// Counts the edges of the `_from` vertex instead
+    #[must_use]
+    #[inline]
+    pub const fn on_from_vertex(mut self) -> Self {
+        self.vertex = CounterCacheVertex::From;
+        self
+    }
+
+    /// Adds `delta` to the counter of the vertex identified by `vertex_id`, the counter never goes
+    /// below zero. Does nothing if the vertex doesn't exist.
+    #[maybe_async::maybe_async]
+    pub(crate) async fn apply<D>(
+        &self,
+        vertex_id: &str,
+        delta: i8,
+        db_accessor: &D,
+    ) -> Result<(), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let (collection, key) = vertex_id.split_once('/').unwrap_or_default();
+        log::debug!(
+            "Updating counter cache {} of {} by {}",
+            self.field,
+            vertex_id,
+            delta
+        );
+        let query = AqlQuery::new(
+            "FOR v IN @@collection FILTER v._key == @key \
+            UPDATE v WITH { [@field]: MAX([(v[@field] || 0) + @delta, 0]) } IN @@collection",
+        )
+        .bind_var("@collection", collection)
+        .bind_var("key", key)
+        .bind_var("field", self.field)
+        .bind_var("delta", delta);
+        let _: Vec<serde_json::Value> = db_accessor.database().aql_query(query).await?;
+        Ok(())
+    }
+}
+
+/// Checks if counter caches of the `edge_collection` edges are registered on the connection
+pub fn is_counted<D>(edge_collection: &str, db_accessor: &D) -> bool
+where
+    D: DatabaseAccess + ?Sized,
+{
+    db_accessor
+        .counter_caches()
+        .iter()
+        .any(|(_, counter_cache)| counter_cache.edge == edge_collection)
+}
+
+/// An edge of a counted collection, see [`is_counted`]
+#[derive(Debug, Clone)]
+pub struct CountedEdge {
+    /// The edge collection
+    pub collection: String,
+    /// The `_from` vertex id
+    pub id_from: String,
+    /// The `_to` vertex id
+    pub id_to: String,
+}
+
+impl CountedEdge {
+    /// Retrieves the vertices of `edge`, if `T` is an edge collection with registered counter
+    /// caches
+    pub fn of<T, D>(edge: &T, db_accessor: &D) -> Result<Option<Self>, Error>
+    where
+        T: Record,
+        D: DatabaseAccess + ?Sized,
+    {
+        if !T::IS_EDGE_COLLECTION || !is_counted(T::COLLECTION_NAME, db_accessor) {
+            return Ok(None);
+        }
+        let value = serde_json::to_value(edge)?;
+        let vertex_id = |field: &str| {
+            value
+                .get(field)
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        Ok(Some(Self {
+            collection: T::COLLECTION_NAME.to_string(),
+            id_from: vertex_id("_from"),
+            id_to: vertex_id("_to"),
+        }))
+    }
+
+    /// Updates by `delta` the registered counter caches of the edge vertices
+    #[maybe_async::maybe_async]
+    pub async fn update<D>(&self, delta: i8, db_accessor: &D) -> Result<(), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let counter_caches = db_accessor
+            .counter_caches()
+            .iter()
+            .filter(|(_, counter_cache)| counter_cache.edge == self.collection);
+        for (collection, counter_cache) in counter_caches {
+            let vertex_id = match counter_cache.vertex {
+                CounterCacheVertex::From => &self.id_from,
+                CounterCacheVertex::To => &self.id_to,
+            };
+            // The edge collection may link vertices of several collections
+            if vertex_id.split('/').next() == Some(*collection) {
+                counter_cache.apply(vertex_id, delta, db_accessor).await?;
+            }
+        }
+        Ok(())
+    }
+}
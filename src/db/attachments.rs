@@ -0,0 +1,403 @@
+use std::io::{Read, Write};
+
+use arangors_lite::index::IndexSettings;
+use arangors_lite::AqlQuery;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::{CollectionSchema, DatabaseSchema, IndexSchema};
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+
+const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+const CHUNK_INDEX_NAME: &str = "attachment_chunks";
+
+/// The metadata of a binary payload stored by [`Attachments`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// The attachment document `_key`
+    #[serde(rename = "_key")]
+    pub key: String,
+    /// The attachment document `_id`
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// The `_id` of the owner document
+    pub owner_id: String,
+    /// The attachment name, like a file name
+    pub name: String,
+    /// The MIME type of the payload
+    pub content_type: String,
+    /// The payload size in bytes
+    pub size: u64,
+    /// The number of chunk documents storing the payload
+    pub chunk_count: u32,
+}
+
+/// Small binary payloads storage, linking attachments to their owner documents.
+///
+/// The payloads are split in base64 encoded chunk documents, the attachment metadata is stored in
+/// a document linked to its owner through an edge. The three collections (`Attachment`,
+/// `AttachmentChunk` and `HasAttachment` by default) must be in the database schema, see
+/// [`add_to_schema`].
+///
+/// # Note
+///
+/// The payloads are streamed chunk by chunk. For large files or heavy traffic, prefer a dedicated
+/// blob storage.
+///
+/// # Example
+///
+/// ```rust no_run
+/// # use aragog::{Attachments, DatabaseConnection, DatabaseRecord, Record};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// # #[derive(Record, Clone, Serialize, Deserialize)]
+/// # struct User {}
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+/// # let user = DatabaseRecord::create(User {}, &db_accessor).await.unwrap();
+/// let attachments = Attachments::new(&db_accessor);
+/// let file = std::fs::File::open("avatar.png").unwrap();
+/// let avatar = attachments.upload_reader(&user, "avatar.png", "image/png", file).await.unwrap();
+/// // The attachments of the user
+/// let user_attachments = attachments.list(user.id()).await.unwrap();
+/// // The payload
+/// let data: Vec<u8> = attachments.download(&avatar).await.unwrap();
+/// # }
+/// ```
+///
+/// [`add_to_schema`]: Self::add_to_schema
+#[derive(Clone, Debug)]
+pub struct Attachments<'a, D: ?Sized> {
+    db_accessor: &'a D,
+    collection: String,
+    chunk_collection: String,
+    edge_collection: String,
+    chunk_size: usize,
+}
+
+impl<'a, D> Attachments<'a, D>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    /// Instantiates the attachments storage on `db_accessor`, with the default collections and a
+    /// 256 KiB chunk size
+    #[must_use]
+    pub fn new(db_accessor: &'a D) -> Self {
+        Self {
+            db_accessor,
+            collection: String::from("Attachment"),
+            chunk_collection: String::from("AttachmentChunk"),
+            edge_collection: String::from("HasAttachment"),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Sets the attachment metadata, chunk and owner edge collection names
+    #[must_use]
+    pub fn collections(
+        mut self,
+        collection: &str,
+        chunk_collection: &str,
+        edge_collection: &str,
+    ) -> Self {
+        self.collection = collection.to_string();
+        self.chunk_collection = chunk_collection.to_string();
+        self.edge_collection = edge_collection.to_string();
+        self
+    }
+
+    /// Sets the maximum payload size stored in a chunk document, at least one byte
+    #[must_use]
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Adds the attachments collections and the chunk index to `schema`, if missing
+    pub fn add_to_schema(&self, schema: &mut DatabaseSchema) {
+        for (name, is_edge_collection) in [
+            (&self.collection, false),
+            (&self.chunk_collection, false),
+            (&self.edge_collection, true),
+        ] {
+            if schema.collection_index(name).is_none() {
+                schema
+                    .collections
+                    .push(CollectionSchema::new(name, is_edge_collection, None));
+            }
+        }
+        let has_chunk_index = schema.indexes.iter().any(|index| {
+            index.collection == self.chunk_collection && index.name == CHUNK_INDEX_NAME
+        });
+        if !has_chunk_index {
+            schema.indexes.push(IndexSchema {
+                name: CHUNK_INDEX_NAME.to_string(),
+                collection: self.chunk_collection.clone(),
+                fields: vec![String::from("attachment"), String::from("index")],
+                settings: IndexSettings::Persistent {
+                    unique: true,
+                    sparse: false,
+                    deduplicate: false,
+                },
+            });
+        }
+    }
+
+    /// Stores `data` as an attachment of `owner`.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure, like [`upload_reader`]
+    ///
+    /// [`upload_reader`]: Self::upload_reader
+    #[maybe_async::maybe_async]
+    pub async fn upload<T: Record>(
+        &self,
+        owner: &DatabaseRecord<T>,
+        name: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Result<Attachment, Error> {
+        self.upload_reader(owner, name, content_type, data).await
+    }
+
+    /// Streams the payload of `reader` as an attachment of `owner`, storing a chunk document
+    /// every [`chunk_size`] bytes.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on `reader` or database request failure, the partially stored attachment being
+    /// removed.
+    ///
+    /// [`chunk_size`]: Self::chunk_size
+    #[maybe_async::maybe_async]
+    pub async fn upload_reader<T: Record, R: Read>(
+        &self,
+        owner: &DatabaseRecord<T>,
+        name: &str,
+        content_type: &str,
+        reader: R,
+    ) -> Result<Attachment, Error> {
+        log::debug!(
+            "Uploading {} attachment {} to {}",
+            owner.id(),
+            name,
+            self.collection
+        );
+        let aql_query = AqlQuery::new(
+            "INSERT { owner_id: @owner, name: @name, content_type: @content_type, size: 0, chunk_count: 0 } \
+            INTO @@collection RETURN NEW",
+        )
+        .bind_var("owner", owner.id().as_str())
+        .bind_var("name", name)
+        .bind_var("content_type", content_type)
+        .bind_var("@collection", self.collection.as_str());
+        let attachment: Vec<Attachment> = self.db_accessor.database().aql_query(aql_query).await?;
+        let mut attachment = attachment
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::InternalError {
+                message: Some(format!("Failed to create {} attachment", name)),
+            })?;
+        let result = self.write_chunks(&mut attachment, reader).await;
+        if let Err(error) = result {
+            if let Err(cleanup_error) = self.delete(&attachment).await {
+                log::error!(
+                    "Failed to remove attachment {}: {}",
+                    attachment.id,
+                    cleanup_error
+                );
+            }
+            return Err(error);
+        }
+        Ok(attachment)
+    }
+
+    /// Retrieves an attachment metadata from its `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if the attachment doesn't exist
+    #[maybe_async::maybe_async]
+    pub async fn find(&self, key: &str) -> Result<Attachment, Error> {
+        let aql_query = AqlQuery::new("RETURN DOCUMENT(@@collection, @key)")
+            .bind_var("@collection", self.collection.as_str())
+            .bind_var("key", key);
+        let attachments: Vec<Option<Attachment>> =
+            self.db_accessor.database().aql_query(aql_query).await?;
+        attachments
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or_else(|| Error::NotFound {
+                item: self.collection.clone(),
+                id: key.to_string(),
+                source: None,
+            })
+    }
+
+    /// Retrieves the attachments of the `owner_id` document, sorted by name
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    #[maybe_async::maybe_async]
+    pub async fn list(&self, owner_id: &str) -> Result<Vec<Attachment>, Error> {
+        let aql_query =
+            AqlQuery::new("FOR a IN 1..1 OUTBOUND @owner @@edge_collection SORT a.name RETURN a")
+                .bind_var("owner", owner_id)
+                .bind_var("@edge_collection", self.edge_collection.as_str());
+        Ok(self.db_accessor.database().aql_query(aql_query).await?)
+    }
+
+    /// Retrieves the whole payload of `attachment`
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure or missing chunk, like [`download_to`]
+    ///
+    /// [`download_to`]: Self::download_to
+    #[maybe_async::maybe_async]
+    pub async fn download(&self, attachment: &Attachment) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::with_capacity(usize::try_from(attachment.size).unwrap_or_default());
+        self.download_to(attachment, &mut data).await?;
+        Ok(data)
+    }
+
+    /// Streams the payload of `attachment` to `writer` chunk by chunk, returning the written size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] on missing chunk and [`Error::UnprocessableEntity`] on corrupted
+    /// chunk or `writer` failure
+    #[maybe_async::maybe_async]
+    pub async fn download_to<W: Write>(
+        &self,
+        attachment: &Attachment,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let mut written = 0;
+        for index in 0..attachment.chunk_count {
+            let aql_query = AqlQuery::new(
+                "FOR c IN @@chunk_collection FILTER c.attachment == @attachment && c.index == @index \
+                LIMIT 1 RETURN c.data",
+            )
+            .bind_var("@chunk_collection", self.chunk_collection.as_str())
+            .bind_var("attachment", attachment.key.as_str())
+            .bind_var("index", index);
+            let chunks: Vec<String> = self.db_accessor.database().aql_query(aql_query).await?;
+            let chunk = chunks.first().ok_or_else(|| Error::NotFound {
+                item: self.chunk_collection.clone(),
+                id: format!("{} chunk {}", attachment.id, index),
+                source: None,
+            })?;
+            let bytes = base64::decode(chunk).map_err(|error| Error::UnprocessableEntity {
+                source: Box::new(error),
+            })?;
+            writer
+                .write_all(&bytes)
+                .map_err(|error| Error::UnprocessableEntity {
+                    source: Box::new(error),
+                })?;
+            written += bytes.len() as u64;
+        }
+        Ok(written)
+    }
+
+    /// Removes `attachment`, its chunks and its owner edge
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    #[maybe_async::maybe_async]
+    pub async fn delete(&self, attachment: &Attachment) -> Result<(), Error> {
+        log::debug!("Removing attachment {}", attachment.id);
+        let database = self.db_accessor.database();
+        let aql_query = AqlQuery::new(
+            "FOR c IN @@chunk_collection FILTER c.attachment == @attachment REMOVE c IN @@chunk_collection",
+        )
+        .bind_var("@chunk_collection", self.chunk_collection.as_str())
+        .bind_var("attachment", attachment.key.as_str());
+        let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+        let aql_query = AqlQuery::new(
+            "FOR e IN @@edge_collection FILTER e._to == @id REMOVE e IN @@edge_collection",
+        )
+        .bind_var("@edge_collection", self.edge_collection.as_str())
+        .bind_var("id", attachment.id.as_str());
+        let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+        let aql_query = AqlQuery::new("REMOVE @key IN @@collection OPTIONS { ignoreErrors: true }")
+            .bind_var("@collection", self.collection.as_str())
+            .bind_var("key", attachment.key.as_str());
+        let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+        Ok(())
+    }
+
+    /// Stores the chunks of `reader`, then updates the `attachment` metadata and links it to
+    /// its owner
+    #[maybe_async::maybe_async]
+    async fn write_chunks<R: Read>(
+        &self,
+        attachment: &mut Attachment,
+        mut reader: R,
+    ) -> Result<(), Error> {
+        let database = self.db_accessor.database();
+        loop {
+            let chunk = read_chunk(&mut reader, self.chunk_size)?;
+            if chunk.is_empty() {
+                break;
+            }
+            let aql_query = AqlQuery::new(
+                "INSERT { attachment: @attachment, index: @index, data: @data } INTO @@chunk_collection",
+            )
+            .bind_var("attachment", attachment.key.as_str())
+            .bind_var("index", attachment.chunk_count)
+            .bind_var("data", base64::encode(&chunk))
+            .bind_var("@chunk_collection", self.chunk_collection.as_str());
+            let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+            attachment.chunk_count += 1;
+            attachment.size += chunk.len() as u64;
+        }
+        let aql_query = AqlQuery::new(
+            "UPDATE @key WITH { size: @size, chunk_count: @chunk_count } IN @@collection",
+        )
+        .bind_var("key", attachment.key.as_str())
+        .bind_var("size", attachment.size)
+        .bind_var("chunk_count", attachment.chunk_count)
+        .bind_var("@collection", self.collection.as_str());
+        let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+        let aql_query =
+            AqlQuery::new("INSERT { _from: @owner, _to: @attachment } INTO @@edge_collection")
+                .bind_var("owner", attachment.owner_id.as_str())
+                .bind_var("attachment", attachment.id.as_str())
+                .bind_var("@edge_collection", self.edge_collection.as_str());
+        let _: Vec<serde_json::Value> = database.aql_query(aql_query).await?;
+        Ok(())
+    }
+}
+
+/// Reads the next chunk of at most `chunk_size` bytes of `reader`, empty at the end of `reader`
+fn read_chunk<R: Read>(reader: &mut R, chunk_size: usize) -> Result<Vec<u8>, Error> {
+    let mut chunk = Vec::with_capacity(chunk_size);
+    reader
+        .take(chunk_size as u64)
+        .read_to_end(&mut chunk)
+        .map_err(|error| Error::UnprocessableEntity {
+            source: Box::new(error),
+        })?;
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks() {
+        let mut reader = &[1_u8, 2, 3, 4, 5][..];
+        assert_eq!(read_chunk(&mut reader, 2).unwrap(), vec![1, 2]);
+        assert_eq!(read_chunk(&mut reader, 2).unwrap(), vec![3, 4]);
+        assert_eq!(read_chunk(&mut reader, 2).unwrap(), vec![5]);
+        assert!(read_chunk(&mut reader, 2).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{self, Display, Formatter};
+
+/// The change of a record field, see [`ChangeSet`] and [`diff`]
+///
+/// [`diff`]: crate::util::diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// The name of the changed field, or its JSON pointer for the [`diff`] changes
+    ///
+    /// [`diff`]: crate::util::diff
+    pub field: String,
+    /// The previous field value, `None` if the field was added
+    pub old: Option<Value>,
+    /// The new field value, `None` if the field was removed
+    pub new: Option<Value>,
+}
+
+/// The fields changed by a record save, see [`DatabaseRecord::save_and_diff`]
+///
+/// [`DatabaseRecord::save_and_diff`]: crate::DatabaseRecord::save_and_diff
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    /// The document revision before the save
+    pub old_rev: String,
+    /// The document revision after the save
+    pub new_rev: String,
+    /// The changed top level fields, sorted by name
+    pub changes: Vec<FieldChange>,
+}
+
+impl ChangeSet {
+    /// Computes the changed top level fields between the `old` and `new` serialized records
+    #[must_use]
+    pub fn new(old_rev: String, new_rev: String, old: &Value, new: &Value) -> Self {
+        let empty = serde_json::Map::new();
+        let old = old.as_object().unwrap_or(&empty);
+        let new = new.as_object().unwrap_or(&empty);
+        let mut fields: Vec<&String> = old.keys().chain(new.keys()).collect();
+        fields.sort();
+        fields.dedup();
+        let changes = fields
+            .into_iter()
+            .filter(|field| old.get(*field) != new.get(*field))
+            .map(|field| FieldChange {
+                field: field.clone(),
+                old: old.get(field).cloned(),
+                new: new.get(field).cloned(),
+            })
+            .collect();
+        Self {
+            old_rev,
+            new_rev,
+            changes,
+        }
+    }
+
+    /// Returns `true` if the save didn't change any field
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Retrieves the change of `field`, if it changed
+    #[must_use]
+    pub fn get(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.field == field)
+    }
+
+    /// Returns `true` if `field` changed
+    #[must_use]
+    pub fn contains(&self, field: &str) -> bool {
+        self.get(field).is_some()
+    }
+
+    /// The names of the changed fields
+    #[must_use]
+    pub fn fields(&self) -> Vec<&str> {
+        self.changes
+            .iter()
+            .map(|change| change.field.as_str())
+            .collect()
+    }
+}
+
+impl Display for ChangeSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}: {} changed",
+            self.old_rev,
+            self.new_rev,
+            self.fields().join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_fields() {
+        let old = serde_json::json!({ "name": "Robert", "age": 18, "nickname": "Bob" });
+        let new = serde_json::json!({ "name": "Robert", "age": 19, "email": "bob@mail.com" });
+        let change_set = ChangeSet::new("1".to_string(), "2".to_string(), &old, &new);
+        assert_eq!(change_set.fields(), vec!["age", "email", "nickname"]);
+        assert_eq!(
+            change_set.get("age"),
+            Some(&FieldChange {
+                field: "age".to_string(),
+                old: Some(Value::from(18)),
+                new: Some(Value::from(19)),
+            })
+        );
+        assert_eq!(change_set.get("email").unwrap().old, None);
+        assert_eq!(change_set.get("nickname").unwrap().new, None);
+        assert!(!change_set.contains("name"));
+        assert_eq!(
+            change_set.to_string(),
+            "1 -> 2: age, email, nickname changed"
+        );
+        assert!(ChangeSet::new("1".to_string(), "2".to_string(), &old, &old).is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+use std::ops::AddAssign;
+
+use crate::Error;
+
+/// The behavior of an import on documents whose `_key` already exists in the collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnDuplicate {
+    /// The document is not imported and counted as an error (default)
+    Error,
+    /// The existing document is partially updated with the imported one
+    Update,
+    /// The existing document is replaced by the imported one
+    Replace,
+    /// The document is not imported and counted as ignored
+    Ignore,
+}
+
+impl Default for OnDuplicate {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+impl Display for OnDuplicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::Error => "error",
+            Self::Update => "update",
+            Self::Replace => "replace",
+            Self::Ignore => "ignore",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// Options of the collection bulk imports, see [`DatabaseCollection::import_jsonl`]
+///
+/// [`DatabaseCollection::import_jsonl`]: crate::DatabaseCollection::import_jsonl
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImportOptions {
+    /// The behavior on documents with an existing `_key`, [`OnDuplicate::Error`] by default
+    pub on_duplicate: OnDuplicate,
+    /// The maximum number of documents sent per request, `1000` by default
+    pub chunk_size: usize,
+    /// Defines if a chunk is entirely rejected when any of its documents fails to be imported.
+    /// `false` by default, the valid documents being imported and the failures reported.
+    ///
+    /// The chunks imported before the failing one are kept.
+    pub complete: bool,
+    /// Defines if aragog should wait for the imported documents to be written on disk.
+    /// By default, the collection behavior is picked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for_sync: Option<bool>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            on_duplicate: OnDuplicate::default(),
+            chunk_size: 1000,
+            complete: false,
+            wait_for_sync: None,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Sets the `on_duplicate` value
+    #[inline]
+    #[must_use]
+    pub const fn on_duplicate(mut self, value: OnDuplicate) -> Self {
+        self.on_duplicate = value;
+        self
+    }
+
+    /// Sets the `chunk_size` value, at least one document is sent per request
+    #[inline]
+    #[must_use]
+    pub const fn chunk_size(mut self, value: usize) -> Self {
+        self.chunk_size = value;
+        self
+    }
+
+    /// Sets the `complete` value
+    #[inline]
+    #[must_use]
+    pub const fn complete(mut self, value: bool) -> Self {
+        self.complete = value;
+        self
+    }
+
+    /// Sets the `wait_for_sync` value
+    #[inline]
+    #[must_use]
+    pub const fn wait_for_sync(mut self, value: bool) -> Self {
+        self.wait_for_sync = Some(value);
+        self
+    }
+
+    pub(crate) fn query_string(&self, collection_name: &str, jsonl: bool) -> String {
+        let mut params = vec![
+            format!("collection={}", collection_name),
+            format!("onDuplicate={}", self.on_duplicate),
+            format!("complete={}", self.complete),
+            String::from("details=true"),
+        ];
+        if jsonl {
+            params.push(String::from("type=documents"));
+        }
+        if let Some(wait_for_sync) = self.wait_for_sync {
+            params.push(format!("waitForSync={}", wait_for_sync));
+        }
+        params.join("&")
+    }
+}
+
+/// The result of a collection bulk import, see [`DatabaseCollection::import_jsonl`]
+///
+/// [`DatabaseCollection::import_jsonl`]: crate::DatabaseCollection::import_jsonl
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportReport {
+    /// The number of imported documents
+    pub created: u64,
+    /// The number of documents that failed to be imported
+    pub errors: u64,
+    /// The number of empty lines
+    pub empty: u64,
+    /// The number of updated or replaced documents, see [`OnDuplicate`]
+    pub updated: u64,
+    /// The number of ignored documents, see [`OnDuplicate`]
+    pub ignored: u64,
+    /// The error messages of the failed documents
+    pub details: Vec<String>,
+}
+
+impl ImportReport {
+    /// The number of processed documents
+    #[must_use]
+    pub const fn processed(&self) -> u64 {
+        self.created + self.errors + self.updated + self.ignored
+    }
+}
+
+impl AddAssign for ImportReport {
+    fn add_assign(&mut self, other: Self) {
+        self.created += other.created;
+        self.errors += other.errors;
+        self.empty += other.empty;
+        self.updated += other.updated;
+        self.ignored += other.ignored;
+        self.details.extend(other.details);
+    }
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} created, {} updated, {} ignored, {} errors",
+            self.created, self.updated, self.ignored, self.errors
+        )
+    }
+}
+
+/// Reads the next chunk of at most `chunk_size` non empty lines of `reader`
+pub fn read_chunk<R: BufRead>(reader: &mut R, chunk_size: usize) -> Result<Vec<String>, Error> {
+    let mut lines = Vec::new();
+    while lines.len() < chunk_size.max(1) {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|error| Error::UnprocessableEntity {
+                source: Box::new(error),
+            })?;
+        if read == 0 {
+            break;
+        }
+        let line = line.trim_end_matches(&['\r', '\n'][..]);
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    Ok(lines)
+}
+
+/// Converts a CSV line into a JSON array line. The numbers and booleans are converted, the
+/// empty values are `null` and the other values are strings.
+pub fn csv_to_json_line(line: &str) -> String {
+    let values: Vec<Value> = split_csv_line(line)
+        .into_iter()
+        .map(|(value, quoted)| csv_value(value, quoted))
+        .collect();
+    Value::Array(values).to_string()
+}
+
+/// Converts the CSV header line into a JSON array of attribute names
+pub fn csv_header_to_json_line(line: &str) -> String {
+    let names: Vec<Value> = split_csv_line(line)
+        .into_iter()
+        .map(|(name, _)| Value::String(name))
+        .collect();
+    Value::Array(names).to_string()
+}
+
+fn csv_value(value: String, quoted: bool) -> Value {
+    if quoted {
+        return Value::String(value);
+    }
+    match value.as_str() {
+        "" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        raw => match (raw.parse::<i64>(), raw.parse::<f64>()) {
+            (Ok(integer), _) => Value::from(integer),
+            (_, Ok(float)) if float.is_finite() => Value::from(float),
+            _ => Value::String(value),
+        },
+    }
+}
+
+/// Splits a CSV line on commas, handling the double quoted values with `""` escapes.
+/// Returns the values with a flag set for quoted values.
+fn split_csv_line(line: &str) -> Vec<(String, bool)> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if current.is_empty() => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' if !in_quotes => {
+                values.push((std::mem::take(&mut current), quoted));
+                quoted = false;
+            }
+            c => current.push(c),
+        }
+    }
+    values.push((current, quoted));
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_conversion() {
+        assert_eq!(
+            csv_header_to_json_line("name,age,\"admin\""),
+            r#"["name","age","admin"]"#
+        );
+        assert_eq!(
+            csv_to_json_line(r#"Robert,18,true,,"42","Surcouf, ""the"" corsair",1.5,inf"#),
+            r#"["Robert",18,true,null,"42","Surcouf, \"the\" corsair",1.5,"inf"]"#
+        );
+    }
+
+    #[test]
+    fn chunks() {
+        let mut reader = "{\"a\":1}\n\n{\"a\":2}\r\n{\"a\":3}".as_bytes();
+        assert_eq!(
+            read_chunk(&mut reader, 2).unwrap(),
+            vec![r#"{"a":1}"#, r#"{"a":2}"#]
+        );
+        assert_eq!(read_chunk(&mut reader, 2).unwrap(), vec![r#"{"a":3}"#]);
+        assert!(read_chunk(&mut reader, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn query_string() {
+        let options = ImportOptions::default()
+            .on_duplicate(OnDuplicate::Replace)
+            .wait_for_sync(true);
+        assert_eq!(
+            options.query_string("User", true),
+            "collection=User&onDuplicate=replace&complete=false&details=true&type=documents&waitForSync=true"
+        );
+    }
+}
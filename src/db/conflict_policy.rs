@@ -0,0 +1,183 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::Merge;
+
+/// The default number of retries of the [`ConflictPolicy`] resolutions
+pub const DEFAULT_CONFLICT_RETRIES: u32 = 3;
+
+/// The merge callback of [`ConflictPolicy::Merge`], receiving the local record and the stored one
+pub type ConflictMerge<T> = Arc<dyn Fn(&T, &T) -> T + Send + Sync>;
+
+/// Defines how a record save resolves a revision conflict, when the stored document changed since
+/// the record retrieval.
+///
+/// The saves resolving the conflicts check the revisions whatever the
+/// [`OperationOptions::ignore_revs`] value, [`ConflictPolicy::Fail`] keeps the operation options.
+/// The policy of a model is set with the `conflict_policy` attribute of the `Record` derive, or
+/// per call with [`DatabaseRecord::save_with_conflict_policy`].
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{ConflictPolicy, Record};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// #[derive(Record, Clone, Serialize, Deserialize)]
+/// #[conflict_policy(merge = "merge_versions", max_retries = 5)]
+/// pub struct Counter {
+///     pub name: String,
+///     pub views: u32,
+/// }
+///
+/// impl Counter {
+///     // Keeps the local name and the highest views count
+///     fn merge_versions(local: &Self, stored: &Self) -> Self {
+///         Self {
+///             name: local.name.clone(),
+///             views: local.views.max(stored.views),
+///         }
+///     }
+/// }
+///
+/// assert!(matches!(Counter::conflict_policy(), ConflictPolicy::Merge { max_retries: 5, .. }));
+/// ```
+///
+/// [`OperationOptions::ignore_revs`]: crate::OperationOptions::ignore_revs
+/// [`DatabaseRecord::save_with_conflict_policy`]: crate::DatabaseRecord::save_with_conflict_policy
+pub enum ConflictPolicy<T> {
+    /// The save fails with the conflict error, the default policy
+    Fail,
+    /// The stored document is reloaded and the local record is saved over it
+    RetryWithReload {
+        /// The maximum number of saves after the first conflict
+        max_retries: u32,
+    },
+    /// The stored document is reloaded and the result of `merge(local, stored)` is saved over it
+    Merge {
+        /// The merge callback, receiving the local record and the stored one
+        merge: ConflictMerge<T>,
+        /// The maximum number of saves after the first conflict
+        max_retries: u32,
+    },
+}
+
+impl<T> ConflictPolicy<T> {
+    /// Instantiates a [`ConflictPolicy::RetryWithReload`] with [`DEFAULT_CONFLICT_RETRIES`] retries
+    #[must_use]
+    #[inline]
+    pub const fn retry_with_reload() -> Self {
+        Self::RetryWithReload {
+            max_retries: DEFAULT_CONFLICT_RETRIES,
+        }
+    }
+
+    /// Instantiates a [`ConflictPolicy::Merge`] with [`DEFAULT_CONFLICT_RETRIES`] retries
+    #[must_use]
+    pub fn merge<F>(merge: F) -> Self
+    where
+        F: Fn(&T, &T) -> T + Send + Sync + 'static,
+    {
+        Self::Merge {
+            merge: Arc::new(merge),
+            max_retries: DEFAULT_CONFLICT_RETRIES,
+        }
+    }
+
+    /// Sets the maximum number of saves after the first conflict, without effect on
+    /// [`ConflictPolicy::Fail`]
+    #[must_use]
+    pub fn max_retries(mut self, value: u32) -> Self {
+        match &mut self {
+            Self::Fail => (),
+            Self::RetryWithReload { max_retries } | Self::Merge { max_retries, .. } => {
+                *max_retries = value;
+            }
+        }
+        self
+    }
+
+    /// The maximum number of saves after the first conflict
+    #[must_use]
+    pub const fn retries(&self) -> u32 {
+        match self {
+            Self::Fail => 0,
+            Self::RetryWithReload { max_retries } | Self::Merge { max_retries, .. } => *max_retries,
+        }
+    }
+
+    /// Resolves a conflict between the `local` record and the `stored` one, updating `local` to
+    /// the record to save
+    pub(crate) fn resolve(&self, local: &mut T, stored: &T) {
+        if let Self::Merge { merge, .. } = self {
+            *local = merge(local, stored);
+        }
+    }
+}
+
+impl<T: Merge + 'static> ConflictPolicy<T> {
+    /// Instantiates a [`ConflictPolicy::Merge`] merging the records field by field with their
+    /// [`Merge`] implementation, with [`DEFAULT_CONFLICT_RETRIES`] retries
+    #[must_use]
+    pub fn merge_fields() -> Self {
+        Self::merge(T::merge)
+    }
+}
+
+impl<T> Default for ConflictPolicy<T> {
+    fn default() -> Self {
+        Self::Fail
+    }
+}
+
+impl<T> Clone for ConflictPolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Fail => Self::Fail,
+            Self::RetryWithReload { max_retries } => Self::RetryWithReload {
+                max_retries: *max_retries,
+            },
+            Self::Merge { merge, max_retries } => Self::Merge {
+                merge: Arc::clone(merge),
+                max_retries: *max_retries,
+            },
+        }
+    }
+}
+
+impl<T> Debug for ConflictPolicy<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fail => f.write_str("Fail"),
+            Self::RetryWithReload { max_retries } => f
+                .debug_struct("RetryWithReload")
+                .field("max_retries", max_retries)
+                .finish(),
+            Self::Merge { max_retries, .. } => f
+                .debug_struct("Merge")
+                .field("max_retries", max_retries)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolutions() {
+        let policy = ConflictPolicy::<u32>::retry_with_reload();
+        assert_eq!(policy.retries(), DEFAULT_CONFLICT_RETRIES);
+        let mut local = 1;
+        policy.resolve(&mut local, &2);
+        assert_eq!(local, 1);
+        let policy =
+            ConflictPolicy::merge(|local: &u32, stored: &u32| local + stored).max_retries(1);
+        assert_eq!(policy.retries(), 1);
+        policy.clone().resolve(&mut local, &2);
+        assert_eq!(local, 3);
+        assert_eq!(format!("{:?}", policy), "Merge { max_retries: 1, .. }");
+        assert_eq!(ConflictPolicy::<u32>::Fail.max_retries(4).retries(), 0);
+    }
+}
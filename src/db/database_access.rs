@@ -1,10 +1,19 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use arangors_lite::transaction::Transaction as TransactionLayer;
 use arangors_lite::Database;
 
 use crate::db::database_collection::DatabaseCollection;
 use crate::db::database_service::{query_records, query_records_in_batches};
+use crate::db::deserialization_mode::DeserializationMode;
+use crate::db::hook_registry::HookRegistry;
+use crate::db::operation_tracker::OperationTracker;
+use crate::db::request_limiter::RequestLimiter;
+use crate::db::velocypack::VelocyPackTransport;
 use crate::query::{Query, QueryCursor, QueryResult};
 use crate::undefined_record::UndefinedRecord;
-use crate::{Error, OperationOptions};
+use crate::{CounterCache, Error, OperationOptions};
 
 /// The `DatabaseAccess` trait of the `Aragog` library.
 ///
@@ -38,6 +47,17 @@ pub trait DatabaseAccess: Sync {
         OperationOptions::default()
     }
 
+    /// Defines the operation options to use on `write` operations on the given `collection`.
+    ///
+    /// By default the [`operation_options`] are used for every collection.
+    ///
+    /// [`operation_options`]: Self::operation_options
+    #[must_use]
+    fn collection_operation_options(&self, collection: &str) -> OperationOptions {
+        let _ = collection;
+        self.operation_options()
+    }
+
     /// Retrieves a Collection from the database accessor.
     fn collection(&self, collection: &str) -> Option<&DatabaseCollection>;
 
@@ -54,6 +74,105 @@ pub trait DatabaseAccess: Sync {
     #[must_use]
     fn database(&self) -> &Database;
 
+    /// Defines the duration above which the AQL queries are logged at `WARN` level.
+    ///
+    /// By default the slow queries are not logged.
+    #[must_use]
+    fn slow_query_threshold(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Defines the strictness of the stored documents deserialization on [`DatabaseRecord`]
+    /// retrievals and queries.
+    ///
+    /// By default the unknown document fields are ignored.
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    #[must_use]
+    fn deserialization_mode(&self) -> DeserializationMode {
+        DeserializationMode::default()
+    }
+
+    /// Defines if the literal values of the queries are passed as bind parameters, making the
+    /// AQL strings identical across calls so `ArangoDB` reuses the cached query plans.
+    ///
+    /// By default the literals are bound. See [`Query::aql_with_bind_vars`].
+    ///
+    /// [`Query::aql_with_bind_vars`]: crate::query::Query::aql_with_bind_vars
+    #[must_use]
+    fn bind_literals(&self) -> bool {
+        true
+    }
+
+    /// Defines the limiter of the concurrent in-flight requests, waited for before every record
+    /// operation, query and query cursor batch.
+    ///
+    /// By default the requests are not limited.
+    #[must_use]
+    fn request_limiter(&self) -> Option<&Arc<RequestLimiter>> {
+        None
+    }
+
+    /// Defines the tracker of the in-flight operations, open transactions and open cursors,
+    /// used by [`DatabaseConnection::shutdown`].
+    ///
+    /// By default the operations are not tracked.
+    ///
+    /// [`DatabaseConnection::shutdown`]: crate::DatabaseConnection::shutdown
+    #[must_use]
+    fn operation_tracker(&self) -> Option<&OperationTracker> {
+        None
+    }
+
+    /// Defines the hooks registered at runtime, called by the record operations along with the
+    /// record hooks.
+    ///
+    /// By default there are no registered hooks.
+    #[must_use]
+    fn hook_registry(&self) -> Option<&HookRegistry> {
+        None
+    }
+
+    /// Defines the current tenant, restricting the operations on the records declaring a
+    /// [`TENANT_FIELD`] to its documents.
+    ///
+    /// By default there is no tenant.
+    ///
+    /// [`TENANT_FIELD`]: crate::Record::TENANT_FIELD
+    #[must_use]
+    fn tenant(&self) -> Option<&str> {
+        None
+    }
+
+    /// Defines the `VelocyPack` transport of the document retrievals and of the query cursors.
+    ///
+    /// By default the responses are JSON.
+    #[must_use]
+    fn velocypack_transport(&self) -> Option<&VelocyPackTransport> {
+        None
+    }
+
+    /// Defines the counter caches maintained by the edge hooks, as pairs of the counted vertex
+    /// collection and of its [`CounterCache`].
+    ///
+    /// By default there are no counter caches.
+    #[must_use]
+    fn counter_caches(&self) -> &[(&'static str, CounterCache)] {
+        &[]
+    }
+
+    /// Defines the `ArangoDB` stream transaction of the accessor, running the field lookups of
+    /// [`DatabaseRecord::find_by_field`] and [`DatabaseRecord::exists_by`].
+    ///
+    /// By default there is no transaction.
+    ///
+    /// [`DatabaseRecord::find_by_field`]: crate::DatabaseRecord::find_by_field
+    /// [`DatabaseRecord::exists_by`]: crate::DatabaseRecord::exists_by
+    #[must_use]
+    fn stream_transaction(&self) -> Option<&TransactionLayer> {
+        None
+    }
+
     /// Runs an AQL query and returns the found documents as undefined records.
     ///
     /// # Note
@@ -90,3 +209,91 @@ pub trait DatabaseAccess: Sync {
         query_records_in_batches(self, query, batch_size).await
     }
 }
+
+macro_rules! impl_database_access_through_deref {
+    ($(#[$meta:meta])* $holder:ty, $($bounds:tt)+) => {
+        $(#[$meta])*
+        impl<D> DatabaseAccess for $holder
+        where
+            D: $($bounds)+,
+        {
+            fn operation_options(&self) -> OperationOptions {
+                (**self).operation_options()
+            }
+
+            fn collection_operation_options(&self, collection: &str) -> OperationOptions {
+                (**self).collection_operation_options(collection)
+            }
+
+            fn collection(&self, collection: &str) -> Option<&DatabaseCollection> {
+                (**self).collection(collection)
+            }
+
+            fn database(&self) -> &Database {
+                (**self).database()
+            }
+
+            fn slow_query_threshold(&self) -> Option<Duration> {
+                (**self).slow_query_threshold()
+            }
+
+            fn deserialization_mode(&self) -> DeserializationMode {
+                (**self).deserialization_mode()
+            }
+
+            fn bind_literals(&self) -> bool {
+                (**self).bind_literals()
+            }
+
+            fn request_limiter(&self) -> Option<&Arc<RequestLimiter>> {
+                (**self).request_limiter()
+            }
+
+            fn operation_tracker(&self) -> Option<&OperationTracker> {
+                (**self).operation_tracker()
+            }
+
+            fn hook_registry(&self) -> Option<&HookRegistry> {
+                (**self).hook_registry()
+            }
+
+            fn tenant(&self) -> Option<&str> {
+                (**self).tenant()
+            }
+
+            fn velocypack_transport(&self) -> Option<&VelocyPackTransport> {
+                (**self).velocypack_transport()
+            }
+
+            fn counter_caches(&self) -> &[(&'static str, CounterCache)] {
+                (**self).counter_caches()
+            }
+
+            fn stream_transaction(&self) -> Option<&TransactionLayer> {
+                (**self).stream_transaction()
+            }
+        }
+    };
+}
+
+impl_database_access_through_deref!(
+    /// References to database accessors can be used directly, allowing generic functions to
+    /// forward the accessor they received.
+    &D,
+    DatabaseAccess + ?Sized
+);
+impl_database_access_through_deref!(
+    /// Boxed database accessors, like `Box<dyn DatabaseAccess>`, can be used directly.
+    Box<D>,
+    DatabaseAccess + ?Sized
+);
+impl_database_access_through_deref!(
+    /// Shared database accessors, like the [`DatabaseConnection::into_shared`] output, can be used
+    /// directly.
+    ///
+    /// `Rc` can't be used as `DatabaseAccess` requires `Sync`.
+    ///
+    /// [`DatabaseConnection::into_shared`]: crate::DatabaseConnection::into_shared
+    Arc<D>,
+    DatabaseAccess + Send + ?Sized
+);
@@ -1,5 +1,14 @@
-use crate::Error;
+use crate::db::collection_import::{
+    csv_header_to_json_line, csv_to_json_line, read_chunk, ImportOptions, ImportReport,
+};
+use crate::db::database_service::{deserialize_response, record_tenant, stream_records};
+use crate::query::{Query, QueryCursor};
+use crate::schema::IndexSchema;
+use crate::{DatabaseAccess, Error, Record};
+use arangors_lite::index::Index;
 use arangors_lite::Collection;
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
 use std::ops::Deref;
 
 /// Struct containing the connection information on a `ArangoDB` collection
@@ -9,6 +18,83 @@ pub struct DatabaseCollection {
     collection: Collection,
 }
 
+/// Storage and index figures of a collection, usable for monitoring purposes.
+///
+/// All sizes are expressed in bytes and, as stated by `ArangoDB`, must be considered as
+/// approximations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionFigures {
+    /// Total document count of the collection
+    pub document_count: u64,
+    /// Number of indexes defined on the collection (including the primary index)
+    pub indexes_count: u64,
+    /// Total size of the collection indexes
+    pub indexes_size: u64,
+    /// Approximate disk usage of the collection documents
+    pub documents_size: u64,
+    /// Is the in-memory hash cache enabled for the collection
+    pub cache_in_use: bool,
+    /// Total memory allocated for the in-memory hash cache
+    pub cache_size: u64,
+    /// Memory currently used by the in-memory hash cache
+    pub cache_usage: u64,
+}
+
+/// Checksum of a collection, allowing to check if its contents changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionChecksum {
+    /// The collection revision id the checksum was computed on
+    pub revision: String,
+    /// The checksum value
+    pub checksum: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawIndexFigures {
+    #[serde(default)]
+    count: u64,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFigures {
+    #[serde(default)]
+    indexes: RawIndexFigures,
+    #[serde(default)]
+    documents_size: u64,
+    #[serde(default)]
+    cache_in_use: bool,
+    #[serde(default)]
+    cache_size: u64,
+    #[serde(default)]
+    cache_usage: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawFiguresResponse {
+    #[serde(default)]
+    count: u64,
+    #[serde(default)]
+    figures: RawFigures,
+}
+
+impl From<RawFiguresResponse> for CollectionFigures {
+    fn from(response: RawFiguresResponse) -> Self {
+        Self {
+            document_count: response.count,
+            indexes_count: response.figures.indexes.count,
+            indexes_size: response.figures.indexes.size,
+            documents_size: response.figures.documents_size,
+            cache_in_use: response.figures.cache_in_use,
+            cache_size: response.figures.cache_size,
+            cache_usage: response.figures.cache_usage,
+        }
+    }
+}
+
 impl DatabaseCollection {
     /// Name of the collection, exactly as defined in database
     #[must_use]
@@ -23,6 +109,10 @@ impl DatabaseCollection {
     ///
     /// On success a `i32` is returned as the document count.
     /// On failure a Error wil be returned.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
     #[maybe_async::maybe_async]
     pub async fn record_count(&self) -> Result<u32, Error> {
         let properties = match self.collection.document_count().await {
@@ -34,6 +124,378 @@ impl DatabaseCollection {
             None => Ok(0),
         }
     }
+
+    /// Retrieves the storage and index figures of this collection: document count, index count
+    /// and size, disk usage and cache usage.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseAccess, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let collection = db_connection.collection("User").unwrap();
+    /// let figures = collection.figures().await.unwrap();
+    /// println!("{} documents using {} bytes", figures.document_count, figures.documents_size);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure or if the response can't be parsed.
+    #[maybe_async::maybe_async]
+    pub async fn figures(&self) -> Result<CollectionFigures, Error> {
+        let url = self
+            .collection
+            .url()
+            .join("figures")
+            .map_err(|e| Error::InternalError {
+                message: Some(e.to_string()),
+            })?;
+        let response = self
+            .collection
+            .session()
+            .get(url.to_string(), "")
+            .await
+            .map_err(Error::from)?;
+        let raw: RawFiguresResponse = deserialize_response(response.body())?;
+        Ok(raw.into())
+    }
+
+    /// Retrieves the checksum of this collection, computed on the document keys.
+    /// Comparing two checksums allows to detect if the collection contents changed.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    #[maybe_async::maybe_async]
+    pub async fn checksum(&self) -> Result<CollectionChecksum, Error> {
+        let checksum = self.collection.checksum().await?;
+        Ok(CollectionChecksum {
+            revision: checksum.revision,
+            checksum: checksum.checksum,
+        })
+    }
+
+    /// Ensures the index described by `index` exists on this collection, creating it if needed.
+    /// If an identical index already exists, it is returned and nothing is created.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the index schema to apply, its `collection` must match this collection name
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseAccess, DatabaseConnection};
+    /// # use aragog::schema::IndexSchema;
+    /// # use arangors_lite::index::IndexSettings;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let collection = db_connection.collection("User").unwrap();
+    /// collection.ensure_index(IndexSchema {
+    ///     name: "by_email".to_string(),
+    ///     collection: "User".to_string(),
+    ///     fields: vec!["email".to_string()],
+    ///     settings: IndexSettings::Persistent {
+    ///         unique: true,
+    ///         sparse: false,
+    ///         deduplicate: false,
+    ///     },
+    /// }).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the index targets an other collection or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn ensure_index(&self, index: IndexSchema) -> Result<IndexSchema, Error> {
+        if index.collection != self.name() {
+            return Err(Error::ValidationError(format!(
+                "index {} targets collection {} instead of {}",
+                index.name,
+                index.collection,
+                self.name()
+            )));
+        }
+        log::debug!("Ensuring index {} on {}", &index.name, self.name());
+        let index: Index = index.into();
+        let created = self
+            .collection
+            .db()
+            .create_index(self.name(), &index)
+            .await?;
+        Ok(IndexSchema::from_index(self.name(), created))
+    }
+
+    /// Lists all the indexes of this collection, including the system indexes (`primary`, `edge`).
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn list_indexes(&self) -> Result<Vec<IndexSchema>, Error> {
+        let indexes = self.collection.db().indexes(self.name()).await?;
+        Ok(indexes
+            .indexes
+            .into_iter()
+            .map(|index| IndexSchema::from_index(self.name(), index))
+            .collect())
+    }
+
+    /// Drops the index named `name` from this collection.
+    ///
+    /// # Errors
+    ///
+    /// Will fail with [`Error::NotFound`] if no index named `name` exists on the collection,
+    /// or on database request failure
+    ///
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    #[maybe_async::maybe_async]
+    pub async fn drop_index(&self, name: &str) -> Result<(), Error> {
+        let database = self.collection.db();
+        let indexes = database.indexes(self.name()).await?;
+        let index = indexes
+            .indexes
+            .into_iter()
+            .find(|index| index.name == name)
+            .ok_or_else(|| Error::NotFound {
+                item: "Index".to_string(),
+                id: format!("{}/{}", self.name(), name),
+                source: None,
+            })?;
+        log::debug!("Deleting index {} on {}", name, self.name());
+        database.delete_index(&index.id).await?;
+        Ok(())
+    }
+
+    /// Imports the JSON documents of `reader`, one per line (JSON Lines), through the `ArangoDB`
+    /// bulk import API. The documents are sent by chunks of `options.chunk_size` lines, the empty
+    /// lines being skipped.
+    ///
+    /// The hooks and validations of the records are **not** launched.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseAccess, DatabaseConnection, ImportOptions, OnDuplicate};
+    /// # use std::io::BufReader;
+    /// # use std::fs::File;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let collection = db_connection.collection("User").unwrap();
+    /// let reader = BufReader::new(File::open("users.jsonl").unwrap());
+    /// let options = ImportOptions::default().on_duplicate(OnDuplicate::Replace);
+    /// let report = collection.import_jsonl(reader, &options).await.unwrap();
+    /// println!("{}", report);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `reader` can't be read or on database request failure, like a rejected chunk
+    /// with the `complete` option. The chunks imported before the failure are kept.
+    #[maybe_async::maybe_async]
+    pub async fn import_jsonl<R: BufRead>(
+        &self,
+        reader: R,
+        options: &ImportOptions,
+    ) -> Result<ImportReport, Error> {
+        self.import_jsonl_with_progress(reader, options, |_| ())
+            .await
+    }
+
+    /// Imports the JSON documents of `reader` like [`import_jsonl`], calling `on_progress` with
+    /// the cumulated report after every chunk.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `reader` can't be read or on database request failure
+    ///
+    /// [`import_jsonl`]: Self::import_jsonl
+    #[maybe_async::maybe_async]
+    pub async fn import_jsonl_with_progress<R, F>(
+        &self,
+        reader: R,
+        options: &ImportOptions,
+        on_progress: F,
+    ) -> Result<ImportReport, Error>
+    where
+        R: BufRead,
+        F: FnMut(&ImportReport),
+    {
+        self.import_lines(reader, None, options, on_progress).await
+    }
+
+    /// Imports the CSV rows of `reader`, the first line being the header of the attribute names,
+    /// through the `ArangoDB` bulk import API. The rows are sent by chunks of
+    /// `options.chunk_size` lines.
+    ///
+    /// The unquoted numbers and booleans are imported as such, the empty values as `null` and
+    /// the other values as strings. The values can't contain line breaks.
+    ///
+    /// The hooks and validations of the records are **not** launched.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `reader` can't be read, on missing header or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn import_csv<R: BufRead>(
+        &self,
+        reader: R,
+        options: &ImportOptions,
+    ) -> Result<ImportReport, Error> {
+        self.import_csv_with_progress(reader, options, |_| ()).await
+    }
+
+    /// Imports the CSV rows of `reader` like [`import_csv`], calling `on_progress` with the
+    /// cumulated report after every chunk.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `reader` can't be read, on missing header or on database request failure
+    ///
+    /// [`import_csv`]: Self::import_csv
+    #[maybe_async::maybe_async]
+    pub async fn import_csv_with_progress<R, F>(
+        &self,
+        mut reader: R,
+        options: &ImportOptions,
+        on_progress: F,
+    ) -> Result<ImportReport, Error>
+    where
+        R: BufRead,
+        F: FnMut(&ImportReport),
+    {
+        let header = read_chunk(&mut reader, 1)?
+            .pop()
+            .ok_or_else(|| Error::ValidationError(String::from("missing CSV header line")))?;
+        let header = csv_header_to_json_line(&header);
+        self.import_lines(reader, Some(&header), options, on_progress)
+            .await
+    }
+
+    /// Exports all the documents of this collection as `T` records, through a streaming cursor
+    /// fetching `batch_size` documents per batch: the documents are read from the database as
+    /// the batches are fetched, without building the whole result first.
+    ///
+    /// The [`default_scope`] and the `db_accessor` tenant of `T` apply, like with
+    /// [`Record::get_in_batches`], as do the `db_accessor` deserialization mode, timeout and
+    /// request limit. An abandoned cursor is removed by [`DatabaseConnection::shutdown`].
+    ///
+    /// Use [`QueryCursor::into_records`] to iterate the documents one by one, for backup or
+    /// reindexing jobs.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseAccess, DatabaseConnection, Record};
+    /// # use futures_util::StreamExt;
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let collection = db_connection.collection("User").unwrap();
+    /// let cursor = collection.export_stream::<User, _>(&db_connection, 1000).await.unwrap();
+    /// let mut users = Box::pin(cursor.into_records());
+    /// while let Some(user) = users.next().await {
+    ///     let user = user.unwrap();
+    ///     // Write the user to the backup
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Forbidden`] if `T` is tenant scoped and `db_accessor` has no tenant or if
+    /// this collection is not the `T` collection, and will fail on database request failure
+    ///
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`Record::get_in_batches`]: crate::Record::get_in_batches
+    /// [`DatabaseConnection::shutdown`]: crate::DatabaseConnection::shutdown
+    /// [`QueryCursor::into_records`]: crate::query::QueryCursor::into_records
+    /// [`Error::Forbidden`]: crate::Error::Forbidden
+    #[maybe_async::maybe_async]
+    pub async fn export_stream<T, D>(
+        &self,
+        db_accessor: &D,
+        batch_size: u32,
+    ) -> Result<QueryCursor<T>, Error>
+    where
+        T: Record,
+        D: DatabaseAccess + ?Sized,
+    {
+        log::debug!(
+            "Exporting {} documents by batches of {}",
+            self.name(),
+            batch_size
+        );
+        let tenant = record_tenant::<T, D>(db_accessor)?;
+        if tenant.is_some() && self.name() != T::COLLECTION_NAME {
+            return Err(Error::Forbidden(None));
+        }
+        stream_records(db_accessor, &Query::new(self.name()), batch_size).await
+    }
+
+    /// Imports the lines of `reader` by chunks, as CSV rows if a `csv_header` is set
+    #[maybe_async::maybe_async]
+    async fn import_lines<R, F>(
+        &self,
+        mut reader: R,
+        csv_header: Option<&str>,
+        options: &ImportOptions,
+        mut on_progress: F,
+    ) -> Result<ImportReport, Error>
+    where
+        R: BufRead,
+        F: FnMut(&ImportReport),
+    {
+        let mut url = self
+            .collection
+            .db()
+            .url()
+            .join("_api/import")
+            .map_err(|e| Error::InternalError {
+                message: Some(e.to_string()),
+            })?;
+        url.set_query(Some(
+            &options.query_string(self.name(), csv_header.is_none()),
+        ));
+        let mut report = ImportReport::default();
+        loop {
+            let lines = read_chunk(&mut reader, options.chunk_size)?;
+            if lines.is_empty() {
+                break;
+            }
+            let body = csv_header.map_or_else(
+                || lines.join("\n"),
+                |header| {
+                    std::iter::once(header.to_string())
+                        .chain(lines.iter().map(|line| csv_to_json_line(line)))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                },
+            );
+            log::debug!("Importing {} documents in {}", lines.len(), self.name());
+            let request = http::Request::post(url.to_string())
+                .body(body)
+                .map_err(|e| Error::InternalError {
+                    message: Some(e.to_string()),
+                })?;
+            let response = self.collection.session().request(request).await?;
+            report += deserialize_response::<ImportReport>(response.body())?;
+            on_progress(&report);
+        }
+        log::info!("Imported documents in {}: {}", self.name(), report);
+        Ok(report)
+    }
 }
 
 impl From<Collection> for DatabaseCollection {
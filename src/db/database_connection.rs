@@ -1,22 +1,57 @@
 use crate::db::database_collection::DatabaseCollection;
 use crate::db::database_connection_builder::{
-    DatabaseConnectionBuilder, DatabaseSchemaOption, DbCredentialsOption,
+    DatabaseConnectionBuilder, DatabaseSchemaOption, DbCredentialsOption, SchemaApplication,
 };
-use crate::schema::{DatabaseSchema, SchemaDatabaseOperation};
-use crate::{DatabaseAccess, Error, OperationOptions};
+use crate::schema::{DatabaseSchema, GraphSchema, SchemaDatabaseOperation, SCHEMA_DOCUMENT_KEY};
+use crate::{
+    CollectionKind, CollectionSummary, CounterCache, DatabaseAccess, DeserializationMode, Error,
+    GraphSummary, HookContext, HookEvent, HookRegistry, OperationOptions, OperationTracker, Record,
+    RequestLimiter, VelocyPackTransport,
+};
+use arangors_lite::collection::CollectionType;
 use arangors_lite::{Connection, Database};
 use std::collections::HashMap;
+#[cfg(not(feature = "blocking"))]
+use std::future::Future;
 use std::marker::Copy;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Struct containing `ArangoDB` connections and information to access the database, collections and documents
+///
+/// The connection is `Send + Sync` and cheap to clone: the collections and the `ArangoDB` sessions
+/// are shared between the clones. To share a single instance across tasks use [`into_shared`],
+/// `Arc<DatabaseConnection>` implements [`DatabaseAccess`].
+///
+/// [`into_shared`]: Self::into_shared
 #[derive(Clone, Debug)]
 pub struct DatabaseConnection {
     /// Map between a collection name and a `DatabaseCollection` instance
-    collections: HashMap<String, DatabaseCollection>,
+    collections: Arc<HashMap<String, DatabaseCollection>>,
     /// The database accessor
     database: Database,
     /// The default options for all `write` operations
     operation_options: OperationOptions,
+    /// The options overriding `operation_options` for specific collections
+    collection_operation_options: Arc<HashMap<String, OperationOptions>>,
+    /// The duration above which the AQL queries are logged
+    slow_query_threshold: Option<Duration>,
+    /// The strictness of the documents deserialization
+    deserialization_mode: DeserializationMode,
+    /// Whether the query literals are passed as bind parameters
+    pub(crate) bind_literals: bool,
+    /// The limiter of the concurrent requests
+    pub(crate) request_limiter: Option<Arc<RequestLimiter>>,
+    /// The tracker of the operations, shared by the clones and the transactions
+    pub(crate) operation_tracker: OperationTracker,
+    /// The hooks registered at runtime, shared by the clones and the transactions
+    pub(crate) hook_registry: HookRegistry,
+    /// The current tenant of the tenant scoped records
+    tenant: Option<String>,
+    /// The `VelocyPack` transport of the document retrievals and query cursors
+    pub(crate) velocypack_transport: Option<VelocyPackTransport>,
+    /// The counter caches maintained by the edge hooks, by counted vertex collection
+    pub(crate) counter_caches: Arc<Vec<(&'static str, CounterCache)>>,
 }
 
 /// Defines which `ArangoDB` authentication mode will be used
@@ -82,11 +117,20 @@ impl DatabaseConnection {
     #[inline]
     pub fn builder() -> DatabaseConnectionBuilder {
         DatabaseConnectionBuilder {
-            apply_schema: false,
+            apply_schema: SchemaApplication::Skip,
             auth_mode: AuthMode::default(),
             credentials: DbCredentialsOption::Auto,
             schema: DatabaseSchemaOption::Auto,
             operation_options: OperationOptions::default(),
+            collection_operation_options: HashMap::new(),
+            slow_query_threshold: None,
+            deserialization_mode: DeserializationMode::default(),
+            bind_literals: true,
+            velocypack_transport: false,
+            max_concurrent_requests: None,
+            counter_caches: Vec::new(),
+            required_schema_version: None,
+            schema_collection_name: None,
         }
     }
 
@@ -94,16 +138,50 @@ impl DatabaseConnection {
     pub(crate) async fn new(
         database: Database,
         schema: DatabaseSchema,
-        apply_schema: bool,
+        apply_schema: SchemaApplication,
         operation_options: OperationOptions,
+        collection_operation_options: HashMap<String, OperationOptions>,
+        slow_query_threshold: Option<Duration>,
+        deserialization_mode: DeserializationMode,
     ) -> Result<Self, Error> {
-        if apply_schema {
-            schema.apply_to_database(&database, true).await?;
+        for (name, options) in &collection_operation_options {
+            let collection = schema.collection(name).ok_or_else(|| Error::InitError {
+                item: name.clone(),
+                message: "Operation options are defined for a collection missing from the schema"
+                    .to_string(),
+            })?;
+            options
+                .validate_for_collection(collection)
+                .map_err(|error| Error::InitError {
+                    item: name.clone(),
+                    message: error.to_string(),
+                })?;
+        }
+        match apply_schema {
+            SchemaApplication::Skip => (),
+            SchemaApplication::Silent => {
+                schema.check_graph_collections(&database).await?;
+                schema.apply_to_database(&database, true).await?;
+            }
+            SchemaApplication::Strict => {
+                schema.check_graph_collections(&database).await?;
+                schema.apply_strict(&database).await?;
+            }
         }
         Ok(Self {
-            collections: Self::load_schema(&database, schema).await?,
+            collections: Arc::new(Self::load_schema(&database, schema).await?),
             database,
             operation_options,
+            collection_operation_options: Arc::new(collection_operation_options),
+            slow_query_threshold,
+            deserialization_mode,
+            bind_literals: true,
+            request_limiter: None,
+            operation_tracker: OperationTracker::new(),
+            hook_registry: HookRegistry::new(),
+            tenant: None,
+            velocypack_transport: None,
+            counter_caches: Arc::new(Vec::new()),
         })
     }
 
@@ -126,6 +204,29 @@ impl DatabaseConnection {
         Ok(db_connection.db(db_name).await?)
     }
 
+    /// Fails if the schema version stored by `aragog_cli` in `collection_name` is not `required`
+    #[maybe_async::maybe_async]
+    pub(crate) async fn check_schema_version(
+        database: &Database,
+        collection_name: &str,
+        required: u64,
+    ) -> Result<(), Error> {
+        log::debug!("Checking database schema version {} ...", required);
+        let document = match database.collection(collection_name).await {
+            Ok(collection) => collection
+                .document::<serde_json::Value>(SCHEMA_DOCUMENT_KEY)
+                .await
+                .map_err(Error::from),
+            Err(error) => Err(Error::from(error)),
+        };
+        let version = match document {
+            Ok(document) => document.document["version"].as_u64(),
+            Err(error) if error.is_not_found() => None,
+            Err(error) => return Err(error),
+        };
+        schema_version_error(version, required).map_or(Ok(()), Err)
+    }
+
     /// retrieves a vector of all collection names from the database
     #[must_use]
     pub fn collections_names(&self) -> Vec<String> {
@@ -133,24 +234,74 @@ impl DatabaseConnection {
     }
 
     #[must_use]
-    pub(crate) fn collections(&self) -> Vec<&DatabaseCollection> {
+    pub(crate) fn collections_operation_options(&self) -> &HashMap<String, OperationOptions> {
+        &self.collection_operation_options
+    }
+
+    #[must_use]
+    pub(crate) fn database_collections(&self) -> Vec<&DatabaseCollection> {
         self.collections.values().collect()
     }
 
     /// **DESTRUCTIVE OPERATION**
     ///
     /// This will truncate all collections in the database, the collection will still exist but
-    /// every document will be destryed.
+    /// every document will be destroyed.
+    ///
+    /// Prefer [`truncate_collections`] to explicitly select the collections to empty.
+    ///
+    /// # Errors
+    ///
+    /// If the truncate fails on some collection the error is returned and the remaining
+    /// collections are not truncated, see the `arangors_lite` documentation on collection truncate.
+    ///
+    /// [`truncate_collections`]: Self::truncate_collections
+    #[maybe_async::maybe_async]
+    pub async fn unsafe_truncate_all(&self) -> Result<(), Error> {
+        log::debug!("Truncating all collections");
+        for collection in self.collections.values() {
+            collection.truncate().await?;
+        }
+        Ok(())
+    }
+
+    /// **DESTRUCTIVE OPERATION**
     ///
-    /// # Panics
+    /// This will truncate the given collections, the collections will still exist but
+    /// every document will be destroyed.
     ///
-    /// If the truncate fails on some collection the method will panic, see the `arangors_lite` documentation
-    /// on collection truncate.
+    /// # Arguments
+    ///
+    /// * `collections` - the names of the collections to truncate
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::DatabaseConnection;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// db_connection.truncate_collections(&["User", "Order"]).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail with [`Error::NotFound`] if a collection is not in the loaded schema, in which
+    /// case no collection is truncated, or if the truncate fails on some collection.
+    ///
+    /// [`Error::NotFound`]: crate::Error::NotFound
     #[maybe_async::maybe_async]
-    pub async fn truncate(&self) {
-        for collection in &self.collections {
-            collection.1.truncate().await.unwrap();
+    pub async fn truncate_collections(&self, collections: &[&str]) -> Result<(), Error> {
+        let collections = collections
+            .iter()
+            .map(|name| self.get_collection(name))
+            .collect::<Result<Vec<&DatabaseCollection>, Error>>()?;
+        for collection in collections {
+            log::debug!("Truncating collection {}", collection.name());
+            collection.truncate().await?;
         }
+        Ok(())
     }
 
     #[maybe_async::maybe_async]
@@ -170,6 +321,389 @@ impl DatabaseConnection {
         Ok(collections)
     }
 
+    /// Runs `operations` with a copy of the connection skipping the [`Record`] hooks: its
+    /// `operation_options` have `ignore_hooks` set to `true`.
+    ///
+    /// Useful for maintenance scripts or bulk operations, instead of using the `force_*` methods of
+    /// [`DatabaseRecord`] on every call or altering the global connection options.
+    /// The current connection is not affected.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// # pub struct User {
+    /// #     pub name: String,
+    /// # }
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let res: Result<(), aragog::Error> = db_connection
+    ///     .without_hooks(|connection| async move {
+    ///         // No hook will be launched here
+    ///         for mut user in User::get(&User::query(), &connection).await?.0 {
+    ///             user.name = user.name.to_lowercase();
+    ///             user.save(&connection).await?;
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    ///
+    /// [`Record`]: crate::Record
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    #[cfg(not(feature = "blocking"))]
+    pub async fn without_hooks<T, O, F>(&self, operations: O) -> T
+    where
+        O: FnOnce(Self) -> F,
+        F: Future<Output = T>,
+    {
+        operations(self.hookless()).await
+    }
+
+    /// Runs `operations` with a copy of the connection skipping the [`Record`] hooks: its
+    /// `operation_options` have `ignore_hooks` set to `true`.
+    ///
+    /// Useful for maintenance scripts or bulk operations, instead of using the `force_*` methods of
+    /// [`DatabaseRecord`] on every call or altering the global connection options.
+    /// The current connection is not affected.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// # pub struct User {
+    /// #     pub name: String,
+    /// # }
+    /// # fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().unwrap();
+    /// let res: Result<(), aragog::Error> = db_connection
+    ///     .without_hooks(|connection| {
+    ///         // No hook will be launched here
+    ///         for mut user in User::get(&User::query(), &connection)?.0 {
+    ///             user.name = user.name.to_lowercase();
+    ///             user.save(&connection)?;
+    ///         }
+    ///         Ok(())
+    ///     });
+    /// # }
+    /// ```
+    ///
+    /// [`Record`]: crate::Record
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    #[cfg(feature = "blocking")]
+    pub fn without_hooks<T, O>(&self, operations: O) -> T
+    where
+        O: FnOnce(Self) -> T,
+    {
+        operations(self.hookless())
+    }
+
+    fn hookless(&self) -> Self {
+        let mut connection = self.clone();
+        connection.operation_options = connection.operation_options.ignore_hooks(true);
+        for options in Arc::make_mut(&mut connection.collection_operation_options).values_mut() {
+            options.ignore_hooks = true;
+        }
+        connection
+    }
+
+    /// Wraps the connection in an [`Arc`], to share it across tasks or threads without cloning.
+    /// `Arc<DatabaseConnection>` implements [`DatabaseAccess`].
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// # pub struct User {
+    /// #     pub name: String,
+    /// # }
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap().into_shared();
+    /// let connection = db_connection.clone();
+    /// tokio::spawn(async move {
+    ///     DatabaseRecord::create(User { name: "Robert".to_string() }, &connection).await.unwrap();
+    /// });
+    /// let users = User::get(&User::query(), &db_connection).await.unwrap();
+    /// # }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    /// Returns a clone of the connection restricted to the documents of `tenant` for the tenant
+    /// scoped records, see [`Record::TENANT_FIELD`]. The clone is cheap and can be built for every
+    /// request.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// #[tenant_scoped(field = "org_id")]
+    /// pub struct Invoice {
+    ///     pub org_id: String,
+    ///     pub amount: u32,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let connection = db_connection.with_tenant("acme");
+    /// // Only the `acme` invoices are returned
+    /// let invoices = Invoice::get(&Invoice::query(), &connection).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Record::TENANT_FIELD`]: crate::Record::TENANT_FIELD
+    #[must_use]
+    pub fn with_tenant(&self, tenant: &str) -> Self {
+        let mut connection = self.clone();
+        connection.tenant = Some(tenant.to_string());
+        connection
+    }
+
+    /// Registers a `hook` of the `T` records called on `event` by the record operations of the
+    /// connection, its clones and its transactions, along with the hooks of the `Record` derive.
+    ///
+    /// Useful for cross-cutting concerns, like stamping or metrics, without changing the models.
+    /// See [`HookRegistry`] for the call order and [`on_any_hook`] for the hooks of every record.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, HookEvent, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// pub struct User {
+    ///     pub name: String,
+    ///     pub created_by: Option<String>,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// db_connection.on_before_create::<User, _>(|user, context| {
+    ///     user.created_by = context.actor.map(ToString::to_string);
+    ///     Ok(())
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// [`HookRegistry`]: crate::HookRegistry
+    /// [`on_any_hook`]: Self::on_any_hook
+    pub fn on_hook<T, F>(&self, event: HookEvent, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.hook_registry.register(event, hook);
+    }
+
+    /// Registers a `hook` of every record called on `event` with the serialized record, see
+    /// [`on_hook`].
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, HookEvent};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// db_connection.on_any_hook(HookEvent::AfterCreate, |_document, context| {
+    ///     log::info!("Created a {} document", context.collection);
+    ///     Ok(())
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_any_hook<F>(&self, event: HookEvent, hook: F)
+    where
+        F: Fn(&mut serde_json::Value, &HookContext<'_>) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.hook_registry.register_any(event, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called before their creation, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_before_create<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::BeforeCreate, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called before their save, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_before_save<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::BeforeSave, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called before their deletion, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_before_delete<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::BeforeDelete, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called after their creation, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_after_create<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::AfterCreate, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called after their save, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_after_save<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::AfterSave, hook);
+    }
+
+    /// Registers a `hook` of the `T` records called after their deletion, see [`on_hook`]
+    ///
+    /// [`on_hook`]: Self::on_hook
+    pub fn on_after_delete<T, F>(&self, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.on_hook(HookEvent::AfterDelete, hook);
+    }
+
+    /// Lists the collections of the database, excluding the system collections, with their type
+    /// and document count. Usable for administration interfaces or health checks.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{CollectionKind, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// for collection in db_connection.collections().await.unwrap() {
+    ///     let edge = collection.kind == CollectionKind::Edge;
+    ///     println!("{} (edge: {}): {} documents", collection.name, edge, collection.document_count);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    #[maybe_async::maybe_async]
+    pub async fn collections(&self) -> Result<Vec<CollectionSummary>, Error> {
+        let infos = self.database.accessible_collections().await?;
+        let mut summaries = Vec::with_capacity(infos.len());
+        for info in infos.into_iter().filter(|info| !info.is_system) {
+            let collection = match self.collections.get(&info.name) {
+                Some(collection) => collection.clone(),
+                None => DatabaseCollection::from(self.database.collection(&info.name).await?),
+            };
+            summaries.push(CollectionSummary {
+                kind: match info.collection_type {
+                    CollectionType::Edge => CollectionKind::Edge,
+                    CollectionType::Document => CollectionKind::Document,
+                },
+                document_count: u64::from(collection.record_count().await?),
+                name: info.name,
+            });
+        }
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(summaries)
+    }
+
+    /// Lists the named graphs of the database with their collections and their number of
+    /// vertices and edges, see [`collections`].
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    ///
+    /// [`collections`]: Self::collections
+    #[maybe_async::maybe_async]
+    pub async fn graphs(&self) -> Result<Vec<GraphSummary>, Error> {
+        let counts: HashMap<String, u64> = self
+            .collections()
+            .await?
+            .into_iter()
+            .map(|collection| (collection.name, collection.document_count))
+            .collect();
+        let count = |names: &[String]| -> u64 {
+            names
+                .iter()
+                .map(|name| counts.get(name).copied().unwrap_or(0))
+                .sum()
+        };
+        let mut summaries: Vec<GraphSummary> = self
+            .database
+            .graphs()
+            .await?
+            .graphs
+            .into_iter()
+            .map(|graph| {
+                let schema = GraphSchema::from(graph);
+                let edge_collections: Vec<String> = schema
+                    .edge_definitions
+                    .iter()
+                    .map(|definition| definition.collection.clone())
+                    .collect();
+                let vertex_collections: Vec<String> = schema
+                    .collection_names()
+                    .into_iter()
+                    .filter(|name| !edge_collections.iter().any(|edge| edge == name))
+                    .map(String::from)
+                    .collect();
+                GraphSummary {
+                    edge_count: count(&edge_collections),
+                    vertex_count: count(&vertex_collections),
+                    name: schema.name,
+                    edge_collections,
+                    vertex_collections,
+                }
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(summaries)
+    }
+
     /// Returns the number of currently running server-side transactions
     #[maybe_async::maybe_async]
     pub async fn transactions_count(&self) -> Result<usize, Error> {
@@ -177,6 +711,35 @@ impl DatabaseConnection {
         Ok(vec.len())
     }
 
+    /// Shuts the connection down for a clean service termination: waits for the in-flight record
+    /// operations and queries of the connection and its clones for at most `timeout`, then aborts
+    /// the open transactions built from the connection and removes the open query cursors.
+    ///
+    /// The connection remains usable afterwards, call this once the service stopped accepting work.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::DatabaseConnection;
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// // On SIGTERM, once the service stopped accepting requests
+    /// db_connection.shutdown(Duration::from_secs(10)).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Timeout`] if operations are still running after `timeout`, the
+    /// transactions and cursors are closed anyway
+    #[maybe_async::maybe_async]
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), Error> {
+        log::info!("Shutting down the database connection");
+        self.operation_tracker.shutdown(timeout).await
+    }
+
     /// Return the check result of db_name
     #[maybe_async::maybe_async]
     pub async fn check_database(&self, name: &str) -> Result<bool, Error> {
@@ -185,11 +748,40 @@ impl DatabaseConnection {
     }
 }
 
+fn schema_version_error(version: Option<u64>, required: u64) -> Option<Error> {
+    let message = match version {
+        Some(version) if version == required => return None,
+        Some(version) if version < required => format!(
+            "The database schema version {} is older than the required version {}, apply the pending migrations",
+            version, required
+        ),
+        Some(version) => format!(
+            "The database schema version {} is newer than the required version {}, the application is outdated",
+            version, required
+        ),
+        None => format!(
+            "The database schema is not versioned, the required version is {}",
+            required
+        ),
+    };
+    Some(Error::InitError {
+        item: "Schema version".to_string(),
+        message,
+    })
+}
+
 impl DatabaseAccess for DatabaseConnection {
     fn operation_options(&self) -> OperationOptions {
         self.operation_options.clone()
     }
 
+    fn collection_operation_options(&self, collection: &str) -> OperationOptions {
+        self.collection_operation_options
+            .get(collection)
+            .cloned()
+            .unwrap_or_else(|| self.operation_options())
+    }
+
     fn collection(&self, collection: &str) -> Option<&DatabaseCollection> {
         self.collections.get(collection)
     }
@@ -197,4 +789,56 @@ impl DatabaseAccess for DatabaseConnection {
     fn database(&self) -> &Database {
         &self.database
     }
+
+    fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_threshold
+    }
+
+    fn deserialization_mode(&self) -> DeserializationMode {
+        self.deserialization_mode
+    }
+
+    fn bind_literals(&self) -> bool {
+        self.bind_literals
+    }
+
+    fn request_limiter(&self) -> Option<&Arc<RequestLimiter>> {
+        self.request_limiter.as_ref()
+    }
+
+    fn operation_tracker(&self) -> Option<&OperationTracker> {
+        Some(&self.operation_tracker)
+    }
+
+    fn hook_registry(&self) -> Option<&HookRegistry> {
+        Some(&self.hook_registry)
+    }
+
+    fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    fn velocypack_transport(&self) -> Option<&VelocyPackTransport> {
+        self.velocypack_transport.as_ref()
+    }
+
+    fn counter_caches(&self) -> &[(&'static str, CounterCache)] {
+        &self.counter_caches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_version_check() {
+        assert!(schema_version_error(Some(3), 3).is_none());
+        for version in [Some(2), Some(4), None] {
+            assert!(matches!(
+                schema_version_error(version, 3),
+                Some(Error::InitError { .. })
+            ));
+        }
+    }
 }
@@ -1,8 +1,16 @@
 #![allow(clippy::redundant_pub_crate)]
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+use std::time::Duration;
 
-use crate::schema::{DatabaseSchema, SCHEMA_DEFAULT_FILE_NAME, SCHEMA_DEFAULT_PATH};
-use crate::{AuthMode, DatabaseConnection, Error, OperationOptions};
+use crate::schema::{
+    DatabaseSchema, SCHEMA_DEFAULT_COLLECTION_NAME, SCHEMA_DEFAULT_FILE_NAME, SCHEMA_DEFAULT_PATH,
+};
+use crate::{
+    AuthMode, CounterCache, DatabaseConnection, DeserializationMode, Error, OperationOptions,
+    Record, RequestLimiter, VelocyPackTransport,
+};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub(crate) struct DbCredentials {
@@ -25,6 +33,13 @@ pub(crate) enum DatabaseSchemaOption {
     Custom(DatabaseSchema),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SchemaApplication {
+    Skip,
+    Silent,
+    Strict,
+}
+
 impl From<DbCredentialsOption> for DbCredentials {
     fn from(option: DbCredentialsOption) -> Self {
         match option {
@@ -66,11 +81,20 @@ impl TryFrom<DatabaseSchemaOption> for DatabaseSchema {
 
 /// Builder for `DatabaseConnection`
 pub struct DatabaseConnectionBuilder {
-    pub(crate) apply_schema: bool,
+    pub(crate) apply_schema: SchemaApplication,
     pub(crate) auth_mode: AuthMode,
     pub(crate) credentials: DbCredentialsOption,
     pub(crate) schema: DatabaseSchemaOption,
     pub(crate) operation_options: OperationOptions,
+    pub(crate) collection_operation_options: HashMap<String, OperationOptions>,
+    pub(crate) slow_query_threshold: Option<Duration>,
+    pub(crate) deserialization_mode: DeserializationMode,
+    pub(crate) bind_literals: bool,
+    pub(crate) velocypack_transport: bool,
+    pub(crate) max_concurrent_requests: Option<usize>,
+    pub(crate) counter_caches: Vec<(&'static str, CounterCache)>,
+    pub(crate) required_schema_version: Option<u64>,
+    pub(crate) schema_collection_name: Option<String>,
 }
 
 impl DatabaseConnectionBuilder {
@@ -108,7 +132,20 @@ impl DatabaseConnectionBuilder {
         let auth_mode = self.auth_mode();
         let apply_schema = self.apply_schema;
         let operation_options = self.operation_options.clone();
+        let collection_operation_options = self.collection_operation_options.clone();
+        let slow_query_threshold = self.slow_query_threshold;
+        let deserialization_mode = self.deserialization_mode;
+        let bind_literals = self.bind_literals;
+        let velocypack_transport = self.velocypack_transport;
+        let max_concurrent_requests = self.max_concurrent_requests;
+        let counter_caches = self.counter_caches.clone();
+        let required_schema_version = self.required_schema_version;
+        let schema_collection_name = self
+            .schema_collection_name
+            .clone()
+            .unwrap_or_else(|| SCHEMA_DEFAULT_COLLECTION_NAME.to_string());
         let schema = self.schema()?;
+        // TODO: expose the HTTP/2 and connection reuse settings once the driver accepts a configured HTTP client
         let database = DatabaseConnection::connect(
             &credentials.db_host,
             &credentials.db_name,
@@ -117,7 +154,28 @@ impl DatabaseConnectionBuilder {
             auth_mode,
         )
         .await?;
-        DatabaseConnection::new(database, schema, apply_schema, operation_options).await
+        if let Some(version) = required_schema_version {
+            DatabaseConnection::check_schema_version(&database, &schema_collection_name, version)
+                .await?;
+        }
+        let velocypack_transport =
+            velocypack_transport.then(|| VelocyPackTransport::new(&database));
+        let mut connection = DatabaseConnection::new(
+            database,
+            schema,
+            apply_schema,
+            operation_options,
+            collection_operation_options,
+            slow_query_threshold,
+            deserialization_mode,
+        )
+        .await?;
+        connection.bind_literals = bind_literals;
+        connection.velocypack_transport = velocypack_transport;
+        connection.request_limiter =
+            max_concurrent_requests.map(|max| Arc::new(RequestLimiter::new(max)));
+        connection.counter_caches = Arc::new(counter_caches);
+        Ok(connection)
     }
 
     /// Specifies a custom authentication mode for `ArangoDB` connection.
@@ -179,11 +237,30 @@ impl DatabaseConnectionBuilder {
     /// This will ignore any errors, so check the `debug` to find a hidden issue.
     ///
     /// Use it when you use your own custom schema and no `aragog_cli` migrations.
+    /// Prefer [`apply_schema_strict`] to be notified of the schema issues.
+    ///
+    /// [`apply_schema_strict`]: Self::apply_schema_strict
     #[must_use]
     #[inline]
     pub fn apply_schema(mut self) -> Self {
         log::debug!("[Database Connection Builder] Schema will be silently applied");
-        self.apply_schema = true;
+        self.apply_schema = SchemaApplication::Silent;
+        self
+    }
+
+    /// Call this method if you want the schema to be applied by computing the difference with
+    /// the database: only the missing elements are created and reported in the `info` logs.
+    /// The connection building fails on any error or incompatible definition, like an index type
+    /// change (see [`DatabaseSchema::apply_strict`]).
+    ///
+    /// Use it when you use your own custom schema and no `aragog_cli` migrations.
+    ///
+    /// [`DatabaseSchema::apply_strict`]: crate::schema::DatabaseSchema::apply_strict
+    #[must_use]
+    #[inline]
+    pub fn apply_schema_strict(mut self) -> Self {
+        log::debug!("[Database Connection Builder] Schema will be strictly applied");
+        self.apply_schema = SchemaApplication::Strict;
         self
     }
 
@@ -222,6 +299,209 @@ impl DatabaseConnectionBuilder {
         self
     }
 
+    /// Specifies custom options for `write` operations (`create`, `save`, `delete`) on the
+    /// `collection` documents, overriding the options set with [`with_operation_options`].
+    ///
+    /// # Note
+    ///
+    /// The connection building will fail if `collection` is not in the schema or if the options
+    /// are not compatible with it (see [`OperationOptions::validate_for_collection`]).
+    ///
+    /// [`with_operation_options`]: Self::with_operation_options
+    /// [`OperationOptions::validate_for_collection`]: crate::OperationOptions::validate_for_collection
+    #[must_use]
+    #[inline]
+    pub fn with_collection_operation_options(
+        mut self,
+        collection: &str,
+        options: OperationOptions,
+    ) -> Self {
+        log::debug!(
+            "[Database Connection Builder] custom operation options will be used for {}: {:?}",
+            collection,
+            options
+        );
+        self.collection_operation_options
+            .insert(collection.to_string(), options);
+        self
+    }
+
+    /// Logs the AQL queries lasting more than `threshold` at `WARN` level, with their duration.
+    /// The string literals and the bind parameters values of the logged queries are redacted.
+    #[must_use]
+    #[inline]
+    pub fn log_slow_queries(mut self, threshold: Duration) -> Self {
+        log::debug!(
+            "[Database Connection Builder] Queries slower than {:?} will be logged",
+            threshold
+        );
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    /// Specifies the strictness of the stored documents deserialization on record retrievals,
+    /// queries and cursors.
+    ///
+    /// If not specified [`DeserializationMode::Lenient`] will be used, ignoring the document
+    /// fields unknown to the records. [`DeserializationMode::Strict`] rejects them, catching
+    /// schema drifts early in development.
+    #[must_use]
+    #[inline]
+    pub fn with_deserialization_mode(mut self, mode: DeserializationMode) -> Self {
+        log::debug!(
+            "[Database Connection Builder] Documents will be deserialized in {:?} mode",
+            mode
+        );
+        self.deserialization_mode = mode;
+        self
+    }
+
+    /// Specifies if the literal values of the queries, like the compared values and the `LIMIT`
+    /// values, are passed as bind parameters.
+    ///
+    /// If not specified the literals are bound: the queries of the same shape are sent with the
+    /// same AQL string, letting `ArangoDB` reuse the cached query plans on read heavy workloads.
+    /// Disabling it renders the literals in the query strings, like [`Query::aql_str`].
+    ///
+    /// [`Query::aql_str`]: crate::query::Query::aql_str
+    #[must_use]
+    #[inline]
+    pub fn with_literal_binding(mut self, bind_literals: bool) -> Self {
+        log::debug!(
+            "[Database Connection Builder] Query literals will be {}",
+            if bind_literals { "bound" } else { "inlined" }
+        );
+        self.bind_literals = bind_literals;
+        self
+    }
+
+    /// Retrieves the documents and the query cursor batches as `VelocyPack` instead of JSON,
+    /// reducing the bandwidth and the parsing of large result sets. The responses are decoded
+    /// according to their content type, so servers answering JSON are still supported.
+    ///
+    /// If not specified the responses are JSON.
+    ///
+    /// # Note
+    ///
+    /// The `VelocyPack` requests reuse the session authentication of the [`AuthMode`]. The
+    /// transactions and the other operations stay JSON.
+    ///
+    /// [`AuthMode`]: crate::AuthMode
+    #[must_use]
+    #[inline]
+    pub fn with_velocypack_transport(mut self) -> Self {
+        log::debug!("[Database Connection Builder] VelocyPack transport will be used");
+        self.velocypack_transport = true;
+        self
+    }
+
+    /// Limits the number of concurrent in-flight requests of the connection, and of its clones
+    /// and transactions, to `max_requests`. Additional record operations and queries wait for a
+    /// request slot, in arrival order, so bursts don't exhaust the server connections.
+    ///
+    /// If not specified the requests are not limited.
+    ///
+    /// # Note
+    ///
+    /// The HTTP client of the driver is not configurable: it uses HTTP/1.1 and keeps its
+    /// connections alive for reuse, limiting the concurrent requests bounds the number of open
+    /// server connections.
+    #[must_use]
+    #[inline]
+    pub fn with_max_concurrent_requests(mut self, max_requests: usize) -> Self {
+        log::debug!(
+            "[Database Connection Builder] At most {} concurrent requests",
+            max_requests
+        );
+        self.max_concurrent_requests = Some(max_requests);
+        self
+    }
+
+    /// Maintains the counter caches declared on `T` (see [`Record::COUNTER_CACHES`]) on the edge
+    /// writes, for the operations of the connection, its clones and its transactions.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Clone, Record, Serialize, Deserialize)]
+    /// #[counter_cache(edge = "Likes", field = "likes_count")]
+    /// pub struct Post {
+    ///     #[serde(default)]
+    ///     pub likes_count: u64,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder()
+    ///     .with_counter_caches::<Post>()
+    ///     .build()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Record::COUNTER_CACHES`]: crate::Record::COUNTER_CACHES
+    #[must_use]
+    pub fn with_counter_caches<T: Record>(mut self) -> Self {
+        log::debug!(
+            "[Database Connection Builder] {} counter caches will be maintained",
+            T::COLLECTION_NAME
+        );
+        self.counter_caches.extend(
+            T::COUNTER_CACHES
+                .iter()
+                .map(|counter_cache| (T::COLLECTION_NAME, *counter_cache)),
+        );
+        self
+    }
+
+    /// Requires the database schema version, synchronized by `aragog_cli` migrations, to be
+    /// `version`: the connection building fails fast if the live database schema is older, newer
+    /// or not versioned, preventing silent mismatches after partial deployments.
+    ///
+    /// The version is read from the `AragogConfiguration` collection unless specified otherwise
+    /// with [`with_schema_collection_name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::DatabaseConnection;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let db_connection = DatabaseConnection::builder()
+    ///     // The version of the latest migration shipped with the application
+    ///     .require_schema_version(1_638_374_400_000)
+    ///     .build()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`with_schema_collection_name`]: Self::with_schema_collection_name
+    #[must_use]
+    #[inline]
+    pub fn require_schema_version(mut self, version: u64) -> Self {
+        log::debug!(
+            "[Database Connection Builder] Schema version {} will be required",
+            version
+        );
+        self.required_schema_version = Some(version);
+        self
+    }
+
+    /// Specifies the collection storing the database schema version, to match a custom
+    /// `aragog_cli` `--aragog-collection` option.
+    ///
+    /// If not specified `AragogConfiguration` will be used.
+    #[must_use]
+    #[inline]
+    pub fn with_schema_collection_name(mut self, name: &str) -> Self {
+        self.schema_collection_name = Some(name.to_string());
+        self
+    }
+
     #[must_use]
     #[inline]
     fn credentials(&self) -> DbCredentials {
@@ -1,8 +1,19 @@
+use crate::counter_cache::CountedEdge;
 use crate::db::database_service;
-use crate::db::database_service::{query_records, query_records_in_batches, raw_query_records};
-use crate::query::{Query, QueryCursor, QueryResult};
-use crate::{DatabaseAccess, EdgeRecord, Error, OperationOptions, Record};
+use crate::db::database_service::{
+    query_facets, query_raw_documents, query_records, query_records_in_batches, raw_query_records,
+};
+use crate::query::utils::is_attribute_path;
+use crate::query::{
+    Comparison, Facets, GraphQueryDirection, GroupedResult, Query, QueryCursor, QueryResult,
+    Related, BOUND_ARRAY_CHUNK_SIZE,
+};
+use crate::{
+    ChangeSet, ConflictPolicy, DatabaseAccess, DocumentId, DocumentKey, EdgeRecord, Error,
+    HookEvent, OperationOptions, RawDocument, Record, RecordAction, UndefinedRecord, UpsertOutcome,
+};
 use arangors_lite::{AqlQuery, Document};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
@@ -29,10 +40,10 @@ use async_graphql::{
 pub struct DatabaseRecord<T> {
     /// The Document unique and indexed `_key`
     #[serde(rename = "_key")]
-    pub(crate) key: String,
+    pub(crate) key: DocumentKey,
     /// The Document unique and indexed `_id`
     #[serde(rename = "_id")]
-    pub(crate) id: String,
+    pub(crate) id: DocumentId,
     /// The Document revision `_rev`
     #[serde(rename = "_rev")]
     pub(crate) rev: String,
@@ -44,6 +55,7 @@ pub struct DatabaseRecord<T> {
 #[allow(dead_code)]
 impl<T: Record> DatabaseRecord<T> {
     #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)] // Kept by value for backwards compatibility
     async fn __create_with_options<D>(
         mut record: T,
         key: Option<String>,
@@ -53,14 +65,32 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
+        options.authorize::<T>(RecordAction::Create, None)?;
         let launch_hooks = !options.ignore_hooks;
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeCreate,
+                &mut record,
+                db_accessor,
+                &options,
+            )?;
             record.before_create_hook(db_accessor).await?;
         }
+        // Checked after the hooks, which may set the tenant
+        database_service::check_tenant(&record, db_accessor)?;
         let mut res =
-            database_service::create_record(record, key, db_accessor, T::COLLECTION_NAME, options)
+            database_service::create_record(record, key, db_accessor, T::COLLECTION_NAME, &options)
                 .await?;
+        if let Some(edge) = CountedEdge::of(&res.record, db_accessor)? {
+            edge.update(1, db_accessor).await?;
+        }
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterCreate,
+                &mut res.record,
+                db_accessor,
+                &options,
+            )?;
             res.record.after_create_hook(db_accessor).await?;
         }
         Ok(res)
@@ -175,7 +205,12 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        Self::create_with_options(record, db_accessor, db_accessor.operation_options()).await
+        Self::create_with_options(
+            record,
+            db_accessor,
+            db_accessor.collection_operation_options(T::COLLECTION_NAME),
+        )
+        .await
     }
 
     /// Creates a document in database with a custom key.
@@ -204,8 +239,13 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        Self::create_with_key_and_options(record, key, db_accessor, db_accessor.operation_options())
-            .await
+        Self::create_with_key_and_options(
+            record,
+            key,
+            db_accessor,
+            db_accessor.collection_operation_options(T::COLLECTION_NAME),
+        )
+        .await
     }
 
     /// Creates a document in database.
@@ -243,13 +283,173 @@ impl<T: Record> DatabaseRecord<T> {
             record,
             db_accessor,
             db_accessor
-                .operation_options()
+                .collection_operation_options(T::COLLECTION_NAME)
                 .ignore_revs(true)
                 .ignore_hooks(true),
         )
         .await
     }
 
+    /// Inserts `record` in database, or replaces the document having the same `field` value.
+    ///
+    /// # Note
+    ///
+    /// This method should be used for very specific cases, prefer using `upsert_by` instead.
+    /// The revision of the replaced document is always checked, whatever the `ignore_revs` option.
+    ///
+    /// # Hooks
+    ///
+    /// An inserted record launches the `T` hooks `before_create` and `after_create`, a replacing
+    /// one the hooks `before_save` and `after_save`, unless the `options` argument disables hooks.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The record attribute path identifying the document, which should be covered by
+    /// a unique index
+    /// * `record` - The document to insert or to replace the matching one with
+    /// * `db_accessor` - database connection reference
+    /// * `options` - Operation options to apply
+    ///
+    /// # Returns
+    ///
+    /// On success the stored record is returned along with the performed write.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`] is returned if the operation or the hooks failed, an
+    /// [`Error::ValidationError`] if `field` isn't an attribute path or has no value in `record`.
+    ///
+    /// [`Error`]: crate::Error
+    /// [`Error::ValidationError`]: crate::Error::ValidationError
+    #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)]
+    pub async fn upsert_by_with_options<D>(
+        field: &str,
+        mut record: T,
+        db_accessor: &D,
+        options: OperationOptions,
+    ) -> Result<(Self, UpsertOutcome), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        if !is_attribute_path(field) {
+            return Err(Error::ValidationError(format!(
+                "{} is not a valid attribute path",
+                field
+            )));
+        }
+        let pointer = format!("/{}", field.replace('.', "/"));
+        let value = serde_json::to_value(&record)?
+            .pointer(&pointer)
+            .filter(|value| !value.is_null())
+            .cloned()
+            .ok_or_else(|| {
+                Error::ValidationError(format!(
+                    "{} record has no {} value to upsert by",
+                    T::COLLECTION_NAME,
+                    field
+                ))
+            })?;
+        // The default scope is skipped as the field is unique over the whole collection
+        let query = T::query()
+            .unscoped()
+            .filter(Comparison::field(field).equals("@value").into())
+            .bind_var("value", value)
+            .limit(1, None);
+        let existing = query_records(db_accessor, &query).await?.0.pop();
+        let mut existing = if let Some(existing) = existing {
+            existing
+        } else {
+            let res = Self::__create_with_options(record, None, db_accessor, options).await?;
+            return Ok((res, UpsertOutcome::Inserted));
+        };
+        options.authorize(RecordAction::Save, Some(&existing))?;
+        let launch_hooks = !options.ignore_hooks;
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeSave,
+                &mut record,
+                db_accessor,
+                &options,
+            )?;
+            record.before_save_hook(db_accessor).await?;
+        }
+        database_service::check_tenant(&record, db_accessor)?;
+        existing.record = record;
+        // The looked up revision is checked so a concurrent write isn't overwritten
+        let mut res = database_service::replace_record(
+            existing,
+            db_accessor,
+            T::COLLECTION_NAME,
+            &options.clone().ignore_revs(false),
+        )
+        .await?;
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterSave,
+                &mut res.record,
+                db_accessor,
+                &options,
+            )?;
+            res.record.after_save_hook(db_accessor).await?;
+        }
+        Ok((res, UpsertOutcome::Updated))
+    }
+
+    /// Inserts `record` in database, or replaces the document having the same `field` value.
+    /// Useful to import documents identified by a unique field.
+    ///
+    /// The matching document is looked up first, ignoring the [`default_scope`] of `T` but not
+    /// its tenant, and is authorized as a `Save` target of the operation actor. A new record is
+    /// authorized as a `Create`.
+    /// The lookup and the write are two requests: a concurrent write fails on the unique index or,
+    /// as the revision of the looked up document is always checked, with [`Error::Conflict`]
+    /// instead of being overwritten.
+    ///
+    /// # Hooks
+    ///
+    /// An inserted record launches the `T` hooks `before_create` and `after_create`, a replacing
+    /// one the hooks `before_save` and `after_save`, unless the `db_accessor` operations options
+    /// specifically disable hooks.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The record attribute path identifying the document, which should be covered by
+    /// a unique index
+    /// * `record` - The document to insert or to replace the matching one with
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success the stored record is returned along with the performed write.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`] is returned if the operation or the hooks failed, an
+    /// [`Error::ValidationError`] if `field` isn't an attribute path or has no value in `record`.
+    ///
+    /// [`Error`]: crate::Error
+    /// [`Error::ValidationError`]: crate::Error::ValidationError
+    /// [`Error::Conflict`]: crate::Error::Conflict
+    /// [`default_scope`]: crate::Record::default_scope
+    #[maybe_async::maybe_async]
+    pub async fn upsert_by<D>(
+        field: &str,
+        record: T,
+        db_accessor: &D,
+    ) -> Result<(Self, UpsertOutcome), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::upsert_by_with_options(
+            field,
+            record,
+            db_accessor,
+            db_accessor.collection_operation_options(T::COLLECTION_NAME),
+        )
+        .await
+    }
+
     /// Writes in the database the new state of the record, "saving it".
     ///
     /// # Note
@@ -277,6 +477,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// [`Error`]: crate::Error
     /// [`DatabaseConnection`]: crate::DatabaseConnection
     #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)] // Kept by value for backwards compatibility
     pub async fn save_with_options<D>(
         &mut self,
         db_accessor: &D,
@@ -285,8 +486,16 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
+        options.authorize(RecordAction::Save, Some(self))?;
+        database_service::check_tenant(&self.record, db_accessor)?;
         let launch_hooks = !options.ignore_hooks;
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeSave,
+                &mut self.record,
+                db_accessor,
+                &options,
+            )?;
             self.record.before_save_hook(db_accessor).await?;
         }
         let mut new_record = database_service::update_record(
@@ -294,10 +503,16 @@ impl<T: Record> DatabaseRecord<T> {
             self.key(),
             db_accessor,
             T::COLLECTION_NAME,
-            options,
+            &options,
         )
         .await?;
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterSave,
+                &mut new_record.record,
+                db_accessor,
+                &options,
+            )?;
             new_record.record.after_save_hook(db_accessor).await?;
         }
         *self = new_record;
@@ -306,6 +521,8 @@ impl<T: Record> DatabaseRecord<T> {
 
     /// Writes in the database the new state of the record, "saving it".
     ///
+    /// The revision conflicts are resolved according to the [`Record::conflict_policy`] of `T`.
+    ///
     /// # Hooks
     ///
     /// This function will launch `T` hooks `before_save` and `after_save` unless the `db_accessor`
@@ -321,15 +538,174 @@ impl<T: Record> DatabaseRecord<T> {
     /// An [`Error`] is returned if the operation or the hooks failed.
     ///
     /// [`Error`]: crate::Error
+    /// [`Record::conflict_policy`]: crate::Record::conflict_policy
     #[maybe_async::maybe_async]
     pub async fn save<D>(&mut self, db_accessor: &D) -> Result<(), Error>
     where
         D: DatabaseAccess + ?Sized,
     {
-        self.save_with_options(db_accessor, db_accessor.operation_options())
+        self.save_with_conflict_policy(db_accessor, &T::conflict_policy())
             .await
     }
 
+    /// Writes in the database the new state of the record like [`save`], resolving the revision
+    /// conflicts with `policy` instead of the [`Record::conflict_policy`] of `T`.
+    ///
+    /// The revisions are checked unless `policy` is [`ConflictPolicy::Fail`]. On a conflict, the
+    /// stored document is reloaded and the record resolved by `policy` is saved with the stored
+    /// revision, until the save succeeds or the policy retries are exhausted. The hooks are
+    /// launched on every attempt.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`] is returned if the operation or the hooks failed, or if the conflict remains
+    /// after the policy retries.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{ConflictPolicy, DatabaseConnection, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct Stock { quantity: u32, reserved: u32 }
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let mut stock = Stock::find("123", &db_accessor).await.unwrap();
+    /// stock.reserved += 1;
+    /// // Reserves one more item on top of the concurrent reservations
+    /// let policy = ConflictPolicy::merge(|_local: &Stock, stored: &Stock| Stock {
+    ///     quantity: stored.quantity,
+    ///     reserved: stored.reserved + 1,
+    /// });
+    /// stock.save_with_conflict_policy(&db_accessor, &policy).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`save`]: Self::save
+    /// [`Error`]: crate::Error
+    /// [`Record::conflict_policy`]: crate::Record::conflict_policy
+    /// [`ConflictPolicy::Fail`]: crate::ConflictPolicy::Fail
+    #[maybe_async::maybe_async]
+    pub async fn save_with_conflict_policy<D>(
+        &mut self,
+        db_accessor: &D,
+        policy: &ConflictPolicy<T>,
+    ) -> Result<(), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let mut options = db_accessor.collection_operation_options(T::COLLECTION_NAME);
+        if !matches!(policy, ConflictPolicy::Fail) {
+            options = options.ignore_revs(false);
+        }
+        let mut retries = 0;
+        loop {
+            let error = match self.save_with_options(db_accessor, options.clone()).await {
+                Err(error) if error.is_revision_conflict() && retries < policy.retries() => error,
+                result => return result,
+            };
+            retries += 1;
+            log::debug!(
+                "Resolving revision conflict of {} {} ({}/{}): {}",
+                T::COLLECTION_NAME,
+                self.key(),
+                retries,
+                policy.retries(),
+                error
+            );
+            let stored = Self::find(self.key(), db_accessor).await?;
+            policy.resolve(&mut self.record, &stored.record);
+            self.rev = stored.rev;
+        }
+    }
+
+    /// Writes in the database the new state of the record like [`save`], returning the fields
+    /// actually changed by the save as a [`ChangeSet`] with their previous and new values.
+    ///
+    /// The previous state is returned by the database along with the new one, without a second
+    /// read, so the changes made by other clients since the record retrieval are included.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks `before_save` and `after_save` unless the `db_accessor`
+    /// operations options specifically disable hooks.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`] is returned if the operation or the hooks failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User { email: String }
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let mut user = User::find("123", &db_accessor).await.unwrap();
+    /// user.email = "new@mail.com".to_string();
+    /// let change_set = user.save_and_diff(&db_accessor).await.unwrap();
+    /// if let Some(change) = change_set.get("email") {
+    ///     println!("email changed from {:?} to {:?}", change.old, change.new);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`save`]: Self::save
+    /// [`ChangeSet`]: crate::ChangeSet
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn save_and_diff<D>(&mut self, db_accessor: &D) -> Result<ChangeSet, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let options = db_accessor.collection_operation_options(T::COLLECTION_NAME);
+        options.authorize(RecordAction::Save, Some(self))?;
+        database_service::check_tenant(&self.record, db_accessor)?;
+        let launch_hooks = !options.ignore_hooks;
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeSave,
+                &mut self.record,
+                db_accessor,
+                &options,
+            )?;
+            self.record.before_save_hook(db_accessor).await?;
+        }
+        let (old_record, mut new_record) = database_service::update_record_returning_old(
+            self.clone(),
+            self.key(),
+            db_accessor,
+            T::COLLECTION_NAME,
+            &options,
+        )
+        .await?;
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterSave,
+                &mut new_record.record,
+                db_accessor,
+                &options,
+            )?;
+            new_record.record.after_save_hook(db_accessor).await?;
+        }
+        let change_set = ChangeSet::new(
+            old_record.rev,
+            new_record.rev.clone(),
+            &serde_json::to_value(&old_record.record)?,
+            &serde_json::to_value(&new_record.record)?,
+        );
+        *self = new_record;
+        Ok(change_set)
+    }
+
     /// Writes in the database the new state of the record.
     ///
     /// # Note
@@ -361,7 +737,7 @@ impl<T: Record> DatabaseRecord<T> {
         self.save_with_options(
             db_accessor,
             db_accessor
-                .operation_options()
+                .collection_operation_options(T::COLLECTION_NAME)
                 .ignore_hooks(true)
                 .ignore_revs(true),
         )
@@ -395,6 +771,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// [`Error`]: crate::Error
     /// [`DatabaseConnection`]: crate::DatabaseConnection
     #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)] // Kept by value for backwards compatibility
     pub async fn delete_with_options<D>(
         &mut self,
         db_accessor: &D,
@@ -403,18 +780,35 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
+        options.authorize(RecordAction::Delete, Some(self))?;
+        database_service::check_tenant(&self.record, db_accessor)?;
         let launch_hooks = !options.ignore_hooks;
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeDelete,
+                &mut self.record,
+                db_accessor,
+                &options,
+            )?;
             self.record.before_delete_hook(db_accessor).await?;
         }
         database_service::remove_record::<T, D>(
             self.key(),
             db_accessor,
             T::COLLECTION_NAME,
-            options,
+            &options,
         )
         .await?;
+        if let Some(edge) = CountedEdge::of(&self.record, db_accessor)? {
+            edge.update(-1, db_accessor).await?;
+        }
         if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterDelete,
+                &mut self.record,
+                db_accessor,
+                &options,
+            )?;
             self.record.after_delete_hook(db_accessor).await?;
         }
         Ok(())
@@ -443,8 +837,11 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        self.delete_with_options(db_accessor, db_accessor.operation_options())
-            .await
+        self.delete_with_options(
+            db_accessor,
+            db_accessor.collection_operation_options(T::COLLECTION_NAME),
+        )
+        .await
     }
 
     /// Removes the record from the database.
@@ -470,89 +867,774 @@ impl<T: Record> DatabaseRecord<T> {
     /// On success `()` is returned, meaning that the record is now deleted, the structure should not be used afterwards.
     /// On failure an [`Error`] is returned.
     ///
-    /// [`Error`]: crate::Error
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn force_delete<D>(&mut self, db_accessor: &D) -> Result<(), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        self.delete_with_options(
+            db_accessor,
+            db_accessor
+                .collection_operation_options(T::COLLECTION_NAME)
+                .ignore_revs(true)
+                .ignore_hooks(true),
+        )
+        .await
+    }
+
+    /// Removes the vertex record from the database through the named graph API, which also
+    /// removes every incident edge defined in the graphs edge definitions.
+    /// Unlike [`delete`], no dangling edge will remain after this operation.
+    ///
+    /// The vertex is removed through the first database graph declaring `T` collection as a vertex
+    /// collection, and its incident edges are also removed from the edge definitions of every other
+    /// graph using `T` collection.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks  `before_delete` and `after_delete` unless the `db_accessor`
+    /// operations options specifically disable hooks.
+    ///
+    /// # Arguments:
+    ///
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success `()` is returned, meaning that the record and its edges are now deleted, the structure should not be used afterwards.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`] is returned if the operation or the hooks failed, or [`Error::NotFound`] if no graph
+    /// uses `T` collection.
+    ///
+    /// [`delete`]: Self::delete
+    /// [`Error`]: crate::Error
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    #[maybe_async::maybe_async]
+    pub async fn detach_delete<D>(&self, db_accessor: &D) -> Result<(), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let options = db_accessor.collection_operation_options(T::COLLECTION_NAME);
+        options.authorize(RecordAction::Delete, Some(self))?;
+        database_service::check_tenant(&self.record, db_accessor)?;
+        let launch_hooks = !options.ignore_hooks;
+        // The hooks run on a copy, the record being removed
+        let mut record = self.record.clone();
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::BeforeDelete,
+                &mut record,
+                db_accessor,
+                &options,
+            )?;
+            record.before_delete_hook(db_accessor).await?;
+        }
+        database_service::remove_vertex(self.key(), db_accessor, T::COLLECTION_NAME, &options)
+            .await?;
+        if launch_hooks {
+            database_service::run_registered_hooks(
+                HookEvent::AfterDelete,
+                &mut record,
+                db_accessor,
+                &options,
+            )?;
+            record.after_delete_hook(db_accessor).await?;
+        }
+        Ok(())
+    }
+
+    /// Creates and returns edge between `from_record` and `target_record`.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks `before_create` and `after_create`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on hook failure or on database request failure. If the `db_accessor` operation options
+    /// of `T` enable [`OperationOptions::check_edge_endpoints`], fails with [`Error::NotFound`] if
+    /// `from_record` or `to_record` doesn't exist.
+    ///
+    /// [`OperationOptions::check_edge_endpoints`]: crate::OperationOptions::check_edge_endpoints
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aragog::{DatabaseRecord, EdgeRecord, Record, DatabaseConnection};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #[derive(Clone, Record, Serialize, Deserialize)]
+    /// struct Edge {
+    ///     description: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder()
+    /// #     .with_schema_path("tests/schema.yaml")
+    /// #     .apply_schema()
+    /// #     .build().await.unwrap();
+    /// # db_accessor.unsafe_truncate_all().await.unwrap();
+    /// let user_a = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    /// let user_b = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    ///
+    /// let edge = DatabaseRecord::link(&user_a, &user_b, &db_accessor,
+    ///     Edge { description: "description".to_string() }
+    /// ).await.unwrap();
+    /// assert_eq!(edge.id_from(), user_a.id());
+    /// assert_eq!(edge.id_to(), user_b.id());
+    /// assert_eq!(&edge.description, "description");
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn link<A, B, D>(
+        from_record: &DatabaseRecord<A>,
+        to_record: &DatabaseRecord<B>,
+        db_accessor: &D,
+        edge_record: T,
+    ) -> Result<DatabaseRecord<EdgeRecord<T>>, Error>
+    where
+        A: Record,
+        B: Record,
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        let edge = EdgeRecord::new_from_records(from_record, to_record, edge_record)?;
+        if db_accessor
+            .collection_operation_options(T::COLLECTION_NAME)
+            .check_edge_endpoints
+        {
+            database_service::check_edge_endpoints(edge.id_from(), edge.id_to(), db_accessor)
+                .await?;
+        }
+        DatabaseRecord::create(edge, db_accessor).await
+    }
+
+    /// Creates and returns edges between every `(id_from, id_to)` document ids pair, with a single
+    /// bulk insert into the edge collection. The edge data is built by `edge_factory` from the
+    /// pair ids.
+    ///
+    /// Prefer it to [`link`] to create many edges, on large graph imports for example: if any edge
+    /// fails to be inserted, none is created. The insert runs in the `db_accessor` transaction if
+    /// any, and with the [`check_edge_endpoints`] option it fails with [`Error::NotFound`] if an
+    /// endpoint document is missing.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks `before_create` and `after_create` for every edge, unless
+    /// the `db_accessor` operations options specifically disable hooks.
+    ///
+    /// # Errors
+    ///
+    /// Fails on invalid ids, on hook failure, on missing endpoint or on database request failure
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aragog::{DatabaseRecord, EdgeRecord, Record, DatabaseConnection};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #[derive(Clone, Record, Serialize, Deserialize)]
+    /// struct Edge {
+    ///     description: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder()
+    /// #     .with_schema_path("tests/schema.yaml")
+    /// #     .apply_schema()
+    /// #     .build().await.unwrap();
+    /// # db_accessor.unsafe_truncate_all().await.unwrap();
+    /// let user_a = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    /// let user_b = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    /// let user_c = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    ///
+    /// let pairs = [(user_a.id(), user_b.id()), (user_a.id(), user_c.id())];
+    /// let edges = DatabaseRecord::link_many(&pairs, |_, to| Edge {
+    ///     description: format!("Knows {}", to)
+    /// }, &db_accessor).await.unwrap();
+    /// assert_eq!(edges.len(), 2);
+    /// assert_eq!(edges[1].id_to(), user_c.id());
+    /// # }
+    /// ```
+    ///
+    /// [`link`]: Self::link
+    /// [`check_edge_endpoints`]: crate::OperationOptions::check_edge_endpoints
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    #[maybe_async::maybe_async]
+    pub async fn link_many<P, F, D>(
+        pairs: &[(P, P)],
+        mut edge_factory: F,
+        db_accessor: &D,
+    ) -> Result<Vec<DatabaseRecord<EdgeRecord<T>>>, Error>
+    where
+        P: AsRef<str> + Sync,
+        F: FnMut(&str, &str) -> T + Send,
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        let options = db_accessor.collection_operation_options(T::COLLECTION_NAME);
+        options.authorize::<EdgeRecord<T>>(RecordAction::Create, None)?;
+        let launch_hooks = !options.ignore_hooks;
+        let mut edges = Vec::with_capacity(pairs.len());
+        for (id_from, id_to) in pairs {
+            let (id_from, id_to) = (id_from.as_ref(), id_to.as_ref());
+            let mut edge = EdgeRecord::new(
+                id_from.to_string(),
+                id_to.to_string(),
+                edge_factory(id_from, id_to),
+            )?;
+            if launch_hooks {
+                database_service::run_registered_hooks(
+                    HookEvent::BeforeCreate,
+                    &mut edge,
+                    db_accessor,
+                    &options,
+                )?;
+                edge.before_create_hook(db_accessor).await?;
+            }
+            edges.push(edge);
+        }
+        let mut records =
+            database_service::create_records(edges, db_accessor, T::COLLECTION_NAME, &options)
+                .await?;
+        for record in &records {
+            if let Some(edge) = CountedEdge::of(&record.record, db_accessor)? {
+                edge.update(1, db_accessor).await?;
+            }
+        }
+        if launch_hooks {
+            for record in &mut records {
+                database_service::run_registered_hooks(
+                    HookEvent::AfterCreate,
+                    &mut record.record,
+                    db_accessor,
+                    &options,
+                )?;
+                record.record.after_create_hook(db_accessor).await?;
+            }
+        }
+        Ok(records)
+    }
+
+    /// Retrieves a record from the database with the associated unique `key`
+    ///
+    /// # Arguments:
+    ///
+    /// * `key` - the unique record key as a string slice
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success `Self` is returned,
+    /// On failure an [`Error`] is returned:
+    /// * [`NotFound`] on invalid document key
+    /// * [`UnprocessableEntity`] on data corruption
+    ///
+    /// [`Error`]: crate::Error
+    /// [`NotFound`]: crate::Error::NotFound
+    /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    ///
+    /// # Note
+    ///
+    /// If `T` has a [`default_scope`] the document is retrieved through a query, and is not found
+    /// if it doesn't match the scope. Use [`find_unscoped`] to skip it.
+    ///
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`find_unscoped`]: Self::find_unscoped
+    #[maybe_async::maybe_async]
+    pub async fn find<D>(key: &str, db_accessor: &D) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        if T::default_scope().is_none() {
+            return Self::find_unscoped(key, db_accessor).await;
+        }
+        let query = T::query()
+            .filter(Comparison::field("_key").equals("@key").into())
+            .bind_var("key", key)
+            .limit(1, None);
+        let mut result = query_records(db_accessor, &query).await?;
+        if result.is_empty() {
+            return Err(Error::NotFound {
+                item: T::COLLECTION_NAME.to_string(),
+                id: key.to_string(),
+                source: None,
+            });
+        }
+        Ok(result.0.remove(0))
+    }
+
+    /// Retrieves a record from the database with the associated unique `key`, ignoring the
+    /// [`default_scope`] of `T`
+    ///
+    /// # Errors
+    ///
+    /// Fails on invalid document key or data corruption, like [`find`]
+    ///
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`find`]: Self::find
+    #[maybe_async::maybe_async]
+    pub async fn find_unscoped<D>(key: &str, db_accessor: &D) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        database_service::retrieve_record(key, db_accessor, T::COLLECTION_NAME).await
+    }
+
+    /// Retrieves a document from the database with the associated unique `key` as raw JSON,
+    /// to be deserialized into a record borrowing from it with [`RawDocument::record`].
+    ///
+    /// The [`default_scope`] and the tenant of `T` apply, like with [`find`].
+    ///
+    /// # Errors
+    ///
+    /// On failure an [`Error`] is returned:
+    /// * [`NotFound`] if no document matches
+    ///
+    /// [`RawDocument::record`]: crate::RawDocument::record
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`find`]: Self::find
+    /// [`Error`]: crate::Error
+    /// [`NotFound`]: crate::Error::NotFound
+    #[maybe_async::maybe_async]
+    pub async fn find_borrowed<D>(key: &str, db_accessor: &D) -> Result<RawDocument, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let query = T::query()
+            .filter(Comparison::field("_key").equals("@key").into())
+            .bind_var("key", key)
+            .limit(1, None);
+        query_raw_documents::<T, D>(db_accessor, &query)
+            .await?
+            .pop()
+            .ok_or_else(|| Error::NotFound {
+                item: T::COLLECTION_NAME.to_string(),
+                id: key.to_string(),
+                source: None,
+            })
+    }
+
+    /// Retrieves all documents from the database matching `query` as raw JSON, to be
+    /// deserialized into records borrowing from them with [`RawDocument::record`].
+    ///
+    /// Every cursor batch is fetched, the documents are kept as received.
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error
+    ///
+    /// [`RawDocument::record`]: crate::RawDocument::record
+    #[maybe_async::maybe_async]
+    pub async fn get_borrowed<D>(query: &Query, db_accessor: &D) -> Result<Vec<RawDocument>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query_raw_documents::<T, D>(db_accessor, query).await
+    }
+
+    /// Retrieves the groups of records of a `query` grouping its documents with
+    /// [`Query::collect_into`], like every order of each user.
+    ///
+    /// # Note
+    ///
+    /// The grouped documents are deserialized directly, without the document model migrations
+    /// and the [`DeserializationMode`].
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error or if a group doesn't match `GroupedResult<K, T>`
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Record, Clone, Serialize, Deserialize)]
+    /// pub struct Order {
+    ///     pub user_id: String,
+    ///     pub total: u32,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let query = Order::query().collect_into("user", "user_id", "orders");
+    /// let groups = DatabaseRecord::<Order>::get_grouped::<String, _>(&query, &db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// for group in groups {
+    ///     println!("{} ordered {} times", group.key, group.documents.len());
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`DeserializationMode`]: crate::DeserializationMode
+    #[maybe_async::maybe_async]
+    pub async fn get_grouped<K, D>(
+        query: &Query,
+        db_accessor: &D,
+    ) -> Result<Vec<GroupedResult<K, T>>, Error>
+    where
+        K: DeserializeOwned,
+        D: DatabaseAccess + ?Sized,
+    {
+        query_raw_documents::<T, D>(db_accessor, query)
+            .await?
+            .iter()
+            .map(|group| Ok(serde_json::from_str(group.json())?))
+            .collect()
+    }
+
+    /// Counts the records matching `query` per value of every field of `fields`, in a single
+    /// query. Every field is mapped to its distinct values along with their number of records,
+    /// the most frequent values first.
+    ///
+    /// The `null` values count the records without the field.
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use aragog::query::{Comparison, Filter};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Record, Clone, Serialize, Deserialize)]
+    /// pub struct Article {
+    ///     pub status: String,
+    ///     pub category: String,
+    ///     pub views: u32,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let query = Article::query().filter(Filter::new(Comparison::field("views").greater_than(10)));
+    /// let facets = DatabaseRecord::<Article>::facets(&query, &["status", "category"], &db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// for (value, count) in &facets["status"] {
+    ///     println!("{}: {} articles", value, count);
+    /// }
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn facets<D>(query: &Query, fields: &[&str], db_accessor: &D) -> Result<Facets, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query_facets::<T, D>(db_accessor, query, fields).await
+    }
+
+    /// Retrieves all records from the database matching `query` along with their related
+    /// documents, loaded with [`Query::with_related`]. The records and their related documents
+    /// are retrieved in a single round trip.
+    ///
+    /// # Note
+    ///
+    /// The documents are deserialized directly, without the document model migrations and the
+    /// [`DeserializationMode`].
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error or if a document doesn't match `Related<T, R>`
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::query::GraphQueryDirection;
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Record, Clone, Serialize, Deserialize)]
+    /// pub struct User {
+    ///     pub name: String,
+    /// }
+    ///
+    /// #[derive(Record, Clone, Serialize, Deserialize)]
+    /// pub struct Post {
+    ///     pub title: String,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// pub struct UserPosts {
+    ///     pub posts: Vec<DatabaseRecord<Post>>,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let query = User::query().with_related("AuthoredBy", GraphQueryDirection::Inbound, "posts");
+    /// let users = DatabaseRecord::<User>::get_with_related::<UserPosts, _>(&query, &db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// for user in users {
+    ///     println!("{} wrote {} posts", user.name, user.related.posts.len());
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`Query::with_related`]: crate::query::Query::with_related
+    /// [`DeserializationMode`]: crate::DeserializationMode
+    #[maybe_async::maybe_async]
+    pub async fn get_with_related<R, D>(
+        query: &Query,
+        db_accessor: &D,
+    ) -> Result<Vec<Related<T, R>>, Error>
+    where
+        R: DeserializeOwned,
+        D: DatabaseAccess + ?Sized,
+    {
+        query_raw_documents::<T, D>(db_accessor, query)
+            .await?
+            .iter()
+            .map(|document| Ok(serde_json::from_str(document.json())?))
+            .collect()
+    }
+
+    /// Retrieves the only record from the database whose `field` equals `value`, typically a
+    /// field with a unique index.
+    ///
+    /// # Arguments:
+    ///
+    /// * `field` - the record field name
+    /// * `value` - the field value, sent as a bind parameter
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Note
+    ///
+    /// The equality query uses the indexes on `field`. If `db_accessor` is a transaction, the
+    /// query runs in the transaction.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let user = User::find_by_field("email", "michel@du93.fr", &db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// On failure an [`Error`] is returned:
+    /// * [`NotFound`] if no document or more than one matches
+    /// * [`UnprocessableEntity`] on data corruption
+    ///
+    /// [`Error`]: crate::Error
+    /// [`NotFound`]: crate::Error::NotFound
+    /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    #[maybe_async::maybe_async]
+    pub async fn find_by_field<D, V>(field: &str, value: V, db_accessor: &D) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Into<serde_json::Value> + Send,
+    {
+        let value = value.into();
+        let query = Self::field_query(field, value.clone()).limit(2, None);
+        let mut result = match db_accessor.stream_transaction() {
+            Some(transaction) => {
+                database_service::query_transaction_records(transaction, db_accessor, &query)
+                    .await?
+            }
+            None => query_records(db_accessor, &query).await?,
+        };
+        if result.len() != 1 {
+            log::error!(
+                "Wrong number of {} with {} == {}: {}",
+                T::COLLECTION_NAME,
+                field,
+                value,
+                result.len()
+            );
+            return Err(Error::NotFound {
+                item: T::COLLECTION_NAME.to_string(),
+                id: format!("{} == {}", field, value),
+                source: None,
+            });
+        }
+        Ok(result.0.remove(0))
+    }
+
+    /// Retrieves all records from the database whose `field` is one of `values`, typically a list
+    /// of keys.
+    ///
+    /// # Arguments:
+    ///
+    /// * `field` - the record field name
+    /// * `values` - the accepted field values, sent as a bind parameter
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Note
+    ///
+    /// The values are split in chunks of [`BOUND_ARRAY_CHUNK_SIZE`], each chunk being queried
+    /// with the same [`in_bound_array`] query, and the results are concatenated in the order of
+    /// the chunks. The query metadata is not kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let keys: Vec<String> = (0..20_000).map(|i| i.to_string()).collect();
+    /// let users = User::get_where_in("_key", &keys, &db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// On failure an [`Error`] is returned:
+    /// * [`UnprocessableEntity`] on data corruption
+    ///
+    /// [`BOUND_ARRAY_CHUNK_SIZE`]: crate::query::BOUND_ARRAY_CHUNK_SIZE
+    /// [`in_bound_array`]: crate::query::ComparisonBuilder::in_bound_array
+    /// [`Error`]: crate::Error
+    /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    #[maybe_async::maybe_async]
+    pub async fn get_where_in<D, V>(
+        field: &str,
+        values: &[V],
+        db_accessor: &D,
+    ) -> Result<QueryResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Serialize + Sync,
+    {
+        let mut documents = Vec::new();
+        for chunk in values.chunks(BOUND_ARRAY_CHUNK_SIZE) {
+            let query = T::query().filter(Comparison::field(field).in_bound_array(chunk).into());
+            let mut result = query_records(db_accessor, &query).await?;
+            documents.append(&mut result.0);
+        }
+        Ok(QueryResult::new(documents))
+    }
+
+    /// Checks if any record whose `field` equals `value` exists.
+    ///
+    /// # Note
+    ///
+    /// The equality query uses the indexes on `field`. If `db_accessor` is a transaction, the
+    /// query runs in the transaction.
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned, `false` if nothing exists.
     #[maybe_async::maybe_async]
-    pub async fn force_delete<D>(&mut self, db_accessor: &D) -> Result<(), Error>
+    pub async fn exists_by<D, V>(field: &str, value: V, db_accessor: &D) -> bool
     where
         D: DatabaseAccess + ?Sized,
+        V: Into<serde_json::Value> + Send,
     {
-        self.delete_with_options(
-            db_accessor,
-            db_accessor
-                .operation_options()
-                .ignore_revs(true)
-                .ignore_hooks(true),
-        )
-        .await
+        let query = Self::field_query(field, value.into());
+        match db_accessor.stream_transaction() {
+            Some(transaction) => database_service::query_transaction_records::<T, D>(
+                transaction,
+                db_accessor,
+                &query.limit(1, None),
+            )
+            .await
+            .map_or(false, |result| !result.is_empty()),
+            None => Self::exists(&query, db_accessor).await,
+        }
     }
 
-    /// Creates and returns edge between `from_record` and `target_record`.
+    fn field_query(field: &str, value: serde_json::Value) -> Query {
+        T::query()
+            .filter(Comparison::field(field).equals("@value").into())
+            .bind_var("value", value)
+    }
+
+    /// Retrieves a record from the database with the associated unique `key`, along with the raw
+    /// document JSON.
     ///
-    /// # Hooks
+    /// The raw JSON contains every document attribute (except the `_key`, `_id` and `_rev` system attributes),
+    /// including the ones unknown to `T` like attributes written by other services.
     ///
-    /// This function will launch `T` hooks `before_create` and `after_create`.
+    /// The [`default_scope`] and the tenant of `T` apply, like with [`find`].
     ///
-    /// # Example
-    /// ```rust
-    /// # use aragog::{DatabaseRecord, EdgeRecord, Record, DatabaseConnection};
-    /// # use serde::{Serialize, Deserialize};
-    /// #
-    /// # #[derive(Record, Clone, Serialize, Deserialize)]
-    /// # struct User {}
-    /// #[derive(Clone, Record, Serialize, Deserialize)]
-    /// struct Edge {
-    ///     description: String,
-    /// }
+    /// # Arguments:
     ///
-    /// # #[tokio::main]
-    /// # async fn main() {
-    /// # let db_accessor = DatabaseConnection::builder()
-    /// #     .with_schema_path("tests/schema.yaml")
-    /// #     .apply_schema()
-    /// #     .build().await.unwrap();
-    /// # db_accessor.truncate();
-    /// let user_a = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
-    /// let user_b = DatabaseRecord::create(User { }, &db_accessor).await.unwrap();
+    /// * `key` - the unique record key as a string slice
+    /// * `db_accessor` - database connection reference
     ///
-    /// let edge = DatabaseRecord::link(&user_a, &user_b, &db_accessor,
-    ///     Edge { description: "description".to_string() }
-    /// ).await.unwrap();
-    /// assert_eq!(edge.id_from(), user_a.id());
-    /// assert_eq!(edge.id_to(), user_b.id());
-    /// assert_eq!(&edge.description, "description");
-    /// # }
-    /// ```
+    /// # Errors
+    ///
+    /// On failure an [`Error`] is returned:
+    /// * [`NotFound`] on invalid document key
+    /// * [`UnprocessableEntity`] on data corruption
+    ///
+    /// [`Error`]: crate::Error
+    /// [`NotFound`]: crate::Error::NotFound
+    /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`find`]: Self::find
     #[maybe_async::maybe_async]
-    pub async fn link<A, B, D>(
-        from_record: &DatabaseRecord<A>,
-        to_record: &DatabaseRecord<B>,
-        db_accessor: &D,
-        edge_record: T,
-    ) -> Result<DatabaseRecord<EdgeRecord<T>>, Error>
+    pub async fn find_raw<D>(key: &str, db_accessor: &D) -> Result<(Self, serde_json::Value), Error>
     where
-        A: Record,
-        B: Record,
         D: DatabaseAccess + ?Sized,
-        T: Record + Send,
     {
-        let edge = EdgeRecord::new(
-            from_record.id().clone(),
-            to_record.id().clone(),
-            edge_record,
-        )?;
-        DatabaseRecord::create(edge, db_accessor).await
+        let document = Self::find_borrowed(key, db_accessor).await?;
+        let raw: DatabaseRecord<UndefinedRecord> = document.record()?;
+        let (record, value) = raw.record.into_parts()?;
+        Ok((
+            Self {
+                key: raw.key,
+                id: raw.id,
+                rev: raw.rev,
+                record,
+            },
+            value,
+        ))
     }
 
-    /// Retrieves a record from the database with the associated unique `key`
+    /// Retrieves a record from the database with the associated unique `key` only if its revision
+    /// differs from `known_rev`, using the `If-None-Match` header.
+    ///
+    /// Useful for cache layers to cheaply check if a document changed.
+    ///
+    /// If `T` has a [`default_scope`] the document is retrieved through a query like with
+    /// [`find`], and is not found if it doesn't match the scope. The revision is then compared
+    /// after the retrieval.
     ///
     /// # Arguments:
     ///
     /// * `key` - the unique record key as a string slice
+    /// * `known_rev` - the known document revision (`_rev`)
     /// * `db_accessor` - database connection reference
     ///
     /// # Returns
     ///
-    /// On success `Self` is returned,
+    /// On success `Some(Self)` is returned if the document was modified, `None` otherwise.
+    ///
+    /// # Errors
+    ///
     /// On failure an [`Error`] is returned:
     /// * [`NotFound`] on invalid document key
     /// * [`UnprocessableEntity`] on data corruption
@@ -560,12 +1642,28 @@ impl<T: Record> DatabaseRecord<T> {
     /// [`Error`]: crate::Error
     /// [`NotFound`]: crate::Error::NotFound
     /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    /// [`default_scope`]: crate::Record::default_scope
+    /// [`find`]: Self::find
     #[maybe_async::maybe_async]
-    pub async fn find<D>(key: &str, db_accessor: &D) -> Result<Self, Error>
+    pub async fn find_if_modified<D>(
+        key: &str,
+        known_rev: &str,
+        db_accessor: &D,
+    ) -> Result<Option<Self>, Error>
     where
         D: DatabaseAccess + ?Sized,
     {
-        database_service::retrieve_record(key, db_accessor, T::COLLECTION_NAME).await
+        if T::default_scope().is_some() {
+            let record = Self::find(key, db_accessor).await?;
+            return Ok((record.rev != known_rev).then(|| record));
+        }
+        database_service::retrieve_record_if_modified(
+            key,
+            known_rev,
+            db_accessor,
+            T::COLLECTION_NAME,
+        )
+        .await
     }
 
     /// Reloads a record from the database, returning the new record.
@@ -652,7 +1750,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// #     .with_schema_path("tests/schema.yaml")
     /// #     .apply_schema()
     /// #     .build().await.unwrap();
-    /// # db_accessor.truncate();
+    /// # db_accessor.unsafe_truncate_all().await.unwrap();
     /// # DatabaseRecord::create(User {username: "RobertSurcouf".to_string() ,age: 18 }, &db_accessor).await.unwrap();
     /// let query = User::query().filter(Filter::new(Comparison::field("username").equals_str("RobertSurcouf"))
     ///     .and(Comparison::field("age").greater_than(10)));
@@ -709,7 +1807,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// #     .with_schema_path("tests/schema.yaml")
     /// #     .apply_schema()
     /// #     .build().await.unwrap();
-    /// # db_accessor.truncate();
+    /// # db_accessor.unsafe_truncate_all().await.unwrap();
     /// # DatabaseRecord::create(User {username: "RobertSurcouf".to_string() ,age: 18 }, &db_accessor).await.unwrap();
     /// let query = User::query().filter(Filter::new(Comparison::field("age").greater_than(10)));
     ///
@@ -747,6 +1845,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// On failure an [`Error`] is returned:
     /// * [`NotFound`] if no document matches the condition
     /// * [`UnprocessableEntity`] on data corruption
+    /// * [`Forbidden`] if `T` is tenant scoped, as the raw query can't be restricted to the tenant
     ///
     /// # Warning
     ///
@@ -767,7 +1866,7 @@ impl<T: Record> DatabaseRecord<T> {
     /// #     .with_schema_path("tests/schema.yaml")
     /// #     .apply_schema()
     /// #     .build().await.unwrap();
-    /// # db_accessor.truncate();
+    /// # db_accessor.unsafe_truncate_all().await.unwrap();
     /// let query = r#"FOR i in User FILTER i.username == "RoertSurcouf" && i.age > 10 return i"#;
     ///
     /// DatabaseRecord::<User>::aql_get(query, &db_accessor).await.unwrap();
@@ -777,11 +1876,15 @@ impl<T: Record> DatabaseRecord<T> {
     /// [`Error`]: crate::Error
     /// [`NotFound`]:crate::Error::NotFound
     /// [`UnprocessableEntity`]:crate::Error::UnprocessableEntity
+    /// [`Forbidden`]:crate::Error::Forbidden
     #[maybe_async::maybe_async]
     pub async fn aql_get<D>(query: &str, db_accessor: &D) -> Result<QueryResult<T>, Error>
     where
         D: DatabaseAccess + ?Sized,
     {
+        if T::TENANT_FIELD.is_some() {
+            return Err(Error::Forbidden(None));
+        }
         raw_query_records(db_accessor, query).await
     }
 
@@ -906,6 +2009,145 @@ impl<T: Record> DatabaseRecord<T> {
         Query::inbound_graph(min, max, named_graph, &self.id)
     }
 
+    /// Retrieves the `N` records directly linked from `self` through `edge_collection`.
+    /// Only the neighbors stored in `N` collection are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_collection`- The name of the traversed edge collection
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Example
+    /// ```rust no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct Group {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let record = User::find("123", &db_accessor).await.unwrap();
+    /// // Equivalent to a 1..1 `OUTBOUND` traversal on `MemberOf` returning `Group` documents
+    /// let groups = record.out_neighbors::<Group, _>("MemberOf", &db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure or if the neighbors can't be deserialized.
+    #[maybe_async::maybe_async]
+    pub async fn out_neighbors<N, D>(
+        &self,
+        edge_collection: &str,
+        db_accessor: &D,
+    ) -> Result<QueryResult<N>, Error>
+    where
+        N: Record + Send,
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::neighbors_query::<N>(self.outbound_query(1, 1, edge_collection))
+            .call(db_accessor)
+            .await
+    }
+
+    /// Retrieves the `N` records directly linked to `self` through `edge_collection`.
+    /// Only the neighbors stored in `N` collection are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_collection`- The name of the traversed edge collection
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Example
+    /// ```rust no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct Group {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let record = Group::find("123", &db_accessor).await.unwrap();
+    /// // Equivalent to a 1..1 `INBOUND` traversal on `MemberOf` returning `User` documents
+    /// let members = record.in_neighbors::<User, _>("MemberOf", &db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure or if the neighbors can't be deserialized.
+    #[maybe_async::maybe_async]
+    pub async fn in_neighbors<N, D>(
+        &self,
+        edge_collection: &str,
+        db_accessor: &D,
+    ) -> Result<QueryResult<N>, Error>
+    where
+        N: Record + Send,
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::neighbors_query::<N>(self.inbound_query(1, 1, edge_collection))
+            .call(db_accessor)
+            .await
+    }
+
+    /// Counts the edges of `edge_collection` connected to `self` in the given `direction`.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_collection`- The name of the edge collection
+    /// * `direction` - The direction of the counted edges
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Example
+    /// ```rust no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::query::GraphQueryDirection;
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let record = User::find("123", &db_accessor).await.unwrap();
+    /// let follower_count = record.degree("Follows", GraphQueryDirection::Inbound, &db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    #[maybe_async::maybe_async]
+    pub async fn degree<D>(
+        &self,
+        edge_collection: &str,
+        direction: GraphQueryDirection,
+        db_accessor: &D,
+    ) -> Result<u64, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        database_service::vertex_degree(&self.id, edge_collection, direction, db_accessor).await
+    }
+
+    fn neighbors_query<N: Record>(query: Query) -> Query {
+        query.filter(
+            Comparison::field("_id")
+                .matches(&format!("^{}/", N::COLLECTION_NAME))
+                .into(),
+        )
+    }
+
     /// Checks if any document matching the associated conditions exist
     ///
     /// # Arguments:
@@ -945,8 +2187,16 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        let aql = query.aql_str();
-        let aql_query = AqlQuery::new(&aql).batch_size(1).count(true);
+        let tenant = match database_service::record_tenant::<T, D>(db_accessor) {
+            Ok(tenant) => tenant,
+            Err(_) => return false,
+        };
+        let query = query.scoped_for::<T>(tenant);
+        let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
+        let mut aql_query = AqlQuery::new(&aql).batch_size(1).count(true);
+        for (var, val) in &bind_vars {
+            aql_query = aql_query.bind_var(var, val.clone());
+        }
         match db_accessor
             .database()
             .aql_query_batch::<Value>(aql_query)
@@ -959,18 +2209,70 @@ impl<T: Record> DatabaseRecord<T> {
             Err(_error) => false,
         }
     }
+}
+
+impl<T> DatabaseRecord<T> {
+    /// Builds a record from its document `_key`, `_id`, `_rev` and model, without any database
+    /// operation or hook.
+    ///
+    /// Useful for test fixtures, caches or records retrieved from other sources than `ArangoDB`.
+    /// The consistency of the values is not checked: `id` should be `$collection_name/$key` and
+    /// `rev` the revision of the stored document for the later operations to succeed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::{DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// #[derive(Clone, Record, Serialize, Deserialize)]
+    /// pub struct User {
+    ///     pub name: String,
+    /// }
+    ///
+    /// let user = DatabaseRecord::from_parts(
+    ///     "123".parse().unwrap(),
+    ///     "User/123".parse().unwrap(),
+    ///     String::from("_cYD7ooe---"),
+    ///     User { name: String::from("Robert") },
+    /// );
+    /// assert_eq!(user.id(), "User/123");
+    /// let (key, _id, _rev, user) = user.into_parts();
+    /// assert_eq!(key, "123");
+    /// assert_eq!(user.name, "Robert");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_parts(key: DocumentKey, id: DocumentId, rev: String, record: T) -> Self {
+        Self {
+            key,
+            id,
+            rev,
+            record,
+        }
+    }
+
+    /// Consumes the record, returning its document `_key`, `_id`, `_rev` and model.
+    /// See [`from_parts`].
+    ///
+    /// [`from_parts`]: Self::from_parts
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (DocumentKey, DocumentId, String, T) {
+        (self.key, self.id, self.rev, self.record)
+    }
 
     /// Getter for the Document `_id` built as `$collection_name/$_key`
     #[inline]
     #[allow(clippy::missing_const_for_fn)] // Can't be const in 1.56
-    pub fn id(&self) -> &String {
+    pub fn id(&self) -> &DocumentId {
         &self.id
     }
 
     /// Getter for the Document `_key`
     #[inline]
     #[allow(clippy::missing_const_for_fn)] // Can't be const in 1.56
-    pub fn key(&self) -> &String {
+    pub fn key(&self) -> &DocumentKey {
         &self.key
     }
 
@@ -980,14 +2282,85 @@ impl<T: Record> DatabaseRecord<T> {
     pub fn rev(&self) -> &String {
         &self.rev
     }
+
+    /// Replaces the inner model by `new_record`, keeping the document `_key`, `_id` and `_rev`.
+    ///
+    /// The change is local, call [`save`] to persist it.
+    ///
+    /// [`save`]: Self::save
+    #[must_use]
+    pub fn replace(mut self, new_record: T) -> Self {
+        self.record = new_record;
+        self
+    }
+
+    /// Replaces the inner model by `new_record`, keeping the document `_key`, `_id` and `_rev`.
+    /// The previous model is returned.
+    ///
+    /// The change is local, call [`save`] to persist it.
+    ///
+    /// [`save`]: Self::save
+    pub fn set_record(&mut self, new_record: T) -> T {
+        std::mem::replace(&mut self.record, new_record)
+    }
+
+    /// Maps the inner model to another model type, keeping the document `_key`, `_id` and `_rev`.
+    ///
+    /// Useful for migrations between model versions sharing a collection. The `_id` is not changed,
+    /// so `U` should be stored in the same collection as `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// #[collection_name = "User"]
+    /// pub struct UserV1 {
+    ///     pub name: String,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// #[collection_name = "User"]
+    /// pub struct UserV2 {
+    ///     pub first_name: String,
+    ///     pub last_name: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let user = UserV1::find("123", &db_connection).await.unwrap();
+    /// let mut user = user.map(|user| {
+    ///     let (first_name, last_name) = user.name.split_once(' ').unwrap_or((&user.name, ""));
+    ///     UserV2 {
+    ///         first_name: first_name.to_string(),
+    ///         last_name: last_name.to_string(),
+    ///     }
+    /// });
+    /// user.save(&db_connection).await.unwrap();
+    /// # }
+    /// ```
+    pub fn map<U, F>(self, f: F) -> DatabaseRecord<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        DatabaseRecord {
+            key: self.key,
+            id: self.id,
+            rev: self.rev,
+            record: f(self.record),
+        }
+    }
 }
 
 #[allow(clippy::used_underscore_binding)]
 impl<T: Record> From<Document<T>> for DatabaseRecord<T> {
     fn from(doc: Document<T>) -> Self {
         Self {
-            key: doc.header._key,
-            id: doc.header._id,
+            key: DocumentKey::new_unchecked(doc.header._key),
+            id: DocumentId::new_unchecked(doc.header._id),
             rev: doc.header._rev,
             record: doc.document,
         }
@@ -1160,8 +2533,8 @@ mod tests {
         }
 
         let db_record = DatabaseRecord {
-            key: "key".to_string(),
-            id: "id".to_string(),
+            key: DocumentKey::new_unchecked("key".to_string()),
+            id: DocumentId::new_unchecked("Doc/key".to_string()),
             rev: "rev".to_string(),
             record: Doc {
                 a: "a".to_string(),
@@ -1179,6 +2552,45 @@ mod tests {
         assert_eq!(parsed_record.record.c, db_record.record.c);
     }
 
+    #[test]
+    fn record_can_be_replaced_and_mapped() {
+        let mut db_record = DatabaseRecord {
+            key: DocumentKey::new_unchecked("key".to_string()),
+            id: DocumentId::new_unchecked("Doc/key".to_string()),
+            rev: "rev".to_string(),
+            record: 10_u16,
+        };
+        assert_eq!(db_record.set_record(20), 10);
+        assert_eq!(db_record.record, 20);
+        let db_record = db_record.replace(30);
+        assert_eq!(db_record.record, 30);
+        let db_record = db_record.map(|value| value.to_string());
+        assert_eq!(db_record.record, "30");
+        assert_eq!(db_record.key, "key");
+        assert_eq!(db_record.id, "Doc/key");
+        assert_eq!(db_record.rev, "rev");
+    }
+
+    #[test]
+    fn record_can_be_built_from_parts() {
+        let db_record = DatabaseRecord::from_parts(
+            "key".parse().unwrap(),
+            "Doc/key".parse().unwrap(),
+            "rev".to_string(),
+            10_u16,
+        );
+        assert_eq!(db_record.key(), "key");
+        assert_eq!(
+            db_record.into_parts(),
+            (
+                DocumentKey::parse("key").unwrap(),
+                DocumentId::parse("Doc/key").unwrap(),
+                "rev".to_string(),
+                10
+            )
+        );
+    }
+
     #[test]
     fn struct_with_enum_serialize_deserialize() {
         #[derive(Serialize, Deserialize, Clone)]
@@ -1193,8 +2605,8 @@ mod tests {
         }
 
         let db_record = DatabaseRecord {
-            key: "key".to_string(),
-            id: "id".to_string(),
+            key: DocumentKey::new_unchecked("key".to_string()),
+            id: DocumentId::new_unchecked("Doc/key".to_string()),
             rev: "rev".to_string(),
             record: Doc {
                 doc: DocEnum::A {
@@ -1219,6 +2631,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn enum_document_deserialize() {
+        #[derive(Serialize, Deserialize, Clone)]
+        enum DocEnum {
+            A { a: String },
+            B { b: bool },
+        }
+
+        #[derive(Serialize, Deserialize, Clone)]
+        #[serde(tag = "type")]
+        enum TaggedDocEnum {
+            A { a: String },
+            B { b: bool },
+        }
+
+        let json = r#"{"_key":"key","_id":"Doc/key","_rev":"rev","B":{"b":true}}"#;
+        let doc: Document<DatabaseRecord<DocEnum>> = serde_json::from_str(json).unwrap();
+        assert_eq!(&doc.document.key, "key");
+        assert!(matches!(doc.document.record, DocEnum::B { b: true }));
+
+        let json = r#"{"_key":"key","_id":"Doc/key","_rev":"rev","type":"A","a":"a"}"#;
+        let doc: Document<DatabaseRecord<TaggedDocEnum>> = serde_json::from_str(json).unwrap();
+        assert_eq!(&doc.document.id, "Doc/key");
+        match doc.document.record {
+            TaggedDocEnum::A { a } => assert_eq!(&a, "a"),
+            TaggedDocEnum::B { .. } => panic!("Wrong enum variant"),
+        }
+    }
+
+    #[test]
+    fn unknown_attributes_round_trip() {
+        #[derive(Serialize, Deserialize, Clone)]
+        struct Doc {
+            a: String,
+            #[serde(flatten)]
+            extra: std::collections::HashMap<String, serde_json::Value>,
+        }
+
+        let json = r#"{"_key":"key","_id":"Doc/key","_rev":"rev","a":"a","b":{"c":true}}"#;
+        let doc: Document<DatabaseRecord<Doc>> = serde_json::from_str(json).unwrap();
+        let record = doc.document;
+        assert_eq!(&record.key, "key");
+        assert_eq!(&record.record.a, "a");
+        // System attributes are not captured by the flattened map
+        assert_eq!(record.record.extra.len(), 1);
+        assert_eq!(record.record.extra["b"], serde_json::json!({ "c": true }));
+        let json = serde_json::to_value(&record.record).unwrap();
+        assert_eq!(json, serde_json::json!({ "a": "a", "b": { "c": true } }));
+    }
+
     #[test]
     fn enum_serialize_deserialize() {
         #[derive(Serialize, Deserialize, Clone)]
@@ -1228,8 +2690,8 @@ mod tests {
         }
 
         let db_record = DatabaseRecord {
-            key: "key".to_string(),
-            id: "id".to_string(),
+            key: DocumentKey::new_unchecked("key".to_string()),
+            id: DocumentId::new_unchecked("Doc/key".to_string()),
             rev: "rev".to_string(),
             record: DocEnum::A {
                 a: "a".to_string(),
@@ -1,5 +1,6 @@
 #![allow(clippy::used_underscore_binding)]
-use crate::{DatabaseRecord, Error, Record};
+use crate::db::versioned_record::{written_model_version, VersionedRecord};
+use crate::{DatabaseRecord, DocumentId, DocumentKey, Error, Record};
 use arangors_lite::document::response::DocumentResponse;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
@@ -11,18 +12,24 @@ pub struct DatabaseRecordDto<T> {
     key: Option<String>,
     #[serde(flatten)]
     pub record: T,
+    #[serde(rename = "_model_version")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model_version: Option<u32>,
 }
 
 impl<T: Record> DatabaseRecordDto<T> {
     #[inline]
     #[must_use]
-    #[allow(clippy::missing_const_for_fn)] // Can't be const in 1.56
     pub fn new(record: T, key: Option<String>) -> Self {
-        Self { key, record }
+        Self {
+            key,
+            record,
+            model_version: written_model_version::<T>(),
+        }
     }
 }
 
-impl<T: Record> TryInto<DatabaseRecord<T>> for DocumentResponse<DatabaseRecord<T>> {
+impl<T: Record> TryInto<DatabaseRecord<T>> for DocumentResponse<VersionedRecord<T>> {
     type Error = Error;
 
     fn try_into(self) -> Result<DatabaseRecord<T>, Self::Error> {
@@ -31,7 +38,7 @@ impl<T: Record> TryInto<DatabaseRecord<T>> for DocumentResponse<DatabaseRecord<T
                 message: Some(String::from("Received unexpected silent document response")),
             }),
             Self::Response { new, header, .. } => match new {
-                Some(value) => Ok(value),
+                Some(value) => Ok(value.into()),
                 None => Err(Error::InternalError {
                     message: Some(format!(
                         "Expected `ArangoDB` to return the new {} document",
@@ -64,8 +71,8 @@ impl<T: Record> TryInto<DatabaseRecord<T>> for DocumentResponse<DatabaseRecordDt
                     }
                 };
                 Ok(DatabaseRecord {
-                    key: header._key.clone(),
-                    id: header._id.clone(),
+                    key: DocumentKey::new_unchecked(header._key.clone()),
+                    id: DocumentId::new_unchecked(header._id.clone()),
                     rev: header._rev,
                     record,
                 })
@@ -1,9 +1,432 @@
+use crate::counter_cache::{self, CountedEdge};
 use crate::db::database_record_dto::DatabaseRecordDto;
+use crate::db::deserialization_mode::{versioned_cursor, StrictRecord};
+use crate::db::operation_tracker::{OperationGuard, OperationTracker};
+use crate::db::raw_document::RawDocument;
+use crate::db::request_limiter::RequestPermit;
+use crate::db::velocypack::VelocyPackTransport;
+use crate::db::versioned_record::VersionedRecord;
+use crate::error::ArangoError as ArangoErrorCode;
 use crate::error::ArangoHttpError;
-use crate::query::{Query, QueryCursor, QueryResult};
-use crate::{DatabaseAccess, DatabaseRecord, Error, OperationOptions, Record};
-use arangors_lite::{AqlOptions, AqlQuery};
+use crate::query::{
+    facets_aql, Facets, GraphQueryDirection, Query, QueryCursor, QueryMetadata, QueryResult,
+};
+use crate::{
+    DatabaseAccess, DatabaseRecord, DeserializationMode, Error, HookContext, HookEvent,
+    OperationOptions, Record,
+};
+use arangors_lite::aql::Cursor;
+use arangors_lite::document::response::DocumentResponse;
+use arangors_lite::graph::Graph;
+use arangors_lite::transaction::Transaction as TransactionLayer;
+use arangors_lite::{AqlOptions, AqlQuery, ArangoError, ClientError, Database};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::value::RawValue;
 use std::convert::TryInto;
+#[cfg(not(feature = "blocking"))]
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Returns the current tenant of the `T` operations, `None` if `T` is not tenant scoped.
+///
+/// Fails with [`Error::Forbidden`] if `T` is tenant scoped and `db_accessor` has no tenant
+pub fn record_tenant<T, D>(db_accessor: &D) -> Result<Option<&str>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    if T::TENANT_FIELD.is_none() {
+        return Ok(None);
+    }
+    db_accessor.tenant().map(Some).ok_or(Error::Forbidden(None))
+}
+
+/// Returns `true` if `record` belongs to the current tenant or if `T` is not tenant scoped
+pub fn is_tenant_record<T, D>(record: &T, db_accessor: &D) -> Result<bool, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let (field, tenant) = match (T::TENANT_FIELD, record_tenant::<T, D>(db_accessor)?) {
+        (Some(field), Some(tenant)) => (field, tenant),
+        _ => return Ok(true),
+    };
+    let pointer = format!("/{}", field.replace('.', "/"));
+    let value = serde_json::to_value(record)?;
+    Ok(value.pointer(&pointer).and_then(serde_json::Value::as_str) == Some(tenant))
+}
+
+/// Calls the hooks of `record` registered at runtime on `db_accessor`
+pub fn run_registered_hooks<T, D>(
+    event: HookEvent,
+    record: &mut T,
+    db_accessor: &D,
+    options: &OperationOptions,
+) -> Result<(), Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let registry = match db_accessor.hook_registry() {
+        Some(registry) => registry,
+        None => return Ok(()),
+    };
+    let context = HookContext {
+        event,
+        collection: T::COLLECTION_NAME,
+        tenant: db_accessor.tenant(),
+        actor: options.actor.as_ref(),
+    };
+    registry.run(record, &context)
+}
+
+/// Fails with [`Error::Forbidden`] if `record` doesn't belong to the current tenant
+pub fn check_tenant<T, D>(record: &T, db_accessor: &D) -> Result<(), Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    if is_tenant_record(record, db_accessor)? {
+        Ok(())
+    } else {
+        Err(Error::Forbidden(None))
+    }
+}
+
+/// Deserializes a raw `ArangoDB` http response body, mapping the error responses
+pub fn deserialize_response<T>(body: &str) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    deserialize_value(serde_json::from_str(body)?)
+}
+
+/// Deserializes a decoded `ArangoDB` http response body, mapping the error responses
+pub fn deserialize_value<T>(value: serde_json::Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    if value.get("error").and_then(serde_json::Value::as_bool) == Some(true) {
+        let arango_error: ArangoError = serde_json::from_value(value)?;
+        return Err(Error::from(ClientError::Arango(arango_error)));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Maps the error of a database call exceeding the `timeout` deadline, or of an AQL query killed
+/// after its `maxRuntime`, to [`Error::Timeout`]
+fn timeout_error(error: Error, timeout: Option<Duration>) -> Error {
+    match timeout {
+        Some(duration)
+            if error.database_error().map(|e| &e.arango_error)
+                == Some(&ArangoErrorCode::QueryKilled) =>
+        {
+            Error::Timeout { duration }
+        }
+        _ => error,
+    }
+}
+
+/// Applies the `timeout` deadline to a database call
+#[cfg(not(feature = "blocking"))]
+pub async fn with_timeout<T, F>(timeout: Option<Duration>, call: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let result = match timeout {
+        Some(duration) => tokio::time::timeout(duration, call)
+            .await
+            .map_err(|_| Error::Timeout { duration })?,
+        None => call.await,
+    };
+    result.map_err(|error| timeout_error(error, timeout))
+}
+
+/// Applies the `timeout` deadline to a database call. Only the AQL `maxRuntime` is enforced with the
+/// `blocking` feature.
+#[cfg(feature = "blocking")]
+pub fn with_timeout<T>(timeout: Option<Duration>, result: Result<T, Error>) -> Result<T, Error> {
+    result.map_err(|error| timeout_error(error, timeout))
+}
+
+/// Replaces the content of the quoted string literals of `aql`, which may hold sensitive values
+fn redact_aql(aql: &str) -> String {
+    let mut redacted = String::with_capacity(aql.len());
+    let mut chars = aql.chars();
+    while let Some(c) = chars.next() {
+        redacted.push(c);
+        if c != '"' && c != '\'' {
+            continue;
+        }
+        while let Some(next) = chars.next() {
+            if next == '\\' {
+                chars.next();
+            } else if next == c {
+                break;
+            }
+        }
+        redacted.push_str("***");
+        redacted.push(c);
+    }
+    redacted
+}
+
+/// Logs the `aql` query at `WARN` level if its execution since `started` exceeded the
+/// `threshold`, with its string literals and bind parameters values redacted
+fn log_slow_query<'a>(
+    threshold: Option<Duration>,
+    started: Instant,
+    aql: &str,
+    bind_vars: impl Iterator<Item = &'a String>,
+) {
+    let elapsed = started.elapsed();
+    if threshold.map_or(true, |threshold| elapsed < threshold) {
+        return;
+    }
+    let bind_vars: Vec<String> = bind_vars.map(|var| format!("@{}: ***", var)).collect();
+    log::warn!(
+        "Slow AQL query ({:?}): `{}` with bind parameters {{{}}}",
+        elapsed,
+        redact_aql(aql),
+        bind_vars.join(", ")
+    );
+}
+
+/// Creates an AQL query cursor, with the `timeout` as query `maxRuntime`.
+/// `arangors_lite` doesn't support the `maxRuntime` option so the request is built manually.
+#[maybe_async::maybe_async]
+async fn create_cursor<T>(
+    database: &Database,
+    aql_query: AqlQuery<'_>,
+    timeout: Option<Duration>,
+) -> Result<Cursor<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    if timeout.is_none() {
+        return Ok(database.aql_query_batch(aql_query).await?);
+    }
+    post_cursor(database, &cursor_body(&aql_query, timeout)?).await
+}
+
+/// Renders the cursor creation body of `aql_query`, with the `timeout` as query `maxRuntime`
+fn cursor_body(
+    aql_query: &AqlQuery<'_>,
+    timeout: Option<Duration>,
+) -> Result<serde_json::Value, Error> {
+    let mut body = serde_json::to_value(aql_query)?;
+    if let Some(timeout) = timeout {
+        body["options"]["maxRuntime"] = serde_json::json!(timeout.as_secs_f64());
+    }
+    Ok(body)
+}
+
+/// Creates, or moves to its next batch with the `PUT` method, a cursor on records deserialized
+/// according to `mode` through the `VelocyPack` transport
+#[maybe_async::maybe_async]
+pub async fn velocypack_record_cursor<T>(
+    transport: &VelocyPackTransport,
+    database: &Database,
+    method: http::Method,
+    path: &str,
+    body: String,
+    mode: DeserializationMode,
+) -> Result<Cursor<VersionedRecord<T>>, Error>
+where
+    T: Record,
+{
+    let value = transport.request(database, method, path, body).await?;
+    Ok(match mode {
+        DeserializationMode::Lenient => deserialize_value(value)?,
+        DeserializationMode::Strict => {
+            versioned_cursor(deserialize_value::<Cursor<StrictRecord<T>>>(value)?)
+        }
+    })
+}
+
+/// Creates an AQL query cursor on records deserialized according to `mode`
+#[maybe_async::maybe_async]
+async fn create_record_cursor<T>(
+    database: &Database,
+    aql_query: AqlQuery<'_>,
+    timeout: Option<Duration>,
+    mode: DeserializationMode,
+) -> Result<Cursor<VersionedRecord<T>>, Error>
+where
+    T: Record,
+{
+    match mode {
+        DeserializationMode::Lenient => create_cursor(database, aql_query, timeout).await,
+        DeserializationMode::Strict => {
+            let cursor: Cursor<StrictRecord<T>> =
+                create_cursor(database, aql_query, timeout).await?;
+            Ok(versioned_cursor(cursor))
+        }
+    }
+}
+
+/// Creates an AQL query cursor from its creation `body`, on records deserialized according to
+/// `mode`
+#[maybe_async::maybe_async]
+async fn post_record_cursor<T>(
+    database: &Database,
+    body: &serde_json::Value,
+    mode: DeserializationMode,
+) -> Result<Cursor<VersionedRecord<T>>, Error>
+where
+    T: Record,
+{
+    match mode {
+        DeserializationMode::Lenient => post_cursor(database, body).await,
+        DeserializationMode::Strict => {
+            let cursor: Cursor<StrictRecord<T>> = post_cursor(database, body).await?;
+            Ok(versioned_cursor(cursor))
+        }
+    }
+}
+
+#[maybe_async::maybe_async]
+async fn post_cursor<T>(database: &Database, body: &serde_json::Value) -> Result<Cursor<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    let internal_error = |e: &dyn std::fmt::Display| Error::InternalError {
+        message: Some(e.to_string()),
+    };
+    let url = database
+        .url()
+        .join("_api/cursor")
+        .map_err(|e| internal_error(&e))?;
+    let request = http::Request::post(url.to_string())
+        .body(body.to_string())
+        .map_err(|e| internal_error(&e))?;
+    let response = database.session().request(request).await?;
+    deserialize_response(response.body())
+}
+
+/// A cursor batch keeping its result documents as raw JSON
+#[derive(Deserialize)]
+struct RawCursor {
+    #[serde(default)]
+    error: bool,
+    #[serde(default)]
+    result: Vec<Box<RawValue>>,
+    #[serde(default, rename = "hasMore")]
+    more: bool,
+    id: Option<String>,
+}
+
+/// Sends a cursor `request`, keeping the result documents as raw JSON.
+/// Unlike [`deserialize_response`] the response is not converted to a `serde_json::Value`.
+#[maybe_async::maybe_async]
+async fn raw_cursor_request(
+    database: &Database,
+    request: Result<http::Request<String>, http::Error>,
+) -> Result<RawCursor, Error> {
+    let request = request.map_err(|e| Error::InternalError {
+        message: Some(e.to_string()),
+    })?;
+    let response = database.session().request(request).await?;
+    let cursor: RawCursor = serde_json::from_str(response.body())?;
+    if cursor.error {
+        deserialize_response::<serde_json::Value>(response.body())?;
+    }
+    Ok(cursor)
+}
+
+/// Retrieves the `T` documents matching `query` as raw JSON, see [`RawDocument`]
+#[maybe_async::maybe_async]
+pub async fn query_raw_documents<T, D>(
+    db_accessor: &D,
+    query: &Query,
+) -> Result<Vec<RawDocument>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
+    log::debug!(
+        "Querying {} raw documents through AQL: `{}`",
+        T::COLLECTION_NAME,
+        aql
+    );
+    let mut aql_query = AqlQuery::new(&aql);
+    for (var, val) in &bind_vars {
+        aql_query = aql_query.bind_var(var, val.clone());
+    }
+    let mut body = serde_json::to_value(&aql_query)?;
+    let timeout = db_accessor.operation_options().timeout;
+    if let Some(timeout) = timeout {
+        body["options"]["maxRuntime"] = serde_json::json!(timeout.as_secs_f64());
+    }
+    let database = db_accessor.database();
+    let url = |path: &str| {
+        database.url().join(path).map_err(|e| Error::InternalError {
+            message: Some(e.to_string()),
+        })
+    };
+    let started = Instant::now();
+    let result = with_timeout(timeout, async {
+        let request = http::Request::post(url("_api/cursor")?.to_string()).body(body.to_string());
+        let mut cursor = raw_cursor_request(database, request).await?;
+        let mut documents = std::mem::take(&mut cursor.result);
+        // The next batches are retrieved under the query request slot
+        while cursor.more {
+            let id = match cursor.id {
+                Some(id) => id,
+                None => break,
+            };
+            let url = url(&format!("_api/cursor/{}", id))?;
+            let request = http::Request::put(url.to_string()).body(String::new());
+            cursor = raw_cursor_request(database, request).await?;
+            documents.append(&mut cursor.result);
+        }
+        Ok(documents.into_iter().map(RawDocument).collect())
+    })
+    .await;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    result
+}
+
+/// A request of a [`DatabaseAccess`], tracked as in flight and holding a request slot if the
+/// requests are limited
+struct RequestGuard<'a> {
+    _operation: Option<OperationGuard<'a>>,
+    _permit: Option<RequestPermit<'a>>,
+}
+
+/// Starts a request of `db_accessor`, waiting for a request slot if its requests are limited
+#[maybe_async::maybe_async]
+async fn start_request<D>(db_accessor: &D) -> RequestGuard<'_>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let operation = db_accessor
+        .operation_tracker()
+        .map(OperationTracker::start_operation);
+    RequestGuard {
+        _operation: operation,
+        _permit: request_permit(db_accessor).await,
+    }
+}
+
+/// Waits for a request slot of `db_accessor`, if its requests are limited
+#[maybe_async::maybe_async]
+pub async fn request_permit<D>(db_accessor: &D) -> Option<RequestPermit<'_>>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let limiter = db_accessor.request_limiter()?;
+    Some(limiter.acquire().await)
+}
 
 #[maybe_async::maybe_async]
 pub async fn update_record<T, D>(
@@ -11,19 +434,88 @@ pub async fn update_record<T, D>(
     key: &str,
     db_accessor: &D,
     collection_name: &str,
-    options: OperationOptions,
+    options: &OperationOptions,
 ) -> Result<DatabaseRecord<T>, Error>
 where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    let _request = start_request(db_accessor).await;
     log::debug!("Updating document {} {}", collection_name, key);
     let collection = db_accessor.get_collection(collection_name)?;
-    let response = match collection.update_document(key, obj, options.into()).await {
-        Ok(resp) => resp,
-        Err(error) => return Err(Error::from(error)),
-    };
-    response.try_into()
+    let timeout = options.timeout;
+    with_timeout(timeout, async {
+        collection
+            .update_document(key, VersionedRecord(obj), options.clone().into())
+            .await
+            .map_err(|error| Error::from(error).with_document(collection_name, Some(key)))?
+            .try_into()
+    })
+    .await
+}
+
+/// Replaces the stored document of the record, instead of merging it like [`update_record`]
+#[maybe_async::maybe_async]
+pub async fn replace_record<T, D>(
+    obj: DatabaseRecord<T>,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<DatabaseRecord<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let key = obj.key().clone();
+    log::debug!("Replacing document {} {}", collection_name, key);
+    let collection = db_accessor.get_collection(collection_name)?;
+    with_timeout(options.timeout, async {
+        collection
+            .replace_document(&key, VersionedRecord(obj), options.clone().into(), None)
+            .await
+            .map_err(|error| Error::from(error).with_document(collection_name, Some(&key)))?
+            .try_into()
+    })
+    .await
+}
+
+/// Updates the record like [`update_record`], also returning the previous document state
+#[maybe_async::maybe_async]
+pub async fn update_record_returning_old<T, D>(
+    obj: DatabaseRecord<T>,
+    key: &str,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<(DatabaseRecord<T>, DatabaseRecord<T>), Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    log::debug!("Updating document {} {}", collection_name, key);
+    let collection = db_accessor.get_collection(collection_name)?;
+    let response = with_timeout(options.timeout, async {
+        collection
+            .update_document(key, VersionedRecord(obj), options.update_options(true))
+            .await
+            .map_err(|error| Error::from(error).with_document(collection_name, Some(key)))
+    })
+    .await?;
+    match response {
+        DocumentResponse::Response {
+            old: Some(old),
+            new: Some(new),
+            ..
+        } => Ok((old.into(), new.into())),
+        _ => Err(Error::InternalError {
+            message: Some(format!(
+                "Expected `ArangoDB` to return the old and new {}/{} documents",
+                collection_name, key
+            )),
+        }),
+    }
 }
 
 #[maybe_async::maybe_async]
@@ -32,20 +524,127 @@ pub async fn create_record<T, D>(
     key: Option<String>,
     db_accessor: &D,
     collection_name: &str,
-    options: OperationOptions,
+    options: &OperationOptions,
 ) -> Result<DatabaseRecord<T>, Error>
 where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    let _request = start_request(db_accessor).await;
     let collection = db_accessor.get_collection(collection_name)?;
     log::debug!("Creating new {} document", collection.name());
+    let document_key = key.clone();
     let dto = DatabaseRecordDto::new(obj, key);
-    let response = match collection.create_document(dto, options.into()).await {
-        Ok(resp) => resp,
-        Err(error) => return Err(Error::from(error)),
+    let timeout = options.timeout;
+    with_timeout(timeout, async {
+        collection
+            .create_document(dto, options.clone().into())
+            .await
+            .map_err(|error| {
+                Error::from(error).with_document(collection_name, document_key.as_deref())
+            })?
+            .try_into()
+    })
+    .await
+}
+
+/// The result of the [`create_records`] query checking the edge endpoints
+#[derive(Deserialize)]
+#[serde(bound = "T: Record")]
+struct CheckedRecords<T> {
+    /// The first missing endpoint id, in which case no document is created
+    missing: Option<String>,
+    created: Vec<VersionedRecord<T>>,
+}
+
+/// Creates the `records` documents with a single AQL `INSERT` query, in the accessor stream
+/// transaction if any. With the `check_edge_endpoints` option the edges are only created if all
+/// their `_from` and `_to` documents exist, in the same query.
+#[maybe_async::maybe_async]
+pub async fn create_records<T, D>(
+    records: Vec<T>,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<Vec<DatabaseRecord<T>>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let collection = db_accessor.get_collection(collection_name)?;
+    log::debug!(
+        "Creating {} new {} documents",
+        records.len(),
+        collection.name()
+    );
+    let documents: Vec<_> = records
+        .into_iter()
+        .map(|record| DatabaseRecordDto::new(record, None))
+        .collect();
+    let sync_option = options
+        .wait_for_sync
+        .map_or_else(String::new, |wait_for_sync| {
+            format!(" OPTIONS {{ waitForSync: {} }}", wait_for_sync)
+        });
+    let check_endpoints = T::IS_EDGE_COLLECTION && options.check_edge_endpoints;
+    let aql = if check_endpoints {
+        format!(
+            "LET missing = FIRST(\
+                FOR doc IN @documents FOR id IN [doc._from, doc._to] \
+                FILTER DOCUMENT(id) == null LIMIT 1 RETURN id\
+            ) \
+            LET created = (\
+                FOR doc IN (missing == null ? @documents : []) \
+                INSERT doc INTO @@collection{} RETURN NEW\
+            ) \
+            RETURN {{ missing, created }}",
+            sync_option
+        )
+    } else {
+        format!(
+            "FOR doc IN @documents INSERT doc INTO @@collection{} RETURN NEW",
+            sync_option
+        )
     };
-    response.try_into()
+    let aql_query = AqlQuery::new(&aql)
+        .bind_var("documents", serde_json::to_value(documents)?)
+        .bind_var("@collection", collection_name);
+    let result: CheckedRecords<T> = with_timeout(options.timeout, async {
+        let result = if check_endpoints {
+            aql_records_query(db_accessor, aql_query).await?.pop()
+        } else {
+            let created: Vec<VersionedRecord<T>> =
+                aql_records_query(db_accessor, aql_query).await?;
+            Some(CheckedRecords {
+                missing: None,
+                created,
+            })
+        };
+        Ok(result.unwrap_or(CheckedRecords {
+            missing: None,
+            created: Vec::new(),
+        }))
+    })
+    .await
+    .map_err(|error| error.with_document(collection_name, None))?;
+    if let Some(id) = result.missing {
+        return Err(endpoint_not_found(&id));
+    }
+    Ok(result.created.into_iter().map(Into::into).collect())
+}
+
+/// Runs the `aql_query` in the accessor stream transaction if any
+#[maybe_async::maybe_async]
+async fn aql_records_query<R, D>(db_accessor: &D, aql_query: AqlQuery<'_>) -> Result<Vec<R>, Error>
+where
+    R: DeserializeOwned,
+    D: DatabaseAccess + ?Sized,
+{
+    Ok(match db_accessor.stream_transaction() {
+        Some(transaction) => transaction.aql_query(aql_query).await?,
+        None => db_accessor.database().aql_query(aql_query).await?,
+    })
 }
 
 #[maybe_async::maybe_async]
@@ -58,13 +657,57 @@ where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    let _request = start_request(db_accessor).await;
     log::debug!("Retrieving {} {} from database", collection_name, key);
     let collection = db_accessor.get_collection(collection_name)?;
-    let record = match collection.document(key).await {
+    let timeout = db_accessor
+        .collection_operation_options(collection_name)
+        .timeout;
+    // The document is retrieved as a `DatabaseRecord` to extract the header fields before
+    // deserializing `T`, allowing externally tagged enum records and model migrations
+    let result = with_timeout(timeout, async {
+        let mode = db_accessor.deserialization_mode();
+        if let Some(transport) = db_accessor.velocypack_transport() {
+            let path = format!(
+                "_api/document/{}/{}",
+                encode_path_segment(collection_name),
+                encode_path_segment(key)
+            );
+            let document = transport
+                .request(
+                    db_accessor.database(),
+                    http::Method::GET,
+                    &path,
+                    String::new(),
+                )
+                .await?;
+            Ok(match mode {
+                DeserializationMode::Lenient => {
+                    deserialize_value::<VersionedRecord<T>>(document)?.into()
+                }
+                DeserializationMode::Strict => {
+                    deserialize_value::<StrictRecord<T>>(document)?.into()
+                }
+            })
+        } else {
+            Ok(match mode {
+                DeserializationMode::Lenient => collection
+                    .document::<VersionedRecord<T>>(key)
+                    .await?
+                    .document
+                    .into(),
+                DeserializationMode::Strict => collection
+                    .document::<StrictRecord<T>>(key)
+                    .await?
+                    .document
+                    .into(),
+            })
+        }
+    })
+    .await;
+    let record: DatabaseRecord<T> = match result {
         Ok(doc) => doc,
-        Err(error) => {
-            println!("{}", error);
-            let err = Error::from(error);
+        Err(err) => {
             if let Error::ArangoError(ref db_error) = err {
                 if ArangoHttpError::NotFound == db_error.http_error {
                     return Err(Error::NotFound {
@@ -77,7 +720,86 @@ where
             return Err(err);
         }
     };
-    Ok(DatabaseRecord::from(record))
+    if !is_tenant_record(&record.record, db_accessor)? {
+        return Err(Error::NotFound {
+            item: collection_name.to_string(),
+            id: key.to_string(),
+            source: None,
+        });
+    }
+    Ok(record)
+}
+
+#[maybe_async::maybe_async]
+pub async fn retrieve_record_if_modified<T, D>(
+    key: &str,
+    known_rev: &str,
+    db_accessor: &D,
+    collection_name: &str,
+) -> Result<Option<DatabaseRecord<T>>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    log::debug!(
+        "Retrieving {} {} from database if revision is not {}",
+        collection_name,
+        key,
+        known_rev
+    );
+    let collection = db_accessor.get_collection(collection_name)?;
+    let url = db_accessor
+        .database()
+        .url()
+        .join(&format!(
+            "_api/document/{}/{}",
+            encode_path_segment(collection_name),
+            encode_path_segment(key)
+        ))
+        .map_err(|e| Error::InternalError {
+            message: Some(e.to_string()),
+        })?;
+    let request = http::Request::get(url.to_string())
+        .header(http::header::IF_NONE_MATCH, format!("\"{}\"", known_rev))
+        .body(String::new())
+        .map_err(|e| Error::InternalError {
+            message: Some(e.to_string()),
+        })?;
+    let timeout = db_accessor
+        .collection_operation_options(collection_name)
+        .timeout;
+    let response = with_timeout(timeout, async {
+        Ok(collection.session().request(request).await?)
+    })
+    .await?;
+    if response.status() == http::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let result: Result<DatabaseRecord<T>, Error> = match db_accessor.deserialization_mode() {
+        DeserializationMode::Lenient => {
+            deserialize_response::<VersionedRecord<T>>(response.body()).map(Into::into)
+        }
+        DeserializationMode::Strict => {
+            deserialize_response::<StrictRecord<T>>(response.body()).map(Into::into)
+        }
+    };
+    match result {
+        Ok(record) if !is_tenant_record(&record.record, db_accessor)? => Err(Error::NotFound {
+            item: collection_name.to_string(),
+            id: key.to_string(),
+            source: None,
+        }),
+        Ok(record) => Ok(Some(record)),
+        Err(Error::ArangoError(db_error)) if db_error.http_error == ArangoHttpError::NotFound => {
+            Err(Error::NotFound {
+                item: collection_name.to_string(),
+                id: key.to_string(),
+                source: Some(db_error),
+            })
+        }
+        Err(error) => Err(error),
+    }
 }
 
 #[maybe_async::maybe_async]
@@ -85,21 +807,281 @@ pub async fn remove_record<T, D>(
     key: &str,
     db_accessor: &D,
     collection_name: &str,
-    options: OperationOptions,
+    options: &OperationOptions,
 ) -> Result<(), Error>
 where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    let _request = start_request(db_accessor).await;
     log::debug!("Removing {} {} from database", collection_name, key);
     let collection = db_accessor.get_collection(collection_name)?;
-    match collection
-        .remove_document::<T>(key, options.into(), None)
-        .await
-    {
-        Ok(_result) => Ok(()),
-        Err(error) => Err(Error::from(error)),
+    let timeout = options.timeout;
+    with_timeout(timeout, async {
+        collection
+            .remove_document::<T>(key, options.clone().into(), None)
+            .await
+            .map_err(|error| Error::from(error).with_document(collection_name, Some(key)))?;
+        Ok(())
+    })
+    .await
+}
+
+/// Percent-encodes `segment` to be used as an URL path segment, keeping only the unreserved
+/// characters
+pub fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Removes the vertex through the API of the first graph using `collection_name`, and its incident
+/// edges from the edge definitions of every other graph
+#[maybe_async::maybe_async]
+pub async fn remove_vertex<D>(
+    key: &str,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<(), Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    log::debug!("Removing vertex {} {} from database", collection_name, key);
+    with_timeout(
+        options.timeout,
+        remove_vertex_and_edges(key, db_accessor, collection_name, options),
+    )
+    .await
+}
+
+#[maybe_async::maybe_async]
+async fn remove_vertex_and_edges<D>(
+    key: &str,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<(), Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let collection = db_accessor.get_collection(collection_name)?;
+    let database = collection.db();
+    let uses_collection = |collections: &[String]| collections.iter().any(|c| c == collection_name);
+    let graphs: Vec<_> = database
+        .graphs()
+        .await?
+        .graphs
+        .into_iter()
+        .filter(|graph| {
+            uses_collection(&graph.orphan_collections)
+                || graph.edge_definitions.iter().any(|definition| {
+                    uses_collection(&definition.from) || uses_collection(&definition.to)
+                })
+        })
+        .collect();
+    let (graph, other_graphs) = graphs.split_first().ok_or_else(|| Error::NotFound {
+        item: "Graph with vertex collection".to_string(),
+        id: collection_name.to_string(),
+        source: None,
+    })?;
+    let incident_edges = |graph: &Graph| -> Vec<String> {
+        graph
+            .edge_definitions
+            .iter()
+            .filter(|definition| {
+                uses_collection(&definition.from) || uses_collection(&definition.to)
+            })
+            .map(|definition| definition.collection.clone())
+            .collect()
+    };
+    let removed_edges = incident_edges(graph);
+    let mut edge_collections: Vec<String> = other_graphs
+        .iter()
+        .flat_map(incident_edges)
+        .filter(|edge_collection| !removed_edges.contains(edge_collection))
+        .collect();
+    edge_collections.sort();
+    edge_collections.dedup();
+    let mut url = database
+        .url()
+        .join(&format!(
+            "_api/gharial/{}/vertex/{}/{}",
+            encode_path_segment(&graph.name),
+            encode_path_segment(collection_name),
+            encode_path_segment(key)
+        ))
+        .map_err(|e| Error::InternalError {
+            message: Some(e.to_string()),
+        })?;
+    let vertex_id = format!("{}/{}", collection_name, key);
+    // The counted incident edges are read before their removal to decrement the other vertices
+    let mut counted_edges = Vec::new();
+    for edge_collection in removed_edges.iter().chain(&edge_collections) {
+        if !counter_cache::is_counted(edge_collection, db_accessor) {
+            continue;
+        }
+        let aql_query = AqlQuery::new(
+            "FOR edge IN @@edge_collection \
+            FILTER edge._from == @vertex OR edge._to == @vertex \
+            RETURN [edge._from, edge._to]",
+        )
+        .bind_var("@edge_collection", edge_collection.as_str())
+        .bind_var("vertex", vertex_id.as_str());
+        let edges: Vec<[String; 2]> = database.aql_query(aql_query).await?;
+        counted_edges.extend(edges.into_iter().map(|[id_from, id_to]| CountedEdge {
+            collection: edge_collection.clone(),
+            id_from,
+            id_to,
+        }));
+    }
+    if let Some(wait_for_sync) = options.wait_for_sync {
+        url.set_query(Some(&format!("waitForSync={}", wait_for_sync)));
+    }
+    let response = collection.session().delete(url.to_string(), "").await?;
+    deserialize_response::<serde_json::Value>(response.body())?;
+    for edge_collection in &edge_collections {
+        log::debug!(
+            "Removing vertex {} incident edges from {}",
+            vertex_id,
+            edge_collection
+        );
+        let aql_query = AqlQuery::new(
+            "FOR edge IN @@edge_collection \
+            FILTER edge._from == @vertex OR edge._to == @vertex \
+            REMOVE edge IN @@edge_collection",
+        )
+        .bind_var("@edge_collection", edge_collection.as_str())
+        .bind_var("vertex", vertex_id.as_str());
+        create_cursor::<serde_json::Value>(&database, aql_query, options.timeout).await?;
+    }
+    for edge in counted_edges {
+        edge.update(-1, db_accessor).await?;
+    }
+    Ok(())
+}
+
+#[maybe_async::maybe_async]
+pub async fn vertex_degree<D>(
+    vertex_id: &str,
+    edge_collection: &str,
+    direction: GraphQueryDirection,
+    db_accessor: &D,
+) -> Result<u64, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let aql = format!(
+        "FOR v, e IN 1..1 {} @vertex @@edge_collection COLLECT WITH COUNT INTO count RETURN count",
+        direction
+    );
+    log::debug!("Computing {} degree through AQL: `{}`", vertex_id, aql);
+    let aql_query = AqlQuery::new(&aql)
+        .bind_var("vertex", vertex_id)
+        .bind_var("@edge_collection", edge_collection);
+    let timeout = db_accessor.operation_options().timeout;
+    let cursor: Cursor<u64> = with_timeout(
+        timeout,
+        create_cursor(db_accessor.database(), aql_query, timeout),
+    )
+    .await?;
+    Ok(cursor.result.first().copied().unwrap_or(0))
+}
+
+/// Checks that the `id_from` and `id_to` documents exist, with a single query
+#[maybe_async::maybe_async]
+pub async fn check_edge_endpoints<D>(
+    id_from: &str,
+    id_to: &str,
+    db_accessor: &D,
+) -> Result<(), Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let aql = "RETURN [DOCUMENT(@from) != null, DOCUMENT(@to) != null]";
+    log::debug!(
+        "Checking edge endpoints {} and {} through AQL: `{}`",
+        id_from,
+        id_to,
+        aql
+    );
+    let aql_query = AqlQuery::new(aql)
+        .bind_var("from", id_from)
+        .bind_var("to", id_to);
+    let timeout = db_accessor.operation_options().timeout;
+    let cursor: Cursor<[bool; 2]> = with_timeout(
+        timeout,
+        create_cursor(db_accessor.database(), aql_query, timeout),
+    )
+    .await?;
+    let exists = cursor.result.first().copied().unwrap_or_default();
+    for (id, exists) in [(id_from, exists[0]), (id_to, exists[1])] {
+        if !exists {
+            return Err(endpoint_not_found(id));
+        }
+    }
+    Ok(())
+}
+
+/// The [`Error::NotFound`] of the missing edge endpoint `id`
+fn endpoint_not_found(id: &str) -> Error {
+    let (collection, key) = id.split_once('/').unwrap_or(("Document", id));
+    Error::NotFound {
+        item: collection.to_string(),
+        id: key.to_string(),
+        source: None,
+    }
+}
+
+/// Counts the `T` documents matching `query` per value of every field of `fields`
+#[maybe_async::maybe_async]
+pub async fn query_facets<T, D>(
+    db_accessor: &D,
+    query: &Query,
+    fields: &[&str],
+) -> Result<Facets, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, mut bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
+    let (aql, facet_vars) = facets_aql(&aql, fields);
+    bind_vars.extend(facet_vars);
+    log::debug!(
+        "Computing {} facets through AQL: `{}`",
+        T::COLLECTION_NAME,
+        aql
+    );
+    let mut aql_query = AqlQuery::new(&aql);
+    for (var, val) in &bind_vars {
+        aql_query = aql_query.bind_var(var, val.clone());
     }
+    let timeout = db_accessor.operation_options().timeout;
+    let started = Instant::now();
+    let result = with_timeout(timeout, async {
+        let cursor: Cursor<Facets> =
+            create_cursor(db_accessor.database(), aql_query, timeout).await?;
+        Ok(cursor.result.into_iter().next().unwrap_or_default())
+    })
+    .await;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    result
 }
 
 #[maybe_async::maybe_async]
@@ -108,16 +1090,121 @@ where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    let _request = start_request(db_accessor).await;
     log::debug!(
         "Querying {} records through AQL: `{}`",
         T::COLLECTION_NAME,
         aql
     );
-    let query_result = match db_accessor.database().aql_str(aql).await {
-        Ok(value) => value,
-        Err(error) => return Err(Error::from(error)),
-    };
-    Ok(query_result.into())
+    let timeout = db_accessor.operation_options().timeout;
+    let started = Instant::now();
+    let query_result: Vec<DatabaseRecord<T>> = with_timeout(timeout, async {
+        let database = db_accessor.database();
+        Ok(match db_accessor.deserialization_mode() {
+            DeserializationMode::Lenient => {
+                let records: Vec<VersionedRecord<T>> = database.aql_str(aql).await?;
+                records.into_iter().map(Into::into).collect()
+            }
+            DeserializationMode::Strict => {
+                let records: Vec<StrictRecord<T>> = database.aql_str(aql).await?;
+                records.into_iter().map(Into::into).collect()
+            }
+        })
+    })
+    .await?;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        aql,
+        std::iter::empty(),
+    );
+    Ok(query_result.into_iter().collect())
+}
+
+/// Counts the `T` documents matching `query`, with the `T` default scope and tenant filter
+#[maybe_async::maybe_async]
+pub async fn count_records<T, D>(db_accessor: &D, query: &Query) -> Result<u32, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
+    log::debug!(
+        "Counting {} records through AQL: `{}`",
+        T::COLLECTION_NAME,
+        aql
+    );
+    let mut aql_query = AqlQuery::new(&aql).batch_size(1).count(true);
+    for (var, val) in &bind_vars {
+        aql_query = aql_query.bind_var(var, val.clone());
+    }
+    let timeout = db_accessor.operation_options().timeout;
+    let started = Instant::now();
+    let result: Result<Cursor<serde_json::Value>, Error> = with_timeout(
+        timeout,
+        create_cursor(db_accessor.database(), aql_query, timeout),
+    )
+    .await;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    Ok(result?
+        .count
+        .map_or(0, |count| u32::try_from(count).unwrap_or(u32::MAX)))
+}
+
+/// Queries the `T` records matching `query` in the stream `transaction`, with the `T` default
+/// scope and tenant filter
+#[maybe_async::maybe_async]
+pub async fn query_transaction_records<T, D>(
+    transaction: &TransactionLayer,
+    db_accessor: &D,
+    query: &Query,
+) -> Result<QueryResult<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
+    log::debug!(
+        "Querying {} records in transaction {} through AQL: `{}`",
+        T::COLLECTION_NAME,
+        transaction.id(),
+        aql
+    );
+    let mut aql_query = AqlQuery::new(&aql);
+    for (var, val) in &bind_vars {
+        aql_query = aql_query.bind_var(var, val.clone());
+    }
+    let timeout = db_accessor.operation_options().timeout;
+    let started = Instant::now();
+    let result: Result<Vec<DatabaseRecord<T>>, Error> = with_timeout(timeout, async {
+        Ok(match db_accessor.deserialization_mode() {
+            DeserializationMode::Lenient => {
+                let records: Vec<VersionedRecord<T>> = transaction.aql_query(aql_query).await?;
+                records.into_iter().map(Into::into).collect()
+            }
+            DeserializationMode::Strict => {
+                let records: Vec<StrictRecord<T>> = transaction.aql_query(aql_query).await?;
+                records.into_iter().map(Into::into).collect()
+            }
+        })
+    })
+    .await;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    Ok(result?.into_iter().collect())
 }
 
 #[maybe_async::maybe_async]
@@ -126,21 +1213,86 @@ where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
-    let aql = query.aql_str();
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
     log::debug!(
         "Querying {} records through AQL: `{}`",
         T::COLLECTION_NAME,
         aql
     );
     let mut aql_query = AqlQuery::new(&aql);
-    for (var, val) in &query.bind_vars {
+    if query.has_limit() {
+        aql_query = aql_query.options(AqlOptions::builder().full_count(true).build());
+    }
+    for (var, val) in &bind_vars {
         aql_query = aql_query.bind_var(var, val.clone());
     }
-    let query_result = match db_accessor.database().aql_query(aql_query).await {
-        Ok(value) => value,
-        Err(error) => return Err(Error::from(error)),
-    };
-    Ok(query_result.into())
+    let database = db_accessor.database();
+    let timeout = db_accessor.operation_options().timeout;
+    let mode = db_accessor.deserialization_mode();
+    let started = Instant::now();
+    let transport = db_accessor.velocypack_transport();
+    let result = with_timeout(timeout, async {
+        let mut cursor = match transport {
+            Some(transport) => {
+                let body = cursor_body(&aql_query, timeout)?.to_string();
+                velocypack_record_cursor::<T>(
+                    transport,
+                    database,
+                    http::Method::POST,
+                    "_api/cursor",
+                    body,
+                    mode,
+                )
+                .await?
+            }
+            None => create_record_cursor::<T>(database, aql_query, timeout, mode).await?,
+        };
+        let mut documents = std::mem::take(&mut cursor.result);
+        let mut metadata = QueryMetadata::new(cursor.extra.as_ref());
+        // The next batches are retrieved under the query request slot
+        while cursor.more {
+            let id = match cursor.id {
+                Some(id) => id,
+                None => break,
+            };
+            cursor = match (transport, mode) {
+                (Some(transport), _) => {
+                    let path = format!("_api/cursor/{}", encode_path_segment(&id));
+                    velocypack_record_cursor(
+                        transport,
+                        database,
+                        http::Method::PUT,
+                        &path,
+                        String::new(),
+                        mode,
+                    )
+                    .await?
+                }
+                (None, DeserializationMode::Lenient) => database.aql_next_batch(&id).await?,
+                (None, DeserializationMode::Strict) => {
+                    versioned_cursor(database.aql_next_batch(&id).await?)
+                }
+            };
+            documents.append(&mut cursor.result);
+            // The statistics are complete in the last batch
+            if cursor.extra.is_some() {
+                metadata = QueryMetadata::new(cursor.extra.as_ref());
+            }
+        }
+        metadata.log_warnings();
+        let documents = documents.into_iter().map(Into::into).collect();
+        Ok(QueryResult(documents, metadata))
+    })
+    .await;
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    result
 }
 
 #[maybe_async::maybe_async]
@@ -153,22 +1305,155 @@ where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
-    let aql = query.aql_str();
+    open_record_cursor(db_accessor, query, batch_size, false).await
+}
+
+/// Opens a streaming cursor on the `T` records of `query`, the results being computed as the
+/// batches are fetched
+#[maybe_async::maybe_async]
+pub async fn stream_records<T, D>(
+    db_accessor: &D,
+    query: &Query,
+    batch_size: u32,
+) -> Result<QueryCursor<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    open_record_cursor(db_accessor, query, batch_size, true).await
+}
+
+/// Opens a cursor on the `T` records of `query` fetching `batch_size` documents per batch.
+/// `arangors_lite` doesn't support the `stream` option so the `stream` cursors are requested
+/// manually.
+#[maybe_async::maybe_async]
+async fn open_record_cursor<T, D>(
+    db_accessor: &D,
+    query: &Query,
+    batch_size: u32,
+    stream: bool,
+) -> Result<QueryCursor<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let _request = start_request(db_accessor).await;
+    let query = query.scoped_for::<T>(record_tenant::<T, D>(db_accessor)?);
+    let (aql, bind_vars) = query.aql_with_bind_vars(db_accessor.bind_literals());
     log::debug!(
         "Querying {} records through AQL with {} batch size: `{}`",
         T::COLLECTION_NAME,
         batch_size,
         aql
     );
-    let mut aql_query = AqlQuery::new(&aql)
-        .batch_size(batch_size)
-        .options(AqlOptions::builder().full_count(true).build());
-    for (var, val) in &query.bind_vars {
+    let mut aql_query = AqlQuery::new(&aql).batch_size(batch_size);
+    if !stream {
+        aql_query = aql_query.options(AqlOptions::builder().full_count(true).build());
+    }
+    if let Some(ttl) = query.cursor_ttl {
+        aql_query = aql_query.ttl(ttl);
+    }
+    for (var, val) in &bind_vars {
         aql_query = aql_query.bind_var(var, val.clone());
     }
-    let cursor = match db_accessor.database().aql_query_batch(aql_query).await {
-        Ok(value) => value,
-        Err(error) => return Err(Error::from(error)),
+    let database = db_accessor.database();
+    let timeout = db_accessor.operation_options().timeout;
+    let mode = db_accessor.deserialization_mode();
+    let started = Instant::now();
+    let transport = db_accessor.velocypack_transport();
+    let mut body = cursor_body(&aql_query, timeout)?;
+    if stream {
+        body["options"]["stream"] = serde_json::json!(true);
+    }
+    let cursor = match transport {
+        Some(transport) => {
+            with_timeout(
+                timeout,
+                velocypack_record_cursor(
+                    transport,
+                    database,
+                    http::Method::POST,
+                    "_api/cursor",
+                    body.to_string(),
+                    mode,
+                ),
+            )
+            .await?
+        }
+        None if stream => with_timeout(timeout, post_record_cursor(database, &body, mode)).await?,
+        None => {
+            with_timeout(
+                timeout,
+                create_record_cursor(database, aql_query, timeout, mode),
+            )
+            .await?
+        }
     };
-    Ok(QueryCursor::new(cursor, db_accessor.database().clone()))
+    log_slow_query(
+        db_accessor.slow_query_threshold(),
+        started,
+        &aql,
+        bind_vars.keys(),
+    );
+    Ok(QueryCursor::new(cursor, database.clone(), mode)
+        .with_transport(transport)
+        .limited(db_accessor.request_limiter())
+        .tracked(db_accessor.operation_tracker()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DatabaseError;
+
+    #[test]
+    fn aql_redaction() {
+        assert_eq!(
+            redact_aql(
+                r#"FOR a IN User FILTER a.email == "robert@surcouf.fr" && a.name != 'Ro\'bert' RETURN a"#
+            ),
+            r#"FOR a IN User FILTER a.email == "***" && a.name != '***' RETURN a"#
+        );
+        assert_eq!(
+            redact_aql("FOR a IN @@collection FILTER a.age > @age RETURN a"),
+            "FOR a IN @@collection FILTER a.age > @age RETURN a"
+        );
+    }
+
+    #[test]
+    fn killed_queries_time_out() {
+        let killed = || {
+            Error::ArangoError(DatabaseError::new(
+                ArangoHttpError::ServerError,
+                ArangoErrorCode::QueryKilled,
+                String::new(),
+            ))
+        };
+        let duration = Duration::from_millis(10);
+        assert!(matches!(
+            timeout_error(killed(), Some(duration)),
+            Error::Timeout { duration: d } if d == duration
+        ));
+        assert!(matches!(
+            timeout_error(killed(), None),
+            Error::ArangoError(_)
+        ));
+    }
+
+    #[cfg(not(feature = "blocking"))]
+    #[tokio::test]
+    async fn deadline_is_applied() {
+        let duration = Duration::from_millis(10);
+        let result: Result<(), Error> =
+            with_timeout(Some(duration), futures_util::future::pending()).await;
+        assert!(matches!(result, Err(Error::Timeout { duration: d }) if d == duration));
+        assert!(with_timeout(Some(duration), async { Ok(()) }).await.is_ok());
+    }
+
+    #[test]
+    fn path_segment_encoding() {
+        assert_eq!(encode_path_segment("user_1-a.b~c"), "user_1-a.b~c");
+        assert_eq!(encode_path_segment("a/b c?d"), "a%2Fb%20c%3Fd");
+        assert_eq!(encode_path_segment("é"), "%C3%A9");
+    }
 }
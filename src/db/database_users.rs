@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::database_service::{deserialize_response, encode_path_segment};
+use crate::{DatabaseAccess, DatabaseConnection, Error};
+
+/// Access level of an `ArangoDB` user on a database or a collection
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessLevel {
+    /// Read and write access (`rw`)
+    #[serde(rename = "rw")]
+    ReadWrite,
+    /// Read only access (`ro`)
+    #[serde(rename = "ro")]
+    ReadOnly,
+    /// No access (`none`)
+    #[serde(rename = "none")]
+    NoAccess,
+}
+
+/// An `ArangoDB` user, as returned by [`DatabaseConnection::list_users`]
+///
+/// [`DatabaseConnection::list_users`]: crate::DatabaseConnection::list_users
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DatabaseUser {
+    /// The user name
+    pub user: String,
+    /// Defines if the user can log in
+    pub active: bool,
+    /// Custom user data
+    #[serde(default)]
+    pub extra: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct UsersResponse {
+    result: Vec<DatabaseUser>,
+}
+
+/// User management, requiring the connection credentials to have administrate access on the
+/// `_system` database.
+impl DatabaseConnection {
+    /// Creates an `ArangoDB` user. The user has no access on any database by default,
+    /// see [`grant_database_access`].
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user name
+    /// * `password` - The user password
+    /// * `active` - Defines if the user can log in
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{AccessLevel, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let user = db_connection.create_user("tenant_1", "password", true).await.unwrap();
+    /// db_connection.grant_database_access(&user.user, AccessLevel::ReadOnly).await.unwrap();
+    /// db_connection
+    ///     .grant_collection_access(&user.user, "Order", AccessLevel::ReadWrite)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`grant_database_access`]: Self::grant_database_access
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the user already exists, with [`Error::Conflict`], or if the connection lacks the
+    /// required permissions.
+    ///
+    /// [`Error::Conflict`]: crate::Error::Conflict
+    #[maybe_async::maybe_async]
+    pub async fn create_user(
+        &self,
+        user: &str,
+        password: &str,
+        active: bool,
+    ) -> Result<DatabaseUser, Error> {
+        log::debug!("Creating user {}", user);
+        let body = serde_json::json!({
+            "user": user,
+            "passwd": password,
+            "active": active,
+        });
+        self.user_request(http::Method::POST, &[], Some(body)).await
+    }
+
+    /// Removes an `ArangoDB` user.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the user doesn't exist or if the connection lacks the required permissions.
+    #[maybe_async::maybe_async]
+    pub async fn remove_user(&self, user: &str) -> Result<(), Error> {
+        log::debug!("Removing user {}", user);
+        let _: serde_json::Value = self
+            .user_request(http::Method::DELETE, &[user], None)
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the `ArangoDB` users.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the connection lacks the required permissions.
+    #[maybe_async::maybe_async]
+    pub async fn list_users(&self) -> Result<Vec<DatabaseUser>, Error> {
+        let response: UsersResponse = self.user_request(http::Method::GET, &[], None).await?;
+        Ok(response.result)
+    }
+
+    /// Sets the access level of `user` on the current database.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the user doesn't exist or if the connection lacks the required permissions.
+    #[maybe_async::maybe_async]
+    pub async fn grant_database_access(
+        &self,
+        user: &str,
+        access: AccessLevel,
+    ) -> Result<(), Error> {
+        let path = [user, "database", self.database().name()];
+        self.grant_access(&path, access).await
+    }
+
+    /// Sets the access level of `user` on the `collection` of the current database.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the user doesn't exist or if the connection lacks the required permissions.
+    #[maybe_async::maybe_async]
+    pub async fn grant_collection_access(
+        &self,
+        user: &str,
+        collection: &str,
+        access: AccessLevel,
+    ) -> Result<(), Error> {
+        let path = [user, "database", self.database().name(), collection];
+        self.grant_access(&path, access).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn grant_access(&self, path: &[&str], access: AccessLevel) -> Result<(), Error> {
+        log::debug!("Granting {:?} access on user {}", access, path.join("/"));
+        let body = serde_json::json!({ "grant": access });
+        let _: serde_json::Value = self
+            .user_request(http::Method::PUT, path, Some(body))
+            .await?;
+        Ok(())
+    }
+
+    /// Sends a request to the `_system` database user API, on the percent-encoded `path`
+    /// segments
+    #[maybe_async::maybe_async]
+    async fn user_request<T>(
+        &self,
+        method: http::Method,
+        path: &[&str],
+        body: Option<serde_json::Value>,
+    ) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let internal_error = |e: &dyn std::fmt::Display| Error::InternalError {
+            message: Some(e.to_string()),
+        };
+        let database = self.database();
+        // The user API is only available on the `_system` database
+        let mut url_path = String::from("/_api/user");
+        for segment in path {
+            url_path.push('/');
+            url_path.push_str(&encode_path_segment(segment));
+        }
+        let url = database
+            .url()
+            .join(&url_path)
+            .map_err(|e| internal_error(&e))?;
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let request = http::Request::builder()
+            .method(method)
+            .uri(url.to_string())
+            .body(body)
+            .map_err(|e| internal_error(&e))?;
+        let response = database.session().request(request).await?;
+        deserialize_response(response.body())
+    }
+}
@@ -0,0 +1,171 @@
+use arangors_lite::Cursor;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::db::versioned_record::{
+    record_from_value, serialize_versioned, VersionedRecord, MODEL_VERSION_FIELD,
+};
+use crate::{DatabaseRecord, Record};
+
+/// The strictness of the stored documents deserialization, see
+/// [`DatabaseConnectionBuilder::with_deserialization_mode`]
+///
+/// [`DatabaseConnectionBuilder::with_deserialization_mode`]: crate::DatabaseConnectionBuilder::with_deserialization_mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeserializationMode {
+    /// The document fields unknown to the record are ignored (default)
+    Lenient,
+    /// The documents with fields unknown to the record fail to be deserialized, like with the
+    /// `#[serde(deny_unknown_fields)]` attribute, catching schema drifts early.
+    ///
+    /// A field is unknown if it is not serialized back by the record, the `null` fields being
+    /// ignored. The documents upgraded through [`Record::migrate_from`] are not checked.
+    Strict,
+}
+
+impl Default for DeserializationMode {
+    fn default() -> Self {
+        Self::Lenient
+    }
+}
+
+/// A [`VersionedRecord`] failing to deserialize when the document has fields unknown to the
+/// record, see [`DeserializationMode::Strict`]
+#[derive(Debug, Clone)]
+pub struct StrictRecord<T>(pub DatabaseRecord<T>);
+
+impl<T: Record> Serialize for StrictRecord<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_versioned(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Record> Deserialize<'de> for StrictRecord<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = DatabaseRecord::<Value>::deserialize(deserializer)?;
+        let stored_version = document
+            .record
+            .get(MODEL_VERSION_FIELD)
+            .and_then(Value::as_u64)
+            .unwrap_or_default();
+        let record: T = record_from_value(document.record.clone()).map_err(D::Error::custom)?;
+        if stored_version >= u64::from(T::MODEL_VERSION) {
+            let serialized = serde_json::to_value(&record).map_err(D::Error::custom)?;
+            let mut unknown = Vec::new();
+            unknown_fields(&document.record, &serialized, "", &mut unknown);
+            if !unknown.is_empty() {
+                return Err(D::Error::custom(format!(
+                    "unknown {} fields in {}: {}",
+                    T::COLLECTION_NAME,
+                    document.id,
+                    unknown.join(", ")
+                )));
+            }
+        }
+        Ok(Self(DatabaseRecord {
+            key: document.key,
+            id: document.id,
+            rev: document.rev,
+            record,
+        }))
+    }
+}
+
+impl<T> From<StrictRecord<T>> for DatabaseRecord<T> {
+    fn from(record: StrictRecord<T>) -> Self {
+        record.0
+    }
+}
+
+impl<T> From<StrictRecord<T>> for VersionedRecord<T> {
+    fn from(record: StrictRecord<T>) -> Self {
+        Self(record.0)
+    }
+}
+
+/// Converts a cursor of strictly deserialized records
+pub fn versioned_cursor<T>(cursor: Cursor<StrictRecord<T>>) -> Cursor<VersionedRecord<T>> {
+    Cursor {
+        count: cursor.count,
+        cached: cursor.cached,
+        more: cursor.more,
+        result: cursor.result.into_iter().map(Into::into).collect(),
+        id: cursor.id,
+        extra: cursor.extra,
+    }
+}
+
+/// Pushes the paths of the `stored` fields missing from the `serialized` record in `unknown`
+fn unknown_fields(stored: &Value, serialized: &Value, path: &str, unknown: &mut Vec<String>) {
+    match (stored, serialized) {
+        (Value::Object(stored), Value::Object(serialized)) => {
+            for (key, value) in stored {
+                let field_path = field_path(path, key);
+                match serialized.get(key) {
+                    Some(serialized_value) => {
+                        unknown_fields(value, serialized_value, &field_path, unknown);
+                    }
+                    None if value.is_null() || is_system_field(path, key) => (),
+                    None => unknown.push(field_path),
+                }
+            }
+        }
+        (Value::Array(stored), Value::Array(serialized)) if stored.len() == serialized.len() => {
+            for (i, (value, serialized_value)) in stored.iter().zip(serialized).enumerate() {
+                unknown_fields(
+                    value,
+                    serialized_value,
+                    &format!("{}[{}]", path, i),
+                    unknown,
+                );
+            }
+        }
+        _ => (),
+    }
+}
+
+fn field_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn is_system_field(path: &str, key: &str) -> bool {
+    path.is_empty() && key == MODEL_VERSION_FIELD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unknown(stored: Value, serialized: Value) -> Vec<String> {
+        let mut unknown = Vec::new();
+        unknown_fields(&stored, &serialized, "", &mut unknown);
+        unknown
+    }
+
+    #[test]
+    fn unknown_field_paths() {
+        let serialized = serde_json::json!({
+            "name": "Robert",
+            "address": { "street": "Rue" },
+            "tags": [{ "label": "a" }]
+        });
+        let stored = serde_json::json!({
+            "name": "Robert",
+            "address": { "street": "Rue", "city": "Paris" },
+            "tags": [{ "label": "a", "color": "red" }],
+            "age": 18,
+            "nickname": null,
+            "_model_version": 1
+        });
+        assert_eq!(
+            unknown(stored, serialized.clone()),
+            vec!["address.city", "age", "tags[0].color"]
+        );
+        assert!(unknown(serialized.clone(), serialized).is_empty());
+    }
+}
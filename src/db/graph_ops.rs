@@ -0,0 +1,462 @@
+use std::collections::HashSet;
+
+use arangors_lite::AqlQuery;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::database_service;
+use crate::query::{GraphQueryDirection, QueryResult};
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+
+const DEFAULT_MAX_DEPTH: u16 = 100;
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+/// The edges traversed by [`GraphOps`]
+#[derive(Clone, Debug)]
+enum GraphTarget {
+    EdgeCollections(Vec<String>),
+    NamedGraph(String),
+}
+
+/// The edge attribute weighting the paths of [`GraphOps`]
+#[derive(Clone, Debug)]
+struct PathWeight {
+    attribute: String,
+    default_weight: f64,
+}
+
+/// A path between two vertices, see [`GraphOps::shortest_path`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphPath {
+    /// The `id` of the path vertices, from the start vertex to the target vertex
+    pub vertices: Vec<String>,
+    /// The `id` of the path edges
+    pub edges: Vec<String>,
+    /// The total weight of the path, see [`GraphOps::weight_attribute`]. Without weight attribute
+    /// every edge weighs `1`.
+    pub weight: f64,
+}
+
+/// High level graph algorithms helpers, composing the traversal AQL queries.
+///
+/// The traversals visit every vertex only once (breadth-first search with global vertex uniqueness),
+/// so the results are deduplicated. Large results are retrieved in batches.
+///
+/// # Example
+///
+/// ```rust no_run
+/// # use aragog::{DatabaseConnection, GraphOps, Record};
+/// # use aragog::query::GraphQueryDirection;
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// # #[derive(Record, Clone, Serialize, Deserialize)]
+/// # struct User {}
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+/// let graph = GraphOps::new(&db_accessor)
+///     .edge_collections(&["Follows"])
+///     .direction(GraphQueryDirection::Outbound);
+/// // The users followed by `User/123`, or by the users they follow
+/// let users = graph.neighbors_within::<User>("User/123", 2).await.unwrap();
+/// // The ids of every vertex reachable from `User/123`
+/// let ids = graph.reachable_from("User/123").await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphOps<'a, D: ?Sized> {
+    db_accessor: &'a D,
+    target: GraphTarget,
+    direction: GraphQueryDirection,
+    max_depth: u16,
+    batch_size: u32,
+    weight: Option<PathWeight>,
+}
+
+impl<'a, D> GraphOps<'a, D>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    /// Instantiates the graph helpers on `db_accessor`.
+    ///
+    /// By default no edge collection is traversed, use [`edge_collections`] or [`named_graph`] to
+    /// define the traversed edges. The traversals follow the edges in both directions.
+    ///
+    /// [`edge_collections`]: Self::edge_collections
+    /// [`named_graph`]: Self::named_graph
+    #[must_use]
+    pub const fn new(db_accessor: &'a D) -> Self {
+        Self {
+            db_accessor,
+            target: GraphTarget::EdgeCollections(Vec::new()),
+            direction: GraphQueryDirection::Any,
+            max_depth: DEFAULT_MAX_DEPTH,
+            batch_size: DEFAULT_BATCH_SIZE,
+            weight: None,
+        }
+    }
+
+    /// Traverses the given edge collections
+    #[must_use]
+    pub fn edge_collections(mut self, edge_collections: &[&str]) -> Self {
+        self.target = GraphTarget::EdgeCollections(
+            edge_collections.iter().map(ToString::to_string).collect(),
+        );
+        self
+    }
+
+    /// Traverses the edges of the given named graph
+    #[must_use]
+    pub fn named_graph(mut self, named_graph: &str) -> Self {
+        self.target = GraphTarget::NamedGraph(named_graph.to_string());
+        self
+    }
+
+    /// Sets the direction of the traversed edges (`GraphQueryDirection::Any` by default)
+    #[must_use]
+    pub const fn direction(mut self, direction: GraphQueryDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the maximum traversal depth of [`reachable_from`] and [`connected_components`] (100 by default)
+    ///
+    /// [`reachable_from`]: Self::reachable_from
+    /// [`connected_components`]: Self::connected_components
+    #[must_use]
+    pub const fn max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the batch size used to retrieve the vertex ids (1000 by default)
+    #[must_use]
+    pub const fn batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Weights the paths of [`shortest_path`] and [`k_shortest_paths`] by the `attribute` of their
+    /// edges, the edges without the attribute weighing `default_weight`. By default every edge
+    /// weighs `1`.
+    ///
+    /// [`shortest_path`]: Self::shortest_path
+    /// [`k_shortest_paths`]: Self::k_shortest_paths
+    #[must_use]
+    pub fn weight_attribute(mut self, attribute: &str, default_weight: f64) -> Self {
+        self.weight = Some(PathWeight {
+            attribute: attribute.to_string(),
+            default_weight,
+        });
+        self
+    }
+
+    /// Retrieves the distinct `T` vertices at most `depth` edges away from the `start` vertex.
+    /// The `start` vertex is not included, and the vertices of a tenant scoped `T` are restricted
+    /// to the connection tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The `id` of the start vertex
+    /// * `depth` - The maximum traversal depth
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure or if the vertices can't be deserialized, and with
+    /// [`Error::Forbidden`] for a tenant scoped `T` without connection tenant.
+    #[maybe_async::maybe_async]
+    pub async fn neighbors_within<T>(
+        &self,
+        start: &str,
+        depth: u16,
+    ) -> Result<QueryResult<T>, Error>
+    where
+        T: Record,
+    {
+        let tenant = database_service::record_tenant::<T, D>(self.db_accessor)?;
+        let (traversal, target_vars) = traversal_aql(&self.target, self.direction, depth);
+        let tenant_filter = match (T::TENANT_FIELD, tenant) {
+            (Some(field), Some(_)) => format!(" FILTER v.{} == @tenant", field),
+            _ => String::new(),
+        };
+        let aql = format!(
+            "{} FILTER IS_SAME_COLLECTION(@collection, v){} RETURN v",
+            traversal, tenant_filter
+        );
+        log::debug!("Retrieving {} neighbors through AQL: `{}`", start, aql);
+        let mut aql_query = bind_target(AqlQuery::new(&aql), &target_vars)
+            .bind_var("start", start)
+            .bind_var("collection", T::COLLECTION_NAME);
+        if let Some(tenant) = tenant {
+            aql_query = aql_query.bind_var("tenant", tenant);
+        }
+        let records: Vec<DatabaseRecord<T>> =
+            self.db_accessor.database().aql_query(aql_query).await?;
+        Ok(records.into())
+    }
+
+    /// Retrieves the `id` of every vertex reachable from the `start` vertex, in the maximum depth
+    /// (see [`max_depth`]). The `start` vertex is not included.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    ///
+    /// [`max_depth`]: Self::max_depth
+    #[maybe_async::maybe_async]
+    pub async fn reachable_from(&self, start: &str) -> Result<Vec<String>, Error> {
+        self.reachable_ids(start, self.direction).await
+    }
+
+    /// Computes the connected components of the vertices of `vertex_collection`. Every component is
+    /// the list of the `id` of its vertices, including the vertices of other collections.
+    ///
+    /// The edges are followed in both directions whatever the [`direction`], computing the weakly
+    /// connected components. A vertex without edges is a component on its own.
+    ///
+    /// # Note
+    ///
+    /// This runs a traversal per component, for large graphs prefer the `ArangoDB` Pregel algorithms.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    ///
+    /// [`direction`]: Self::direction
+    #[maybe_async::maybe_async]
+    pub async fn connected_components(
+        &self,
+        vertex_collection: &str,
+    ) -> Result<Vec<Vec<String>>, Error> {
+        let aql_query = AqlQuery::new("FOR v IN @@collection RETURN v._id")
+            .bind_var("@collection", vertex_collection);
+        let vertices = self.collect_ids(aql_query).await?;
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for vertex in vertices {
+            if visited.contains(&vertex) {
+                continue;
+            }
+            let mut component = self
+                .reachable_ids(&vertex, GraphQueryDirection::Any)
+                .await?;
+            component.insert(0, vertex);
+            visited.extend(component.iter().cloned());
+            components.push(component);
+        }
+        Ok(components)
+    }
+
+    /// Retrieves the path from the `from` vertex to the `to` vertex with the lowest total weight,
+    /// see [`weight_attribute`]. Returns `None` if `to` can't be reached.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    ///
+    /// [`weight_attribute`]: Self::weight_attribute
+    #[maybe_async::maybe_async]
+    pub async fn shortest_path(&self, from: &str, to: &str) -> Result<Option<GraphPath>, Error> {
+        let paths = self.k_shortest_paths(from, to, 1).await?;
+        Ok(paths.into_iter().next())
+    }
+
+    /// Retrieves at most `limit` paths from the `from` vertex to the `to` vertex, by increasing
+    /// total weight (see [`weight_attribute`]).
+    ///
+    /// # Errors
+    ///
+    /// Will fail on database request failure.
+    ///
+    /// [`weight_attribute`]: Self::weight_attribute
+    #[maybe_async::maybe_async]
+    pub async fn k_shortest_paths(
+        &self,
+        from: &str,
+        to: &str,
+        limit: u32,
+    ) -> Result<Vec<GraphPath>, Error> {
+        let (aql, target_vars) =
+            k_shortest_paths_aql(&self.target, self.direction, self.weight.as_ref());
+        log::debug!(
+            "Retrieving the paths from {} to {} through AQL: `{}`",
+            from,
+            to,
+            aql
+        );
+        let aql_query = bind_target(AqlQuery::new(&aql), &target_vars)
+            .bind_var("from", from)
+            .bind_var("to", to)
+            .bind_var("limit", limit);
+        let paths = self.db_accessor.database().aql_query(aql_query).await?;
+        Ok(paths)
+    }
+
+    #[maybe_async::maybe_async]
+    async fn reachable_ids(
+        &self,
+        start: &str,
+        direction: GraphQueryDirection,
+    ) -> Result<Vec<String>, Error> {
+        let (traversal, target_vars) = traversal_aql(&self.target, direction, self.max_depth);
+        let aql = format!("{} RETURN v._id", traversal);
+        log::debug!(
+            "Retrieving vertices reachable from {} through AQL: `{}`",
+            start,
+            aql
+        );
+        let aql_query = bind_target(AqlQuery::new(&aql), &target_vars).bind_var("start", start);
+        self.collect_ids(aql_query).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn collect_ids(&self, aql_query: AqlQuery<'_>) -> Result<Vec<String>, Error> {
+        // The cursor batches are retrieved under a single request slot
+        let _permit = database_service::request_permit(self.db_accessor).await;
+        let database = self.db_accessor.database();
+        let mut cursor = database
+            .aql_query_batch::<String>(aql_query.batch_size(self.batch_size))
+            .await?;
+        let mut ids = std::mem::take(&mut cursor.result);
+        while cursor.more {
+            let id = match cursor.id {
+                Some(id) => id,
+                None => break,
+            };
+            cursor = database.aql_next_batch(&id).await?;
+            ids.append(&mut cursor.result);
+        }
+        Ok(ids)
+    }
+}
+
+/// Renders the traversed edges of `target`, along with their bind variables. The edge collection
+/// and graph names are bound instead of being rendered in the query.
+fn target_aql(target: &GraphTarget) -> (String, Vec<(String, Value)>) {
+    match target {
+        GraphTarget::EdgeCollections(collections) => {
+            let vars: Vec<(String, Value)> = collections
+                .iter()
+                .enumerate()
+                .map(|(i, collection)| (format!("@edge_{}", i), Value::from(collection.as_str())))
+                .collect();
+            let aql = vars
+                .iter()
+                .map(|(var, _)| format!("@{}", var))
+                .collect::<Vec<String>>()
+                .join(", ");
+            (aql, vars)
+        }
+        GraphTarget::NamedGraph(name) => (
+            String::from("GRAPH @graph"),
+            vec![(String::from("graph"), Value::from(name.as_str()))],
+        ),
+    }
+}
+
+/// Binds the `target_vars` rendered by [`target_aql`] to `aql_query`
+fn bind_target<'a>(
+    mut aql_query: AqlQuery<'a>,
+    target_vars: &'a [(String, Value)],
+) -> AqlQuery<'a> {
+    for (var, value) in target_vars {
+        aql_query = aql_query.bind_var(var, value.clone());
+    }
+    aql_query
+}
+
+/// Renders the traversal AQL from the `@start` vertex, with the `v` vertex variable, along with
+/// the bind variables of the traversed edges
+fn traversal_aql(
+    target: &GraphTarget,
+    direction: GraphQueryDirection,
+    max_depth: u16,
+) -> (String, Vec<(String, Value)>) {
+    let (target, target_vars) = target_aql(target);
+    let aql = format!(
+        r#"FOR v IN 1..{} {} @start {} OPTIONS {{ order: "bfs", uniqueVertices: "global" }}"#,
+        max_depth, direction, target
+    );
+    (aql, target_vars)
+}
+
+/// Renders the paths AQL from the `@from` vertex to the `@to` vertex, limited to `@limit` paths,
+/// along with the bind variables of the traversed edges
+fn k_shortest_paths_aql(
+    target: &GraphTarget,
+    direction: GraphQueryDirection,
+    weight: Option<&PathWeight>,
+) -> (String, Vec<(String, Value)>) {
+    let (target, target_vars) = target_aql(target);
+    let options = weight.map_or_else(String::new, |weight| {
+        format!(
+            " OPTIONS {{ weightAttribute: {}, defaultWeight: {} }}",
+            Value::from(weight.attribute.as_str()),
+            Value::from(weight.default_weight)
+        )
+    });
+    let aql = format!(
+        "FOR p IN {} K_SHORTEST_PATHS @from TO @to {}{} LIMIT @limit \
+        RETURN {{ vertices: p.vertices[*]._id, edges: p.edges[*]._id, weight: p.weight }}",
+        direction, target, options
+    );
+    (aql, target_vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traversal() {
+        let target = GraphTarget::EdgeCollections(vec!["Follows".to_string(), "Likes".to_string()]);
+        assert_eq!(
+            traversal_aql(&target, GraphQueryDirection::Outbound, 2),
+            (
+                r#"FOR v IN 1..2 OUTBOUND @start @@edge_0, @@edge_1 OPTIONS { order: "bfs", uniqueVertices: "global" }"#.to_string(),
+                vec![
+                    ("@edge_0".to_string(), Value::from("Follows")),
+                    ("@edge_1".to_string(), Value::from("Likes")),
+                ]
+            )
+        );
+        let target = GraphTarget::NamedGraph("Social".to_string());
+        assert_eq!(
+            traversal_aql(&target, GraphQueryDirection::Any, 100),
+            (
+                r#"FOR v IN 1..100 ANY @start GRAPH @graph OPTIONS { order: "bfs", uniqueVertices: "global" }"#.to_string(),
+                vec![("graph".to_string(), Value::from("Social"))]
+            )
+        );
+    }
+
+    #[test]
+    fn k_shortest_paths() {
+        let target = GraphTarget::EdgeCollections(vec!["Roads".to_string()]);
+        let weight = PathWeight {
+            attribute: "distance".to_string(),
+            default_weight: 1.5,
+        };
+        assert_eq!(
+            k_shortest_paths_aql(&target, GraphQueryDirection::Outbound, Some(&weight)),
+            (
+                "FOR p IN OUTBOUND K_SHORTEST_PATHS @from TO @to @@edge_0 \
+                OPTIONS { weightAttribute: \"distance\", defaultWeight: 1.5 } LIMIT @limit \
+                RETURN { vertices: p.vertices[*]._id, edges: p.edges[*]._id, weight: p.weight }"
+                    .to_string(),
+                vec![("@edge_0".to_string(), Value::from("Roads"))]
+            )
+        );
+        let target = GraphTarget::NamedGraph("Map".to_string());
+        assert_eq!(
+            k_shortest_paths_aql(&target, GraphQueryDirection::Any, None),
+            (
+                "FOR p IN ANY K_SHORTEST_PATHS @from TO @to GRAPH @graph LIMIT @limit \
+                RETURN { vertices: p.vertices[*]._id, edges: p.edges[*]._id, weight: p.weight }"
+                    .to_string(),
+                vec![("graph".to_string(), Value::from("Map"))]
+            )
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use serde_json::Value;
+
+use crate::{Error, Record};
+
+/// The step of a record operation triggering the hooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookEvent {
+    /// Before the record creation
+    BeforeCreate,
+    /// Before the record save
+    BeforeSave,
+    /// Before the record deletion
+    BeforeDelete,
+    /// After the record creation
+    AfterCreate,
+    /// After the record save
+    AfterSave,
+    /// After the record deletion
+    AfterDelete,
+}
+
+/// The context of a hook registered on a [`HookRegistry`]
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext<'a> {
+    /// The operation step
+    pub event: HookEvent,
+    /// The collection of the record
+    pub collection: &'a str,
+    /// The current tenant, see [`DatabaseAccess::tenant`]
+    ///
+    /// [`DatabaseAccess::tenant`]: crate::DatabaseAccess::tenant
+    pub tenant: Option<&'a str>,
+    /// The operation actor, see [`OperationOptions::actor`]
+    ///
+    /// [`OperationOptions::actor`]: crate::OperationOptions::actor
+    pub actor: Option<&'a Value>,
+}
+
+/// A hook receiving the serialized record
+type ErasedHook = Arc<dyn Fn(&mut Value, &HookContext<'_>) -> Result<(), Error> + Send + Sync>;
+
+/// The hooks of a record type, `None` for the hooks of every record
+type HookKey = (Option<&'static str>, HookEvent);
+
+/// Hooks registered at runtime on a [`DatabaseConnection`], called along with the hooks of the
+/// `Record` derive for cross-cutting concerns like metrics or auditing. See
+/// [`DatabaseConnection::on_hook`].
+///
+/// The registered hooks are called before the record hooks, in registration order, the hooks of
+/// every record first. They are skipped along with the record hooks by
+/// [`OperationOptions::ignore_hooks`].
+///
+/// The registry is cheap to clone, the clones share the registered hooks.
+///
+/// # Note
+///
+/// The record is passed to the hooks through its JSON representation: when hooks are registered
+/// for a record type, its records are serialized and deserialized back around the hook calls.
+///
+/// [`DatabaseConnection`]: crate::DatabaseConnection
+/// [`DatabaseConnection::on_hook`]: crate::DatabaseConnection::on_hook
+/// [`OperationOptions::ignore_hooks`]: crate::OperationOptions::ignore_hooks
+#[derive(Clone, Default)]
+pub struct HookRegistry {
+    hooks: Arc<RwLock<HashMap<HookKey, Vec<ErasedHook>>>>,
+}
+
+impl HookRegistry {
+    /// Instantiates a registry without hooks
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a `hook` of the `T` records, called on `event`
+    pub fn register<T, F>(&self, event: HookEvent, hook: F)
+    where
+        T: Record + 'static,
+        F: Fn(&mut T, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.insert(
+            (Some(std::any::type_name::<T>()), event),
+            Arc::new(move |value, context| {
+                let mut record: T = serde_json::from_value(value.take())?;
+                hook(&mut record, context)?;
+                *value = serde_json::to_value(&record)?;
+                Ok(())
+            }),
+        );
+    }
+
+    /// Registers a `hook` of every record, called on `event` with the serialized record
+    pub fn register_any<F>(&self, event: HookEvent, hook: F)
+    where
+        F: Fn(&mut Value, &HookContext<'_>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.insert((None, event), Arc::new(hook));
+    }
+
+    /// The number of registered hooks
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hooks
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Are there no registered hooks
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, key: HookKey, hook: ErasedHook) {
+        self.hooks
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key)
+            .or_default()
+            .push(hook);
+    }
+
+    /// Calls the hooks of `record` registered on `context.event`
+    pub(crate) fn run<T: Record>(
+        &self,
+        record: &mut T,
+        context: &HookContext<'_>,
+    ) -> Result<(), Error> {
+        let hooks: Vec<ErasedHook> = {
+            let hooks = self.hooks.read().unwrap_or_else(PoisonError::into_inner);
+            [None, Some(std::any::type_name::<T>())]
+                .iter()
+                .filter_map(|target| hooks.get(&(*target, context.event)))
+                .flatten()
+                .cloned()
+                .collect()
+        };
+        if hooks.is_empty() {
+            return Ok(());
+        }
+        let mut value = serde_json::to_value(&*record)?;
+        for hook in hooks {
+            hook(&mut value, context)?;
+        }
+        *record = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+impl Debug for HookRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookRegistry")
+            .field("hooks", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UndefinedRecord;
+    use serde_json::json;
+
+    #[test]
+    fn hooks_are_called_in_order() {
+        let registry = HookRegistry::new();
+        registry.register::<UndefinedRecord, _>(HookEvent::BeforeCreate, |doc, context| {
+            doc["tenant"] = json!(context.tenant);
+            doc["count"] = json!(doc["count"].as_u64().unwrap() * 10);
+            Ok(())
+        });
+        registry.register_any(HookEvent::BeforeCreate, |value, _| {
+            value["count"] = json!(1);
+            Ok(())
+        });
+        registry.register::<UndefinedRecord, _>(HookEvent::AfterCreate, |_, _| {
+            Err(Error::Forbidden(None))
+        });
+        assert_eq!(registry.len(), 3);
+        let mut doc = UndefinedRecord(json!({ "count": 5 }));
+        let mut context = HookContext {
+            event: HookEvent::BeforeCreate,
+            collection: UndefinedRecord::COLLECTION_NAME,
+            tenant: Some("acme"),
+            actor: None,
+        };
+        registry.clone().run(&mut doc, &context).unwrap();
+        assert_eq!(doc.0, json!({ "tenant": "acme", "count": 10 }));
+        context.event = HookEvent::AfterCreate;
+        assert!(registry.run(&mut doc, &context).is_err());
+        context.event = HookEvent::BeforeDelete;
+        registry.run(&mut doc, &context).unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// The type of a database collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionKind {
+    /// A document collection
+    Document,
+    /// An edge collection
+    Edge,
+}
+
+/// A database collection summary, see [`DatabaseConnection::collections`]
+///
+/// [`DatabaseConnection::collections`]: crate::DatabaseConnection::collections
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionSummary {
+    /// The collection name
+    pub name: String,
+    /// The collection type
+    pub kind: CollectionKind,
+    /// The number of documents of the collection
+    pub document_count: u64,
+}
+
+/// A database named graph summary, see [`DatabaseConnection::graphs`]
+///
+/// [`DatabaseConnection::graphs`]: crate::DatabaseConnection::graphs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphSummary {
+    /// The graph name
+    pub name: String,
+    /// The edge collections of the graph edge definitions
+    pub edge_collections: Vec<String>,
+    /// The vertex collections of the graph, including the orphan collections
+    pub vertex_collections: Vec<String>,
+    /// The total number of documents of the edge collections
+    pub edge_count: u64,
+    /// The total number of documents of the vertex collections
+    pub vertex_count: u64,
+}
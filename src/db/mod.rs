@@ -1,10 +1,25 @@
+pub mod attachments;
+pub mod change_set;
+pub mod collection_import;
+pub mod conflict_policy;
 pub mod database_access;
 pub mod database_collection;
 pub mod database_connection;
 pub mod database_connection_builder;
 pub mod database_record;
 mod database_record_dto;
-mod database_service;
+pub mod database_service;
+pub mod database_users;
+pub mod deserialization_mode;
+pub mod graph_ops;
+pub mod hook_registry;
+pub mod introspection;
 pub mod operation_options;
+pub mod operation_tracker;
+pub mod raw_document;
+pub mod request_limiter;
 /// The transaction module
 pub mod transaction;
+pub mod upsert_outcome;
+pub mod velocypack;
+pub mod versioned_record;
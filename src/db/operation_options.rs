@@ -1,12 +1,29 @@
 #![allow(clippy::option_if_let_else)]
-use arangors_lite::document::options::{InsertOptions, RemoveOptions, UpdateOptions};
+use crate::schema::CollectionSchema;
+use crate::{DatabaseRecord, Error, Record, RecordAction};
+use arangors_lite::document::options::{
+    InsertOptions, RemoveOptions, ReplaceOptions, UpdateOptions,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::time::Duration;
 
-#[derive(Clone, Debug)]
 /// Struct defining some options for database `write` operations (create, update, delete)
+///
+/// The options can be built with the chained setters or deserialized, from a configuration file
+/// for example. Missing fields use the default values:
+///
+/// ```rust
+/// # use aragog::OperationOptions;
+/// let options: OperationOptions = serde_yaml::from_str("wait_for_sync: true").unwrap();
+/// assert_eq!(options, OperationOptions::default().wait_for_sync(true));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct OperationOptions {
     /// Defines if aragog should wait for the operation to be written on disk
     ///
     /// If set on `true` the requests might be slower. By default, the collection behavior is picked
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_for_sync: Option<bool>,
     /// Defines if aragog should ignore the `ArangoDB` document revision system (`_rev` field)
     ///
@@ -17,6 +34,38 @@ pub struct OperationOptions {
     ///
     /// [`Record`]: crate::Record
     pub ignore_hooks: bool,
+    /// Defines an overall deadline for the database calls, failing with [`Error::Timeout`] when
+    /// exceeded. Also used as the AQL queries `maxRuntime`. By default, there is no deadline.
+    ///
+    /// The deadline requires a `tokio` runtime with the time driver enabled. With the `blocking`
+    /// feature there is no client side deadline: only AQL queries are bounded, through their
+    /// `maxRuntime`, and the document, collection and graph API calls may exceed the timeout.
+    ///
+    /// Serialized as a number of seconds.
+    ///
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_timeout",
+        deserialize_with = "deserialize_timeout"
+    )]
+    pub timeout: Option<Duration>,
+    /// The document of the actor performing the operation, checked by [`Record::authorize_write`]
+    /// before the write. By default, there is no actor and no check.
+    ///
+    /// Not serialized, see [`actor`].
+    ///
+    /// [`Record::authorize_write`]: crate::Record::authorize_write
+    /// [`actor`]: Self::actor
+    #[serde(skip)]
+    pub actor: Option<serde_json::Value>,
+    /// Defines if [`DatabaseRecord::link`] should check that both the `_from` and `_to` documents
+    /// exist before creating the edge, failing with [`Error::NotFound`] otherwise. By default set
+    /// to `false`
+    ///
+    /// [`DatabaseRecord::link`]: crate::DatabaseRecord::link
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    pub check_edge_endpoints: bool,
 }
 
 impl OperationOptions {
@@ -43,6 +92,85 @@ impl OperationOptions {
         self.ignore_hooks = value;
         self
     }
+
+    /// Sets the `timeout` value
+    #[inline]
+    #[must_use]
+    pub const fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
+    /// Sets the `check_edge_endpoints` value
+    #[inline]
+    #[must_use]
+    pub const fn check_edge_endpoints(mut self, value: bool) -> Self {
+        self.check_edge_endpoints = value;
+        self
+    }
+
+    /// Sets the `actor` performing the operations, every write will be authorized through
+    /// [`Record::authorize_write`].
+    ///
+    /// If `actor` can't be serialized, every authorization check will fail.
+    ///
+    /// [`Record::authorize_write`]: crate::Record::authorize_write
+    #[must_use]
+    pub fn actor<A: Record>(mut self, actor: &DatabaseRecord<A>) -> Self {
+        self.actor = Some(serde_json::to_value(actor).unwrap_or(serde_json::Value::Null));
+        self
+    }
+
+    /// Checks that the operation `actor`, if any, is allowed to perform `action` on `target`
+    pub(crate) fn authorize<T: Record>(
+        &self,
+        action: RecordAction,
+        target: Option<&DatabaseRecord<T>>,
+    ) -> Result<(), Error> {
+        self.actor
+            .as_ref()
+            .map_or(Ok(()), |actor| T::authorize_write(actor, action, target))
+    }
+
+    /// Checks that the options are compatible with the given collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::ValidationError`] listing every invalid option:
+    /// - `wait_for_sync` is set to `false` on a collection defined with `wait_for_sync` in its
+    ///   schema, as `ArangoDB` would silently ignore it
+    /// - `timeout` is zero, failing every operation
+    /// - `check_edge_endpoints` is enabled on a document collection, which has no edge endpoints
+    ///
+    /// The other options are valid for any collection.
+    ///
+    /// [`Error::ValidationError`]: crate::Error::ValidationError
+    pub fn validate_for_collection(&self, collection: &CollectionSchema) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        if self.wait_for_sync == Some(false) && collection.wait_for_sync == Some(true) {
+            errors.push(format!(
+                "wait_for_sync can't be disabled on collection {} which always waits for sync",
+                collection.name
+            ));
+        }
+        if self.timeout == Some(Duration::ZERO) {
+            errors.push(format!(
+                "timeout of collection {} operations can't be zero",
+                collection.name
+            ));
+        }
+        if self.check_edge_endpoints && !collection.is_edge_collection {
+            errors.push(format!(
+                "check_edge_endpoints can't be enabled on document collection {}",
+                collection.name
+            ));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(errors.join(", ")))
+        }
+    }
 }
 
 impl Default for OperationOptions {
@@ -51,10 +179,36 @@ impl Default for OperationOptions {
             wait_for_sync: None, // We keep it at None to use the collection value
             ignore_revs: true,
             ignore_hooks: false,
+            timeout: None,
+            actor: None,
+            check_edge_endpoints: false,
         }
     }
 }
 
+#[allow(clippy::ref_option, clippy::trivially_copy_pass_by_ref)]
+fn serialize_timeout<S: Serializer>(
+    timeout: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    timeout.map(|t| t.as_secs_f64()).serialize(serializer)
+}
+
+fn deserialize_timeout<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    let seconds: Option<f64> = Option::deserialize(deserializer)?;
+    seconds
+        .map(|s| {
+            if s.is_finite() && s >= 0.0 {
+                Ok(Duration::from_secs_f64(s))
+            } else {
+                Err(de::Error::custom("expected a positive number of seconds"))
+            }
+        })
+        .transpose()
+}
+
 impl From<OperationOptions> for InsertOptions {
     fn from(option: OperationOptions) -> Self {
         let builder = Self::builder()
@@ -69,12 +223,28 @@ impl From<OperationOptions> for InsertOptions {
     }
 }
 
-impl From<OperationOptions> for UpdateOptions {
+impl OperationOptions {
+    /// The update options, also returning the previous document state if `return_old` is set
+    pub(crate) fn update_options(&self, return_old: bool) -> UpdateOptions {
+        let builder = UpdateOptions::builder()
+            .keep_null(true)
+            .ignore_revs(self.ignore_revs)
+            .return_new(true) // TODO: allow customization on this option
+            .return_old(return_old)
+            .silent(false);
+        if let Some(value) = self.wait_for_sync {
+            builder.wait_for_sync(value).build()
+        } else {
+            builder.build()
+        }
+    }
+}
+
+impl From<OperationOptions> for ReplaceOptions {
     fn from(option: OperationOptions) -> Self {
         let builder = Self::builder()
-            .keep_null(true)
             .ignore_revs(option.ignore_revs)
-            .return_new(true) // TODO: allow customization on this option
+            .return_new(true)
             .return_old(false)
             .silent(false);
         if let Some(value) = option.wait_for_sync {
@@ -85,6 +255,12 @@ impl From<OperationOptions> for UpdateOptions {
     }
 }
 
+impl From<OperationOptions> for UpdateOptions {
+    fn from(option: OperationOptions) -> Self {
+        option.update_options(false)
+    }
+}
+
 impl From<OperationOptions> for RemoveOptions {
     fn from(option: OperationOptions) -> Self {
         let builder = Self::builder().return_old(false).silent(true); // On deletion we don't need meta data
@@ -95,3 +271,74 @@ impl From<OperationOptions> for RemoveOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialization() {
+        let options = OperationOptions::default().ignore_hooks(true);
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ignore_revs": true,
+                "ignore_hooks": true,
+                "check_edge_endpoints": false
+            })
+        );
+        let options: OperationOptions =
+            serde_json::from_value(serde_json::json!({ "wait_for_sync": false })).unwrap();
+        assert_eq!(options, OperationOptions::default().wait_for_sync(false));
+        assert!(
+            serde_json::from_value::<OperationOptions>(serde_json::json!({ "wait": true }))
+                .is_err()
+        );
+        let options: OperationOptions = serde_yaml::from_str("timeout: 2.5").unwrap();
+        assert_eq!(
+            options,
+            OperationOptions::default().timeout(Duration::from_millis(2500))
+        );
+        assert_eq!(
+            serde_json::to_value(&options).unwrap()["timeout"],
+            serde_json::json!(2.5)
+        );
+        assert!(serde_yaml::from_str::<OperationOptions>("timeout: -1").is_err());
+    }
+
+    #[test]
+    fn collection_validation() {
+        let collection = CollectionSchema::new("User", false, Some(true));
+        assert!(OperationOptions::default()
+            .validate_for_collection(&collection)
+            .is_ok());
+        assert!(OperationOptions::default()
+            .wait_for_sync(true)
+            .validate_for_collection(&collection)
+            .is_ok());
+        assert!(OperationOptions::default()
+            .wait_for_sync(false)
+            .validate_for_collection(&collection)
+            .is_err());
+        let collection = CollectionSchema::new("User", false, None);
+        assert!(OperationOptions::default()
+            .wait_for_sync(false)
+            .validate_for_collection(&collection)
+            .is_ok());
+        assert!(OperationOptions::default()
+            .timeout(Duration::ZERO)
+            .validate_for_collection(&collection)
+            .is_err());
+        assert!(OperationOptions::default()
+            .check_edge_endpoints(true)
+            .validate_for_collection(&collection)
+            .is_err());
+        let collection = CollectionSchema::new("ChildOf", true, None);
+        assert!(OperationOptions::default()
+            .check_edge_endpoints(true)
+            .timeout(Duration::from_secs(1))
+            .validate_for_collection(&collection)
+            .is_ok());
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use arangors_lite::Database;
+
+use crate::db::transaction::TransactionHandle;
+use crate::query::delete_cursor;
+use crate::Error;
+
+/// Interval between two checks of the in-flight operations during a shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Tracks the in-flight operations, the open transactions and the open cursors of a
+/// [`DatabaseConnection`] and its clones, see [`DatabaseConnection::shutdown`].
+///
+/// The tracker is cheap to clone, the clones share the tracked state.
+///
+/// [`DatabaseConnection`]: crate::DatabaseConnection
+/// [`DatabaseConnection::shutdown`]: crate::DatabaseConnection::shutdown
+#[derive(Debug, Clone, Default)]
+pub struct OperationTracker {
+    inner: Arc<TrackedState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedState {
+    in_flight: AtomicUsize,
+    transactions: Mutex<Vec<Weak<TransactionHandle>>>,
+    /// The open cursors identifiers and their database
+    cursors: Mutex<HashMap<String, Database>>,
+}
+
+/// An in-flight operation of an [`OperationTracker`], finished on drop
+#[derive(Debug)]
+pub struct OperationGuard<'a> {
+    tracker: &'a OperationTracker,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl OperationTracker {
+    /// Instantiates a tracker without operations
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of record operations and queries currently running
+    #[must_use]
+    pub fn in_flight_operations(&self) -> usize {
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// The number of transactions neither committed nor aborted
+    #[must_use]
+    pub fn open_transactions(&self) -> usize {
+        self.live_transactions()
+            .iter()
+            .filter(|handle| !handle.is_finished())
+            .count()
+    }
+
+    /// The number of server side cursors with batches left to fetch
+    #[must_use]
+    pub fn open_cursors(&self) -> usize {
+        lock(&self.inner.cursors).len()
+    }
+
+    pub(crate) fn start_operation(&self) -> OperationGuard<'_> {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        OperationGuard { tracker: self }
+    }
+
+    pub(crate) fn register_transaction(&self, handle: &Arc<TransactionHandle>) {
+        let mut transactions = lock(&self.inner.transactions);
+        transactions.retain(|handle| handle.strong_count() > 0);
+        transactions.push(Arc::downgrade(handle));
+    }
+
+    pub(crate) fn register_cursor(&self, id: &str, database: &Database) {
+        lock(&self.inner.cursors).insert(id.to_string(), database.clone());
+    }
+
+    /// Stops tracking the cursor, returns `false` if it was already closed by a shutdown
+    pub(crate) fn release_cursor(&self, id: &str) -> bool {
+        lock(&self.inner.cursors).remove(id).is_some()
+    }
+
+    fn live_transactions(&self) -> Vec<Arc<TransactionHandle>> {
+        lock(&self.inner.transactions)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    /// Waits for the in-flight operations for at most `timeout`, then aborts the open
+    /// transactions and removes the open cursors.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Timeout`] if operations are still running after `timeout`, the
+    /// transactions and cursors being closed anyway
+    #[maybe_async::maybe_async]
+    pub(crate) async fn shutdown(&self, timeout: Duration) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+        let mut drained = true;
+        while self.in_flight_operations() > 0 {
+            if Instant::now() >= deadline {
+                log::warn!(
+                    "Shutting down with {} operations still running",
+                    self.in_flight_operations()
+                );
+                drained = false;
+                break;
+            }
+            poll_interval().await;
+        }
+        let transactions = std::mem::take(&mut *lock(&self.inner.transactions));
+        for handle in transactions.iter().filter_map(Weak::upgrade) {
+            if handle.is_finished() {
+                continue;
+            }
+            log::debug!("Aborting transaction {} on shutdown", handle.id());
+            if let Err(error) = handle.abort().await {
+                log::warn!("Failed to abort transaction {}: {}", handle.id(), error);
+            }
+        }
+        let cursors = std::mem::take(&mut *lock(&self.inner.cursors));
+        for (id, database) in cursors {
+            delete_cursor(database, id).await;
+        }
+        if drained {
+            Ok(())
+        } else {
+            Err(Error::Timeout { duration: timeout })
+        }
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+async fn poll_interval() {
+    tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+}
+
+#[cfg(feature = "blocking")]
+fn poll_interval() {
+    std::thread::sleep(SHUTDOWN_POLL_INTERVAL);
+}
+
+impl Drop for OperationGuard<'_> {
+    fn drop(&mut self) {
+        self.tracker.inner.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(all(test, not(feature = "blocking")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn shutdown_waits_for_operations() {
+        let tracker = OperationTracker::new();
+        let operation = tracker.start_operation();
+        assert_eq!(tracker.in_flight_operations(), 1);
+        assert!(tracker
+            .shutdown(Duration::from_millis(20))
+            .await
+            .unwrap_err()
+            .is_timeout());
+        let (result, _) = tokio::join!(tracker.shutdown(Duration::from_secs(5)), async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(operation);
+        });
+        result.unwrap();
+        assert_eq!(tracker.in_flight_operations(), 0);
+        assert_eq!(tracker.open_transactions(), 0);
+        assert_eq!(tracker.open_cursors(), 0);
+    }
+}
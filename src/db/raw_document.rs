@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::value::RawValue;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{DatabaseRecord, Error};
+
+/// A stored document kept as raw JSON, retrieved through [`Record::find_borrowed`] or
+/// [`Record::get_borrowed`].
+///
+/// The document is deserialized on demand by [`record`], into records borrowing their strings
+/// from the raw JSON instead of allocating them. For large documents this avoids most of the
+/// copies of the usual retrievals, the `serde_json::Value` conversion included.
+///
+/// # Note
+///
+/// The borrowed records are not [`Record`] implementations: the document model migrations and the
+/// [`DeserializationMode`] don't apply.
+/// Only the strings without escape sequences can be borrowed as `&str`, use `Cow<str>` with the
+/// `#[serde(borrow)]` attribute for strings that may be escaped.
+///
+/// # Example
+///
+/// ```rust no_run
+/// # use aragog::{DatabaseConnection, Record};
+/// # use serde::{Deserialize, Serialize};
+/// # use std::borrow::Cow;
+/// #
+/// #[derive(Record, Clone, Serialize, Deserialize)]
+/// pub struct Article {
+///     pub title: String,
+///     pub body: String,
+/// }
+///
+/// // A read only view of `Article` borrowing from the raw document
+/// #[derive(Deserialize)]
+/// pub struct ArticleView<'a> {
+///     #[serde(borrow)]
+///     pub title: Cow<'a, str>,
+///     #[serde(borrow)]
+///     pub body: Cow<'a, str>,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+/// let document = Article::find_borrowed("123", &db_accessor).await.unwrap();
+/// let article = document.record::<ArticleView>().unwrap();
+/// println!("{}: {}", article.key(), article.record.title);
+/// # }
+/// ```
+///
+/// [`Record::find_borrowed`]: crate::Record::find_borrowed
+/// [`Record::get_borrowed`]: crate::Record::get_borrowed
+/// [`Record`]: crate::Record
+/// [`DeserializationMode`]: crate::DeserializationMode
+/// [`record`]: Self::record
+#[derive(Debug, Clone)]
+pub struct RawDocument(pub(crate) Box<RawValue>);
+
+impl RawDocument {
+    /// The raw JSON of the document
+    #[must_use]
+    #[inline]
+    pub fn json(&self) -> &str {
+        self.0.get()
+    }
+
+    /// Deserializes the document into a record borrowing from `self`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::UnprocessableEntity`] if the document doesn't match `T`
+    pub fn record<'a, T>(&'a self) -> Result<DatabaseRecord<T>, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        Ok(serde_json::from_str(self.0.get())?)
+    }
+}
+
+impl Display for RawDocument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.json())
+    }
+}
+
+impl Serialize for RawDocument {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[derive(Deserialize)]
+    struct View<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        description: Cow<'a, str>,
+    }
+
+    #[test]
+    fn records_borrow_from_the_document() {
+        let json = r#"{"_key":"1","_id":"Dish/1","_rev":"a","name":"Pizza","description":"A \"good\" one","price":10}"#;
+        let document = RawDocument(RawValue::from_string(json.to_string()).unwrap());
+        let record = document.record::<View>().unwrap();
+        assert_eq!(record.key(), "1");
+        assert_eq!(record.id(), "Dish/1");
+        assert_eq!(record.record.name, "Pizza");
+        // Borrowed from the raw document
+        assert!(document
+            .json()
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&record.record.name.as_ptr()));
+        assert_eq!(record.record.description, "A \"good\" one");
+        assert!(document.record::<DatabaseRecord<u32>>().is_err());
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(feature = "blocking")]
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+/// Limits the concurrent in-flight requests of a [`DatabaseConnection`], see
+/// [`DatabaseConnectionBuilder::with_max_concurrent_requests`].
+///
+/// The waiting requests are served in arrival order, so a burst of requests can't starve the
+/// requests issued before it.
+///
+/// [`DatabaseConnection`]: crate::DatabaseConnection
+/// [`DatabaseConnectionBuilder::with_max_concurrent_requests`]: crate::DatabaseConnectionBuilder::with_max_concurrent_requests
+#[derive(Debug)]
+pub struct RequestLimiter {
+    max_requests: usize,
+    #[cfg(not(feature = "blocking"))]
+    semaphore: tokio::sync::Semaphore,
+    #[cfg(feature = "blocking")]
+    queue: Mutex<TicketQueue>,
+    #[cfg(feature = "blocking")]
+    released: Condvar,
+}
+
+/// First come first served permits queue
+#[cfg(feature = "blocking")]
+#[derive(Debug)]
+struct TicketQueue {
+    available: usize,
+    next_ticket: u64,
+    serving: u64,
+}
+
+/// A request slot of a [`RequestLimiter`], released on drop
+#[derive(Debug)]
+pub struct RequestPermit<'a> {
+    #[cfg(not(feature = "blocking"))]
+    _permit: tokio::sync::SemaphorePermit<'a>,
+    #[cfg(feature = "blocking")]
+    limiter: &'a RequestLimiter,
+}
+
+impl RequestLimiter {
+    /// Instantiates a limiter allowing `max_requests` concurrent requests, at least one
+    #[must_use]
+    pub fn new(max_requests: usize) -> Self {
+        let max_requests = max_requests.max(1);
+        Self {
+            max_requests,
+            #[cfg(not(feature = "blocking"))]
+            semaphore: tokio::sync::Semaphore::new(max_requests),
+            #[cfg(feature = "blocking")]
+            queue: Mutex::new(TicketQueue {
+                available: max_requests,
+                next_ticket: 0,
+                serving: 0,
+            }),
+            #[cfg(feature = "blocking")]
+            released: Condvar::new(),
+        }
+    }
+
+    /// The maximum number of concurrent requests
+    #[must_use]
+    #[inline]
+    pub const fn max_requests(&self) -> usize {
+        self.max_requests
+    }
+
+    /// The number of requests which can start without waiting
+    #[must_use]
+    pub fn available_requests(&self) -> usize {
+        #[cfg(not(feature = "blocking"))]
+        return self.semaphore.available_permits();
+        #[cfg(feature = "blocking")]
+        return self.lock_queue().available;
+    }
+
+    /// Waits for a request slot, the slots being granted in arrival order
+    ///
+    /// # Panics
+    ///
+    /// Never, the inner semaphore is never closed
+    #[cfg(not(feature = "blocking"))]
+    pub async fn acquire(&self) -> RequestPermit<'_> {
+        let permit = self.semaphore.acquire().await.unwrap();
+        RequestPermit { _permit: permit }
+    }
+
+    /// Waits for a request slot, the slots being granted in arrival order
+    #[cfg(feature = "blocking")]
+    pub fn acquire(&self) -> RequestPermit<'_> {
+        let mut queue = self.lock_queue();
+        let ticket = queue.next_ticket;
+        queue.next_ticket += 1;
+        while queue.serving != ticket || queue.available == 0 {
+            queue = self
+                .released
+                .wait(queue)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        queue.serving += 1;
+        queue.available -= 1;
+        drop(queue);
+        // The next ticket may be served if slots remain
+        self.released.notify_all();
+        RequestPermit { limiter: self }
+    }
+
+    #[cfg(feature = "blocking")]
+    fn lock_queue(&self) -> MutexGuard<'_, TicketQueue> {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Drop for RequestPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.lock_queue().available += 1;
+        self.limiter.released.notify_all();
+    }
+}
+
+#[cfg(all(test, not(feature = "blocking")))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn requests_wait_for_a_slot() {
+        let limiter = RequestLimiter::new(2);
+        let first = limiter.acquire().await;
+        let _second = limiter.acquire().await;
+        assert_eq!(limiter.available_requests(), 0);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), limiter.acquire())
+                .await
+                .is_err()
+        );
+        drop(first);
+        assert_eq!(limiter.available_requests(), 1);
+        let _third = limiter.acquire().await;
+        assert_eq!(limiter.max_requests(), 2);
+        assert_eq!(RequestLimiter::new(0).max_requests(), 1);
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Waits for `tickets` tickets to be taken, the waiting requests being queued
+    fn wait_for_tickets(limiter: &RequestLimiter, tickets: u64) {
+        while limiter.lock_queue().next_ticket < tickets {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn requests_wait_for_a_slot_in_arrival_order() {
+        let limiter = Arc::new(RequestLimiter::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let first = limiter.acquire();
+        assert_eq!(limiter.available_requests(), 0);
+        let waiting: Vec<_> = (1..=3)
+            .map(|i| {
+                let (request_limiter, order) = (Arc::clone(&limiter), Arc::clone(&order));
+                let handle = thread::spawn(move || {
+                    let _permit = request_limiter.acquire();
+                    order.lock().unwrap().push(i);
+                    thread::sleep(Duration::from_millis(5));
+                });
+                wait_for_tickets(&limiter, i + 1);
+                handle
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(20));
+        assert!(order.lock().unwrap().is_empty());
+        drop(first);
+        for handle in waiting {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(limiter.available_requests(), 1);
+        assert_eq!(RequestLimiter::new(0).max_requests(), 1);
+    }
+}
@@ -1,7 +1,9 @@
 #[cfg(not(feature = "blocking"))]
 use std::future::Future;
 
-use arangors_lite::transaction::{Status, Transaction as TransactionLayer};
+use std::sync::Arc;
+
+pub(crate) use transaction_handle::TransactionHandle;
 
 pub use {
     transaction_builder::TransactionBuilder, transaction_connection::TransactionDatabaseConnection,
@@ -12,6 +14,7 @@ use crate::{DatabaseConnection, Error};
 
 mod transaction_builder;
 mod transaction_connection;
+mod transaction_handle;
 mod transaction_output;
 
 /// Struct representing a `ArangoDB` transaction.
@@ -40,7 +43,7 @@ mod transaction_output;
 ///     .build()
 ///     .await
 ///     .unwrap();
-/// # db_connection.truncate().await;
+/// # db_connection.unsafe_truncate_all().await.unwrap();
 /// // Build a transaction connection from the main database connection
 /// let transaction = Transaction::new(&db_connection).await.unwrap();
 /// // Safely execute document operations in the transaction, the transaction will be closed afterwards
@@ -76,16 +79,17 @@ mod transaction_output;
 /// [`DatabaseConnection`]: crate::DatabaseConnection
 #[derive(Debug)]
 pub struct Transaction {
-    accessor: TransactionLayer,
+    handle: Arc<TransactionHandle>,
     database_connection: TransactionDatabaseConnection,
 }
 
 impl Transaction {
-    /// Transaction unique identifier
+    /// Transaction unique identifier, the identifier changes on every
+    /// [`TransactionDatabaseConnection::checkpoint`]
     #[must_use]
     #[inline]
     pub fn id(&self) -> &str {
-        self.accessor.id()
+        self.handle.id()
     }
 
     /// Instantiates a new `Transaction` from a [`DatabaseConnection`] on all collections
@@ -152,14 +156,7 @@ impl Transaction {
     /// For a more practical and safer use, use the `safe_execute` method which allows multiple operations
     #[maybe_async::maybe_async]
     pub async fn commit(&self) -> Result<(), Error> {
-        let status = self.accessor.commit().await?;
-        log::debug!("Transaction committed with status: {:?}", status);
-        if !matches!(status, Status::Committed) {
-            let msg = format!("Unexpected {:?} transaction status after commit", status);
-            log::error!("{}", msg);
-            return Err(Error::InternalError { message: Some(msg) });
-        }
-        Ok(())
+        self.handle.commit().await
     }
 
     /// Tries to abort all operations from the transaction.
@@ -208,14 +205,7 @@ impl Transaction {
     /// For a more practical and safer use, use the `safe_execute` method which allows multiple operations
     #[maybe_async::maybe_async]
     pub async fn abort(&self) -> Result<(), Error> {
-        let status = self.accessor.abort().await?;
-        log::debug!("Transaction aborted with status: {:?}", status);
-        if !matches!(status, Status::Aborted) {
-            let msg = format!("Unexpected {:?} transaction status after abort", status);
-            log::error!("{}", msg);
-            return Err(Error::InternalError { message: Some(msg) });
-        }
-        Ok(())
+        self.handle.abort().await
     }
 
     /// Allows to run multiple operations using the transaction connection. If an operation fails or an `Err`
@@ -245,7 +235,7 @@ impl Transaction {
     ///     # .build()
     ///     # .await
     ///     # .unwrap();
-    /// # db_connection.truncate().await;
+    /// # db_connection.unsafe_truncate_all().await.unwrap();
     /// // Build a transaction connection from the main database connection
     /// let transaction = Transaction::new(&db_connection).await.unwrap();
     /// // Safely execute document operations in the transaction
@@ -267,6 +257,7 @@ impl Transaction {
     /// # Note
     ///
     /// Don't use `unwrap()` in the closure, as if the code panics the transaction won't be aborted nor commited.
+    /// Long batch jobs can commit intermediate operations with [`TransactionDatabaseConnection::checkpoint`].
     #[cfg(not(feature = "blocking"))]
     pub async fn safe_execute<T, O, F>(&self, operations: O) -> Result<TransactionOutput<T>, Error>
     where
@@ -309,7 +300,7 @@ impl Transaction {
     ///     # .build()
     ///     # .await
     ///     # .unwrap();
-    /// # db_connection.truncate().await;
+    /// # db_connection.unsafe_truncate_all().await.unwrap();
     /// // Build a transaction connection from the main database connection
     /// let transaction = Transaction::new(&db_connection).await.unwrap();
     /// // Safely execute document operations in the transaction
@@ -331,6 +322,7 @@ impl Transaction {
     /// # Note
     ///
     /// Don't use `unwrap()` in the closure, as if the code panics the transaction won't be aborted nor commited.
+    /// Long batch jobs can commit intermediate operations with [`TransactionDatabaseConnection::checkpoint`].
     #[cfg(feature = "blocking")]
     pub fn safe_execute<T, O>(&self, operations: O) -> Result<TransactionOutput<T>, Error>
     where
@@ -356,6 +348,11 @@ impl Transaction {
                 self.commit().await?;
                 Ok(TransactionOutput::Committed(value))
             }
+            // A failed checkpoint already committed the transaction, which can't be aborted
+            Err(err) if self.handle.is_finished() => {
+                log::debug!("Transaction failed with: {} after its last commit", err);
+                Err(err)
+            }
             Err(err) => {
                 log::debug!("Transaction failed with: {}. Aborting..", err);
                 self.abort().await?;
@@ -1,8 +1,8 @@
-use crate::db::database_collection::DatabaseCollection;
+use crate::db::transaction::transaction_handle::TransactionHandle;
 use crate::transaction::{Transaction, TransactionDatabaseConnection};
 use crate::{DatabaseAccess, DatabaseConnection, Error, OperationOptions};
-use arangors_lite::transaction::{TransactionCollections, TransactionSettings};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 const LOCK_TIMEOUT: usize = 60000;
 
@@ -48,12 +48,13 @@ impl TransactionBuilder {
     }
 
     /// Defines custom `write` operation options for this transaction.
-    /// By default the options set in the [`DatabaseConnection`] are used.
+    /// By default the options set in the [`DatabaseConnection`] are used, including the
+    /// collection specific options which are ignored if custom options are defined.
     ///
     /// [`DatabaseConnection`]: crate::DatabaseConnection
     #[must_use]
     #[inline]
-    pub const fn operation_options(mut self, options: OperationOptions) -> Self {
+    pub fn operation_options(mut self, options: OperationOptions) -> Self {
         self.operation_options = Some(options);
         self
     }
@@ -64,41 +65,44 @@ impl TransactionBuilder {
         let collection_names = self
             .collections
             .unwrap_or_else(|| db_connection.collections_names());
-        let accessor = db_connection
-            .database()
-            .begin_transaction(
-                TransactionSettings::builder()
-                    .lock_timeout(self.lock_timeout.unwrap_or(LOCK_TIMEOUT))
-                    .wait_for_sync(self.wait_for_sync.unwrap_or(false))
-                    .collections(
-                        TransactionCollections::builder()
-                            .write(collection_names)
-                            .build(),
-                    )
-                    .build(),
-            )
-            .await?;
-        log::trace!("Initialized ArangoDB transaction {}", accessor.id());
-        let mut collections = HashMap::new();
-        for collection in db_connection.collections() {
-            let inner_collection = collection.clone_with_transaction(accessor.id().clone())?;
-            collections.insert(
-                collection.name().to_string(),
-                DatabaseCollection::from(inner_collection),
-            );
-        }
-        //
+        let handle = TransactionHandle::begin(
+            db_connection.database(),
+            db_connection.database_collections().into_iter(),
+            collection_names,
+            self.wait_for_sync.unwrap_or(false),
+            self.lock_timeout.unwrap_or(LOCK_TIMEOUT),
+        )
+        .await?;
         log::trace!("Initialized Aragog transaction connection");
         let database = db_connection.database().clone();
-        let operation_options = self
-            .operation_options
-            .unwrap_or_else(|| db_connection.operation_options());
+        let (operation_options, collection_operation_options) = self.operation_options.map_or_else(
+            || {
+                (
+                    db_connection.operation_options(),
+                    db_connection.collections_operation_options().clone(),
+                )
+            },
+            |options| (options, HashMap::new()),
+        );
+        let handle = Arc::new(handle);
+        db_connection
+            .operation_tracker
+            .register_transaction(&handle);
         Ok(Transaction {
-            accessor,
+            handle: handle.clone(),
             database_connection: TransactionDatabaseConnection {
-                collections,
+                handle,
                 database,
                 operation_options,
+                collection_operation_options,
+                slow_query_threshold: db_connection.slow_query_threshold(),
+                deserialization_mode: db_connection.deserialization_mode(),
+                bind_literals: db_connection.bind_literals(),
+                request_limiter: db_connection.request_limiter.clone(),
+                operation_tracker: db_connection.operation_tracker.clone(),
+                hook_registry: db_connection.hook_registry.clone(),
+                tenant: db_connection.tenant().map(String::from),
+                counter_caches: db_connection.counter_caches.clone(),
             },
         })
     }
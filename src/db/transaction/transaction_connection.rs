@@ -1,18 +1,97 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
+use arangors_lite::transaction::Transaction as TransactionLayer;
 use arangors_lite::Database;
 
 use crate::db::database_collection::DatabaseCollection;
-use crate::{DatabaseAccess, OperationOptions};
+use crate::db::transaction::transaction_handle::TransactionHandle;
+use crate::{
+    CounterCache, DatabaseAccess, DeserializationMode, Error, HookRegistry, OperationOptions,
+    OperationTracker, RequestLimiter,
+};
 
 /// Struct equivalent to [`DatabaseConnection`] for transactional operations.
 ///
 /// [`DatabaseConnection`]: crate::DatabaseConnection
 #[derive(Debug, Clone)]
 pub struct TransactionDatabaseConnection {
-    pub(crate) collections: HashMap<String, DatabaseCollection>,
+    pub(crate) handle: Arc<TransactionHandle>,
     pub(crate) database: Database,
     pub(crate) operation_options: OperationOptions,
+    pub(crate) collection_operation_options: HashMap<String, OperationOptions>,
+    pub(crate) slow_query_threshold: Option<Duration>,
+    pub(crate) deserialization_mode: DeserializationMode,
+    pub(crate) bind_literals: bool,
+    pub(crate) request_limiter: Option<Arc<RequestLimiter>>,
+    pub(crate) operation_tracker: OperationTracker,
+    pub(crate) hook_registry: HookRegistry,
+    pub(crate) tenant: Option<String>,
+    pub(crate) counter_caches: Arc<Vec<(&'static str, CounterCache)>>,
+}
+
+impl TransactionDatabaseConnection {
+    /// Commits all operations done so far and transparently continues in a new `ArangoDB`
+    /// transaction with the same settings, allowing long batch jobs to avoid holding a huge
+    /// transaction open.
+    ///
+    /// The committed operations can no longer be aborted: if the `safe_execute` closure fails
+    /// afterwards, only the operations following the last checkpoint are aborted. Every clone of
+    /// the connection, like [`Transaction::database_connection`], continues with the new
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the current transaction can't be committed or if the new one can't begin.
+    /// In the latter case the committed operations are kept, the transaction is finished and
+    /// [`Error::CheckpointFailed`] is returned: `safe_execute` won't try to abort it.
+    ///
+    /// [`Error::CheckpointFailed`]: crate::Error::CheckpointFailed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::{DatabaseConnection, transaction::Transaction, Record, Validate, DatabaseRecord};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// #[derive(Debug, Clone, Record, Validate, Serialize, Deserialize)]
+    /// pub struct User {
+    ///     pub field1: String,
+    ///     pub field2: String
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder()
+    ///     # .with_schema_path("tests/schema.yaml")
+    ///     # .apply_schema()
+    ///     # .build()
+    ///     # .await
+    ///     # .unwrap();
+    /// let transaction = Transaction::new(&db_connection).await.unwrap();
+    /// transaction.safe_execute(|connection| async move {
+    ///     for i in 0..100 {
+    ///         let doc = User {
+    ///             field1: i.to_string(),
+    ///             field2: String::from("bar"),
+    ///         };
+    ///         DatabaseRecord::create(doc, &connection).await?;
+    ///         // Commit every 10 documents
+    ///         if i % 10 == 9 {
+    ///             connection.checkpoint().await?;
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Transaction::database_connection`]: crate::transaction::Transaction::database_connection
+    #[maybe_async::maybe_async]
+    pub async fn checkpoint(&self) -> Result<(), Error> {
+        self.handle.checkpoint(&self.database).await
+    }
 }
 
 impl DatabaseAccess for TransactionDatabaseConnection {
@@ -20,11 +99,54 @@ impl DatabaseAccess for TransactionDatabaseConnection {
         self.operation_options.clone()
     }
 
+    fn collection_operation_options(&self, collection: &str) -> OperationOptions {
+        self.collection_operation_options
+            .get(collection)
+            .cloned()
+            .unwrap_or_else(|| self.operation_options())
+    }
+
     fn collection(&self, collection: &str) -> Option<&DatabaseCollection> {
-        self.collections.get(collection)
+        self.handle.collection(collection)
     }
 
     fn database(&self) -> &Database {
         &self.database
     }
+
+    fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_threshold
+    }
+
+    fn deserialization_mode(&self) -> DeserializationMode {
+        self.deserialization_mode
+    }
+
+    fn bind_literals(&self) -> bool {
+        self.bind_literals
+    }
+
+    fn request_limiter(&self) -> Option<&Arc<RequestLimiter>> {
+        self.request_limiter.as_ref()
+    }
+
+    fn operation_tracker(&self) -> Option<&OperationTracker> {
+        Some(&self.operation_tracker)
+    }
+
+    fn hook_registry(&self) -> Option<&HookRegistry> {
+        Some(&self.hook_registry)
+    }
+
+    fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    fn counter_caches(&self) -> &[(&'static str, CounterCache)] {
+        &self.counter_caches
+    }
+
+    fn stream_transaction(&self) -> Option<&TransactionLayer> {
+        Some(self.handle.current())
+    }
 }
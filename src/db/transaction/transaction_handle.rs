@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use arangors_lite::transaction::{
+    Status, Transaction as TransactionLayer, TransactionCollections, TransactionSettings,
+};
+use arangors_lite::Database;
+use once_cell::sync::OnceCell;
+
+use crate::db::database_collection::DatabaseCollection;
+use crate::Error;
+
+/// An `ArangoDB` transaction begun by a [`TransactionHandle`], with the collections bound to it
+#[derive(Debug)]
+struct TransactionStep {
+    accessor: TransactionLayer,
+    collections: HashMap<String, DatabaseCollection>,
+    /// The transaction begun by the next checkpoint, set once
+    next: OnceCell<Box<Self>>,
+}
+
+impl TransactionStep {
+    fn new<'a>(
+        accessor: TransactionLayer,
+        collections: impl Iterator<Item = &'a DatabaseCollection>,
+    ) -> Result<Self, Error> {
+        let mut bound_collections = HashMap::new();
+        for collection in collections {
+            let inner_collection = collection.clone_with_transaction(accessor.id().clone())?;
+            bound_collections.insert(
+                collection.name().to_string(),
+                DatabaseCollection::from(inner_collection),
+            );
+        }
+        Ok(Self {
+            accessor,
+            collections: bound_collections,
+            next: OnceCell::new(),
+        })
+    }
+}
+
+/// The `ArangoDB` transactions shared by a [`Transaction`] and its connections. A checkpoint
+/// appends a new transaction, which all of them continue with.
+///
+/// The committed transactions are kept along with their collections until the handle is dropped,
+/// so the current transaction can be borrowed without locking.
+///
+/// [`Transaction`]: crate::transaction::Transaction
+#[derive(Debug)]
+pub struct TransactionHandle {
+    first: TransactionStep,
+    /// Whether the current transaction is committed or aborted
+    finished: AtomicBool,
+    collection_names: Vec<String>,
+    wait_for_sync: bool,
+    lock_timeout: usize,
+}
+
+impl TransactionHandle {
+    /// Begins an `ArangoDB` transaction with the given settings, binding `collections` to it
+    #[maybe_async::maybe_async]
+    pub async fn begin<'a>(
+        database: &Database,
+        collections: impl Iterator<Item = &'a DatabaseCollection>,
+        collection_names: Vec<String>,
+        wait_for_sync: bool,
+        lock_timeout: usize,
+    ) -> Result<Self, Error> {
+        let accessor = database
+            .begin_transaction(Self::settings(
+                &collection_names,
+                wait_for_sync,
+                lock_timeout,
+            ))
+            .await?;
+        log::trace!("Initialized ArangoDB transaction {}", accessor.id());
+        Ok(Self {
+            first: TransactionStep::new(accessor, collections)?,
+            finished: AtomicBool::new(false),
+            collection_names,
+            wait_for_sync,
+            lock_timeout,
+        })
+    }
+
+    fn settings(
+        collection_names: &[String],
+        wait_for_sync: bool,
+        lock_timeout: usize,
+    ) -> TransactionSettings {
+        TransactionSettings::builder()
+            .lock_timeout(lock_timeout)
+            .wait_for_sync(wait_for_sync)
+            .collections(
+                TransactionCollections::builder()
+                    .write(collection_names.to_vec())
+                    .build(),
+            )
+            .build()
+    }
+
+    fn current_step(&self) -> &TransactionStep {
+        let mut step = &self.first;
+        while let Some(next) = step.next.get() {
+            step = next;
+        }
+        step
+    }
+
+    /// The current `ArangoDB` transaction
+    pub fn current(&self) -> &TransactionLayer {
+        &self.current_step().accessor
+    }
+
+    pub fn id(&self) -> &str {
+        self.current().id()
+    }
+
+    /// The `collection` bound to the current transaction
+    pub fn collection(&self, collection: &str) -> Option<&DatabaseCollection> {
+        self.current_step().collections.get(collection)
+    }
+
+    /// Whether the current transaction is committed or aborted
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+
+    #[maybe_async::maybe_async]
+    pub async fn commit(&self) -> Result<(), Error> {
+        let status = self.current().commit().await?;
+        log::debug!("Transaction committed with status: {:?}", status);
+        if !matches!(status, Status::Committed) {
+            let msg = format!("Unexpected {:?} transaction status after commit", status);
+            log::error!("{}", msg);
+            return Err(Error::InternalError { message: Some(msg) });
+        }
+        self.finished.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[maybe_async::maybe_async]
+    pub async fn abort(&self) -> Result<(), Error> {
+        let status = self.current().abort().await?;
+        log::debug!("Transaction aborted with status: {:?}", status);
+        if !matches!(status, Status::Aborted) {
+            let msg = format!("Unexpected {:?} transaction status after abort", status);
+            log::error!("{}", msg);
+            return Err(Error::InternalError { message: Some(msg) });
+        }
+        self.finished.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Commits the current transaction and begins a new one with the same settings and
+    /// collections. If the new transaction fails to begin, the handle stays finished and an
+    /// [`Error::CheckpointFailed`] is returned.
+    #[maybe_async::maybe_async]
+    pub async fn checkpoint(&self, database: &Database) -> Result<(), Error> {
+        let step = self.current_step();
+        self.commit().await?;
+        let accessor = database
+            .begin_transaction(Self::settings(
+                &self.collection_names,
+                self.wait_for_sync,
+                self.lock_timeout,
+            ))
+            .await
+            .map_err(|error| Error::CheckpointFailed(Box::new(error.into())))?;
+        log::debug!(
+            "Transaction checkpoint, continuing with transaction {}",
+            accessor.id()
+        );
+        let next = match TransactionStep::new(accessor, step.collections.values()) {
+            Ok(next) => next,
+            Err(error) => return Err(Error::CheckpointFailed(Box::new(error))),
+        };
+        if let Err(next) = step.next.set(Box::new(next)) {
+            // A concurrent checkpoint already continued the committed transaction
+            next.accessor.abort().await?;
+            return Err(Error::InternalError {
+                message: Some(String::from("Concurrent transaction checkpoints")),
+            });
+        }
+        self.finished.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+}
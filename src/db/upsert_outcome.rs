@@ -0,0 +1,18 @@
+/// The write performed by [`DatabaseRecord::upsert_by`]
+///
+/// [`DatabaseRecord::upsert_by`]: crate::DatabaseRecord::upsert_by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// No document matched, the record was inserted
+    Inserted,
+    /// The matching document was replaced by the record
+    Updated,
+}
+
+impl UpsertOutcome {
+    /// Returns `true` if the record was inserted
+    #[must_use]
+    pub const fn is_inserted(self) -> bool {
+        matches!(self, Self::Inserted)
+    }
+}
@@ -0,0 +1,521 @@
+use arangors_lite::Database;
+use http::header::{HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use http::Method;
+use serde_json::{Map, Number, Value};
+
+use crate::Error;
+
+/// The `VelocyPack` media type
+pub const VELOCYPACK_CONTENT_TYPE: &str = "application/x-velocypack";
+
+/// The attribute names of the `VelocyPack` integer object keys
+const TRANSLATED_KEYS: [&str; 5] = ["_key", "_rev", "_id", "_from", "_to"];
+
+/// Retrieves the documents and the cursor batches of a [`DatabaseConnection`] as `VelocyPack`,
+/// more compact than JSON, see [`DatabaseConnectionBuilder::with_velocypack_transport`].
+///
+/// The requests accept the `application/x-velocypack` content type and the responses are decoded
+/// according to their content type, the JSON responses being still supported. The request bodies
+/// stay JSON.
+///
+/// [`DatabaseConnection`]: crate::DatabaseConnection
+/// [`DatabaseConnectionBuilder::with_velocypack_transport`]: crate::DatabaseConnectionBuilder::with_velocypack_transport
+#[derive(Clone)]
+pub struct VelocyPackTransport {
+    authorization: Option<HeaderValue>,
+}
+
+impl std::fmt::Debug for VelocyPackTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VelocyPackTransport")
+            .finish_non_exhaustive()
+    }
+}
+
+impl VelocyPackTransport {
+    /// Authenticates the requests with the session authorization of `database`, the basic
+    /// credentials or the JWT according to the connection [`AuthMode`].
+    ///
+    /// [`AuthMode`]: crate::AuthMode
+    pub(crate) fn new(database: &Database) -> Self {
+        let mut session = (*database.session()).clone();
+        Self {
+            authorization: session.headers().get(AUTHORIZATION).cloned(),
+        }
+    }
+
+    /// Sends the JSON `body` to the `path` API of `database`, and decodes the response
+    #[maybe_async::maybe_async]
+    pub(crate) async fn request(
+        &self,
+        database: &Database,
+        method: Method,
+        path: &str,
+        body: String,
+    ) -> Result<Value, Error> {
+        let internal_error = |e: &dyn std::fmt::Display| Error::InternalError {
+            message: Some(e.to_string()),
+        };
+        let url = database.url().join(path).map_err(|e| internal_error(&e))?;
+        let mut request = database
+            .session()
+            .client
+            .request(method, url.as_str())
+            .header(ACCEPT, VELOCYPACK_CONTENT_TYPE)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(authorization) = &self.authorization {
+            request = request.header(AUTHORIZATION, authorization.clone());
+        }
+        let response = request.send().await.map_err(|e| internal_error(&e))?;
+        let is_velocypack = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .map_or(false, |content_type| {
+                content_type
+                    .as_bytes()
+                    .starts_with(VELOCYPACK_CONTENT_TYPE.as_bytes())
+            });
+        let body = response.bytes().await.map_err(|e| internal_error(&e))?;
+        if is_velocypack {
+            from_slice(&body)
+        } else {
+            Ok(serde_json::from_slice(&body)?)
+        }
+    }
+}
+
+/// Decodes a `VelocyPack` value into JSON.
+///
+/// The binary, BCD, custom, `MinKey` and `MaxKey` types have no JSON equivalent and are not
+/// supported, the UTC dates are decoded as their milliseconds since the Unix epoch.
+///
+/// # Errors
+///
+/// Returns an [`Error::InternalError`] if `bytes` is not a valid or supported `VelocyPack` value
+pub fn from_slice(bytes: &[u8]) -> Result<Value, Error> {
+    let (value, _) = decode(bytes)?;
+    Ok(value)
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InternalError {
+        message: Some(format!("Invalid VelocyPack: {}", message)),
+    }
+}
+
+/// Reads the little endian unsigned integer of `width` bytes at `offset`
+fn read_u64(bytes: &[u8], offset: usize, width: usize) -> Result<u64, Error> {
+    let slice = offset
+        .checked_add(width)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| invalid("truncated value"))?;
+    Ok(slice
+        .iter()
+        .rev()
+        .fold(0_u64, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+/// Reads the little endian length or offset of `width` bytes at `offset`
+fn read_uint(bytes: &[u8], offset: usize, width: usize) -> Result<usize, Error> {
+    usize::try_from(read_u64(bytes, offset, width)?).map_err(|_| invalid("length overflow"))
+}
+
+/// Reads the variable length integer at `offset`, returning it along with its byte length
+fn read_varint(bytes: &[u8], offset: usize) -> Result<(usize, usize), Error> {
+    let mut value = 0_usize;
+    for (i, byte) in bytes.iter().skip(offset).enumerate().take(9) {
+        value |= usize::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(invalid("truncated length"))
+}
+
+/// Reads the variable length integer ending at the last byte of `bytes`, stored backwards
+fn read_reverse_varint(bytes: &[u8]) -> Result<usize, Error> {
+    let mut value = 0_usize;
+    for (i, byte) in bytes.iter().rev().enumerate().take(9) {
+        value |= usize::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid("truncated item count"))
+}
+
+/// Returns the `bytes` of the value of `length` bytes, or fails if they are truncated
+fn value_bytes(bytes: &[u8], length: usize) -> Result<&[u8], Error> {
+    bytes
+        .get(..length)
+        .ok_or_else(|| invalid("truncated value"))
+}
+
+/// Decodes the value at the start of `bytes`, returning it along with its byte length
+fn decode(bytes: &[u8]) -> Result<(Value, usize), Error> {
+    let head = *bytes.first().ok_or_else(|| invalid("empty value"))?;
+    let width = |base: u8| 1_usize << ((head - base) % 4);
+    Ok(match head {
+        0x01 => (Value::Array(Vec::new()), 1),
+        0x02..=0x05 => {
+            let width = width(0x02);
+            let length = read_uint(bytes, 1, width)?;
+            let bytes = value_bytes(bytes, length)?;
+            // The items may be preceded by padding zero bytes, 0x00 not being a valid value
+            let mut offset = 1 + width;
+            while bytes.get(offset) == Some(&0) {
+                offset += 1;
+            }
+            let mut items = Vec::new();
+            while offset < length {
+                let (item, size) = decode(&bytes[offset..])?;
+                items.push(item);
+                offset += size;
+            }
+            (Value::Array(items), length)
+        }
+        0x06..=0x09 => {
+            let (length, offsets) = index_table(bytes, head == 0x09, width(0x06))?;
+            let bytes = value_bytes(bytes, length)?;
+            let items = offsets
+                .into_iter()
+                .map(|offset| decode(bytes.get(offset..).unwrap_or_default()).map(|(item, _)| item))
+                .collect::<Result<_, _>>()?;
+            (Value::Array(items), length)
+        }
+        0x0a => (Value::Object(Map::new()), 1),
+        0x0b..=0x12 => {
+            let width = width(0x0b);
+            let (length, offsets) = index_table(bytes, width == 8, width)?;
+            let bytes = value_bytes(bytes, length)?;
+            let mut object = Map::new();
+            for offset in offsets {
+                let (key, value) = decode_entry(bytes.get(offset..).unwrap_or_default())?;
+                object.insert(key, value.0);
+            }
+            (Value::Object(object), length)
+        }
+        0x13 | 0x14 => decode_compact(bytes, head == 0x14)?,
+        0x18 => (Value::Null, 1),
+        0x19 => (Value::Bool(false), 1),
+        0x1a => (Value::Bool(true), 1),
+        0x1b => {
+            let bits = read_u64(bytes, 1, 8)?;
+            let number = Number::from_f64(f64::from_bits(bits)).map_or(Value::Null, Value::Number);
+            (number, 9)
+        }
+        0x1c => {
+            let millis = i64::from_le_bytes(read_u64(bytes, 1, 8)?.to_le_bytes());
+            (Value::from(millis), 9)
+        }
+        0x20..=0x27 => {
+            let width = usize::from(head - 0x1f);
+            let value = read_u64(bytes, 1, width)?;
+            // Sign extension of the `width` bytes value
+            let shift = 64 - 8 * (u32::from(head) - 0x1f);
+            let value = i64::from_le_bytes((value << shift).to_le_bytes()) >> shift;
+            (Value::from(value), 1 + width)
+        }
+        0x28..=0x2f => {
+            let width = usize::from(head - 0x27);
+            (Value::from(read_u64(bytes, 1, width)?), 1 + width)
+        }
+        0x30..=0x39 => (Value::from(head - 0x30), 1),
+        0x3a..=0x3f => (Value::from(i64::from(head) - 0x40), 1),
+        0x40..=0xbe => {
+            let length = usize::from(head - 0x40);
+            (decode_string(bytes, 1, length)?, 1 + length)
+        }
+        0xbf => {
+            let length = read_uint(bytes, 1, 8)?;
+            (decode_string(bytes, 9, length)?, 9 + length)
+        }
+        _ => return Err(invalid(&format!("unsupported type 0x{:02x}", head))),
+    })
+}
+
+/// Decodes the compact array or object at the start of `bytes`, returning it along with its byte
+/// length
+fn decode_compact(bytes: &[u8], is_object: bool) -> Result<(Value, usize), Error> {
+    let (length, length_size) = read_varint(bytes, 1)?;
+    let bytes = value_bytes(bytes, length)?;
+    let count = read_reverse_varint(bytes)?;
+    let mut offset = 1 + length_size;
+    let entries = (0..count).map(|_| {
+        let entry = bytes.get(offset..).unwrap_or_default();
+        let (key, (value, size)) = if is_object {
+            decode_entry(entry)?
+        } else {
+            (String::new(), decode(entry)?)
+        };
+        offset += size;
+        Ok((key, value))
+    });
+    let value = if is_object {
+        Value::Object(entries.collect::<Result<_, Error>>()?)
+    } else {
+        Value::Array(
+            entries
+                .map(|entry| entry.map(|(_, item)| item))
+                .collect::<Result<_, Error>>()?,
+        )
+    };
+    Ok((value, length))
+}
+
+/// Reads the byte length and the item offsets of an indexed array or object
+fn index_table(bytes: &[u8], long: bool, width: usize) -> Result<(usize, Vec<usize>), Error> {
+    let length = read_uint(bytes, 1, width)?;
+    // The item count follows the byte length, or ends the value for 8 bytes lengths
+    let (count, table_end) = if long {
+        let end = length.checked_sub(8).ok_or_else(|| invalid("bad length"))?;
+        (read_uint(bytes, end, 8)?, end)
+    } else {
+        (read_uint(bytes, 1 + width, width)?, length)
+    };
+    let table_start = count
+        .checked_mul(width)
+        .and_then(|size| table_end.checked_sub(size))
+        .ok_or_else(|| invalid("bad index table"))?;
+    let offsets = (0..count)
+        .map(|i| read_uint(bytes, table_start + i * width, width))
+        .collect::<Result<_, _>>()?;
+    Ok((length, offsets))
+}
+
+/// Decodes the object entry at the start of `bytes`, returning its key and its value along with
+/// the entry byte length
+fn decode_entry(bytes: &[u8]) -> Result<(String, (Value, usize)), Error> {
+    let (key, key_size) = decode(bytes)?;
+    let key = match key {
+        Value::String(key) => key,
+        Value::Number(number) => number
+            .as_u64()
+            .and_then(|index| TRANSLATED_KEYS.get(usize::try_from(index).ok()?.checked_sub(1)?))
+            .map(|key| (*key).to_string())
+            .ok_or_else(|| invalid("unknown attribute key"))?,
+        _ => return Err(invalid("non string attribute key")),
+    };
+    let (value, value_size) = decode(bytes.get(key_size..).unwrap_or_default())?;
+    Ok((key, (value, key_size + value_size)))
+}
+
+fn decode_string(bytes: &[u8], offset: usize, length: usize) -> Result<Value, Error> {
+    let slice = offset
+        .checked_add(length)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| invalid("truncated string"))?;
+    let string = std::str::from_utf8(slice).map_err(|_| invalid("non UTF-8 string"))?;
+    Ok(Value::from(string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn decodes_scalars() {
+        assert_eq!(from_slice(&[0x18]).unwrap(), Value::Null);
+        assert_eq!(from_slice(&[0x1a]).unwrap(), json!(true));
+        assert_eq!(from_slice(&[0x35]).unwrap(), json!(5));
+        assert_eq!(from_slice(&[0x3e]).unwrap(), json!(-2));
+        assert_eq!(from_slice(&[0x28, 0xff]).unwrap(), json!(255));
+        assert_eq!(from_slice(&[0x21, 0xd4, 0xfe]).unwrap(), json!(-300));
+        let mut double = vec![0x1b];
+        double.extend_from_slice(&1.5_f64.to_le_bytes());
+        assert_eq!(from_slice(&double).unwrap(), json!(1.5));
+        assert_eq!(from_slice(&[0x43, b'a', b'b', b'c']).unwrap(), json!("abc"));
+        let mut long_string = vec![0xbf, 3, 0, 0, 0, 0, 0, 0, 0];
+        long_string.extend_from_slice(b"xyz");
+        assert_eq!(from_slice(&long_string).unwrap(), json!("xyz"));
+    }
+
+    #[test]
+    fn decodes_arrays() {
+        assert_eq!(from_slice(&[0x01]).unwrap(), json!([]));
+        assert_eq!(
+            from_slice(&[0x02, 0x05, 0x31, 0x32, 0x33]).unwrap(),
+            json!([1, 2, 3])
+        );
+        assert_eq!(
+            from_slice(&[0x06, 0x0a, 0x02, 0x31, 0x43, b'a', b'b', b'c', 0x03, 0x04]).unwrap(),
+            json!([1, "abc"])
+        );
+        assert_eq!(
+            from_slice(&[0x13, 0x06, 0x31, 0x28, 0x10, 0x02]).unwrap(),
+            json!([1, 16])
+        );
+    }
+
+    #[test]
+    fn decodes_objects() {
+        assert_eq!(from_slice(&[0x0a]).unwrap(), json!({}));
+        let object = [
+            0x0b, 0x0c, 0x02, 0x41, b'a', 0x28, 0x0c, 0x41, b'b', 0x1a, 0x03, 0x07,
+        ];
+        assert_eq!(from_slice(&object).unwrap(), json!({ "a": 12, "b": true }));
+        let compact = [0x14, 0x08, 0x31, 0x43, b'a', b'b', b'c', 0x01];
+        assert_eq!(from_slice(&compact).unwrap(), json!({ "_key": "abc" }));
+    }
+
+    /// Appends the little endian `value` of `width` bytes to `bytes`
+    fn push_uint(bytes: &mut Vec<u8>, value: usize, width: usize) {
+        bytes.extend_from_slice(&value.to_le_bytes()[..width]);
+    }
+
+    /// Encodes the `items` as an indexed array or object of `head`, with `width` bytes lengths
+    fn indexed(head: u8, width: usize, items: &[&[u8]]) -> Vec<u8> {
+        let header_size = if width == 8 { 1 + width } else { 1 + 2 * width };
+        let items_size: usize = items.iter().map(|item| item.len()).sum();
+        let count_size = if width == 8 { 8 } else { 0 };
+        let length = header_size + items_size + items.len() * width + count_size;
+        let mut bytes = vec![head];
+        push_uint(&mut bytes, length, width);
+        if width != 8 {
+            push_uint(&mut bytes, items.len(), width);
+        }
+        let mut offsets = Vec::new();
+        for item in items {
+            offsets.push(bytes.len());
+            bytes.extend_from_slice(item);
+        }
+        for offset in offsets {
+            push_uint(&mut bytes, offset, width);
+        }
+        if width == 8 {
+            push_uint(&mut bytes, items.len(), 8);
+        }
+        bytes
+    }
+
+    #[test]
+    fn decodes_integers_of_every_width() {
+        for width in 1..=8_u8 {
+            let mut int = vec![0x1f + width];
+            int.extend(std::iter::repeat(0xff).take(usize::from(width)));
+            assert_eq!(from_slice(&int).unwrap(), json!(-1), "int width {}", width);
+            let mut uint = vec![0x27 + width, 0x2a];
+            uint.extend(std::iter::repeat(0).take(usize::from(width) - 1));
+            assert_eq!(
+                from_slice(&uint).unwrap(),
+                json!(42),
+                "uint width {}",
+                width
+            );
+        }
+        let mut max = vec![0x2f];
+        max.extend_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(from_slice(&max).unwrap(), json!(u64::MAX));
+        let mut min = vec![0x27];
+        min.extend_from_slice(&i64::MIN.to_le_bytes());
+        assert_eq!(from_slice(&min).unwrap(), json!(i64::MIN));
+        assert_eq!(from_slice(&[0x30]).unwrap(), json!(0));
+        assert_eq!(from_slice(&[0x39]).unwrap(), json!(9));
+        assert_eq!(from_slice(&[0x3a]).unwrap(), json!(-6));
+        assert_eq!(from_slice(&[0x3f]).unwrap(), json!(-1));
+    }
+
+    #[test]
+    fn decodes_doubles_and_dates() {
+        let mut double = vec![0x1b];
+        double.extend_from_slice(&(-0.25_f64).to_le_bytes());
+        assert_eq!(from_slice(&double).unwrap(), json!(-0.25));
+        let mut nan = vec![0x1b];
+        nan.extend_from_slice(&f64::NAN.to_le_bytes());
+        assert_eq!(from_slice(&nan).unwrap(), Value::Null);
+        let mut date = vec![0x1c];
+        date.extend_from_slice(&1_600_000_000_000_i64.to_le_bytes());
+        assert_eq!(from_slice(&date).unwrap(), json!(1_600_000_000_000_i64));
+        let mut before_epoch = vec![0x1c];
+        before_epoch.extend_from_slice(&(-1_000_i64).to_le_bytes());
+        assert_eq!(from_slice(&before_epoch).unwrap(), json!(-1_000));
+    }
+
+    #[test]
+    fn decodes_arrays_of_every_width() {
+        let items: [&[u8]; 3] = [&[0x31], &[0x41, b'a'], &[0x1a]];
+        for (i, width) in [1, 2, 4, 8].iter().enumerate() {
+            let head = u8::try_from(i).unwrap();
+            let mut plain = vec![0x02 + head];
+            push_uint(&mut plain, 1 + width + 4, *width);
+            plain.extend(items.concat());
+            assert_eq!(
+                from_slice(&plain).unwrap(),
+                json!([1, "a", true]),
+                "array width {}",
+                width
+            );
+            assert_eq!(
+                from_slice(&indexed(0x06 + head, *width, &items)).unwrap(),
+                json!([1, "a", true]),
+                "indexed array width {}",
+                width
+            );
+        }
+        // Padding zero bytes may precede the items
+        assert_eq!(
+            from_slice(&[0x03, 0x07, 0x00, 0x00, 0x00, 0x31, 0x32]).unwrap(),
+            json!([1, 2])
+        );
+    }
+
+    #[test]
+    fn decodes_objects_of_every_width() {
+        let entries: [&[u8]; 2] = [&[0x41, b'a', 0x31], &[0x32, 0x43, b'k', b'e', b'y']];
+        for (i, width) in [1, 2, 4, 8].iter().enumerate() {
+            let head = u8::try_from(i).unwrap();
+            for base in [0x0b, 0x0f] {
+                assert_eq!(
+                    from_slice(&indexed(base + head, *width, &entries)).unwrap(),
+                    json!({ "a": 1, "_rev": "key" }),
+                    "object 0x{:02x} width {}",
+                    base + head,
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_nested_compact_values() {
+        let inner = [0x13, 0x05, 0x31, 0x32, 0x02];
+        let mut object = vec![0x14, 0x00, 0x41, b'x'];
+        object.extend_from_slice(&inner);
+        object.extend_from_slice(&[0x41, b'y', 0x18, 0x02]);
+        object[1] = u8::try_from(object.len()).unwrap();
+        assert_eq!(
+            from_slice(&object).unwrap(),
+            json!({ "x": [1, 2], "y": null })
+        );
+        // A two bytes length, with more than 127 items
+        let mut array = vec![0x13, 0x00, 0x00];
+        array.extend(std::iter::repeat(0x31).take(200));
+        array.extend_from_slice(&[0x01, 0xc8]);
+        let length = array.len();
+        array[1] = u8::try_from(length & 0x7f).unwrap() | 0x80;
+        array[2] = u8::try_from(length >> 7).unwrap();
+        assert_eq!(from_slice(&array).unwrap(), json!(vec![1; 200]));
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        // Illegal, MinKey, MaxKey, binary, BCD and custom types
+        for head in [0x17, 0x1e, 0x1f, 0xc0, 0xc8, 0xd0, 0xd8, 0xf0] {
+            assert!(
+                from_slice(&[head, 0x01, 0x00]).is_err(),
+                "type 0x{:02x}",
+                head
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(from_slice(&[]).is_err());
+        assert!(from_slice(&[0x43, b'a']).is_err());
+        assert!(from_slice(&[0x06, 0x0a, 0x02]).is_err());
+        assert!(from_slice(&[0xc0, 0x01, 0x00]).is_err());
+    }
+}
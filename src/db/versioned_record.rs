@@ -0,0 +1,94 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{DatabaseRecord, Error, Record};
+
+/// The document field storing the [`Record::MODEL_VERSION`]
+pub const MODEL_VERSION_FIELD: &str = "_model_version";
+
+/// The model version to write in the documents, `None` for models without version
+pub fn written_model_version<T: Record>() -> Option<u32> {
+    (T::MODEL_VERSION > 0).then(|| T::MODEL_VERSION)
+}
+
+/// Deserializes a stored document without its system attributes, upgrading it with
+/// [`Record::migrate_from`] if its `_model_version` is older than the model one.
+pub fn record_from_value<T: Record>(mut value: Value) -> Result<T, Error> {
+    let version = match value
+        .as_object_mut()
+        .and_then(|object| object.remove(MODEL_VERSION_FIELD))
+    {
+        Some(version) => serde_json::from_value(version)?,
+        None => 0,
+    };
+    if version < T::MODEL_VERSION {
+        log::debug!(
+            "Migrating {} document from model version {} to {}",
+            T::COLLECTION_NAME,
+            version,
+            T::MODEL_VERSION
+        );
+        T::migrate_from(version, value)
+    } else {
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// A [`DatabaseRecord`] (de)serialized along with its model version: the `_model_version` field
+/// is written for versioned models and the older documents are upgraded on deserialization.
+#[derive(Debug, Clone)]
+pub struct VersionedRecord<T>(pub DatabaseRecord<T>);
+
+#[derive(Serialize)]
+struct VersionedRecordRef<'a, T> {
+    #[serde(flatten)]
+    record: &'a DatabaseRecord<T>,
+    #[serde(rename = "_model_version", skip_serializing_if = "Option::is_none")]
+    model_version: Option<u32>,
+}
+
+/// Serializes `record` along with its model version
+pub fn serialize_versioned<T, S>(
+    record: &DatabaseRecord<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Record,
+    S: Serializer,
+{
+    VersionedRecordRef {
+        record,
+        model_version: written_model_version::<T>(),
+    }
+    .serialize(serializer)
+}
+
+impl<T: Record> Serialize for VersionedRecord<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_versioned(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Record> Deserialize<'de> for VersionedRecord<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Models without version skip the intermediate JSON value
+        if T::MODEL_VERSION == 0 {
+            return DatabaseRecord::deserialize(deserializer).map(Self);
+        }
+        let document = DatabaseRecord::<Value>::deserialize(deserializer)?;
+        let record = record_from_value(document.record).map_err(D::Error::custom)?;
+        Ok(Self(DatabaseRecord {
+            key: document.key,
+            id: document.id,
+            rev: document.rev,
+            record,
+        }))
+    }
+}
+
+impl<T> From<VersionedRecord<T>> for DatabaseRecord<T> {
+    fn from(record: VersionedRecord<T>) -> Self {
+        record.0
+    }
+}
@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::util::{validate_document_id, validate_document_key};
+use crate::{DatabaseRecord, Error, Record};
+
+/// A validated `ArangoDB` document id (`_id`), like `Dish/123`.
+///
+/// Parse the ids received from the outside, in URLs for example, to get consistent validation and
+/// errors (see [`validate_document_id`]). The id is serialized as a string and its format is
+/// checked on deserialization.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::DocumentId;
+/// let id: DocumentId = "Dish/123".parse().unwrap();
+/// assert_eq!(id.collection(), "Dish");
+/// assert_eq!(id.key(), "123");
+/// assert_eq!(id, DocumentId::new("Dish", "123").unwrap());
+/// assert!("Dish/".parse::<DocumentId>().is_err());
+/// ```
+///
+/// [`validate_document_id`]: crate::util::validate_document_id
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DocumentId(String);
+
+impl DocumentId {
+    /// Builds a document id from its `collection` name and document `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if `key` or the built id is invalid
+    pub fn new(collection: &str, key: &str) -> Result<Self, Error> {
+        validate_document_key(key)?;
+        Self::parse(format!("{}/{}", collection, key))
+    }
+
+    /// Builds a document id from a `T` record `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if `key` is invalid
+    pub fn for_record<T: Record>(key: &str) -> Result<Self, Error> {
+        Self::new(T::COLLECTION_NAME, key)
+    }
+
+    /// Parses a `collection/key` document id
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if `id` is not a valid document id
+    pub fn parse(id: impl Into<String>) -> Result<Self, Error> {
+        let id = id.into();
+        validate_document_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// The document id as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The collection name of the document
+    #[must_use]
+    pub fn collection(&self) -> &str {
+        self.0.split('/').next().unwrap_or_default()
+    }
+
+    /// The key of the document
+    #[must_use]
+    pub fn key(&self) -> &str {
+        self.0.split('/').nth(1).unwrap_or_default()
+    }
+
+    /// The key of the document as a [`DocumentKey`]
+    #[must_use]
+    pub fn document_key(&self) -> DocumentKey {
+        DocumentKey::new_unchecked(self.key().to_string())
+    }
+
+    /// Returns `true` if the document is stored in the `T` collection
+    #[must_use]
+    pub fn is<T: Record>(&self) -> bool {
+        self.collection() == T::COLLECTION_NAME
+    }
+}
+
+impl<T> From<&DatabaseRecord<T>> for DocumentId {
+    fn from(record: &DatabaseRecord<T>) -> Self {
+        record.id().clone()
+    }
+}
+
+/// A validated `ArangoDB` document key (`_key`), like `123`.
+///
+/// Parse the keys received from the outside, in URLs for example, to get consistent validation
+/// and errors (see [`validate_document_key`]). The key is serialized as a string and its format is
+/// checked on deserialization.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::DocumentKey;
+/// let key: DocumentKey = "123".parse().unwrap();
+/// assert_eq!(key, "123");
+/// assert!("Dish/123".parse::<DocumentKey>().is_err());
+/// ```
+///
+/// [`validate_document_key`]: crate::util::validate_document_key
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DocumentKey(String);
+
+impl DocumentKey {
+    /// Parses a document key
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if `key` is not a valid document key
+    pub fn parse(key: impl Into<String>) -> Result<Self, Error> {
+        let key = key.into();
+        validate_document_key(&key)?;
+        Ok(Self(key))
+    }
+
+    /// The document key as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The document id of the key in the `T` collection
+    #[must_use]
+    pub fn id_for<T: Record>(&self) -> DocumentId {
+        DocumentId::new_unchecked(format!("{}/{}", T::COLLECTION_NAME, self.0))
+    }
+}
+
+impl<T> From<&DatabaseRecord<T>> for DocumentKey {
+    fn from(record: &DatabaseRecord<T>) -> Self {
+        record.key().clone()
+    }
+}
+
+/// Implements the string conversions and comparisons of the document identifier newtypes
+macro_rules! impl_string_newtype {
+    ($name:ident) => {
+        impl $name {
+            /// Wraps a value returned by the database, which is valid
+            pub(crate) const fn new_unchecked(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Self::parse(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::parse(value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                self == &other.0
+            }
+        }
+
+        impl PartialEq<$name> for str {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for &str {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+
+impl_string_newtype!(DocumentId);
+impl_string_newtype!(DocumentKey);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_serializes() {
+        let id = DocumentId::new("Dish", "123").unwrap();
+        assert_eq!(id.as_str(), "Dish/123");
+        assert!(DocumentId::new("Dish", "1/2").is_err());
+        let id: DocumentId = serde_json::from_str(r#""Dish/123""#).unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""Dish/123""#);
+        assert!(serde_json::from_str::<DocumentId>(r#""Dish""#).is_err());
+        assert_eq!(id.document_key(), "123");
+    }
+
+    #[test]
+    fn keys_compare_with_strings() {
+        let key = DocumentKey::parse("123").unwrap();
+        assert_eq!(key, "123");
+        assert_eq!(String::from("123"), key);
+        assert_eq!(key.len(), 3);
+        assert_eq!(
+            key.id_for::<crate::UndefinedRecord>(),
+            "Undefined Collection/123"
+        );
+        assert!(serde_json::from_str::<DocumentKey>(r#""1/2""#).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DatabaseAccess, DatabaseRecord, Error, Record, Validate};
+use crate::{util, DatabaseAccess, DatabaseRecord, DocumentId, Error, Record, Validate};
 use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 
@@ -15,10 +15,10 @@ use std::ops::{Deref, DerefMut};
 pub struct EdgeRecord<T> {
     /// The `_from` field of `ArangoDB` edge documents
     #[serde(rename(serialize = "_from", deserialize = "_from"))]
-    from: String,
+    from: DocumentId,
     /// The `to` field of `ArangoDB` edge documents
     #[serde(rename(serialize = "_to", deserialize = "_to"))]
-    to: String,
+    to: DocumentId,
     /// The main document data, must implement [`Record`].
     ///
     /// Note: The data is flattened on save, so you won't have any field named `data` in your database.
@@ -39,17 +39,89 @@ impl<T: Record> EdgeRecord<T> {
     ///
     /// # Errors
     ///
-    /// This function validates the format of the id fields which can result in an error.
-    pub fn new(id_from: String, id_to: String, data: T) -> Result<Self, Error> {
+    /// This function validates the format of the id fields which can result in an error, see
+    /// [`validate_document_id`].
+    ///
+    /// [`validate_document_id`]: crate::util::validate_document_id
+    pub fn new(
+        id_from: impl Into<String>,
+        id_to: impl Into<String>,
+        data: T,
+    ) -> Result<Self, Error> {
         let res = Self {
-            from: id_from,
-            to: id_to,
+            from: DocumentId::new_unchecked(id_from.into()),
+            to: DocumentId::new_unchecked(id_to.into()),
             data,
         };
         res.validate()?;
         Ok(res)
     }
 
+    /// Instantiates an Edge record from the document keys, the `_from` and `_to` ids are built with the
+    /// `A` and `B` collection names.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_from` - The **from** document `key`, of a `A` record
+    /// * `key_to` - The **to** document `key`, of a `B` record
+    /// * `data` - The main document data
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::{EdgeRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct Group {}
+    /// #[derive(Clone, Record, Serialize, Deserialize)]
+    /// struct MemberOf {}
+    ///
+    /// let edge = EdgeRecord::new_from_keys::<User, Group>("123", "456", MemberOf {}).unwrap();
+    /// assert_eq!(edge.id_from(), "User/123");
+    /// assert_eq!(edge.id_to(), "Group/456");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function validates the format of the built ids which can result in an error.
+    pub fn new_from_keys<A, B>(key_from: &str, key_to: &str, data: T) -> Result<Self, Error>
+    where
+        A: Record,
+        B: Record,
+    {
+        Self::new(
+            format!("{}/{}", A::COLLECTION_NAME, key_from),
+            format!("{}/{}", B::COLLECTION_NAME, key_to),
+            data,
+        )
+    }
+
+    /// Instantiates an Edge record linking the two given records
+    ///
+    /// # Arguments
+    ///
+    /// * `from_record` - The **from** document
+    /// * `to_record` - The **to** document
+    /// * `data` - The main document data
+    ///
+    /// # Errors
+    ///
+    /// This function validates the format of the id fields which can result in an error.
+    pub fn new_from_records<A, B>(
+        from_record: &DatabaseRecord<A>,
+        to_record: &DatabaseRecord<B>,
+        data: T,
+    ) -> Result<Self, Error>
+    where
+        A: Record,
+        B: Record,
+    {
+        Self::new(from_record.id().clone(), to_record.id().clone(), data)
+    }
+
     /// Retrieves the `from` document from the database
     #[maybe_async::maybe_async]
     pub async fn from_record<D, R>(&self, db_access: &D) -> Result<DatabaseRecord<R>, Error>
@@ -74,7 +146,7 @@ impl<T: Record> EdgeRecord<T> {
     #[allow(clippy::missing_const_for_fn)] // Can't be const in 1.56
     #[must_use]
     #[inline]
-    pub fn id_from(&self) -> &String {
+    pub fn id_from(&self) -> &DocumentId {
         &self.from
     }
 
@@ -82,7 +154,7 @@ impl<T: Record> EdgeRecord<T> {
     #[allow(clippy::missing_const_for_fn)] // Can't be const in 1.56
     #[must_use]
     #[inline]
-    pub fn id_to(&self) -> &String {
+    pub fn id_to(&self) -> &DocumentId {
         &self.to
     }
 
@@ -129,15 +201,12 @@ impl<T: Record> EdgeRecord<T> {
     fn validate_edge_fields(&self, errors: &mut Vec<String>) {
         let array = [("from", self.id_from()), ("to", self.id_to())];
         for (name, field) in array {
-            let vec: Vec<&str> = field.split('/').collect();
-            let [left, right]: [_; 2] = if let Ok(v) = vec.try_into() {
-                v
-            } else {
-                errors.push(format!(r#"{} "{}" is not a valid id"#, name, field));
-                continue;
-            };
-            Self::validate_min_len(name, left, 2, errors);
-            Self::validate_min_len(name, right, 2, errors);
+            if !util::is_document_id(field) {
+                errors.push(format!(
+                    r#"{} "{}" is not a valid document id"#,
+                    name, field
+                ));
+            }
         }
     }
 }
@@ -151,6 +220,17 @@ impl<T: Record> Validate for EdgeRecord<T> {
 #[maybe_async::maybe_async]
 impl<T: Record + Send> Record for EdgeRecord<T> {
     const COLLECTION_NAME: &'static str = T::COLLECTION_NAME;
+    const IS_EDGE_COLLECTION: bool = true;
+    const MODEL_VERSION: u32 = T::MODEL_VERSION;
+
+    fn migrate_from(version: u32, value: serde_json::Value) -> Result<Self, Error> {
+        let edge: EdgeRecord<serde_json::Value> = serde_json::from_value(value)?;
+        Ok(Self {
+            from: edge.from,
+            to: edge.to,
+            data: T::migrate_from(version, edge.data)?,
+        })
+    }
 
     async fn before_create_hook<D>(&mut self, db_accessor: &D) -> Result<(), Error>
     where
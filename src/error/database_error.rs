@@ -12,6 +12,60 @@ pub struct DatabaseError {
     pub arango_error: ArangoError,
     /// The error message
     pub message: String,
+    /// The document targeted by the failed operation, see [`Self::document`]
+    document: Option<String>,
+}
+
+impl DatabaseError {
+    /// Instantiates an error without targeted document
+    #[must_use]
+    pub const fn new(
+        http_error: ArangoHttpError,
+        arango_error: ArangoError,
+        message: String,
+    ) -> Self {
+        Self {
+            http_error,
+            arango_error,
+            message,
+            document: None,
+        }
+    }
+
+    /// The document targeted by the failed operation, if any.
+    ///
+    /// Formatted as a document `_id` (`collection/key`), or as the collection name if the document key is unknown.
+    #[must_use]
+    pub fn document(&self) -> Option<&str> {
+        self.document.as_deref()
+    }
+
+    /// Attaches the targeted document `collection` and `key` to the error
+    #[must_use]
+    pub fn with_document(mut self, collection: &str, key: Option<&str>) -> Self {
+        self.document = Some(key.map_or_else(
+            || collection.to_string(),
+            |key| format!("{}/{}", collection, key),
+        ));
+        self
+    }
+
+    /// The collection of the document targeted by the failed operation, if any
+    #[must_use]
+    pub fn collection_name(&self) -> Option<&str> {
+        self.document
+            .as_deref()
+            .and_then(|document| document.split('/').next())
+    }
+
+    /// The key of the document targeted by the failed operation, if any
+    #[must_use]
+    pub fn document_key(&self) -> Option<&str> {
+        self.document
+            .as_deref()
+            .and_then(|document| document.split_once('/'))
+            .map(|(_, key)| key)
+    }
 }
 
 impl Display for DatabaseError {
@@ -22,7 +76,11 @@ impl Display for DatabaseError {
                    error_num: {}\n\
                    message: {}",
             self.http_error, self.arango_error, self.message
-        )
+        )?;
+        if let Some(document) = &self.document {
+            write!(f, "\ndocument: {}", document)?;
+        }
+        Ok(())
     }
 }
 
@@ -30,10 +88,10 @@ impl Error for DatabaseError {}
 
 impl From<DriverError> for DatabaseError {
     fn from(error: DriverError) -> Self {
-        Self {
-            http_error: ArangoHttpError::from_code(error.code()),
-            arango_error: ArangoError::from_error_num(error.error_num()),
-            message: error.message().to_string(),
-        }
+        Self::new(
+            ArangoHttpError::from_code(error.code()),
+            ArangoError::from_error_num(error.error_num()),
+            error.message().to_string(),
+        )
     }
 }
@@ -0,0 +1,109 @@
+//! Conversions of [`Error`] into http framework error types, enabled by the `actix-web`, `axum` and
+//! `warp` features.
+//!
+//! The responses use [`Error::http_code`] as status code and a JSON body:
+//!
+//! ```json
+//! { "code": 400, "message": "Validations failed: `name 'a' is too short, min length: 3`" }
+//! ```
+//!
+//! [`Error`]: crate::Error
+//! [`Error::http_code`]: crate::Error::http_code
+use crate::Error;
+
+impl Error {
+    /// The JSON body of the http framework error responses
+    #[allow(dead_code)]
+    fn response_body(&self) -> serde_json::Value {
+        // Internal details and contexts are not exposed
+        let message = match self.root() {
+            Self::InternalError { .. }
+            | Self::ArangoError(_)
+            | Self::InitError { .. }
+            | Self::CheckpointFailed(_) => "Internal Error".to_string(),
+            error => error.to_string(),
+        };
+        serde_json::json!({
+            "code": self.http_code(),
+            "message": message,
+        })
+    }
+}
+
+#[cfg(feature = "actix-web")]
+impl actix_web::ResponseError for Error {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.http_code())
+            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::build(self.status_code()).json(self.response_body())
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for Error {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_code())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        (status, axum::Json(self.response_body())).into_response()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl From<Error> for axum::response::Response {
+    fn from(error: Error) -> Self {
+        axum::response::IntoResponse::into_response(error)
+    }
+}
+
+// `warp` provides the `From<Error>` implementation for `warp::Rejection`
+#[cfg(feature = "warp")]
+impl warp::reject::Reject for Error {}
+
+#[cfg(feature = "warp")]
+impl Error {
+    /// Builds the `warp` reply of the error, to be used when recovering from rejections
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// use warp::{Rejection, Reply};
+    ///
+    /// async fn recover(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    ///     match rejection.find::<aragog::Error>() {
+    ///         Some(error) => Ok(error.warp_reply()),
+    ///         None => Err(rejection),
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn warp_reply(&self) -> warp::reply::WithStatus<warp::reply::Json> {
+        let status = warp::http::StatusCode::from_u16(self.http_code())
+            .unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR);
+        warp::reply::with_status(warp::reply::json(&self.response_body()), status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_body() {
+        let body = Error::ValidationError("name is missing".to_string()).response_body();
+        assert_eq!(
+            body,
+            serde_json::json!({ "code": 400, "message": "Validations failed: `name is missing`" })
+        );
+        let body = Error::InternalError {
+            message: Some("secret details".to_string()),
+        }
+        .response_body();
+        assert_eq!(
+            body,
+            serde_json::json!({ "code": 500, "message": "Internal Error" })
+        );
+    }
+}
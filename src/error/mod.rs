@@ -1,4 +1,5 @@
 use arangors_lite::ClientError;
+use std::time::Duration;
 use thiserror::Error;
 pub use {
     arango_error::ArangoError, arango_http_error::ArangoHttpError, database_error::DatabaseError,
@@ -7,6 +8,7 @@ pub use {
 mod arango_error;
 mod arango_http_error;
 mod database_error;
+mod http_framework;
 
 /// Error enum used for the Arango ORM mapped as potential Http errors
 #[derive(Debug, Error)]
@@ -55,6 +57,22 @@ pub enum Error {
     /// Can be interpreted as a HTTP code `409` Conflict.
     #[error("Conflict")]
     Conflict(#[source] DatabaseError),
+    /// A unique constraint was violated (`ArangoDB` error `1210`), like a duplicate document key or
+    /// a duplicate value in a unique index.
+    ///
+    /// Can be interpreted as a HTTP code `409` Conflict.
+    #[error("Unique constraint violated")]
+    UniqueViolation {
+        /// The collection of the document, if known
+        collection: Option<String>,
+        /// The violated index name, if found in the database error message
+        index: Option<String>,
+        /// The key of the already existing conflicting document, if found in the database error message
+        conflicting_key: Option<String>,
+        /// The database source error
+        #[source]
+        source: Box<DatabaseError>,
+    },
     /// Failed to load config or initialize the app.
     ///
     /// Can be interpreted as a HTTP code `500` Internal Error.
@@ -73,6 +91,35 @@ pub enum Error {
     /// Can be interpreted as a HTTP code `403` forbidden.
     #[error("Forbidden")]
     Forbidden(#[source] Option<DatabaseError>),
+    /// The operation exceeded the `timeout` of its [`OperationOptions`].
+    /// Can be interpreted as a HTTP code `504` gateway timeout.
+    ///
+    /// [`OperationOptions`]: crate::OperationOptions
+    #[error("Operation timed out after {duration:?}")]
+    Timeout {
+        /// The exceeded timeout
+        duration: Duration,
+    },
+    /// A [`TransactionDatabaseConnection::checkpoint`] committed the previous operations but the
+    /// next transaction failed to begin: the transaction is finished and can't be aborted.
+    /// Can be interpreted as a HTTP code `500` Internal Error.
+    ///
+    /// [`TransactionDatabaseConnection::checkpoint`]: crate::transaction::TransactionDatabaseConnection::checkpoint
+    #[error("Transaction checkpoint failed after commit")]
+    CheckpointFailed(#[source] Box<Self>),
+    /// An error with additional context, see [`Error::context`].
+    ///
+    /// The HTTP code and predicates are the ones of the `source` error.
+    #[error("{context}")]
+    WithContext {
+        /// The context message
+        context: String,
+        /// The source error
+        #[source]
+        source: Box<Self>,
+        /// The backtrace captured when the context was added, with the `backtrace` feature
+        backtrace: Option<String>,
+    },
 }
 
 impl Error {
@@ -87,10 +134,201 @@ impl Error {
             Self::NotFound { .. } => 404,
             Self::Forbidden(_) => 403,
             Self::Unauthorized(_) => 401,
-            Self::ArangoError(_) | Self::InitError { .. } | Self::InternalError { .. } => 500,
-            Self::Conflict(_) => 409,
+            Self::ArangoError(_)
+            | Self::InitError { .. }
+            | Self::InternalError { .. }
+            | Self::CheckpointFailed(_) => 500,
+            Self::Conflict(_) | Self::UniqueViolation { .. } => 409,
+            Self::Timeout { .. } => 504,
+            Self::WithContext { source, .. } => source.http_code(),
+        }
+    }
+
+    /// Adds a context message to the error, useful to identify which call site produced the error.
+    ///
+    /// With the `backtrace` feature a backtrace is captured, according to the `RUST_BACKTRACE`
+    /// and `RUST_LIB_BACKTRACE` environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, Record, Error};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Debug, Clone, Record, Serialize, Deserialize)]
+    /// # pub struct User {
+    /// #     pub name: String,
+    /// # }
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Error> {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    /// let user = User::find("123", &db_connection)
+    ///     .await
+    ///     .map_err(|e| e.context("Failed to load the current user"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn context<S: Into<String>>(self, context: S) -> Self {
+        Self::WithContext {
+            context: context.into(),
+            source: Box::new(self),
+            backtrace: capture_backtrace(),
+        }
+    }
+
+    /// Retrieves the root error, without the context layers added with [`Error::context`]
+    #[must_use]
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::WithContext { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Retrieves the backtrace captured with the last added context, with the `backtrace` feature
+    #[must_use]
+    pub fn backtrace(&self) -> Option<&str> {
+        match self {
+            Self::WithContext { backtrace, .. } => backtrace.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the database error source, if any
+    #[must_use]
+    pub const fn database_error(&self) -> Option<&DatabaseError> {
+        match self {
+            Self::NotFound { source, .. } => source.as_ref(),
+            Self::ArangoError(error) | Self::Conflict(error) => Some(error),
+            Self::UniqueViolation { source, .. } => Some(source),
+            Self::WithContext { source, .. } | Self::CheckpointFailed(source) => {
+                source.database_error()
+            }
+            Self::Unauthorized(error) | Self::Forbidden(error) => error.as_ref(),
+            Self::InternalError { .. }
+            | Self::ValidationError(_)
+            | Self::UnprocessableEntity { .. }
+            | Self::InitError { .. }
+            | Self::Timeout { .. } => None,
         }
     }
+
+    /// Is the error a missing item error
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.root(), Self::NotFound { .. })
+            || self.database_error().map_or(false, |error| {
+                error.http_error == ArangoHttpError::NotFound
+                    || error.arango_error == ArangoError::ArangoDocumentNotFound
+            })
+    }
+
+    /// Is the error a database conflict, like a revision conflict or a unique constraint violation
+    #[must_use]
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self.root(),
+            Self::Conflict(_) | Self::UniqueViolation { .. }
+        )
+    }
+
+    /// Is the error a document revision conflict (`ArangoDB` error `1200` or HTTP `412`), raised
+    /// by a write checking the revisions on a document changed in the meantime
+    #[must_use]
+    pub fn is_revision_conflict(&self) -> bool {
+        self.database_error().map_or(false, |error| {
+            error.arango_error == ArangoError::ArangoConflict
+                || error.http_error == ArangoHttpError::PreconditionFailed
+        })
+    }
+
+    /// Is the error an operation timeout
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.root(), Self::Timeout { .. })
+    }
+
+    /// Is the error a unique constraint violation
+    #[must_use]
+    pub fn is_unique_violation(&self) -> bool {
+        matches!(self.root(), Self::UniqueViolation { .. })
+    }
+
+    /// Attaches the targeted document `collection` and `key` to the database error source, if any
+    #[must_use]
+    pub fn with_document(self, collection: &str, key: Option<&str>) -> Self {
+        let add = |error: DatabaseError| error.with_document(collection, key);
+        match self {
+            Self::NotFound { item, id, source } => Self::NotFound {
+                item,
+                id,
+                source: source.map(add),
+            },
+            Self::ArangoError(error) => Self::ArangoError(add(error)),
+            Self::Conflict(error) => Self::Conflict(add(error)),
+            Self::UniqueViolation {
+                index,
+                conflicting_key,
+                source,
+                ..
+            } => Self::UniqueViolation {
+                collection: Some(collection.to_string()),
+                index,
+                conflicting_key,
+                source: Box::new(add(*source)),
+            },
+            Self::Unauthorized(error) => Self::Unauthorized(error.map(add)),
+            Self::Forbidden(error) => Self::Forbidden(error.map(add)),
+            Self::WithContext {
+                context,
+                source,
+                backtrace,
+            } => Self::WithContext {
+                context,
+                source: Box::new(source.with_document(collection, key)),
+                backtrace,
+            },
+            error => error,
+        }
+    }
+
+    /// Builds a [`Error::UniqueViolation`] parsing the database error message, formatted like:
+    /// `unique constraint violated - in index primary of type primary over '_key'; conflicting key: CustomKey`
+    fn unique_violation(error: DatabaseError) -> Self {
+        let index = error
+            .message
+            .split_once("in index ")
+            .and_then(|(_, rest)| rest.split(' ').next())
+            .map(ToString::to_string);
+        let conflicting_key = error
+            .message
+            .split_once("conflicting key: ")
+            .map(|(_, key)| key.trim().to_string());
+        Self::UniqueViolation {
+            collection: error.collection_name().map(ToString::to_string),
+            index,
+            conflicting_key,
+            source: Box::new(error),
+        }
+    }
+}
+
+// The `backtrace` feature requires a more recent Rust version than the crate MSRV
+#[cfg(feature = "backtrace")]
+#[allow(clippy::incompatible_msrv)]
+fn capture_backtrace() -> Option<String> {
+    let backtrace = std::backtrace::Backtrace::capture();
+    match backtrace.status() {
+        std::backtrace::BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "backtrace"))]
+#[allow(clippy::missing_const_for_fn)]
+fn capture_backtrace() -> Option<String> {
+    None
 }
 
 impl From<ClientError> for Error {
@@ -102,6 +340,11 @@ impl From<ClientError> for Error {
                 match arango_error.http_error {
                     ArangoHttpError::Unauthorized => Self::Unauthorized(Some(arango_error)),
                     ArangoHttpError::Forbidden => Self::Forbidden(Some(arango_error)),
+                    _ if arango_error.arango_error
+                        == ArangoError::ArangoUniqueConstraintViolated =>
+                    {
+                        Self::unique_violation(arango_error)
+                    }
                     ArangoHttpError::Conflict => Self::Conflict(arango_error),
                     _ => Self::ArangoError(arango_error),
                 }
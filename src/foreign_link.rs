@@ -38,11 +38,11 @@ use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
 /// #    .build()
 /// #    .await
 /// #    .unwrap();
-/// # database_connection.truncate().await;
+/// # database_connection.unsafe_truncate_all().await.unwrap();
 /// let user = DatabaseRecord::create(User {}, &database_connection).await.unwrap();
 /// let order = Order {
 ///     content: "content".to_string(),
-///     user_id: user.key().clone()
+///     user_id: user.key().to_string()
 /// };
 /// let linked_user = order.linked_model(&database_connection).await.unwrap();
 /// assert_eq!(user.id(), linked_user.id());
@@ -80,7 +80,7 @@ pub trait ForeignLink<T: Record> {
     async fn linked_model<D>(&self, db_access: &D) -> Result<DatabaseRecord<T>, Error>
     where
         Self: Sized,
-        T: 'async_trait,
+        T: Send + 'async_trait,
         D: DatabaseAccess + ?Sized,
     {
         DatabaseRecord::find(self.foreign_key(), db_access).await
@@ -0,0 +1,363 @@
+use arangors_lite::AqlQuery;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::query::utils::is_attribute_path;
+use crate::schema::DatabaseSchema;
+use crate::{DatabaseAccess, Error, ForeignLink, Record};
+
+/// A foreign key field to check, see [`check_with_foreign_keys`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForeignKey {
+    /// The collection of the documents holding the foreign key
+    pub collection: String,
+    /// The attribute path of the foreign key, like `user_id`
+    pub field: String,
+    /// The collection of the linked documents, whose keys are stored in `field`
+    pub target_collection: String,
+}
+
+impl ForeignKey {
+    /// Builds the foreign key of the [`ForeignLink`] from `T` to `L`, stored in `field`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::{integrity::ForeignKey, ForeignLink, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {}
+    ///
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct Order {
+    ///     pub user_id: String,
+    /// }
+    ///
+    /// impl ForeignLink<User> for Order {
+    ///     fn foreign_key(&self) -> &str {
+    ///         &self.user_id
+    ///     }
+    /// }
+    ///
+    /// let foreign_key = ForeignKey::new::<Order, User>("user_id");
+    /// assert_eq!(foreign_key.target_collection, "User");
+    /// ```
+    #[must_use]
+    pub fn new<T, L>(field: &str) -> Self
+    where
+        T: Record + ForeignLink<L>,
+        L: Record,
+    {
+        Self {
+            collection: T::COLLECTION_NAME.to_string(),
+            field: field.to_string(),
+            target_collection: L::COLLECTION_NAME.to_string(),
+        }
+    }
+}
+
+/// An edge whose `_from` or `_to` vertex is missing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DanglingEdge {
+    /// The edge collection
+    pub collection: String,
+    /// The edge `_id`
+    pub id: String,
+    /// The edge `_from` vertex id
+    pub from: String,
+    /// The edge `_to` vertex id
+    pub to: String,
+    /// Is the `_from` vertex missing
+    pub missing_from: bool,
+    /// Is the `_to` vertex missing
+    pub missing_to: bool,
+}
+
+/// A document whose foreign key points to a missing document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// The checked foreign key
+    pub foreign_key: ForeignKey,
+    /// The `_id` of the document holding the foreign key
+    pub id: String,
+    /// The key of the missing document
+    pub key: Value,
+}
+
+/// A repair action of an [`IntegrityReport`], see [`repair`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RepairAction {
+    /// Removes the dangling edge
+    RemoveEdge {
+        /// The edge collection
+        collection: String,
+        /// The edge `_id`
+        id: String,
+    },
+    /// Removes the broken foreign key attribute from the document
+    UnsetField {
+        /// The document collection
+        collection: String,
+        /// The document `_id`
+        id: String,
+        /// The attribute path of the foreign key
+        field: String,
+    },
+}
+
+/// The relation integrity issues found by [`check`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// The edges with a missing `_from` or `_to` vertex
+    pub dangling_edges: Vec<DanglingEdge>,
+    /// The foreign keys pointing to missing documents
+    pub broken_links: Vec<BrokenLink>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no issue was found
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.dangling_edges.is_empty() && self.broken_links.is_empty()
+    }
+
+    /// The actions fixing the reported issues: the dangling edges are removed and the broken
+    /// foreign keys unset. Review them before calling [`repair`], as removing data can't be
+    /// undone.
+    #[must_use]
+    pub fn repair_actions(&self) -> Vec<RepairAction> {
+        let edges = self
+            .dangling_edges
+            .iter()
+            .map(|edge| RepairAction::RemoveEdge {
+                collection: edge.collection.clone(),
+                id: edge.id.clone(),
+            });
+        let links = self
+            .broken_links
+            .iter()
+            .map(|link| RepairAction::UnsetField {
+                collection: link.foreign_key.collection.clone(),
+                id: link.id.clone(),
+                field: link.foreign_key.field.clone(),
+            });
+        edges.chain(links).collect()
+    }
+}
+
+/// Scans the edge collections of `schema` for edges whose `_from` or `_to` vertex is missing.
+///
+/// Every edge document is read, run the check during low traffic on large collections.
+///
+/// # Example
+///
+/// ```rust no_run
+/// # use aragog::{integrity, schema::DatabaseSchema, DatabaseConnection};
+/// # #[tokio::main]
+/// # async fn main() {
+/// let db_connection = DatabaseConnection::builder().build().await.unwrap();
+/// let schema = DatabaseSchema::load("./src/config/db/schema.yaml").unwrap();
+/// let report = integrity::check(&db_connection, &schema).await.unwrap();
+/// for edge in &report.dangling_edges {
+///     println!("{} links missing documents", edge.id);
+/// }
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Will fail on database request failure
+#[maybe_async::maybe_async]
+pub async fn check<D>(db_accessor: &D, schema: &DatabaseSchema) -> Result<IntegrityReport, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    check_with_foreign_keys(db_accessor, schema, &[]).await
+}
+
+/// Scans the edge collections of `schema` like [`check`], and the `foreign_keys` fields for keys
+/// of missing documents. The documents without foreign key value are ignored.
+///
+/// # Errors
+///
+/// Returns an [`Error::ValidationError`] if a foreign key field isn't a valid attribute path, and
+/// will fail on database request failure
+#[maybe_async::maybe_async]
+pub async fn check_with_foreign_keys<D>(
+    db_accessor: &D,
+    schema: &DatabaseSchema,
+    foreign_keys: &[ForeignKey],
+) -> Result<IntegrityReport, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let mut report = IntegrityReport::default();
+    for collection in schema.collections.iter().filter(|c| c.is_edge_collection) {
+        log::debug!("Checking {} edges integrity", collection.name);
+        let aql = "FOR edge IN @@collection \
+            LET missing_from = DOCUMENT(edge._from) == null \
+            LET missing_to = DOCUMENT(edge._to) == null \
+            FILTER missing_from OR missing_to \
+            RETURN { collection: @name, id: edge._id, from: edge._from, to: edge._to, \
+            missing_from, missing_to }";
+        let query = AqlQuery::new(aql)
+            .bind_var("@collection", collection.name.as_str())
+            .bind_var("name", collection.name.as_str());
+        let edges: Vec<DanglingEdge> = db_accessor.database().aql_query(query).await?;
+        report.dangling_edges.extend(edges);
+    }
+    for foreign_key in foreign_keys {
+        log::debug!(
+            "Checking {}.{} foreign keys integrity",
+            foreign_key.collection,
+            foreign_key.field
+        );
+        if !is_attribute_path(&foreign_key.field) {
+            return Err(Error::ValidationError(format!(
+                "{} is not a valid attribute path",
+                foreign_key.field
+            )));
+        }
+        let aql = format!(
+            "FOR doc IN @@collection \
+            FILTER doc.{field} != null \
+            FILTER DOCUMENT(@target, doc.{field}) == null \
+            RETURN {{ id: doc._id, key: doc.{field} }}",
+            field = foreign_key.field
+        );
+        let query = AqlQuery::new(&aql)
+            .bind_var("@collection", foreign_key.collection.as_str())
+            .bind_var("target", foreign_key.target_collection.as_str());
+        let links: Vec<BrokenLinkDocument> = db_accessor.database().aql_query(query).await?;
+        report
+            .broken_links
+            .extend(links.into_iter().map(|link| BrokenLink {
+                foreign_key: foreign_key.clone(),
+                id: link.id,
+                key: link.key,
+            }));
+    }
+    Ok(report)
+}
+
+/// Applies the `actions`, usually the [`IntegrityReport::repair_actions`], and returns the number
+/// of modified documents. The documents already removed are skipped.
+///
+/// # Errors
+///
+/// Returns an [`Error::ValidationError`] if a field isn't a valid attribute path, and will fail
+/// on database request failure. The previous actions stay applied.
+#[maybe_async::maybe_async]
+pub async fn repair<D>(db_accessor: &D, actions: &[RepairAction]) -> Result<usize, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    let mut count = 0;
+    for action in actions {
+        log::debug!("Applying integrity repair {:?}", action);
+        let query = match action {
+            RepairAction::RemoveEdge { collection, id } => AqlQuery::new(
+                "REMOVE PARSE_IDENTIFIER(@id).key IN @@collection \
+                OPTIONS { ignoreErrors: true } RETURN OLD._id",
+            )
+            .bind_var("id", id.as_str())
+            .bind_var("@collection", collection.as_str()),
+            RepairAction::UnsetField {
+                collection,
+                id,
+                field,
+            } => {
+                if !is_attribute_path(field) {
+                    return Err(Error::ValidationError(format!(
+                        "{} is not a valid attribute path",
+                        field
+                    )));
+                }
+                AqlQuery::new(
+                    "UPDATE PARSE_IDENTIFIER(@id).key WITH @patch IN @@collection \
+                    OPTIONS { keepNull: false, ignoreErrors: true } RETURN NEW._id",
+                )
+                .bind_var("id", id.as_str())
+                .bind_var("patch", null_patch(field))
+                .bind_var("@collection", collection.as_str())
+            }
+        };
+        let ids: Vec<Value> = db_accessor.database().aql_query(query).await?;
+        count += ids.iter().filter(|id| !id.is_null()).count();
+    }
+    Ok(count)
+}
+
+#[derive(Deserialize)]
+struct BrokenLinkDocument {
+    id: String,
+    key: Value,
+}
+
+/// Builds the `UPDATE` patch setting the `field` attribute path to `null`
+fn null_patch(field: &str) -> Value {
+    field.rsplit('.').fold(Value::Null, |value, attribute| {
+        let mut object = Map::new();
+        object.insert(attribute.to_string(), value);
+        Value::Object(object)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_patch_is_nested() {
+        assert_eq!(
+            null_patch("user_id"),
+            serde_json::json!({ "user_id": null })
+        );
+        assert_eq!(
+            null_patch("owner.user_id"),
+            serde_json::json!({ "owner": { "user_id": null } })
+        );
+    }
+
+    #[test]
+    fn repair_actions_cover_the_report() {
+        let foreign_key = ForeignKey {
+            collection: String::from("Order"),
+            field: String::from("user_id"),
+            target_collection: String::from("User"),
+        };
+        let report = IntegrityReport {
+            dangling_edges: vec![DanglingEdge {
+                collection: String::from("PartOf"),
+                id: String::from("PartOf/1"),
+                from: String::from("Dish/1"),
+                to: String::from("Menu/1"),
+                missing_from: false,
+                missing_to: true,
+            }],
+            broken_links: vec![BrokenLink {
+                foreign_key,
+                id: String::from("Order/1"),
+                key: Value::from("123"),
+            }],
+        };
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.repair_actions(),
+            vec![
+                RepairAction::RemoveEdge {
+                    collection: String::from("PartOf"),
+                    id: String::from("PartOf/1"),
+                },
+                RepairAction::UnsetField {
+                    collection: String::from("Order"),
+                    id: String::from("Order/1"),
+                    field: String::from("user_id"),
+                },
+            ]
+        );
+        assert!(IntegrityReport::default().is_clean());
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use serde_json::{json, Map, Value};
+
+use crate::Record;
+
+/// Describes the serialized form of a type as a [JSON Schema](https://json-schema.org).
+///
+/// Can be derived for structs with named fields, newtype structs and enums with unit variants.
+/// The derive follows the `serde` `rename`, `rename_all`, `skip`, `default`, `skip_serializing_if`
+/// and `flatten` attributes. Fields of foreign types can use the schema of another type:
+///
+/// ```rust
+/// # use aragog::{JsonSchema, Record};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Serialize, Deserialize, Record, JsonSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct Order {
+///     pub customer_name: String,
+///     pub quantity: u32,
+///     pub comment: Option<String>,
+///     // Serialized as a RFC 3339 string
+///     #[json_schema(as = "String")]
+///     pub delivery_date: String,
+/// }
+/// ```
+pub trait JsonSchema {
+    /// Whether the value may be missing from the serialized document
+    const OPTIONAL: bool = false;
+
+    /// The JSON Schema of the type
+    fn json_schema() -> Value;
+}
+
+macro_rules! impl_json_schema {
+    ($schema_type:literal, $($ty:ty),+) => {
+        $(
+            impl JsonSchema for $ty {
+                fn json_schema() -> Value {
+                    json!({ "type": $schema_type })
+                }
+            }
+        )+
+    };
+}
+
+impl_json_schema!("string", String, str, char);
+impl_json_schema!("boolean", bool);
+impl_json_schema!("integer", i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_json_schema!("number", f32, f64);
+impl_json_schema!("null", ());
+
+impl JsonSchema for Value {
+    fn json_schema() -> Value {
+        json!({})
+    }
+}
+
+impl<T: JsonSchema + ?Sized> JsonSchema for &T {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn json_schema() -> Value {
+        T::json_schema()
+    }
+}
+
+impl<T: JsonSchema + ?Sized> JsonSchema for Box<T> {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn json_schema() -> Value {
+        T::json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn json_schema() -> Value {
+        json!({ "anyOf": [T::json_schema(), { "type": "null" }] })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema() })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for [T] {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema() })
+    }
+}
+
+impl<T: JsonSchema, S> JsonSchema for HashSet<T, S> {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema(), "uniqueItems": true })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeSet<T> {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema(), "uniqueItems": true })
+    }
+}
+
+impl<T: JsonSchema, S> JsonSchema for HashMap<String, T, S> {
+    fn json_schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::json_schema() })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeMap<String, T> {
+    fn json_schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::json_schema() })
+    }
+}
+
+/// Exports the JSON Schema and the TypeScript definitions of [`Record`] models, named after their
+/// collection, to keep the types of API consumers synchronized with the Rust documents.
+///
+/// The exported types describe the serialized [`DatabaseRecord`], with the `_key`, `_id` and
+/// `_rev` fields. The properties are sorted by name.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{JsonSchema, JsonSchemaExporter, Record};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Serialize, Deserialize, Record, JsonSchema)]
+/// pub struct Dish {
+///     pub name: String,
+///     pub price: u16,
+///     pub description: Option<String>,
+/// }
+///
+/// let exporter = JsonSchemaExporter::new().model::<Dish>();
+/// assert_eq!(
+///     exporter.typescript(),
+///     "export interface Dish {\n  _id: string;\n  _key: string;\n  _rev: string;\n  \
+///     description?: string | null;\n  name: string;\n  price: number;\n}\n"
+/// );
+/// ```
+///
+/// [`DatabaseRecord`]: crate::DatabaseRecord
+#[derive(Debug, Clone, Default)]
+pub struct JsonSchemaExporter {
+    models: Vec<(String, Value)>,
+}
+
+impl JsonSchemaExporter {
+    /// Instantiates an exporter without models
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `T` model, named after its collection
+    #[must_use]
+    pub fn model<T: Record + JsonSchema>(mut self) -> Self {
+        let mut schema = T::json_schema();
+        if let Some(object) = schema.as_object_mut() {
+            let mut properties = Map::new();
+            for field in ["_key", "_id", "_rev"] {
+                properties.insert(field.to_string(), json!({ "type": "string" }));
+            }
+            if let Some(Value::Object(fields)) = object.remove("properties") {
+                properties.extend(fields);
+            }
+            let mut required = vec![json!("_key"), json!("_id"), json!("_rev")];
+            if let Some(Value::Array(fields)) = object.remove("required") {
+                required.extend(fields);
+            }
+            object.insert("properties".to_string(), Value::Object(properties));
+            object.insert("required".to_string(), Value::Array(required));
+        }
+        self.models.push((T::COLLECTION_NAME.to_string(), schema));
+        self
+    }
+
+    /// Renders a JSON Schema document with a definition per model
+    #[must_use]
+    pub fn json_schema(&self) -> Value {
+        let definitions: Map<String, Value> = self.models.iter().cloned().collect();
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "definitions": definitions,
+        })
+    }
+
+    /// Renders a TypeScript interface per model
+    #[must_use]
+    pub fn typescript(&self) -> String {
+        let mut output = String::new();
+        for (i, (name, schema)) in self.models.iter().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            match schema.get("properties") {
+                Some(Value::Object(_)) => {
+                    let _ = writeln!(
+                        output,
+                        "export interface {} {}",
+                        name,
+                        typescript_object(schema, 0)
+                    );
+                }
+                _ => {
+                    let _ = writeln!(
+                        output,
+                        "export type {} = {};",
+                        name,
+                        typescript_type(schema)
+                    );
+                }
+            }
+        }
+        output
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Renders an object schema with properties as a multi line TypeScript object type
+fn typescript_object(schema: &Value, depth: usize) -> String {
+    let indent = "  ".repeat(depth + 1);
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|fields| fields.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut output = String::from("{\n");
+    if let Some(Value::Object(properties)) = schema.get("properties") {
+        for (name, property) in properties {
+            let marker = if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            };
+            let name = if is_identifier(name) {
+                name.clone()
+            } else {
+                Value::from(name.as_str()).to_string()
+            };
+            let ty = match property.get("properties") {
+                Some(Value::Object(_)) => typescript_object(property, depth + 1),
+                _ => typescript_type(property),
+            };
+            let _ = writeln!(output, "{}{}{}: {};", indent, name, marker, ty);
+        }
+    }
+    let _ = write!(output, "{}}}", "  ".repeat(depth));
+    output
+}
+
+/// Renders a schema as an inline TypeScript type
+fn typescript_type(schema: &Value) -> String {
+    if let Some(Value::Array(variants)) = schema.get("anyOf") {
+        return variants
+            .iter()
+            .map(typescript_type)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => String::from("string"),
+        Some("integer" | "number") => String::from("number"),
+        Some("boolean") => String::from("boolean"),
+        Some("null") => String::from("null"),
+        Some("array") => {
+            let items = schema
+                .get("items")
+                .map_or_else(|| String::from("unknown"), typescript_type);
+            if items.contains(' ') {
+                format!("({})[]", items)
+            } else {
+                format!("{}[]", items)
+            }
+        }
+        Some("object") => match (schema.get("properties"), schema.get("additionalProperties")) {
+            (Some(Value::Object(_)), _) => typescript_object(schema, 0).replace('\n', " "),
+            (_, Some(values)) => format!("Record<string, {}>", typescript_type(values)),
+            _ => String::from("Record<string, unknown>"),
+        },
+        _ => String::from("unknown"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typescript_types() {
+        assert_eq!(
+            typescript_type(&Option::<Vec<u32>>::json_schema()),
+            "number[] | null"
+        );
+        assert_eq!(
+            typescript_type(&Vec::<Option<String>>::json_schema()),
+            "(string | null)[]"
+        );
+        assert_eq!(
+            typescript_type(&BTreeMap::<String, bool>::json_schema()),
+            "Record<string, boolean>"
+        );
+        assert_eq!(typescript_type(&Value::json_schema()), "unknown");
+        assert_eq!(
+            typescript_type(&json!({ "type": "string", "enum": ["Draft", "Published"] })),
+            "\"Draft\" | \"Published\""
+        );
+    }
+
+    #[test]
+    fn typescript_objects() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "first-name": { "type": "string" },
+                "address": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"]
+                }
+            },
+            "required": ["name", "address"]
+        });
+        assert_eq!(
+            typescript_object(&schema, 0),
+            "{\n  address: {\n    city: string;\n  };\n  \"first-name\"?: string;\n  name: string;\n}"
+        );
+    }
+}
@@ -42,6 +42,7 @@
 //!     * `New`: The structure can be initialized from an other type (a form for example). It allows to maintain a privacy level in the model and to use different data formats.
 //!     * `Update`: The structure can be updated from an other type (a form for example). It allows to maintain a privacy level in the model and to use different data formats.
 //! * Different operations can return a `Error` error that can easily be transformed into a Http Error
+//!   (see the `actix-web`, `axum` and `warp` features)
 //! * Transactional operations
 //!
 //! For detailed explanations on theses feature, read the [book](https://gitlab.com/qonfucius/aragog/-/tree/master/book) ([published version](https://aragog.rs/book))
@@ -126,7 +127,7 @@
 //!         .build()
 //!         .await
 //!         .unwrap();
-//! #     database_connection.truncate().await;
+//! #     database_connection.unsafe_truncate_all().await.unwrap();
 //!     // Define a document
 //!     let mut user = User {
 //!         username: String::from("LeRevenant1234"),
@@ -171,7 +172,7 @@
 //! #[tokio::main]
 //! async fn main() {
 //! # let database_connection = DatabaseConnection::builder().with_schema_path("tests/schema.yaml").apply_schema().build().await.unwrap();
-//! #  database_connection.truncate().await;
+//! #  database_connection.unsafe_truncate_all().await.unwrap();
 //!     // Define a document
 //!     let mut dish = DatabaseRecord::create(Dish {
 //!         name: "Pizza".to_string(),
@@ -211,7 +212,7 @@
 //!     let database_connection = DatabaseConnection::builder()
 //!         # .with_schema_path("tests/schema.yaml").apply_schema()
 //!         .build().await.unwrap();
-//!     #  database_connection.truncate().await;
+//!     #  database_connection.unsafe_truncate_all().await.unwrap();
 //!
 //!     // Instantiate a new transaction
 //!     let transaction = Transaction::new(&database_connection).await.unwrap();
@@ -265,7 +266,7 @@
 //! # #[tokio::main]
 //! # async fn main() {
 //! # let database_connection = DatabaseConnection::builder().with_schema_path("tests/schema.yaml").apply_schema().build().await.unwrap();
-//! # database_connection.truncate().await;
+//! # database_connection.unsafe_truncate_all().await.unwrap();
 //! # let mut user = User {
 //! #     username: String::from("LeRevenant1234"),
 //! #     first_name: String::from("Robert"),
@@ -310,7 +311,7 @@
 //! # #[tokio::main]
 //! # async fn main() {
 //! let database_connection = DatabaseConnection::builder().with_schema_path("tests/schema.yaml").apply_schema().build().await.unwrap();
-//! # database_connection.truncate().await;
+//! # database_connection.unsafe_truncate_all().await.unwrap();
 //! # let mut user = User {
 //! #     username: String::from("LeRevenant1234"),
 //! #     first_name: String::from("Robert"),
@@ -492,41 +493,84 @@
 #![allow(clippy::future_not_send, clippy::module_name_repetitions)]
 
 pub extern crate async_trait;
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub extern crate inventory;
+pub extern crate serde;
+pub extern crate serde_json;
 
 #[cfg(feature = "derive")]
 #[doc(hidden)]
 pub use aragog_macros::*;
 
 pub use arangors_lite::{AqlQuery, Database};
+pub use counter_cache::{CounterCache, CounterCacheVertex};
+pub use db::attachments::{Attachment, Attachments};
+pub use db::change_set::{ChangeSet, FieldChange};
+pub use db::collection_import::{ImportOptions, ImportReport, OnDuplicate};
+pub use db::conflict_policy::{ConflictMerge, ConflictPolicy, DEFAULT_CONFLICT_RETRIES};
+pub use db::database_collection::{CollectionChecksum, CollectionFigures, DatabaseCollection};
+pub use db::database_users::{AccessLevel, DatabaseUser};
+pub use db::graph_ops::{GraphOps, GraphPath};
+pub use db::hook_registry::{HookContext, HookEvent, HookRegistry};
+pub use db::introspection::{CollectionKind, CollectionSummary, GraphSummary};
+pub use db::request_limiter::{RequestLimiter, RequestPermit};
+pub use document_id::{DocumentId, DocumentKey};
+pub use json_schema::{JsonSchema, JsonSchemaExporter};
+pub use merge::{Merge, Union};
+pub use record::RecordAction;
+#[cfg(feature = "registry")]
+pub use registry::{registered_records, RegisteredRecord};
+pub use state_machine::{State, StateMachine};
 #[cfg(not(feature = "minimal_traits"))]
 pub use {authorize_action::AuthorizeAction, new::New, update::Update};
 pub use {
     db::database_access::DatabaseAccess, db::database_connection::AuthMode,
     db::database_connection::DatabaseConnection,
     db::database_connection_builder::DatabaseConnectionBuilder,
-    db::database_record::DatabaseRecord, db::operation_options::OperationOptions, db::transaction,
-    edge_record::EdgeRecord, error::Error, foreign_link::ForeignLink, link::Link, record::Record,
-    undefined_record::UndefinedRecord, validate::Validate,
+    db::database_record::DatabaseRecord, db::deserialization_mode::DeserializationMode,
+    db::operation_options::OperationOptions, db::operation_tracker::OperationTracker,
+    db::raw_document::RawDocument, db::transaction, db::upsert_outcome::UpsertOutcome,
+    db::velocypack::VelocyPackTransport, edge_record::EdgeRecord, error::Error,
+    foreign_link::ForeignLink, link::Link, polymorphic_ref::PolymorphicRef, record::Record,
+    repository::Repository, undefined_record::UndefinedRecord, validate::Validate,
 };
 
 #[cfg(not(feature = "minimal_traits"))]
 mod authorize_action;
+#[cfg(feature = "blocking")]
+#[doc(hidden)]
+pub mod blocking_hook;
+mod counter_cache;
 mod db;
+mod document_id;
 mod edge_record;
 mod foreign_link;
+mod json_schema;
 mod link;
 #[cfg(not(feature = "minimal_traits"))]
 mod new;
+mod polymorphic_ref;
 mod record;
+#[cfg(feature = "registry")]
+mod registry;
+mod repository;
+mod state_machine;
 #[cfg(not(feature = "minimal_traits"))]
 mod update;
 mod validate;
 
 /// Error handling
 pub mod error;
+/// Relation integrity checks of the graph data, like the dangling edges.
+pub mod integrity;
+/// The field merge strategies of the [`Merge`] derive
+pub mod merge;
 /// contains querying struct and functions.
 pub mod query;
 /// Database schema construction utility, available for advanced development.
 /// For classic usage use the `aragog_cli` and its migration engine to generate your schema
 pub mod schema;
 mod undefined_record;
+/// Helpers for applications, like the record diffs.
+pub mod util;
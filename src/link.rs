@@ -40,12 +40,12 @@ use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
 /// #    .build()
 /// #    .await
 /// #    .unwrap();
-/// # database_connection.truncate().await;
+/// # database_connection.unsafe_truncate_all().await.unwrap();
 /// let user = DatabaseRecord::create(User {}, &database_connection).await.unwrap();
 /// let order = DatabaseRecord::create(
 ///     Order {
 ///         content: "content".to_string(),
-///         user_id: user.key().clone()
+///         user_id: user.key().to_string()
 ///     },
 ///     &database_connection).await.unwrap();
 /// let orders = user.linked_models(&database_connection).await.unwrap();
@@ -0,0 +1,169 @@
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{BuildHasher, Hash};
+
+/// Merges the concurrent versions of a type, the local one and the stored one, field by field.
+///
+/// Used by [`ConflictPolicy::merge_fields`] to resolve the save conflicts of a record: instead of
+/// the last write winning, the changes of concurrent writers to disjoint or numeric fields are
+/// kept.
+///
+/// Can be derived for structs with named fields. The fields keep their local value by default,
+/// the `#[merge(strategy = "...")]` attribute selects another strategy of the [`merge`] module:
+/// * `local`: the local value
+/// * `stored`: the stored value, for the fields owned by other writers
+/// * `max`/`min`: the greatest/lowest value
+/// * `union`: the [`Union`] of collections
+/// * `merge`: the [`Merge`] implementation of the field type
+///
+/// The `#[merge(with = "path::to::function")]` attribute uses a custom function taking the local
+/// and the stored values.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{Merge, Record};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// #[derive(Record, Merge, Clone, Serialize, Deserialize)]
+/// #[conflict_policy(merge_fields)]
+/// pub struct Article {
+///     pub title: String,
+///     #[merge(strategy = "max")]
+///     pub views: u32,
+///     #[merge(strategy = "union")]
+///     pub tags: Vec<String>,
+///     #[merge(strategy = "stored")]
+///     pub moderated: bool,
+/// }
+///
+/// let local = Article {
+///     title: String::from("New title"),
+///     views: 10,
+///     tags: vec![String::from("rust")],
+///     moderated: false,
+/// };
+/// let stored = Article {
+///     title: String::from("Old title"),
+///     views: 12,
+///     tags: vec![String::from("db")],
+///     moderated: true,
+/// };
+/// let merged = Article::merge(&local, &stored);
+/// assert_eq!(merged.title, "New title");
+/// assert_eq!(merged.views, 12);
+/// assert_eq!(merged.tags, vec![String::from("rust"), String::from("db")]);
+/// assert!(merged.moderated);
+/// ```
+///
+/// [`ConflictPolicy::merge_fields`]: crate::ConflictPolicy::merge_fields
+/// [`merge`]: crate::merge
+pub trait Merge {
+    /// Merges the `local` version with the `stored` one
+    #[must_use]
+    fn merge(local: &Self, stored: &Self) -> Self;
+}
+
+/// Union of collections, used by the `union` merge strategy
+pub trait Union {
+    /// The items of `self` followed by the items of `other` missing from `self`
+    #[must_use]
+    fn union(&self, other: &Self) -> Self;
+}
+
+impl<T: PartialEq + Clone> Union for Vec<T> {
+    fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for item in other {
+            if !self.contains(item) {
+                union.push(item.clone());
+            }
+        }
+        union
+    }
+}
+
+impl<T: Eq + Hash + Clone, S: BuildHasher + Clone> Union for HashSet<T, S> {
+    fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter().cloned());
+        union
+    }
+}
+
+impl<T: Ord + Clone> Union for BTreeSet<T> {
+    fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other).cloned().collect()
+    }
+}
+
+impl<T: Union + Clone> Union for Option<T> {
+    fn union(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Some(local), Some(stored)) => Some(local.union(stored)),
+            (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The `local` merge strategy: keeps the local value
+#[must_use]
+pub fn local<T: Clone>(local: &T, _stored: &T) -> T {
+    local.clone()
+}
+
+/// The `stored` merge strategy: keeps the stored value
+#[must_use]
+pub fn stored<T: Clone>(_local: &T, stored: &T) -> T {
+    stored.clone()
+}
+
+/// The `max` merge strategy: keeps the greatest value, the local one if they are not comparable
+#[must_use]
+pub fn max<T: PartialOrd + Clone>(local: &T, stored: &T) -> T {
+    if stored > local {
+        stored.clone()
+    } else {
+        local.clone()
+    }
+}
+
+/// The `min` merge strategy: keeps the lowest value, the local one if they are not comparable
+#[must_use]
+pub fn min<T: PartialOrd + Clone>(local: &T, stored: &T) -> T {
+    if stored < local {
+        stored.clone()
+    } else {
+        local.clone()
+    }
+}
+
+/// The `union` merge strategy: keeps the [`Union`] of the local and stored collections
+#[must_use]
+pub fn union<T: Union>(local: &T, stored: &T) -> T {
+    local.union(stored)
+}
+
+/// The `merge` merge strategy: merges the values with their [`Merge`] implementation
+#[must_use]
+pub fn merge<T: Merge>(local: &T, stored: &T) -> T {
+    T::merge(local, stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strategies() {
+        assert_eq!(max(&1.5, &f64::NAN), 1.5);
+        assert_eq!(min(&3, &2), 2);
+        assert_eq!(stored(&"a", &"b"), "b");
+        assert_eq!(union(&vec![1, 2], &vec![3, 2, 1, 4]), vec![1, 2, 3, 4]);
+        assert_eq!(
+            union(&Some(BTreeSet::from([2])), &Some(BTreeSet::from([1]))),
+            Some(BTreeSet::from([1, 2]))
+        );
+        assert_eq!(union(&None, &Some(vec![1])), Some(vec![1]));
+    }
+}
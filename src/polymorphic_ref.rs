@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+use crate::util::validate_document_id;
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+
+/// A reference to a document of any collection, stored as its `_id` (`Post/123`).
+///
+/// Allows "commentable" like relations, where a field may reference documents of different
+/// collections. The reference is serialized as a string and its format is checked on
+/// deserialization.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{PolymorphicRef, Record};
+/// # use aragog::query::Comparison;
+/// # use serde::{Serialize, Deserialize};
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// pub struct Comment {
+///     pub content: String,
+///     pub commentable: PolymorphicRef,
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// pub struct Post {}
+///
+/// let commentable = PolymorphicRef::new("Post/123").unwrap();
+/// assert_eq!(commentable.collection(), "Post");
+/// assert_eq!(commentable.key(), "123");
+/// assert!(commentable.is::<Post>());
+///
+/// // The comments of any post
+/// let query = Comment::query().filter(Comparison::field("commentable").in_collection("Post").into());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PolymorphicRef(String);
+
+impl PolymorphicRef {
+    /// Builds a reference from a document `_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if `id` is not a valid document id, see
+    /// [`validate_document_id`]
+    pub fn new(id: impl Into<String>) -> Result<Self, Error> {
+        let id = id.into();
+        validate_document_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// The referenced document `_id`
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// The collection of the referenced document
+    #[must_use]
+    pub fn collection(&self) -> &str {
+        self.0.split('/').next().unwrap_or_default()
+    }
+
+    /// The key of the referenced document
+    #[must_use]
+    pub fn key(&self) -> &str {
+        self.0.split('/').nth(1).unwrap_or_default()
+    }
+
+    /// Returns `true` if the referenced document is stored in the `T` collection
+    #[must_use]
+    pub fn is<T: Record>(&self) -> bool {
+        self.collection() == T::COLLECTION_NAME
+    }
+
+    /// Retrieves the referenced document as a `T` record, the `T` type is usually picked from the
+    /// [`collection`] of the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error::ValidationError`] if the document is not stored in the `T` collection,
+    /// or fails like [`DatabaseRecord::find`]
+    ///
+    /// [`collection`]: Self::collection
+    #[maybe_async::maybe_async]
+    pub async fn resolve<T, D>(&self, db_accessor: &D) -> Result<DatabaseRecord<T>, Error>
+    where
+        T: Record,
+        D: DatabaseAccess + ?Sized,
+    {
+        if !self.is::<T>() {
+            return Err(Error::ValidationError(format!(
+                "{} is not a {} document",
+                self.0,
+                T::COLLECTION_NAME
+            )));
+        }
+        DatabaseRecord::find(self.key(), db_accessor).await
+    }
+}
+
+impl<T: Record> From<&DatabaseRecord<T>> for PolymorphicRef {
+    fn from(record: &DatabaseRecord<T>) -> Self {
+        Self(record.id().to_string())
+    }
+}
+
+impl TryFrom<String> for PolymorphicRef {
+    type Error = Error;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::new(id)
+    }
+}
+
+impl From<PolymorphicRef> for String {
+    fn from(reference: PolymorphicRef) -> Self {
+        reference.0
+    }
+}
+
+impl Display for PolymorphicRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_document_ids() {
+        let reference = PolymorphicRef::new("Post/123").unwrap();
+        assert_eq!(reference.collection(), "Post");
+        assert_eq!(reference.key(), "123");
+        assert_eq!(reference.to_string(), "Post/123");
+        for id in ["Post", "Post/", "/123", "Post/1/2"] {
+            assert!(PolymorphicRef::new(id).is_err(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn serializes_as_string() {
+        let reference: PolymorphicRef = serde_json::from_str(r#""Post/123""#).unwrap();
+        assert_eq!(serde_json::to_string(&reference).unwrap(), r#""Post/123""#);
+        assert!(serde_json::from_str::<PolymorphicRef>(r#""Post""#).is_err());
+    }
+}
@@ -1,9 +1,15 @@
+use std::convert::TryFrom;
 use std::fmt::Display;
 
 use num::Num;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
-use crate::query::utils::{string_array_from_array, string_array_from_array_str};
+use crate::query::utils::{
+    is_attribute_path, string_array_from_array, string_array_from_array_str, BoundValues,
+};
 use crate::query::Filter;
+use crate::Error;
 
 /// Macro to simplify the [`Comparison`] construction:
 ///
@@ -51,6 +57,11 @@ macro_rules! compare {
     };
 }
 
+/// Maximum number of values bound in a single query by [`Record::get_where_in`]
+///
+/// [`Record::get_where_in`]: crate::Record::get_where_in
+pub const BOUND_ARRAY_CHUNK_SIZE: usize = 5_000;
+
 /// Builder for [`Comparison`]
 #[derive(Clone, Debug)]
 pub struct ComparisonBuilder {
@@ -58,15 +69,105 @@ pub struct ComparisonBuilder {
     statement: String,
 }
 
+/// Builder for the [`Comparison`] of a value, see [`Comparison::value`]
+#[derive(Clone, Debug)]
+pub struct ValueComparisonBuilder {
+    value: Value,
+}
+
+impl ValueComparisonBuilder {
+    /// Finalizes the current query item builder with an inclusion in an array field comparison.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::value(10).in_field("allowed_ages");
+    /// let query = Query::new("Events").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), "FOR a in Events FILTER 10 IN a.allowed_ages return a");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn in_field(self, array_field_name: &str) -> Comparison {
+        Comparison::field(array_field_name).contains(self.value)
+    }
+
+    /// Finalizes the current query item builder with an exclusion from an array field comparison.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::value("admin").not_in_field("roles");
+    /// let query = Query::new("Users").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER "admin" NOT IN a.roles return a"#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn not_in_field(self, array_field_name: &str) -> Comparison {
+        Comparison::field(array_field_name).not_contains(self.value)
+    }
+}
+
 /// Struct representing one AQL comparison in a [`Query`].
 ///
+/// # Serialization
+///
+/// The comparisons of a field with literal values are serialized to and deserialized from JSON,
+/// so that API clients can send structured filter definitions:
+///
+/// ```json
+/// { "field": "age", "comparator": "greater_than", "value": 18 }
+/// { "field": "emails", "quantifier": "any", "comparator": "like", "value": "%gmail.com" }
+/// ```
+///
+/// The `comparator` is named after the [`ComparisonBuilder`] method, from `equals` to
+/// `not_contains` (`null`, `true` and `false` are compared with `equals` and `different_than`),
+/// and the optional `quantifier` is `all`, `any` or `none`. The field must be an attribute path,
+/// like `address.city`, and the value is always rendered as a JSON literal, so deserialized
+/// comparisons can't inject AQL. The [`statement`] comparisons and the comparisons to AQL
+/// expressions or bind parameters fail to serialize.
+///
+/// ```rust
+/// # use aragog::query::Comparison;
+/// let comparison: Comparison = serde_json::from_str(
+///     r#"{ "field": "age", "comparator": "greater_than", "value": 18 }"#
+/// ).unwrap();
+/// assert_eq!(comparison.aql_str("a"), "a.age > 18");
+/// assert!(serde_json::from_str::<Comparison>(
+///     r#"{ "field": "age || true", "comparator": "equals", "value": 18 }"#
+/// ).is_err());
+/// ```
+///
 /// [`Query`]: crate::query::Query
+/// [`statement`]: Self::statement
 #[derive(Clone, Debug)]
 pub struct Comparison {
     is_field: bool,
     left_value: String,
     comparator: String,
     right_value: String,
+    value: ComparisonValue,
+}
+
+/// How the right value of a [`Comparison`] is rendered
+#[derive(Clone, Debug)]
+enum ComparisonValue {
+    /// Always rendered in the query string, like the AQL keywords and expressions
+    Inline,
+    /// A literal passed as a bind parameter when the query literals are bound
+    Literal(Value),
+    /// A literal rendered from its `Display` implementation, passed as a bind parameter when the
+    /// query literals are bound if the rendered value is valid JSON. Otherwise it is an AQL
+    /// expression, like `a.other_field` or `@bind_var`, rendered in the query string
+    Rendered,
+    /// Always passed as a bind parameter
+    Bound(Value),
+    /// A literal on the left side of an array membership comparison, passed as a bind parameter
+    /// when the query literals are bound. The array expression on the right side is the field
+    Member(Value),
 }
 
 impl ComparisonBuilder {
@@ -111,6 +212,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: format!(r#""{}""#, value),
+            value: ComparisonValue::Literal(Value::String(value.to_string())),
         }
     }
 
@@ -155,6 +257,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "!=".to_string(),
             right_value: format!(r#""{}""#, value),
+            value: ComparisonValue::Literal(Value::String(value.to_string())),
         }
     }
 
@@ -178,6 +281,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "=~".to_string(),
             right_value: format!(r#""{}""#, regular_expression),
+            value: ComparisonValue::Literal(Value::String(regular_expression.to_string())),
         }
     }
 
@@ -201,6 +305,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "!~".to_string(),
             right_value: format!(r#""{}""#, regular_expression),
+            value: ComparisonValue::Literal(Value::String(regular_expression.to_string())),
         }
     }
 
@@ -224,6 +329,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "LIKE".to_string(),
             right_value: format!(r#""{}""#, pattern),
+            value: ComparisonValue::Literal(Value::String(pattern.to_string())),
         }
     }
 
@@ -247,9 +353,31 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "NOT LIKE".to_string(),
             right_value: format!(r#""{}""#, pattern),
+            value: ComparisonValue::Literal(Value::String(pattern.to_string())),
         }
     }
 
+    /// Finalizes the current query item builder with a document id collection comparison.
+    /// The field to be matched should be a document `_id`, like a [`PolymorphicRef`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::field("commentable").in_collection("Post");
+    /// let query = Query::new("Comments").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), r#"FOR a in Comments FILTER a.commentable LIKE "Post/%" return a"#);
+    /// ```
+    ///
+    /// [`PolymorphicRef`]: crate::PolymorphicRef
+    #[inline]
+    #[must_use]
+    pub fn in_collection(self, collection: &str) -> Comparison {
+        // `_` is a `LIKE` wildcard allowed in collection names
+        self.like(&format!(r"{}/%", collection.replace('_', r"\\_")))
+    }
+
     /// Finalizes the current query item builder with an equality comparison.
     ///
     /// # Note
@@ -293,6 +421,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -339,6 +468,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "!=".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -366,6 +496,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: ">".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -393,6 +524,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: ">=".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -420,6 +552,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "<".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -447,6 +580,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "<=".to_string(),
             right_value: format!(r#"{}"#, value),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -473,6 +607,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "IN".to_string(),
             right_value: string_array_from_array(array),
+            value: ComparisonValue::Rendered,
         }
     }
 
@@ -499,6 +634,87 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "NOT IN".to_string(),
             right_value: string_array_from_array(array),
+            value: ComparisonValue::Rendered,
+        }
+    }
+
+    /// Finalizes the current query item builder with an inclusion in an array passed as a bind
+    /// parameter.
+    ///
+    /// Unlike [`in_array`] the values are not rendered in the AQL string, which keeps the query
+    /// short and identical whatever the values, improving parse time and plan cache hits on large
+    /// lists. The bind parameters are named after their position in the query (`@value_0`,
+    /// `@value_1`...).
+    ///
+    /// # Note
+    ///
+    /// [`Query::aql_str`] renders only the query string, use [`Query::aql_with_bind_vars`] to
+    /// retrieve the bound values. [`Comparison::aql_str`] renders the values inline.
+    /// For very large lists prefer [`Record::get_where_in`] which splits the values in chunks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    /// # use serde_json::json;
+    ///
+    /// let query_item = Comparison::field("username").in_bound_array(&["felix", "bianca"]);
+    /// let query = Query::new("Users").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username IN @value_0 return a");
+    /// let (_, bind_vars) = query.aql_with_bind_vars(false);
+    /// assert_eq!(bind_vars["value_0"], json!(["felix", "bianca"]));
+    /// ```
+    ///
+    /// [`in_array`]: Self::in_array
+    /// [`Query::aql_str`]: crate::query::Query::aql_str
+    /// [`Query::aql_with_bind_vars`]: crate::query::Query::aql_with_bind_vars
+    /// [`Record::get_where_in`]: crate::Record::get_where_in
+    #[inline]
+    #[must_use]
+    pub fn in_bound_array<T>(self, array: &[T]) -> Comparison
+    where
+        T: Serialize,
+    {
+        self.bound_array_comparison("IN", array)
+    }
+
+    /// Finalizes the current query item builder with an exclusion from an array passed as a bind
+    /// parameter. See [`in_bound_array`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::field("age").not_in_bound_array(&[1, 11, 16, 18]);
+    /// let query = Query::new("Users").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age NOT IN @value_0 return a");
+    /// ```
+    ///
+    /// [`in_bound_array`]: Self::in_bound_array
+    #[inline]
+    #[must_use]
+    pub fn not_in_bound_array<T>(self, array: &[T]) -> Comparison
+    where
+        T: Serialize,
+    {
+        self.bound_array_comparison("NOT IN", array)
+    }
+
+    fn bound_array_comparison<T>(self, comparator: &str, array: &[T]) -> Comparison
+    where
+        T: Serialize,
+    {
+        let value = serde_json::to_value(array).unwrap_or_else(|error| {
+            log::error!("Failed to serialize bound array: {}", error);
+            Value::Array(Vec::new())
+        });
+        Comparison {
+            is_field: self.is_field,
+            left_value: self.statement,
+            comparator: comparator.to_string(),
+            right_value: value.to_string(),
+            value: ComparisonValue::Bound(value),
         }
     }
 
@@ -525,6 +741,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "IN".to_string(),
             right_value: string_array_from_array_str(array),
+            value: ComparisonValue::Literal(string_values(array)),
         }
     }
 
@@ -551,6 +768,63 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "NOT IN".to_string(),
             right_value: string_array_from_array_str(array),
+            value: ComparisonValue::Literal(string_values(array)),
+        }
+    }
+
+    /// Finalizes the current query item builder with an array membership comparison, the
+    /// current array field or statement must contain `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::field("tags").contains("rust");
+    /// let query = Query::new("Articles").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), r#"FOR a in Articles FILTER "rust" IN a.tags return a"#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains<T>(self, value: T) -> Comparison
+    where
+        T: Into<Value>,
+    {
+        let value = value.into();
+        Comparison {
+            is_field: self.is_field,
+            left_value: value.to_string(),
+            comparator: "IN".to_string(),
+            right_value: self.statement,
+            value: ComparisonValue::Member(value),
+        }
+    }
+
+    /// Finalizes the current query item builder with an array exclusion comparison, the current
+    /// array field or statement must not contain `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    ///
+    /// let query_item = Comparison::field("allowed_ages").not_contains(10);
+    /// let query = Query::new("Events").filter(Filter::new(query_item));
+    /// assert_eq!(query.aql_str(), "FOR a in Events FILTER 10 NOT IN a.allowed_ages return a");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn not_contains<T>(self, value: T) -> Comparison
+    where
+        T: Into<Value>,
+    {
+        let value = value.into();
+        Comparison {
+            is_field: self.is_field,
+            left_value: value.to_string(),
+            comparator: "NOT IN".to_string(),
+            right_value: self.statement,
+            value: ComparisonValue::Member(value),
         }
     }
 
@@ -575,6 +849,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "null".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -597,6 +872,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "null".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -619,6 +895,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "!=".to_string(),
             right_value: "null".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -644,6 +921,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "true".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -667,6 +945,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "true".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -692,6 +971,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "false".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 
@@ -715,6 +995,7 @@ impl ComparisonBuilder {
             left_value: self.statement,
             comparator: "==".to_string(),
             right_value: "false".to_string(),
+            value: ComparisonValue::Inline,
         }
     }
 }
@@ -824,6 +1105,28 @@ impl Comparison {
         }
     }
 
+    /// Instantiates a new builder for a `Comparison` of `value` against an array field, the value
+    /// will be used as the left value of the comparison.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    /// Query::new("Events").filter(Filter::new(Comparison::value(10).in_field("allowed_ages")));
+    /// // or
+    /// Query::new("Events").filter(Comparison::value(10).in_field("allowed_ages").into());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn value<T>(value: T) -> ValueComparisonBuilder
+    where
+        T: Into<Value>,
+    {
+        ValueComparisonBuilder {
+            value: value.into(),
+        }
+    }
+
     /// Appends the filter current condition(s) with a new one with a `AND` logic.
     /// `self` will be treated as a `Filter`.
     /// ```rust
@@ -884,20 +1187,306 @@ impl Comparison {
     /// ```
     #[must_use]
     pub fn aql_str(&self, collection_id: &str) -> String {
+        self.render(collection_id, None)
+    }
+
+    /// The compared document field, `None` for the [`statement`] comparisons
+    ///
+    /// [`statement`]: Self::statement
+    pub(crate) fn compared_field(&self) -> Option<&str> {
+        if !self.is_field {
+            return None;
+        }
+        if let ComparisonValue::Member(_) = self.value {
+            return Some(&self.right_value);
+        }
+        self.left_value.split(' ').next()
+    }
+
+    /// Renders `self`, passing the bound value and, if enabled, the literal value as bind
+    /// parameters if `bound_values` is set
+    pub(crate) fn render(
+        &self,
+        collection_id: &str,
+        bound_values: Option<&mut BoundValues>,
+    ) -> String {
         let id = if self.is_field {
             format!("{}.", collection_id)
         } else {
             String::new()
         };
+        if let ComparisonValue::Member(value) = &self.value {
+            let left_value = match bound_values {
+                Some(bound_values) if bound_values.bind_literals => {
+                    bound_values.bind(value.clone())
+                }
+                _ => self.left_value.clone(),
+            };
+            return format!(
+                "{} {} {}{}",
+                left_value, &self.comparator, id, &self.right_value
+            );
+        }
+        let bound_value = match (&self.value, bound_values) {
+            (ComparisonValue::Bound(value), Some(bound_values)) => {
+                Some(bound_values.bind(value.clone()))
+            }
+            (ComparisonValue::Literal(value), Some(bound_values)) if bound_values.bind_literals => {
+                Some(bound_values.bind(value.clone()))
+            }
+            (ComparisonValue::Rendered, Some(bound_values)) if bound_values.bind_literals => {
+                serde_json::from_str(&self.right_value)
+                    .ok()
+                    .map(|value| bound_values.bind(value))
+            }
+            _ => None,
+        };
+        let right_value = bound_value.as_deref().unwrap_or(&self.right_value);
         format!(
             "{}{} {} {}",
-            id, &self.left_value, &self.comparator, &self.right_value
+            id, &self.left_value, &self.comparator, right_value
         )
     }
 }
 
+/// The string values of `array` as a JSON array
+fn string_values<T: Display>(array: &[T]) -> Value {
+    Value::Array(
+        array
+            .iter()
+            .map(|element| Value::String(element.to_string()))
+            .collect(),
+    )
+}
+
 impl From<Comparison> for Filter {
     fn from(comparison: Comparison) -> Self {
         Self::new(comparison)
     }
 }
+
+/// The `ALL`, `ANY` or `NONE` array operator of a serialized [`Comparison`]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Quantifier {
+    All,
+    Any,
+    None,
+}
+
+impl Quantifier {
+    const fn aql(self) -> &'static str {
+        match self {
+            Self::All => "ALL",
+            Self::Any => "ANY",
+            Self::None => "NONE",
+        }
+    }
+
+    fn from_aql(keyword: &str) -> Option<Self> {
+        [Self::All, Self::Any, Self::None]
+            .into_iter()
+            .find(|quantifier| quantifier.aql() == keyword)
+    }
+}
+
+/// The comparator of a serialized [`Comparison`], named after the [`ComparisonBuilder`] method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Comparator {
+    Equals,
+    DifferentThan,
+    GreaterThan,
+    GreaterOrEqual,
+    LesserThan,
+    LesserOrEqual,
+    InArray,
+    NotInArray,
+    Matches,
+    DoesNotMatch,
+    Like,
+    NotLike,
+    Contains,
+    NotContains,
+}
+
+impl Comparator {
+    const ALL: [Self; 14] = [
+        Self::Equals,
+        Self::DifferentThan,
+        Self::GreaterThan,
+        Self::GreaterOrEqual,
+        Self::LesserThan,
+        Self::LesserOrEqual,
+        Self::InArray,
+        Self::NotInArray,
+        Self::Matches,
+        Self::DoesNotMatch,
+        Self::Like,
+        Self::NotLike,
+        Self::Contains,
+        Self::NotContains,
+    ];
+
+    const fn aql(self) -> &'static str {
+        match self {
+            Self::Equals => "==",
+            Self::DifferentThan => "!=",
+            Self::GreaterThan => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::LesserThan => "<",
+            Self::LesserOrEqual => "<=",
+            Self::InArray | Self::Contains => "IN",
+            Self::NotInArray | Self::NotContains => "NOT IN",
+            Self::Matches => "=~",
+            Self::DoesNotMatch => "!~",
+            Self::Like => "LIKE",
+            Self::NotLike => "NOT LIKE",
+        }
+    }
+
+    const fn is_membership(self) -> bool {
+        matches!(self, Self::Contains | Self::NotContains)
+    }
+}
+
+/// The JSON representation of a [`Comparison`]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ComparisonDefinition {
+    field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quantifier: Option<Quantifier>,
+    comparator: Comparator,
+    value: Value,
+}
+
+impl TryFrom<&Comparison> for ComparisonDefinition {
+    type Error = Error;
+
+    fn try_from(comparison: &Comparison) -> Result<Self, Self::Error> {
+        let unsupported = || {
+            Error::ValidationError(format!(
+                "The `{} {} {}` comparison can't be serialized",
+                comparison.left_value, comparison.comparator, comparison.right_value
+            ))
+        };
+        if !comparison.is_field {
+            return Err(unsupported());
+        }
+        let is_membership = matches!(comparison.value, ComparisonValue::Member(_));
+        let comparator = Comparator::ALL
+            .into_iter()
+            .find(|comparator| {
+                comparator.aql() == comparison.comparator
+                    && comparator.is_membership() == is_membership
+            })
+            .ok_or_else(unsupported)?;
+        let (field, quantifier) = if is_membership {
+            (comparison.right_value.as_str(), None)
+        } else {
+            match comparison.left_value.split_once(' ') {
+                Some((field, keyword)) => (
+                    field,
+                    Some(Quantifier::from_aql(keyword).ok_or_else(unsupported)?),
+                ),
+                None => (comparison.left_value.as_str(), None),
+            }
+        };
+        if !is_attribute_path(field) {
+            return Err(unsupported());
+        }
+        let value = match &comparison.value {
+            ComparisonValue::Literal(value)
+            | ComparisonValue::Bound(value)
+            | ComparisonValue::Member(value) => value.clone(),
+            // Expressions like `a.other_field` or `@bind_var` are not valid JSON
+            ComparisonValue::Inline | ComparisonValue::Rendered => {
+                serde_json::from_str(&comparison.right_value).map_err(|_| unsupported())?
+            }
+        };
+        Ok(Self {
+            field: field.to_string(),
+            quantifier,
+            comparator,
+            value,
+        })
+    }
+}
+
+impl TryFrom<ComparisonDefinition> for Comparison {
+    type Error = Error;
+
+    fn try_from(definition: ComparisonDefinition) -> Result<Self, Self::Error> {
+        let ComparisonDefinition {
+            field,
+            quantifier,
+            comparator,
+            value,
+        } = definition;
+        if !is_attribute_path(&field) {
+            return Err(Error::ValidationError(format!(
+                r#""{}" is not a valid comparison field"#,
+                field
+            )));
+        }
+        let invalid_value = |expected: &str| {
+            Error::ValidationError(format!(
+                "The `{}` comparison of {} expects {}",
+                serde_json::to_value(comparator).unwrap_or_default(),
+                field,
+                expected
+            ))
+        };
+        match comparator {
+            Comparator::InArray | Comparator::NotInArray if !value.is_array() => {
+                return Err(invalid_value("an array"));
+            }
+            Comparator::Matches
+            | Comparator::DoesNotMatch
+            | Comparator::Like
+            | Comparator::NotLike
+                if !value.is_string() =>
+            {
+                return Err(invalid_value("a string"));
+            }
+            Comparator::Contains | Comparator::NotContains if quantifier.is_some() => {
+                return Err(invalid_value("no quantifier"));
+            }
+            _ => (),
+        }
+        if comparator.is_membership() {
+            let builder = Comparison::field(&field);
+            return Ok(if comparator == Comparator::Contains {
+                builder.contains(value)
+            } else {
+                builder.not_contains(value)
+            });
+        }
+        Ok(Self {
+            is_field: true,
+            left_value: match quantifier {
+                Some(quantifier) => format!("{} {}", field, quantifier.aql()),
+                None => field,
+            },
+            comparator: comparator.aql().to_string(),
+            right_value: value.to_string(),
+            value: ComparisonValue::Literal(value),
+        })
+    }
+}
+
+impl Serialize for Comparison {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ComparisonDefinition::try_from(self)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Comparison {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(ComparisonDefinition::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,212 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt::{self, Display, Formatter};
+
+use crate::db::versioned_record::MODEL_VERSION_FIELD;
+use crate::{Error, Record};
+
+/// A query result document which failed to be deserialized as a record, see
+/// [`QueryResult::get_records_with_errors`]
+///
+/// [`QueryResult::get_records_with_errors`]: crate::query::QueryResult::get_records_with_errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializationError {
+    /// The document `_id`
+    pub id: String,
+    /// The path of the failing value in the document (`address.street`, `tags[2]`), `.` for the
+    /// document itself
+    pub path: String,
+    /// The deserialization failure, like the found and expected types
+    pub message: String,
+}
+
+impl DeserializationError {
+    /// Diagnoses the deserialization of `value` as `T`, which failed with `error`
+    pub(crate) fn diagnose<T: Record>(id: &str, value: &Value, error: &Error) -> Self {
+        let version = value
+            .get(MODEL_VERSION_FIELD)
+            .and_then(Value::as_u64)
+            .unwrap_or_default();
+        let diagnosis = if version < u64::from(T::MODEL_VERSION) {
+            None
+        } else {
+            path_error::<T>(value)
+        };
+        // Without diagnosis the failure comes from a model migration
+        let (path, message) = diagnosis.unwrap_or_else(|| {
+            let message = std::error::Error::source(error)
+                .map_or_else(|| error.to_string(), ToString::to_string);
+            (String::from("."), message)
+        });
+        Self {
+            id: id.to_string(),
+            path,
+            message,
+        }
+    }
+}
+
+impl Display for DeserializationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at `{}`: {}", self.id, self.path, self.message)
+    }
+}
+
+/// Deserializes the pretty JSON representation of `value` as `T`, locating the failure from its
+/// line. Returns `None` if the deserialization succeeds.
+fn path_error<T: DeserializeOwned>(value: &Value) -> Option<(String, String)> {
+    let mut value = value.clone();
+    if let Some(object) = value.as_object_mut() {
+        object.remove(MODEL_VERSION_FIELD);
+    }
+    let pretty = serde_json::to_string_pretty(&value).ok()?;
+    let error = serde_json::from_str::<T>(&pretty).err()?;
+    let position = format!(" at line {} column {}", error.line(), error.column());
+    let message = error.to_string();
+    let message = message.strip_suffix(&position).unwrap_or(&message);
+    Some((
+        json_path_at_line(&pretty, error.line()),
+        message.to_string(),
+    ))
+}
+
+enum Frame {
+    Object(Option<String>),
+    Array(usize),
+}
+
+/// The path of the value at `line` (starting at 1) of a `serde_json` pretty representation
+fn json_path_at_line(pretty: &str, line: usize) -> String {
+    let mut stack = Vec::new();
+    for text in pretty.lines().take(line) {
+        let text = text.trim().trim_end_matches(',');
+        if text.starts_with('}') || text.starts_with(']') {
+            stack.pop();
+            continue;
+        }
+        let value = match stack.last_mut() {
+            Some(Frame::Object(key)) => {
+                let (name, value) = split_key(text);
+                *key = Some(name);
+                value
+            }
+            Some(Frame::Array(count)) => {
+                *count += 1;
+                text
+            }
+            None => text,
+        };
+        if value == "{" {
+            stack.push(Frame::Object(None));
+        } else if value == "[" {
+            stack.push(Frame::Array(0));
+        }
+    }
+    let segments: Vec<String> = stack
+        .iter()
+        .filter_map(|frame| match frame {
+            Frame::Object(Some(key)) => Some(format!(".{}", key)),
+            Frame::Array(count) if *count > 0 => Some(format!("[{}]", count - 1)),
+            _ => None,
+        })
+        .collect();
+    let path = segments.concat();
+    if path.is_empty() {
+        String::from(".")
+    } else {
+        path.trim_start_matches('.').to_string()
+    }
+}
+
+/// Splits a `"key": value` line into the unescaped key and the value
+fn split_key(text: &str) -> (String, &str) {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => {
+                let key = serde_json::from_str(&text[..=i]).unwrap_or_default();
+                let value = text[i + 1..].trim_start_matches(':').trim_start();
+                return (key, value);
+            }
+            _ => escaped = false,
+        }
+    }
+    (String::new(), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Address {
+        street: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        name: String,
+        age: u16,
+        address: Address,
+        tags: Vec<String>,
+    }
+
+    fn error_of(value: Value) -> (String, String) {
+        path_error::<User>(&value).unwrap()
+    }
+
+    #[test]
+    fn failure_paths() {
+        let user = serde_json::json!({
+            "name": "Robert",
+            "age": 18,
+            "address": { "street": "Rue" },
+            "tags": ["a", "b"],
+            "_model_version": 1
+        });
+        assert!(path_error::<User>(&user).is_none());
+
+        let mut wrong_age = user.clone();
+        wrong_age["age"] = Value::from("18");
+        assert_eq!(
+            error_of(wrong_age),
+            (
+                String::from("age"),
+                String::from(r#"invalid type: string "18", expected u16"#)
+            )
+        );
+
+        let mut wrong_street = user.clone();
+        wrong_street["address"]["street"] = Value::from(10);
+        assert_eq!(error_of(wrong_street).0, "address.street");
+
+        let mut wrong_tag = user.clone();
+        wrong_tag["tags"][1] = Value::Null;
+        assert_eq!(error_of(wrong_tag).0, "tags[1]");
+
+        let mut missing_street = user.clone();
+        missing_street["address"] = serde_json::json!({ "city": "Paris" });
+        assert_eq!(
+            error_of(missing_street),
+            (
+                String::from("address"),
+                String::from("missing field `street`")
+            )
+        );
+
+        let missing_name = serde_json::json!({ "age": 18 });
+        assert_eq!(error_of(missing_name).0, ".");
+    }
+
+    #[test]
+    fn escaped_keys() {
+        assert_eq!(
+            split_key(r#""a \"b\"": 10"#),
+            (String::from(r#"a "b""#), "10")
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// The facets of a query, see [`Query::facets`]: the distinct values of every faceted field along
+/// with their number of documents, the most frequent values first.
+///
+/// [`Query::facets`]: crate::query::Query::facets
+pub type Facets = HashMap<String, Vec<(Value, usize)>>;
+
+/// Renders the query counting the documents of `aql` per value of every field of `fields`, along
+/// with its bind variables. The field names and attribute paths are bound instead of being
+/// rendered in the query, any attribute name being allowed.
+pub fn facets_aql(aql: &str, fields: &[&str]) -> (String, HashMap<String, Value>) {
+    let mut bind_vars = HashMap::new();
+    let facets: Vec<String> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let name_var = format!("facet_name_{}", i);
+            let attributes: String = field
+                .split('.')
+                .enumerate()
+                .map(|(j, attribute)| {
+                    let attribute_var = format!("facet_attribute_{}_{}", i, j);
+                    let access = format!("[@{}]", attribute_var);
+                    bind_vars.insert(attribute_var, Value::from(attribute));
+                    access
+                })
+                .collect();
+            bind_vars.insert(name_var.clone(), Value::from(*field));
+            format!(
+                "[@{}]: (FOR facet in facet_documents \
+                    COLLECT value = facet{} WITH COUNT INTO count \
+                    SORT count DESC, value ASC \
+                    return [value, count])",
+                name_var, attributes
+            )
+        })
+        .collect();
+    let aql = format!(
+        "LET facet_documents = ({}) return {{ {} }}",
+        aql,
+        facets.join(", ")
+    );
+    (aql, bind_vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_a_collect_per_field() {
+        let (aql, bind_vars) = facets_aql(
+            "FOR a in Article return a",
+            &["status", "author.created-at"],
+        );
+        assert_eq!(
+            aql,
+            "LET facet_documents = (FOR a in Article return a) return { \
+                [@facet_name_0]: (FOR facet in facet_documents \
+                    COLLECT value = facet[@facet_attribute_0_0] WITH COUNT INTO count \
+                    SORT count DESC, value ASC return [value, count]), \
+                [@facet_name_1]: (FOR facet in facet_documents \
+                    COLLECT value = facet[@facet_attribute_1_0][@facet_attribute_1_1] \
+                    WITH COUNT INTO count \
+                    SORT count DESC, value ASC return [value, count]) \
+            }"
+        );
+        assert_eq!(bind_vars["facet_name_1"], "author.created-at");
+        assert_eq!(bind_vars["facet_attribute_1_0"], "author");
+        assert_eq!(bind_vars["facet_attribute_1_1"], "created-at");
+        assert_eq!(bind_vars.len(), 5);
+    }
+}
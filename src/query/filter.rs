@@ -1,8 +1,14 @@
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter, Result};
 
+use serde::{Deserialize, Serialize};
+
+use crate::query::utils::BoundValues;
 use crate::query::Comparison;
+use crate::Error;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 enum Operator {
     And,
     Or,
@@ -22,7 +28,25 @@ impl Display for Operator {
 }
 
 /// Allows to filter a query according to different [`Comparison`].
-#[derive(Clone, Debug)]
+///
+/// # Serialization
+///
+/// Filters are serialized as their serialized [`Comparison`] list along with the `and` or `or`
+/// operators joining them:
+///
+/// ```rust
+/// # use aragog::query::{Comparison, Filter};
+/// let filter: Filter = serde_json::from_str(r#"{
+///     "comparisons": [
+///         { "field": "age", "comparator": "greater_than", "value": 18 },
+///         { "field": "roles", "comparator": "contains", "value": "admin" }
+///     ],
+///     "operators": ["or"]
+/// }"#).unwrap();
+/// assert_eq!(filter.aql_str("a"), r#"a.age > 18 || "admin" IN a.roles"#);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "FilterDefinition")]
 pub struct Filter {
     comparisons: Vec<Comparison>,
     operators: Vec<Operator>,
@@ -110,6 +134,22 @@ impl Filter {
     /// ```
     #[must_use]
     pub fn aql_str(&self, collection_id: &str) -> String {
+        self.render(collection_id, None)
+    }
+
+    /// Renders `self`, passing the bound values as bind parameters if `bound_values` is set
+    /// The fields compared by the filter
+    pub(crate) fn fields(&self) -> impl Iterator<Item = &str> {
+        self.comparisons
+            .iter()
+            .filter_map(Comparison::compared_field)
+    }
+
+    pub(crate) fn render(
+        &self,
+        collection_id: &str,
+        mut bound_values: Option<&mut BoundValues>,
+    ) -> String {
         let mut res = String::new();
         for (i, comparison) in self.comparisons.iter().enumerate() {
             let operator_str = if i >= self.operators.len() {
@@ -120,10 +160,42 @@ impl Filter {
             res = format!(
                 "{} {}{}",
                 res,
-                comparison.aql_str(collection_id),
+                comparison.render(collection_id, bound_values.as_deref_mut()),
                 operator_str
             );
         }
         String::from(res.trim_start())
     }
 }
+
+/// The JSON representation of a [`Filter`]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterDefinition {
+    comparisons: Vec<Comparison>,
+    #[serde(default)]
+    operators: Vec<Operator>,
+}
+
+impl TryFrom<FilterDefinition> for Filter {
+    type Error = Error;
+
+    fn try_from(definition: FilterDefinition) -> std::result::Result<Self, Self::Error> {
+        if definition.comparisons.is_empty() {
+            return Err(Error::ValidationError(String::from(
+                "A filter expects at least one comparison",
+            )));
+        }
+        if definition.operators.len() + 1 != definition.comparisons.len() {
+            return Err(Error::ValidationError(format!(
+                "A filter of {} comparisons expects {} operators",
+                definition.comparisons.len(),
+                definition.comparisons.len() - 1
+            )));
+        }
+        Ok(Self {
+            comparisons: definition.comparisons,
+            operators: definition.operators,
+        })
+    }
+}
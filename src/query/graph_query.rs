@@ -1,9 +1,13 @@
 use std::fmt::{self, Display, Formatter};
 
-#[derive(Copy, Clone, Debug)]
+/// The direction of a graph traversal
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum GraphQueryDirection {
+    /// Follows the edges from `_from` to `_to`
     Outbound,
+    /// Follows the edges from `_to` to `_from`
     Inbound,
+    /// Follows the edges in both directions
     Any,
 }
 
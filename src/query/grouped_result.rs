@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::DatabaseRecord;
+
+/// A group of documents sharing the same key, returned by the queries grouping their documents
+/// with [`Query::collect_into`].
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::GroupedResult;
+/// # use aragog::Record;
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// #[derive(Record, Clone, Serialize, Deserialize)]
+/// pub struct Order {
+///     pub user_id: String,
+///     pub total: u32,
+/// }
+///
+/// let group: GroupedResult<String, Order> = serde_json::from_value(serde_json::json!({
+///     "key": "User/123",
+///     "documents": [
+///         { "_key": "1", "_id": "Order/1", "_rev": "1", "user_id": "User/123", "total": 10 },
+///         { "_key": "2", "_id": "Order/2", "_rev": "1", "user_id": "User/123", "total": 32 },
+///     ]
+/// })).unwrap();
+/// assert_eq!(group.key, "User/123");
+/// assert_eq!(group.documents.iter().map(|order| order.total).sum::<u32>(), 42);
+/// ```
+///
+/// [`Query::collect_into`]: crate::query::Query::collect_into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupedResult<K, T> {
+    /// The value of the grouping field shared by the documents
+    pub key: K,
+    /// The grouped documents
+    pub documents: Vec<DatabaseRecord<T>>,
+}
+
+/// The `COLLECT ... INTO ...` clause of a [`Query`]
+///
+/// [`Query`]: crate::query::Query
+#[derive(Debug, Clone)]
+pub struct CollectInto {
+    /// The variable of the grouping key
+    pub key: String,
+    /// The grouping field of the queried documents
+    pub field: String,
+    /// The variable of the grouped documents
+    pub into: String,
+}
+
+impl CollectInto {
+    /// Renders the clause and the `RETURN` statement of the groups of the `collection_id` documents
+    pub fn render(&self, collection_id: &str) -> String {
+        format!(
+            "COLLECT {key} = {id}.{field} INTO {into} = {id} return {{ key: {key}, documents: {into} }}",
+            key = self.key,
+            id = collection_id,
+            field = self.field,
+            into = self.into
+        )
+    }
+}
@@ -1,27 +1,53 @@
 #![allow(clippy::use_self)]
-use crate::query::graph_query::{GraphQueryData, GraphQueryDirection};
-use crate::query::operations::{AqlOperation, OperationContainer};
+use crate::query::graph_query::GraphQueryData;
+use crate::query::grouped_result::CollectInto;
+use crate::query::operations::{edge_identifier, AqlOperation, OperationContainer};
 use crate::query::query_id_helper::get_str_identifier;
-use crate::query::utils::{string_from_array, OptionalQueryString};
+use crate::query::related::{render_projection, RelatedQuery};
+use crate::query::utils::{string_from_array, BoundValues, OptionalQueryString};
 use crate::undefined_record::UndefinedRecord;
-use crate::{DatabaseAccess, Error, Record};
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+pub(crate) use facets::facets_aql;
+pub(crate) use query_cursor::delete_cursor;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 pub use {
-    comparison::Comparison, comparison::ComparisonBuilder, filter::Filter,
-    query_cursor::QueryCursor, query_result::QueryResult,
+    comparison::Comparison,
+    comparison::ComparisonBuilder,
+    comparison::ValueComparisonBuilder,
+    comparison::BOUND_ARRAY_CHUNK_SIZE,
+    deserialization_error::DeserializationError,
+    facets::Facets,
+    filter::Filter,
+    graph_query::GraphQueryDirection,
+    grouped_result::GroupedResult,
+    page::{Page, PageEnvelope},
+    query_cursor::QueryCursor,
+    query_result::QueryResult,
+    query_stats::{QueryStats, QueryWarning},
+    related::Related,
 };
 
 mod comparison;
+mod deserialization_error;
+mod facets;
 mod filter;
 mod graph_query;
+mod grouped_result;
 mod operations;
+mod page;
 mod query_cursor;
+mod query_definition;
 mod query_id_helper;
 mod query_result;
-mod utils;
+mod query_stats;
+mod related;
+
+pub(crate) use query_stats::QueryMetadata;
+pub(crate) mod utils;
 
 /// Macro to simplify the [`Query`] construction:
 ///
@@ -47,10 +73,13 @@ macro_rules! query {
 
 /// The direction for [`Query::sort`] method
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortDirection {
     /// Ascending
+    #[serde(alias = "Asc")]
     Asc,
     /// Descending
+    #[serde(alias = "Desc")]
     Desc,
 }
 
@@ -91,18 +120,55 @@ impl Display for SortDirection {
 /// # }
 /// ```
 ///
+/// # Serialization
+///
+/// The collection queries are serialized to and deserialized from JSON, so that API clients can
+/// send structured list queries. The operations are serialized in order, the filters as
+/// [`Filter`] definitions:
+///
+/// ```rust
+/// # use aragog::query::Query;
+/// let query: Query = serde_json::from_str(r#"{
+///     "collection": "User",
+///     "operations": [
+///         { "filter": { "comparisons": [{ "field": "age", "comparator": "greater_than", "value": 18 }] } },
+///         { "sort": { "field": "name", "direction": "asc" } },
+///         { "limit": { "limit": 20, "skip": 40 } }
+///     ]
+/// }"#).unwrap();
+/// assert_eq!(query.aql_str(), "FOR a in User FILTER a.age > 18 SORT a.name ASC LIMIT 40, 20 return a");
+/// ```
+///
+/// Only the `filter`, `sort`, `limit` and `sample` operations, the `distinct` flag, the index
+/// hints, the cursor ttl and the [`bind_vars`] have a JSON representation: the graph traversals,
+/// sub queries, unions, related documents and groupings fail to serialize. The [`unscoped`] flag
+/// isn't serialized: the default scope and the tenant filter of the queried record still apply
+/// to a deserialized query run through [`Record::get`].
+///
 /// [`aql_str`]: Self::aql_str
+/// [`bind_vars`]: Self::bind_vars
+/// [`unscoped`]: Self::unscoped
+/// [`Record::get`]: crate::Record::get
 #[derive(Clone, Debug)]
 pub struct Query {
     with_collections: OptionalQueryString,
     collection: String,
     graph_data: Option<GraphQueryData>,
     operations: OperationContainer,
+    default_scope: Option<Filter>,
+    unscoped: bool,
+    tenant_field: Option<String>,
     distinct: bool,
-    sub_query: Option<String>,
+    collect_into: Option<CollectInto>,
+    related: Vec<RelatedQuery>,
+    sub_query: Option<Box<Query>>,
+    union: Vec<Query>,
     item_identifier: usize,
+    index_hint: Vec<String>,
+    force_index_hint: bool,
     /// bind parameters to substitute in query string
     pub bind_vars: HashMap<String, Value>,
+    pub(crate) cursor_ttl: Option<u32>,
 }
 
 impl Query {
@@ -127,10 +193,19 @@ impl Query {
             collection: String::from(collection_name),
             graph_data: None,
             operations: OperationContainer(vec![]),
+            default_scope: None,
+            unscoped: false,
+            tenant_field: None,
             distinct: false,
+            collect_into: None,
+            related: vec![],
             sub_query: None,
+            union: vec![],
             item_identifier: 0,
+            index_hint: vec![],
+            force_index_hint: false,
             bind_vars: HashMap::default(),
+            cursor_ttl: None,
         }
     }
 
@@ -355,7 +430,7 @@ impl Query {
             max,
             named_graph,
         });
-        self.sub_query = Some(query.aql_str());
+        self.sub_query = Some(Box::new(query));
         self
     }
 
@@ -455,6 +530,48 @@ impl Query {
         self.join(min, max, query, GraphQueryDirection::Any, named_graph)
     }
 
+    /// Creates a new `Query` on the documents of every query of `queries`, to query several
+    /// collections at once. The documents of the queries are concatenated with the AQL `UNION`
+    /// function, the query operations apply to the concatenated documents.
+    ///
+    /// The documents of various collections are retrieved as [`UndefinedRecord`] with
+    /// [`raw_call`], [`QueryResult::get_records`] deserializing the documents of a collection.
+    ///
+    /// # Note
+    ///
+    /// The record default scopes don't apply to the united queries, the record tenant filters the
+    /// united documents
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::union(vec![Query::new("Cats"), Query::new("Dogs")])
+    ///     .filter(Comparison::field("age").greater_than(2).into());
+    /// assert_eq!(query.aql_str(), String::from("\
+    ///     FOR b in UNION((FOR a in Cats return a), (FOR a in Dogs return a)) \
+    ///         FILTER b.age > 2 \
+    ///         return b\
+    /// "));
+    /// ```
+    ///
+    /// [`UndefinedRecord`]: crate::UndefinedRecord
+    /// [`raw_call`]: Self::raw_call
+    /// [`QueryResult::get_records`]: crate::query::QueryResult::get_records
+    #[must_use]
+    pub fn union(queries: Vec<Self>) -> Self {
+        let item_identifier = queries
+            .iter()
+            .map(|query| query.item_identifier + 1)
+            .max()
+            .unwrap_or_default();
+        Self {
+            union: queries,
+            item_identifier,
+            ..Self::new("")
+        }
+    }
+
     /// Allow the current traversing `Query` to filter the traversed collections and avoid potentian deadlocks.
     ///
     /// # Arguments
@@ -532,6 +649,178 @@ impl Query {
         self
     }
 
+    /// Allows to filter a current `Query` by a reusable named scope, declared on the record with
+    /// the `#[scope(name = "condition")]` attribute of the `Record` derive macro.
+    ///
+    /// Every scope is rendered as its own `FILTER` operation, chained scopes must all match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// # use aragog::Record;
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// #[scope(active = "active == true", adults = "age >= 18 && age < 130")]
+    /// pub struct User {
+    ///     pub active: bool,
+    ///     pub age: u16,
+    /// }
+    ///
+    /// let query = User::query().scope(User::active()).scope(User::adults());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in User FILTER a.active == true FILTER a.age >= 18 && a.age < 130 return a"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn scope(self, scope: Filter) -> Self {
+        self.filter(scope)
+    }
+
+    /// Removes the record default scope from the current `Query`, declared on the record with the
+    /// `#[default_scope("condition")]` attribute of the `Record` derive macro.
+    ///
+    /// Without it the default scope is applied to the queries built with [`Record::query`] and to
+    /// the queries of the record collection run through [`Record::get`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// # use aragog::Record;
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// #[default_scope("archived == false")]
+    /// pub struct Article {
+    ///     pub archived: bool,
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Article::query().aql_str(),
+    ///     "FOR a in Article FILTER a.archived == false return a"
+    /// );
+    /// assert_eq!(Article::query().unscoped().aql_str(), "FOR a in Article return a");
+    /// ```
+    ///
+    /// [`Record::query`]: crate::Record::query
+    /// [`Record::get`]: crate::Record::get
+    #[inline]
+    #[must_use]
+    pub fn unscoped(mut self) -> Self {
+        self.default_scope = None;
+        self.unscoped = true;
+        self
+    }
+
+    /// Checks that the current `Query` only filters and sorts on the
+    /// [`allowed_query_fields`] of `T`, before running queries built from user input like
+    /// deserialized [`Filter`]s. Catches the typos and blocks the probing of the hidden fields.
+    ///
+    /// Only the top level attribute of the nested fields is checked (`address` for
+    /// `address.city`). The [`statement`] comparisons, the edge operations and the sub queries
+    /// are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::ValidationError`] if a field is not allowed, or if the query iterates
+    /// on an other collection than the `T` one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// # use aragog::Record;
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {
+    ///     pub name: String,
+    ///     #[db(redact)]
+    ///     pub password: String,
+    /// }
+    ///
+    /// let query = User::query().filter(Comparison::field("name").equals_str("Robert").into());
+    /// assert!(query.validate_fields::<User>().is_ok());
+    /// let query = User::query().filter(Comparison::field("password").like("a%").into());
+    /// assert!(query.validate_fields::<User>().is_err());
+    /// ```
+    ///
+    /// [`allowed_query_fields`]: crate::Record::allowed_query_fields
+    /// [`statement`]: crate::query::Comparison::statement
+    pub fn validate_fields<T: Record>(&self) -> Result<(), Error> {
+        if self.graph_data.is_none()
+            && self.union.is_empty()
+            && self.collection != T::COLLECTION_NAME
+        {
+            return Err(Error::ValidationError(format!(
+                "The query of {} can't be validated against the {} fields",
+                self.collection,
+                T::COLLECTION_NAME
+            )));
+        }
+        let allowed = match T::allowed_query_fields() {
+            Some(allowed) => allowed,
+            None => return Ok(()),
+        };
+        for field in self.operations.fields() {
+            let attribute = field.split('.').next().unwrap_or_default();
+            if !["_key", "_id", "_rev"].contains(&attribute) && !allowed.contains(&attribute) {
+                return Err(Error::ValidationError(format!(
+                    r#""{}" is not a queryable field of {}"#,
+                    field,
+                    T::COLLECTION_NAME
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the record default scope, unless the query is [`unscoped`] or already scoped
+    ///
+    /// [`unscoped`]: Self::unscoped
+    pub(crate) fn with_default_scope(mut self, default_scope: Option<Filter>) -> Self {
+        if !self.unscoped && self.default_scope.is_none() {
+            self.default_scope = default_scope;
+        }
+        self
+    }
+
+    /// Applies the `tenant` filter to the `T` documents returned by the current query, including
+    /// traversals and unions, and the `T` default scope if it iterates on the `T` collection.
+    /// The tenant filter can't be removed with [`unscoped`].
+    ///
+    /// [`unscoped`]: Self::unscoped
+    pub(crate) fn scoped_for<T: Record>(&self, tenant: Option<&str>) -> Cow<'_, Self> {
+        let mut query = Cow::Borrowed(self);
+        if let (Some(field), Some(tenant)) = (T::TENANT_FIELD, tenant) {
+            let query = query.to_mut();
+            query
+                .bind_vars
+                .insert(String::from("tenant"), Value::from(tenant));
+            // The returned documents are iterated by the innermost joined query
+            let mut returning = query;
+            while let Some(sub_query) = returning.sub_query.as_deref_mut() {
+                returning = sub_query;
+            }
+            returning.tenant_field = Some(field.to_string());
+        }
+        if self.graph_data.is_some()
+            || !self.union.is_empty()
+            || self.collection != T::COLLECTION_NAME
+            || self.unscoped
+            || self.default_scope.is_some()
+        {
+            return query;
+        }
+        match T::default_scope() {
+            Some(default_scope) => {
+                Cow::Owned(query.into_owned().with_default_scope(Some(default_scope)))
+            }
+            None => query,
+        }
+    }
+
     /// Allows to filter a current `Query` by different comparisons but using the `PRUNE` keyword.
     ///
     /// # Note
@@ -553,6 +842,59 @@ impl Query {
         self
     }
 
+    /// Allows to filter a current graph `Query` by comparisons on the traversed edges instead of
+    /// the vertices, like the edge relation type or weight.
+    ///
+    /// The comparisons reference the edge variable, declared along with the vertex variable
+    /// (`FOR a, a_e IN ...`).
+    ///
+    /// # Note
+    ///
+    /// The `filter_edges` operation only works for graph queries
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, Comparison};
+    /// let query = Query::outbound(1, 2, "ChildOf", "User/123")
+    ///     .filter_edges(Comparison::field("kind").equals_str("adopted").into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_e in 1..2 OUTBOUND 'User/123' ChildOf FILTER a_e.kind == \"adopted\" return a"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn filter_edges(mut self, filter: Filter) -> Self {
+        self.operations.0.push(AqlOperation::EdgeFilter(filter));
+        self
+    }
+
+    /// Allows to stop a current graph `Query` traversal by comparisons on the traversed edges
+    /// instead of the vertices, using the `PRUNE` keyword.
+    ///
+    /// # Note
+    ///
+    /// The `prune_edges` operation only works for graph queries (See `ArangoDB` documentation)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, Comparison};
+    /// let query = Query::outbound(1, 5, "ChildOf", "User/123")
+    ///     .prune_edges(Comparison::field("weight").greater_than(10).into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_e in 1..5 OUTBOUND 'User/123' ChildOf PRUNE a_e.weight > 10 return a"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn prune_edges(mut self, filter: Filter) -> Self {
+        self.operations.0.push(AqlOperation::EdgePrune(filter));
+        self
+    }
+
     /// Allows to paginate a current `Query`.
     ///
     /// # Arguments
@@ -574,6 +916,134 @@ impl Query {
         self
     }
 
+    /// Retrieves `count` random documents of the current `Query`, rendered as
+    /// `SORT RAND() LIMIT count`.
+    ///
+    /// # Performance
+    ///
+    /// Every document matching the previous operations is read and sorted to pick the sample, which
+    /// is fine for small collections or selective filters but scans the whole collection
+    /// otherwise. Filter the documents first, on indexed fields, to sample large collections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// // 5 random products in stock
+    /// let query = Query::new("Product")
+    ///     .filter(Comparison::field("stock").greater_than(0).into())
+    ///     .sample(5);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Product FILTER a.stock > 0 SORT RAND() LIMIT 5 return a"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sample(mut self, count: u32) -> Self {
+        self.operations.0.push(AqlOperation::Sample { count });
+        self
+    }
+
+    /// Allows to paginate a current `Query` by page number, starting at `1`. The result can be
+    /// converted with [`QueryResult::into_page`] to be serialized with its pagination metadata.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// // The third page of 20 users
+    /// let query = Query::new("User").paginate(3, 20);
+    /// assert_eq!(query.aql_str(), "FOR a in User LIMIT 40, 20 return a");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn paginate(self, page: u32, per_page: u32) -> Self {
+        let skip = page.max(1).saturating_sub(1).saturating_mul(per_page);
+        self.limit(per_page, Some(skip))
+    }
+
+    /// Sets the time to live of the server side cursor of the batched calls, in seconds.
+    ///
+    /// The cursor is removed from the server if not used during that time, the next batches can't
+    /// be retrieved afterwards. The `ArangoDB` default is 30 seconds.
+    ///
+    /// # Note
+    ///
+    /// The server side cursor is also removed when the [`QueryCursor`] is dropped before its last
+    /// batch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// // The cursor will be kept 5 minutes between two batches
+    /// let query = Query::new("User").cursor_ttl(300);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn cursor_ttl(mut self, ttl: u32) -> Self {
+        self.cursor_ttl = Some(ttl);
+        self
+    }
+
+    /// Hints the optimizer to use the `indexes` of the queried collection, in order of preference,
+    /// rendered as an `OPTIONS { indexHint: [...] }` clause of the `FOR` statement. Useful to pin
+    /// the query plan of hot paths when the optimizer chooses badly.
+    ///
+    /// The hint is ignored by `ArangoDB` if none of the indexes can be used, see
+    /// [`force_index_hint`] to fail instead.
+    ///
+    /// # Note
+    ///
+    /// The hint only applies to collection queries, it is not rendered for graph traversals and
+    /// [`union`] queries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::new("User")
+    ///     .index_hint(&["email_idx"])
+    ///     .filter(Comparison::field("email").equals_str("robert@mail.com").into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in User OPTIONS { indexHint: [\"email_idx\"] } \
+    ///         FILTER a.email == \"robert@mail.com\" return a"
+    /// );
+    /// ```
+    ///
+    /// [`force_index_hint`]: Self::force_index_hint
+    /// [`union`]: Self::union
+    #[must_use]
+    pub fn index_hint(mut self, indexes: &[&str]) -> Self {
+        self.index_hint = indexes.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Makes the query fail if the indexes of [`index_hint`] can't be used, rendered as
+    /// `forceIndexHint: true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("User").index_hint(&["email_idx", "name_idx"]).force_index_hint();
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in User OPTIONS { indexHint: [\"email_idx\",\"name_idx\"], forceIndexHint: true } \
+    ///         return a"
+    /// );
+    /// ```
+    ///
+    /// [`index_hint`]: Self::index_hint
+    #[inline]
+    #[must_use]
+    pub const fn force_index_hint(mut self) -> Self {
+        self.force_index_hint = true;
+        self
+    }
+
     /// Allows to avoid duplicate elements for a `Query`.
     ///
     /// # Note
@@ -595,6 +1065,91 @@ impl Query {
         self
     }
 
+    /// Groups the queried documents by their `field` value, keeping the documents of every group.
+    /// The query returns a group per `field` value, deserializable into a [`GroupedResult`], to
+    /// retrieve for example every user with their orders in a single query.
+    ///
+    /// The `key` and `into` arguments name the AQL variables of the grouping key and of the
+    /// grouped documents. The grouping applies to the last sub query and replaces its `RETURN`
+    /// statement, the [`distinct`] option being ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("Order").collect_into("user", "user_id", "orders");
+    /// assert_eq!(query.aql_str(), String::from("\
+    ///     FOR a in Order \
+    ///         COLLECT user = a.user_id INTO orders = a \
+    ///         return { key: user, documents: orders }\
+    /// "));
+    /// ```
+    ///
+    /// [`distinct`]: Self::distinct
+    /// [`GroupedResult`]: crate::query::GroupedResult
+    #[must_use]
+    pub fn collect_into(mut self, key: &str, field: &str, into: &str) -> Self {
+        if let Some(sub_query) = self.sub_query {
+            self.sub_query = Some(Box::new(sub_query.collect_into(key, field, into)));
+        } else {
+            self.collect_into = Some(CollectInto {
+                key: key.to_string(),
+                field: field.to_string(),
+                into: into.to_string(),
+            });
+        }
+        self
+    }
+
+    /// Loads the documents related to the queried ones through `edge_collection`, in the same
+    /// query. The related documents are added to the returned documents as an `alias` array,
+    /// the results being deserializable into a [`Related`] record.
+    ///
+    /// The related documents apply to the last sub query and can be combined with [`distinct`],
+    /// not with [`collect_into`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::new("User")
+    ///     .with_related("AuthoredBy", GraphQueryDirection::Inbound, "posts")
+    ///     .with_related("MemberOf", GraphQueryDirection::Outbound, "groups");
+    /// assert_eq!(query.aql_str(), String::from("\
+    ///     FOR a in User \
+    ///         return MERGE(a, { \
+    ///             posts: (FOR related_0 in 1..1 INBOUND a AuthoredBy return related_0), \
+    ///             groups: (FOR related_1 in 1..1 OUTBOUND a MemberOf return related_1) \
+    ///         })\
+    /// "));
+    /// ```
+    ///
+    /// [`Related`]: crate::query::Related
+    /// [`distinct`]: Self::distinct
+    /// [`collect_into`]: Self::collect_into
+    #[must_use]
+    pub fn with_related(
+        mut self,
+        edge_collection: &str,
+        direction: GraphQueryDirection,
+        alias: &str,
+    ) -> Self {
+        if let Some(sub_query) = self.sub_query {
+            self.sub_query = Some(Box::new(sub_query.with_related(
+                edge_collection,
+                direction,
+                alias,
+            )));
+        } else {
+            self.related.push(RelatedQuery {
+                edge_collection: edge_collection.to_string(),
+                direction,
+                alias: alias.to_string(),
+            });
+        }
+        self
+    }
+
     /// Renders the AQL string corresponding to the current `Query`
     ///
     /// # Example
@@ -630,16 +1185,92 @@ impl Query {
     ///         return DISTINCT a\
     /// "));
     /// ```
+    ///
+    /// # Note
+    ///
+    /// The literal values are rendered in the query string, bound comparisons like
+    /// [`in_bound_array`] are rendered as bind parameters. The queries are executed with their
+    /// literals bound, as rendered by [`aql_with_bind_vars`].
+    ///
+    /// [`in_bound_array`]: crate::query::ComparisonBuilder::in_bound_array
+    /// [`aql_with_bind_vars`]: Self::aql_with_bind_vars
     #[inline]
     #[must_use]
     pub fn aql_str(&self) -> String {
+        self.render(&mut BoundValues::default())
+    }
+
+    /// Renders the AQL string corresponding to the current `Query` along with every bind
+    /// parameter to send with it: the [`bind_vars`] and the values of the bound comparisons.
+    ///
+    /// If `bind_literals` is set, the literal values of the comparisons and of the `LIMIT`
+    /// operations are passed as bind parameters as well. Queries of the same shape then render
+    /// the same AQL string whatever the values, letting `ArangoDB` reuse the cached query plans.
+    /// The AQL keywords (`null`, `true`, `false`) and the expressions, like
+    /// `Comparison::field("a").equals("@bind_var")`, stay in the query string.
+    ///
+    /// The bind parameters are named after their position in the query (`@value_0`,
+    /// `@value_1`...).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    /// # use serde_json::json;
+    /// let query = Query::new("User")
+    ///     .filter(Filter::new(Comparison::field("_key").in_bound_array(&["1", "2", "3"])))
+    ///     .filter(Filter::new(Comparison::field("age").greater_than(18)))
+    ///     .bind_var("role", "admin");
+    /// let (aql, bind_vars) = query.aql_with_bind_vars(false);
+    /// assert_eq!(aql, "FOR a in User FILTER a._key IN @value_0 FILTER a.age > 18 return a");
+    /// assert_eq!(bind_vars["value_0"], json!(["1", "2", "3"]));
+    /// assert_eq!(bind_vars["role"], json!("admin"));
+    ///
+    /// let (aql, bind_vars) = query.aql_with_bind_vars(true);
+    /// assert_eq!(aql, "FOR a in User FILTER a._key IN @value_0 FILTER a.age > @value_1 return a");
+    /// assert_eq!(bind_vars["value_1"], json!(18));
+    /// ```
+    ///
+    /// [`bind_vars`]: Self::bind_vars
+    #[must_use]
+    pub fn aql_with_bind_vars(&self, bind_literals: bool) -> (String, HashMap<String, Value>) {
+        let mut bound_values = BoundValues::new(bind_literals);
+        let aql = self.render(&mut bound_values);
+        let mut bind_vars = HashMap::new();
+        self.collect_bind_vars(&mut bind_vars);
+        bind_vars.extend(bound_values.into_bind_vars());
+        (aql, bind_vars)
+    }
+
+    /// Collects the explicit bind parameters of `self` and of its sub queries
+    fn collect_bind_vars(&self, bind_vars: &mut HashMap<String, Value>) {
+        if let Some(sub_query) = &self.sub_query {
+            sub_query.collect_bind_vars(bind_vars);
+        }
+        for query in &self.union {
+            query.collect_bind_vars(bind_vars);
+        }
+        bind_vars.extend(
+            self.bind_vars
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+
+    fn render(&self, bound_values: &mut BoundValues) -> String {
         let collection_id = get_str_identifier(self.item_identifier);
         let mut res = self.with_collections.to_string();
         if let Some(graph_data) = &self.graph_data {
+            let edge_variable = if self.operations.has_edge_operations() {
+                format!(", {}", edge_identifier(&collection_id))
+            } else {
+                String::new()
+            };
             res = format!(
-                "{}FOR {} in {}..{} {} {} {}{}",
+                "{}FOR {}{} in {}..{} {} {} {}{}",
                 res,
                 collection_id,
+                edge_variable,
                 graph_data.min,
                 graph_data.max,
                 graph_data.direction,
@@ -647,25 +1278,89 @@ impl Query {
                 if graph_data.named_graph { "GRAPH " } else { "" },
                 &self.collection
             );
-        } else {
+        } else if self.union.is_empty() {
             res = format!("{}FOR {} in {}", res, collection_id, &self.collection);
+            if !self.index_hint.is_empty() {
+                res = format!(
+                    "{} OPTIONS {{ indexHint: {}{} }}",
+                    res,
+                    Value::from(self.index_hint.clone()),
+                    if self.force_index_hint {
+                        ", forceIndexHint: true"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        } else {
+            res = format!(
+                "{}FOR {} in {}",
+                res,
+                collection_id,
+                self.render_union(bound_values)
+            );
+        }
+        if let Some(field) = &self.tenant_field {
+            res = format!("{} FILTER {}.{} == @tenant", res, collection_id, field);
+        }
+        if let Some(default_scope) = &self.default_scope {
+            res = format!(
+                "{} FILTER {}",
+                res,
+                default_scope.render(&collection_id, Some(&mut *bound_values))
+            );
         }
         if !self.operations.0.is_empty() {
-            res = format!("{} {}", res, self.operations.aql_str(&collection_id));
+            res = format!(
+                "{} {}",
+                res,
+                self.operations.render(&collection_id, bound_values)
+            );
         }
         if let Some(sub_query) = &self.sub_query {
-            res = format!("{} {}", res, sub_query);
+            res = format!("{} {}", res, sub_query.render(bound_values));
+        } else if let Some(collect_into) = &self.collect_into {
+            res = format!("{} {}", res, collect_into.render(&collection_id));
         } else {
             res = format!(
                 "{} return {}{}",
                 res,
                 if self.distinct { "DISTINCT " } else { "" },
-                &collection_id
+                if self.related.is_empty() {
+                    collection_id
+                } else {
+                    render_projection(&collection_id, &self.related)
+                }
             );
         }
         res
     }
 
+    /// Renders the concatenation of the [`union`] queries
+    ///
+    /// [`union`]: Self::union
+    fn render_union(&self, bound_values: &mut BoundValues) -> String {
+        let queries: Vec<String> = self
+            .union
+            .iter()
+            .map(|query| format!("({})", query.render(bound_values)))
+            .collect();
+        // `UNION` requires at least two arrays
+        if queries.len() == 1 {
+            queries.join("")
+        } else {
+            format!("UNION({})", queries.join(", "))
+        }
+    }
+
+    /// Does the query contain a `LIMIT` operation
+    pub(crate) fn has_limit(&self) -> bool {
+        self.operations
+            .0
+            .iter()
+            .any(|operation| matches!(operation, AqlOperation::Limit { .. }))
+    }
+
     /// Finds all documents in database matching the current `Query`.
     /// This will return a wrapper for `serde_json`::`Value` as an `UndefinedRecord`
     ///
@@ -699,6 +1394,79 @@ impl Query {
         T::get(self, db_accessor).await
     }
 
+    /// Finds the groups of records of the current `Query`, grouping its documents with
+    /// [`collect_into`].
+    ///
+    /// # Note
+    /// Simple wrapper for [`DatabaseRecord`]::[`get_grouped`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error or if a group doesn't match `GroupedResult<K, T>`
+    ///
+    /// [`collect_into`]: Self::collect_into
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_grouped`]: crate::DatabaseRecord::get_grouped
+    #[maybe_async::maybe_async]
+    pub async fn call_grouped<D, K, T>(
+        &self,
+        db_accessor: &D,
+    ) -> Result<Vec<GroupedResult<K, T>>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        K: serde::de::DeserializeOwned,
+        T: Record + Send,
+    {
+        DatabaseRecord::<T>::get_grouped(self, db_accessor).await
+    }
+
+    /// Finds all records in database matching the current `Query` along with their related
+    /// documents, loaded with [`with_related`].
+    ///
+    /// # Note
+    /// Simple wrapper for [`DatabaseRecord`]::[`get_with_related`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error or if a document doesn't match `Related<T, R>`
+    ///
+    /// [`with_related`]: Self::with_related
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_with_related`]: crate::DatabaseRecord::get_with_related
+    #[maybe_async::maybe_async]
+    pub async fn call_with_related<D, T, R>(
+        &self,
+        db_accessor: &D,
+    ) -> Result<Vec<Related<T, R>>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+        R: serde::de::DeserializeOwned,
+    {
+        DatabaseRecord::<T>::get_with_related(self, db_accessor).await
+    }
+
+    /// Counts the records matching the current `Query` per value of every field of `fields`, for
+    /// faceted listings.
+    ///
+    /// # Note
+    /// Simple wrapper for [`DatabaseRecord`]::[`facets`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on query error
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`facets`]: crate::DatabaseRecord::facets
+    #[maybe_async::maybe_async]
+    pub async fn facets<D, T>(&self, fields: &[&str], db_accessor: &D) -> Result<Facets, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        DatabaseRecord::<T>::facets(self, fields, db_accessor).await
+    }
+
     /// Finds all documents in database matching the current `Query` using batches.
     /// This will return a wrapper for `serde_json`::`Value` as an `UndefinedRecord` inside a cursor.
     ///
@@ -1,9 +1,12 @@
+use crate::query::utils::BoundValues;
 use crate::query::{Filter, SortDirection};
 
 #[derive(Debug, Clone)]
 pub enum AqlOperation {
     Filter(Filter),
     Prune(Filter),
+    EdgeFilter(Filter),
+    EdgePrune(Filter),
     Limit {
         skip: Option<u32>,
         limit: u32,
@@ -12,14 +15,61 @@ pub enum AqlOperation {
         field: String,
         direction: SortDirection,
     },
+    Sample {
+        count: u32,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub struct OperationContainer(pub Vec<AqlOperation>);
 
+/// Renders a `LIMIT` value, as a bind parameter if the literals are bound
+fn literal(value: u32, bound_values: &mut BoundValues) -> String {
+    if bound_values.bind_literals {
+        bound_values.bind(value.into())
+    } else {
+        value.to_string()
+    }
+}
+
+/// The traversed edge variable name of a graph query item
+#[must_use]
+pub fn edge_identifier(collection_id: &str) -> String {
+    format!("{}_e", collection_id)
+}
+
 impl OperationContainer {
+    /// The document fields filtered and sorted by the operations, the edge fields excluded
+    #[must_use]
+    pub fn fields(&self) -> Vec<&str> {
+        self.0
+            .iter()
+            .flat_map(|operation| -> Box<dyn Iterator<Item = &str> + '_> {
+                match operation {
+                    AqlOperation::Filter(filter) | AqlOperation::Prune(filter) => {
+                        Box::new(filter.fields())
+                    }
+                    AqlOperation::Sort { field, .. } => Box::new(std::iter::once(field.as_str())),
+                    _ => Box::new(std::iter::empty()),
+                }
+            })
+            .collect()
+    }
+
+    /// Does the container reference the traversed edge variable
     #[must_use]
-    pub fn aql_str(&self, collection_id: &str) -> String {
+    pub fn has_edge_operations(&self) -> bool {
+        self.0.iter().any(|operation| {
+            matches!(
+                operation,
+                AqlOperation::EdgeFilter(_) | AqlOperation::EdgePrune(_)
+            )
+        })
+    }
+
+    /// Renders the operations, passing the bound values as bind parameters
+    #[must_use]
+    pub fn render(&self, collection_id: &str, bound_values: &mut BoundValues) -> String {
         let mut res = String::new();
         let mut last_was_sort = false;
         for operation in &self.0 {
@@ -27,17 +77,56 @@ impl OperationContainer {
                 AqlOperation::Limit { skip, limit } => {
                     let skip_str = match skip {
                         None => String::new(),
-                        Some(val) => format!("{}, ", val),
+                        Some(val) => format!("{}, ", literal(*val, bound_values)),
                     };
-                    res = format!("{} LIMIT {}{}", res, skip_str, limit);
+                    res = format!(
+                        "{} LIMIT {}{}",
+                        res,
+                        skip_str,
+                        literal(*limit, bound_values)
+                    );
+                    last_was_sort = false;
+                }
+                AqlOperation::Sample { count } => {
+                    res = format!(
+                        "{} SORT RAND() LIMIT {}",
+                        res,
+                        literal(*count, bound_values)
+                    );
                     last_was_sort = false;
                 }
                 AqlOperation::Filter(filter) => {
-                    res = format!("{} FILTER {}", res, filter.aql_str(collection_id));
+                    res = format!(
+                        "{} FILTER {}",
+                        res,
+                        filter.render(collection_id, Some(&mut *bound_values))
+                    );
                     last_was_sort = false;
                 }
                 AqlOperation::Prune(filter) => {
-                    res = format!("{} PRUNE {}", res, filter.aql_str(collection_id));
+                    res = format!(
+                        "{} PRUNE {}",
+                        res,
+                        filter.render(collection_id, Some(&mut *bound_values))
+                    );
+                    last_was_sort = false;
+                }
+                AqlOperation::EdgeFilter(filter) => {
+                    let edge_id = edge_identifier(collection_id);
+                    res = format!(
+                        "{} FILTER {}",
+                        res,
+                        filter.render(&edge_id, Some(&mut *bound_values))
+                    );
+                    last_was_sort = false;
+                }
+                AqlOperation::EdgePrune(filter) => {
+                    let edge_id = edge_identifier(collection_id);
+                    res = format!(
+                        "{} PRUNE {}",
+                        res,
+                        filter.render(&edge_id, Some(&mut *bound_values))
+                    );
                     last_was_sort = false;
                 }
                 AqlOperation::Sort { field, direction } => {
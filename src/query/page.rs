@@ -0,0 +1,206 @@
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::query::QueryResult;
+use crate::{DatabaseRecord, Record};
+
+/// The keys of the serialized [`Page`] and [`QueryResult`] envelopes:
+/// `{ "data": [...], "meta": { "total": 42, "page": 1, "per_page": 10 } }` by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageEnvelope {
+    /// The key of the records array, `data` by default
+    pub data: String,
+    /// The key of the pagination metadata object, `meta` by default
+    pub meta: String,
+}
+
+impl Default for PageEnvelope {
+    fn default() -> Self {
+        Self {
+            data: String::from("data"),
+            meta: String::from("meta"),
+        }
+    }
+}
+
+impl PageEnvelope {
+    /// Instantiates an envelope with the given records and metadata keys
+    #[must_use]
+    pub fn new(data: &str, meta: &str) -> Self {
+        Self {
+            data: data.to_string(),
+            meta: meta.to_string(),
+        }
+    }
+}
+
+/// A page of records, serialized with its pagination metadata to be returned from API handlers
+/// directly. See [`Query::paginate`] and [`QueryResult::into_page`].
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{DatabaseRecord, Record};
+/// # use aragog::query::{PageEnvelope, QueryResult};
+/// # use serde::{Serialize, Deserialize};
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// pub struct User {
+///     pub name: String,
+/// }
+///
+/// # let users: QueryResult<User> = QueryResult::new(vec![]);
+/// let page = users.into_page(2, 10).with_envelope(PageEnvelope::new("users", "pagination"));
+/// assert_eq!(
+///     serde_json::to_value(&page).unwrap(),
+///     serde_json::json!({ "users": [], "pagination": { "total": 10, "page": 2, "per_page": 10 } })
+/// );
+/// ```
+///
+/// [`Query::paginate`]: crate::query::Query::paginate
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The records of the page
+    pub records: Vec<DatabaseRecord<T>>,
+    /// The total number of records matching the query, on every page
+    pub total: usize,
+    /// The page number, starting at `1`
+    pub page: u32,
+    /// The maximum number of records per page
+    pub per_page: u32,
+    /// The serialization envelope keys
+    pub envelope: PageEnvelope,
+}
+
+impl<T> Page<T> {
+    /// Sets the serialization envelope keys
+    #[must_use]
+    pub fn with_envelope(mut self, envelope: PageEnvelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// The number of pages, `0` if no record matches
+    #[must_use]
+    pub fn total_pages(&self) -> usize {
+        let per_page = self.per_page.max(1) as usize;
+        (self.total + per_page - 1) / per_page
+    }
+
+    /// Returns `true` if there are records on the next pages
+    #[must_use]
+    pub fn has_next_page(&self) -> bool {
+        (self.page as usize) < self.total_pages()
+    }
+}
+
+impl<T: Record> QueryResult<T> {
+    /// Converts the result of a [`Query::paginate`] query to a page. The total number of records
+    /// is the query full count.
+    ///
+    /// [`Query::paginate`]: crate::query::Query::paginate
+    #[must_use]
+    pub fn into_page(self, page: u32, per_page: u32) -> Page<T> {
+        let page = page.max(1);
+        let skipped = (page - 1) as usize * per_page as usize;
+        let total = self.full_count().unwrap_or_else(|| skipped + self.len());
+        Page {
+            records: self.0,
+            total,
+            page,
+            per_page,
+            envelope: PageEnvelope::default(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PageMeta {
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    per_page: Option<u32>,
+}
+
+fn serialize_envelope<T, S>(
+    serializer: S,
+    envelope: &PageEnvelope,
+    records: &[DatabaseRecord<T>],
+    meta: &PageMeta,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry(&envelope.data, records)?;
+    map.serialize_entry(&envelope.meta, meta)?;
+    map.end()
+}
+
+impl<T: Serialize> Serialize for Page<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let meta = PageMeta {
+            total: self.total,
+            page: Some(self.page),
+            per_page: Some(self.per_page),
+        };
+        serialize_envelope(serializer, &self.envelope, &self.records, &meta)
+    }
+}
+
+/// Serializes the records in the default [`PageEnvelope`], the total being the query full count
+/// if available: `{ "data": [...], "meta": { "total": 42 } }`
+impl<T: Serialize> Serialize for QueryResult<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let meta = PageMeta {
+            total: self.full_count().unwrap_or(self.0.len()),
+            page: None,
+            per_page: None,
+        };
+        serialize_envelope(serializer, &PageEnvelope::default(), &self.0, &meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocumentId, DocumentKey};
+
+    fn page(total: usize, page: u32) -> Page<serde_json::Value> {
+        Page {
+            records: Vec::new(),
+            total,
+            page,
+            per_page: 10,
+            envelope: PageEnvelope::default(),
+        }
+    }
+
+    #[test]
+    fn page_count() {
+        assert_eq!(page(0, 1).total_pages(), 0);
+        assert_eq!(page(10, 1).total_pages(), 1);
+        assert_eq!(page(11, 1).total_pages(), 2);
+        assert!(page(11, 1).has_next_page());
+        assert!(!page(11, 2).has_next_page());
+    }
+
+    #[test]
+    fn envelope() {
+        let mut page = page(12, 2);
+        page.records.push(DatabaseRecord {
+            key: DocumentKey::new_unchecked(String::from("1")),
+            id: DocumentId::new_unchecked(String::from("User/1")),
+            rev: String::from("a"),
+            record: serde_json::json!({ "name": "Robert" }),
+        });
+        assert_eq!(
+            serde_json::to_value(&page).unwrap(),
+            serde_json::json!({
+                "data": [{ "_key": "1", "_id": "User/1", "_rev": "a", "name": "Robert" }],
+                "meta": { "total": 12, "page": 2, "per_page": 10 }
+            })
+        );
+    }
+}
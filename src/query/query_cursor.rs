@@ -1,10 +1,30 @@
+use std::sync::Arc;
+
 use arangors_lite::{Cursor, Database};
+#[cfg(not(feature = "blocking"))]
+use futures_util::{
+    future::{select, Either},
+    stream::{self, FuturesUnordered, Stream, StreamExt},
+};
+#[cfg(not(feature = "blocking"))]
+use std::future::Future;
 
-use crate::query::QueryResult;
-use crate::{DatabaseRecord, Record};
+use crate::db::database_service::{encode_path_segment, velocypack_record_cursor};
+use crate::db::deserialization_mode::versioned_cursor;
+use crate::db::versioned_record::VersionedRecord;
+use crate::query::{QueryMetadata, QueryResult, QueryStats, QueryWarning};
+use crate::{
+    DatabaseRecord, DeserializationMode, Error, OperationTracker, Record, RequestLimiter,
+    RequestPermit, VelocyPackTransport,
+};
 
 /// Results of AQL query as a cursor in order to batch the communication between server and client.
 ///
+/// Dropping the cursor before its last batch removes the server side cursor in the background
+/// (see [`Query::cursor_ttl`]).
+///
+/// [`Query::cursor_ttl`]: crate::query::Query::cursor_ttl
+///
 /// # Relevant methods:
 /// - `next_batch` to move the cursor to the next batch
 /// - `has_more` to check if the current batch is the final one
@@ -33,7 +53,7 @@ use crate::{DatabaseRecord, Record};
 /// #     .with_schema_path("tests/schema.yaml")
 /// #     .apply_schema()
 /// #     .build().await.unwrap();
-/// # db_accessor.truncate();
+/// # db_accessor.unsafe_truncate_all().await.unwrap();
 /// # DatabaseRecord::create(User {username: "RobertSurcouf".to_string() ,age: 18 }, &db_accessor).await.unwrap();
 /// // Define a query
 /// let query = User::query().filter(Filter::new(Comparison::field("age").greater_than(10)));
@@ -50,8 +70,17 @@ use crate::{DatabaseRecord, Record};
 /// ```
 #[derive(Debug)]
 pub struct QueryCursor<T> {
-    pub(crate) cursor: Cursor<DatabaseRecord<T>>,
+    pub(crate) cursor: Cursor<VersionedRecord<T>>,
     pub(crate) database: Database,
+    mode: DeserializationMode,
+    /// The `VelocyPack` transport of the next batches
+    transport: Option<VelocyPackTransport>,
+    /// The limiter of the next batches requests
+    limiter: Option<Arc<RequestLimiter>>,
+    /// The tracker of the open cursors, see [`DatabaseConnection::shutdown`]
+    ///
+    /// [`DatabaseConnection::shutdown`]: crate::DatabaseConnection::shutdown
+    tracker: Option<OperationTracker>,
     #[cfg(feature = "blocking")]
     pending_result: Option<QueryResult<T>>,
 }
@@ -60,20 +89,57 @@ impl<T: Record> QueryCursor<T> {
     #[must_use]
     #[inline]
     #[allow(clippy::missing_const_for_fn)]
-    pub(crate) fn new(cursor: Cursor<DatabaseRecord<T>>, database: Database) -> Self {
+    pub(crate) fn new(
+        cursor: Cursor<VersionedRecord<T>>,
+        database: Database,
+        mode: DeserializationMode,
+    ) -> Self {
         Self {
             #[cfg(feature = "blocking")]
-            pending_result: Some(cursor.result.clone().into()),
+            pending_result: Some(cursor.result.iter().cloned().map(Into::into).collect()),
             cursor,
             database,
+            mode,
+            transport: None,
+            limiter: None,
+            tracker: None,
+        }
+    }
+
+    /// Retrieves the next batches through `transport`, if any
+    #[must_use]
+    pub(crate) fn with_transport(mut self, transport: Option<&VelocyPackTransport>) -> Self {
+        self.transport = transport.cloned();
+        self
+    }
+
+    /// Waits for a request slot of `limiter` before retrieving the next batches, if any
+    #[must_use]
+    pub(crate) fn limited(mut self, limiter: Option<&Arc<RequestLimiter>>) -> Self {
+        self.limiter = limiter.cloned();
+        self
+    }
+
+    /// Tracks the server side cursor with `tracker` until its last batch, or its drop
+    #[must_use]
+    pub(crate) fn tracked(mut self, tracker: Option<&OperationTracker>) -> Self {
+        if let (Some(tracker), Some(id), true) = (tracker, &self.cursor.id, self.cursor.more) {
+            tracker.register_cursor(id, &self.database);
+            self.tracker = Some(tracker.clone());
         }
+        self
     }
 
     /// Get the current cursor result
     #[must_use]
     #[inline]
     pub fn result(&self) -> QueryResult<T> {
-        self.cursor.result.clone().into()
+        self.cursor
+            .result
+            .iter()
+            .cloned()
+            .map(DatabaseRecord::from)
+            .collect()
     }
 
     /// Does the cursor have more batches
@@ -90,25 +156,257 @@ impl<T: Record> QueryCursor<T> {
         self.cursor.extra.as_ref()?.stats.as_ref()?.full_count
     }
 
+    /// The query execution statistics, like the number of scanned documents, as returned with
+    /// the current batch
+    #[must_use]
+    pub fn stats(&self) -> Option<QueryStats> {
+        self.cursor.extra.as_ref()?.stats.as_ref().map(Into::into)
+    }
+
+    /// The warnings raised by the database during the query execution, as returned with the
+    /// current batch
+    #[must_use]
+    pub fn warnings(&self) -> Vec<QueryWarning> {
+        QueryMetadata::new(self.cursor.extra.as_ref()).warnings
+    }
+
     /// Moves the cursor to the next batch and returns the result
     #[maybe_async::maybe_async]
     pub async fn next_batch(&mut self) -> Option<QueryResult<T>> {
+        match self.fetch_next_batch().await {
+            Ok(result) => result,
+            Err(error) => {
+                log::error!("Failed to get next batch: {}", error);
+                None
+            }
+        }
+    }
+
+    #[maybe_async::maybe_async]
+    async fn fetch_next_batch(&mut self) -> Result<Option<QueryResult<T>>, Error> {
         if !self.has_more() {
-            return None;
+            return Ok(None);
         }
-        if let Some(ref id) = self.cursor.id {
-            self.cursor = match self.database.aql_next_batch(id).await {
-                Ok(cursor) => cursor,
-                Err(error) => {
-                    log::error!("Failed to get next batch: {}", error);
-                    return None;
+        let id = self.cursor.id.clone().ok_or_else(|| Error::InternalError {
+            message: Some("No `id` associated to Aql Cursor".to_string()),
+        })?;
+        let _permit = batch_permit(self.limiter.as_deref()).await;
+        self.cursor = match (&self.transport, self.mode) {
+            (Some(transport), mode) => {
+                let path = format!("_api/cursor/{}", encode_path_segment(&id));
+                velocypack_record_cursor(
+                    transport,
+                    &self.database,
+                    http::Method::PUT,
+                    &path,
+                    String::new(),
+                    mode,
+                )
+                .await?
+            }
+            (None, DeserializationMode::Lenient) => self.database.aql_next_batch(&id).await?,
+            (None, DeserializationMode::Strict) => {
+                versioned_cursor::<T>(self.database.aql_next_batch(&id).await?)
+            }
+        };
+        if !self.cursor.more {
+            if let Some(tracker) = &self.tracker {
+                tracker.release_cursor(&id);
+            }
+        }
+        Ok(Some(self.result()))
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl<T: Record> QueryCursor<T> {
+    /// Processes every batch of the cursor, starting with the current one, with `f`.
+    ///
+    /// The next batch is fetched while the previous ones are processed, and at most `limit`
+    /// batches are processed concurrently. Use this for large result sets to avoid waiting for the
+    /// database between the batches.
+    ///
+    /// # Note
+    ///
+    /// Not available with the `blocking` feature, use the `Iterator` implementation instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::query::{Query, QueryCursor};
+    /// # use serde::{Serialize, Deserialize};
+    /// # use aragog::{DatabaseConnection, Record};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let cursor: QueryCursor<User> = User::query().call_in_batches(&db_accessor, 1000).await.unwrap();
+    /// cursor
+    ///     .for_each_concurrent(4, |batch| async move {
+    ///         // Export the batch
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will fail if a batch can't be retrieved, the batches already retrieved are processed.
+    pub async fn for_each_concurrent<F, Fut>(mut self, limit: usize, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(QueryResult<T>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let limit = limit.max(1);
+        let mut in_flight = FuturesUnordered::new();
+        let mut next = Some(self.result());
+        let mut error = None;
+        while let Some(batch) = next.take() {
+            while in_flight.len() >= limit {
+                in_flight.next().await;
+            }
+            in_flight.push(f(batch));
+            // The pending batches are processed while fetching the next one
+            let fetch = self.fetch_next_batch();
+            futures_util::pin_mut!(fetch);
+            let fetched = loop {
+                if in_flight.is_empty() {
+                    break fetch.await;
+                }
+                if let Either::Left((fetched, _)) = select(fetch.as_mut(), in_flight.next()).await {
+                    break fetched;
                 }
             };
-            Some(self.result())
-        } else {
-            log::error!("No `id` associated to Aql Cursor");
-            None
+            match fetched {
+                Ok(batch) => next = batch,
+                Err(e) => error = Some(e),
+            }
+        }
+        while in_flight.next().await.is_some() {}
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Converts the cursor into a stream of its documents, starting with the current batch and
+    /// fetching the next ones on demand. A batch retrieval failure ends the stream.
+    ///
+    /// # Note
+    ///
+    /// With the `blocking` feature, an iterator of the documents is returned.
+    pub fn into_records(self) -> impl Stream<Item = Result<DatabaseRecord<T>, Error>> {
+        let batch = self.result().0.into_iter();
+        stream::unfold(Some((self, batch)), |state| async move {
+            let (mut cursor, mut batch) = state?;
+            loop {
+                if let Some(record) = batch.next() {
+                    return Some((Ok(record), Some((cursor, batch))));
+                }
+                match cursor.fetch_next_batch().await {
+                    Ok(Some(result)) => batch = result.0.into_iter(),
+                    Ok(None) => return None,
+                    Err(error) => return Some((Err(error), None)),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T: Record> QueryCursor<T> {
+    /// Converts the cursor into an iterator of its documents, starting with the current batch and
+    /// fetching the next ones on demand. A batch retrieval failure ends the iteration.
+    ///
+    /// # Note
+    ///
+    /// Without the `blocking` feature, a stream of the documents is returned.
+    pub fn into_records(mut self) -> impl Iterator<Item = Result<DatabaseRecord<T>, Error>> {
+        let mut batch = self.result().0.into_iter();
+        self.pending_result = None;
+        let mut failed = false;
+        std::iter::from_fn(move || loop {
+            if let Some(record) = batch.next() {
+                return Some(Ok(record));
+            }
+            if failed {
+                return None;
+            }
+            match self.fetch_next_batch() {
+                Ok(Some(result)) => batch = result.0.into_iter(),
+                Ok(None) => return None,
+                Err(error) => {
+                    failed = true;
+                    return Some(Err(error));
+                }
+            }
+        })
+    }
+}
+
+impl<T> Drop for QueryCursor<T> {
+    fn drop(&mut self) {
+        if !self.cursor.more {
+            return;
+        }
+        let id = match self.cursor.id.take() {
+            Some(id) => id,
+            None => return,
+        };
+        // The cursor may have been removed by a connection shutdown
+        if let Some(tracker) = &self.tracker {
+            if !tracker.release_cursor(&id) {
+                return;
+            }
+        }
+        let database = self.database.clone();
+        #[cfg(not(feature = "blocking"))]
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(delete_cursor(database, id));
+            }
+            Err(_) => log::debug!(
+                "No async runtime to delete the cursor {}, it will expire after its ttl",
+                id
+            ),
         }
+        #[cfg(feature = "blocking")]
+        std::thread::spawn(move || delete_cursor(database, id));
+    }
+}
+
+/// Waits for a request slot of `limiter`, if the batch requests are limited
+#[maybe_async::maybe_async]
+async fn batch_permit(limiter: Option<&RequestLimiter>) -> Option<RequestPermit<'_>> {
+    Some(limiter?.acquire().await)
+}
+
+/// Removes the server side cursor, the failures are only logged as the cursor will expire anyway.
+/// The arguments are owned to be moved in the spawned task.
+#[maybe_async::maybe_async]
+#[allow(clippy::needless_pass_by_value)]
+pub async fn delete_cursor(database: Database, id: String) {
+    log::debug!("Deleting cursor {}", id);
+    let request = database
+        .url()
+        .join(&format!("_api/cursor/{}", id))
+        .map_err(|e| e.to_string())
+        .and_then(|url| {
+            http::Request::delete(url.to_string())
+                .body(String::new())
+                .map_err(|e| e.to_string())
+        });
+    let result = match request {
+        Ok(request) => database
+            .session()
+            .request(request)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        log::warn!("Failed to delete cursor {}: {}", id, error);
     }
 }
 
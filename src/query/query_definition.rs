@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::query::operations::AqlOperation;
+use crate::query::utils::is_attribute_path;
+use crate::query::{Filter, Query, SortDirection};
+use crate::util::is_collection_name;
+use crate::Error;
+
+/// The JSON representation of a [`Query`] operation
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OperationDefinition {
+    Filter(Filter),
+    Sort {
+        field: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        direction: Option<SortDirection>,
+    },
+    Limit {
+        limit: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<u32>,
+    },
+    Sample {
+        count: u32,
+    },
+}
+
+/// The JSON representation of a [`Query`]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QueryDefinition {
+    collection: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    operations: Vec<OperationDefinition>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    distinct: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    index_hint: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    force_index_hint: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cursor_ttl: Option<u32>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    bind_vars: HashMap<String, Value>,
+}
+
+impl TryFrom<&Query> for QueryDefinition {
+    type Error = Error;
+
+    fn try_from(query: &Query) -> Result<Self, Self::Error> {
+        let unsupported = || {
+            Error::ValidationError(String::from(
+                "Only the collection queries with filter, sort, limit and sample operations can be serialized",
+            ))
+        };
+        if query.with_collections.0.is_some()
+            || query.graph_data.is_some()
+            || query.sub_query.is_some()
+            || !query.union.is_empty()
+            || !query.related.is_empty()
+            || query.collect_into.is_some()
+        {
+            return Err(unsupported());
+        }
+        let operations = query
+            .operations
+            .0
+            .iter()
+            .map(|operation| match operation {
+                AqlOperation::Filter(filter) => Ok(OperationDefinition::Filter(filter.clone())),
+                AqlOperation::Sort { field, direction } => Ok(OperationDefinition::Sort {
+                    field: field.clone(),
+                    direction: Some(direction.clone()),
+                }),
+                AqlOperation::Limit { skip, limit } => Ok(OperationDefinition::Limit {
+                    limit: *limit,
+                    skip: *skip,
+                }),
+                AqlOperation::Sample { count } => Ok(OperationDefinition::Sample { count: *count }),
+                AqlOperation::Prune(_)
+                | AqlOperation::EdgeFilter(_)
+                | AqlOperation::EdgePrune(_) => Err(unsupported()),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            collection: query.collection.clone(),
+            operations,
+            distinct: query.distinct,
+            index_hint: query.index_hint.clone(),
+            force_index_hint: query.force_index_hint,
+            cursor_ttl: query.cursor_ttl,
+            bind_vars: query.bind_vars.clone(),
+        })
+    }
+}
+
+impl TryFrom<QueryDefinition> for Query {
+    type Error = Error;
+
+    fn try_from(definition: QueryDefinition) -> Result<Self, Self::Error> {
+        if !is_collection_name(&definition.collection) {
+            return Err(Error::ValidationError(format!(
+                r#""{}" is not a valid collection name"#,
+                definition.collection
+            )));
+        }
+        let mut query = Self::new(&definition.collection);
+        for operation in definition.operations {
+            query = match operation {
+                OperationDefinition::Filter(filter) => query.filter(filter),
+                OperationDefinition::Sort { field, direction } => {
+                    if !is_attribute_path(&field) {
+                        return Err(Error::ValidationError(format!(
+                            r#""{}" is not a valid sort field"#,
+                            field
+                        )));
+                    }
+                    query.sort(&field, direction)
+                }
+                OperationDefinition::Limit { limit, skip } => query.limit(limit, skip),
+                OperationDefinition::Sample { count } => query.sample(count),
+            };
+        }
+        if definition.distinct {
+            query = query.distinct();
+        }
+        query.index_hint = definition.index_hint;
+        query.force_index_hint = definition.force_index_hint;
+        query.cursor_ttl = definition.cursor_ttl;
+        query.bind_vars = definition.bind_vars;
+        Ok(query)
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QueryDefinition::try_from(self)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(QueryDefinition::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
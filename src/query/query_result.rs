@@ -1,17 +1,23 @@
+use crate::db::versioned_record::record_from_value;
+use crate::query::{DeserializationError, QueryMetadata, QueryStats, QueryWarning};
 use crate::undefined_record::UndefinedRecord;
 use crate::{DatabaseRecord, Error, Record};
 use std::ops::{Deref, DerefMut};
 
-/// Query result containing the queried documents
+/// Query result containing the queried documents, and the query metadata when retrieved from
+/// the database (see [`stats`] and [`warnings`])
+///
+/// [`stats`]: Self::stats
+/// [`warnings`]: Self::warnings
 #[derive(Debug, Clone)]
-pub struct QueryResult<T>(pub Vec<DatabaseRecord<T>>);
+pub struct QueryResult<T>(pub Vec<DatabaseRecord<T>>, pub(crate) QueryMetadata);
 
 impl<T: Clone + Record> QueryResult<T> {
     /// Instantiates a new `QueryResult` from a document collection
     #[must_use]
     #[inline]
     pub fn new(documents: Vec<DatabaseRecord<T>>) -> Self {
-        Self(documents)
+        Self(documents, QueryMetadata::default())
     }
 
     /// Consumes and returns the only document of the current `QueryResult`.
@@ -49,6 +55,30 @@ impl<T: Clone + Record> QueryResult<T> {
     }
 }
 
+impl<T> QueryResult<T> {
+    /// The query execution statistics, like the number of scanned documents.
+    /// Not available for results built without querying the database, or served from the query cache.
+    #[must_use]
+    #[inline]
+    pub const fn stats(&self) -> Option<&QueryStats> {
+        self.1.stats.as_ref()
+    }
+
+    /// The warnings raised by the database during the query execution
+    #[must_use]
+    #[inline]
+    pub fn warnings(&self) -> &[QueryWarning] {
+        &self.1.warnings
+    }
+
+    /// Total number of documents matching the query without its `LIMIT` operation.
+    /// Only available for queries with a `LIMIT` operation.
+    #[must_use]
+    pub fn full_count(&self) -> Option<usize> {
+        self.stats()?.full_count
+    }
+}
+
 impl QueryResult<UndefinedRecord> {
     /// Retrieves deserialized documents from the json results. The documents not matching `T` will not be returned.
     ///
@@ -79,7 +109,7 @@ impl QueryResult<UndefinedRecord> {
     pub fn get_records<T: Record>(&self) -> QueryResult<T> {
         self.iter()
             .filter_map(|db_record| {
-                serde_json::from_value(db_record.0.clone())
+                record_from_value(db_record.0.clone())
                     .ok()
                     .map(|record| DatabaseRecord {
                         key: db_record.key.clone(),
@@ -90,6 +120,56 @@ impl QueryResult<UndefinedRecord> {
             })
             .collect()
     }
+
+    /// Retrieves deserialized documents from the json results like [`get_records`], also returning
+    /// the documents not matching `T` along with the cause of their deserialization failure: the
+    /// path of the failing value in the document and the found and expected types.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::Query, Record, DatabaseConnection};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {}
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let json_results = Query::new("User").call(&db_accessor).await.unwrap();
+    ///
+    /// let (users, errors) = json_results.get_records_with_errors::<User>();
+    /// for error in errors {
+    ///     println!("{} is not a valid user: {}", error.id, error.message);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`get_records`]: Self::get_records
+    #[must_use]
+    pub fn get_records_with_errors<T: Record>(
+        &self,
+    ) -> (QueryResult<T>, Vec<DeserializationError>) {
+        let mut records = Vec::with_capacity(self.len());
+        let mut errors = Vec::new();
+        for db_record in self.iter() {
+            match record_from_value(db_record.0.clone()) {
+                Ok(record) => records.push(DatabaseRecord {
+                    key: db_record.key.clone(),
+                    id: db_record.id.clone(),
+                    rev: db_record.rev.clone(),
+                    record,
+                }),
+                Err(error) => {
+                    let error =
+                        DeserializationError::diagnose::<T>(&db_record.id, &db_record.0, &error);
+                    log::debug!("Failed to deserialize {}", error);
+                    errors.push(error);
+                }
+            }
+        }
+        (QueryResult::new(records), errors)
+    }
 }
 
 impl<T: Record> FromIterator<DatabaseRecord<T>> for QueryResult<T> {
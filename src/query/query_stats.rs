@@ -0,0 +1,117 @@
+use arangors_lite::aql::{QueryExtra, QueryStats as ArangoQueryStats};
+use serde::{Deserialize, Serialize};
+
+/// Execution statistics of an AQL query, returned by the database along with the query results
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueryStats {
+    /// The number of documents created, updated or removed by the query
+    pub writes_executed: usize,
+    /// The number of failed write operations ignored because of the `ignoreErrors` option
+    pub writes_ignored: usize,
+    /// The number of documents iterated over when scanning a collection without an index
+    pub scanned_full: usize,
+    /// The number of documents iterated over when scanning a collection using an index
+    pub scanned_index: usize,
+    /// The number of documents removed by the `FILTER` conditions
+    pub filtered: usize,
+    /// The number of documents matching the query without its top level `LIMIT`, only available
+    /// for queries with a `LIMIT` operation
+    pub full_count: Option<usize>,
+    /// The number of cluster internal http requests
+    pub http_requests: usize,
+    /// The query execution time, in seconds
+    pub execution_time: f64,
+}
+
+/// A warning raised by the database during an AQL query execution, like a division by zero or
+/// an invalid function argument
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryWarning {
+    /// The `ArangoDB` error number of the warning
+    pub code: u32,
+    /// The warning message
+    pub message: String,
+}
+
+/// The metadata of a query response
+#[derive(Debug, Clone, Default)]
+pub struct QueryMetadata {
+    pub stats: Option<QueryStats>,
+    pub warnings: Vec<QueryWarning>,
+}
+
+impl From<&ArangoQueryStats> for QueryStats {
+    fn from(stats: &ArangoQueryStats) -> Self {
+        Self {
+            writes_executed: stats.writes_executed,
+            writes_ignored: stats.writes_ignored,
+            scanned_full: stats.scanned_full,
+            scanned_index: stats.scanned_index,
+            filtered: stats.filtered,
+            full_count: stats.full_count,
+            http_requests: stats.http_requests,
+            execution_time: stats.execution_time,
+        }
+    }
+}
+
+impl QueryMetadata {
+    pub fn new(extra: Option<&QueryExtra>) -> Self {
+        extra.map_or_else(Self::default, |extra| Self {
+            stats: extra.stats.as_ref().map(Into::into),
+            warnings: extra
+                .warnings
+                .iter()
+                .flatten()
+                .filter_map(|warning| serde_json::from_value(warning.clone()).ok())
+                .collect(),
+        })
+    }
+
+    /// Logs the query warnings
+    pub fn log_warnings(&self) {
+        for warning in &self.warnings {
+            log::warn!("AQL warning {}: {}", warning.code, warning.message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_from_extra() {
+        let extra: QueryExtra = serde_json::from_value(serde_json::json!({
+            "stats": {
+                "writesExecuted": 0,
+                "writesIgnored": 0,
+                "scannedFull": 5,
+                "scannedIndex": 0,
+                "filtered": 3,
+                "fullCount": 5,
+                "httpRequests": 0,
+                "executionTime": 0.001
+            },
+            "warnings": [
+                { "code": 1562, "message": "division by zero" }
+            ]
+        }))
+        .unwrap();
+        let metadata = QueryMetadata::new(Some(&extra));
+        let stats = metadata.stats.unwrap();
+        assert_eq!(stats.scanned_full, 5);
+        assert_eq!(stats.filtered, 3);
+        assert_eq!(stats.full_count, Some(5));
+        assert_eq!(
+            metadata.warnings,
+            vec![QueryWarning {
+                code: 1562,
+                message: "division by zero".to_string()
+            }]
+        );
+        let metadata = QueryMetadata::new(None);
+        assert!(metadata.stats.is_none());
+        assert!(metadata.warnings.is_empty());
+    }
+}
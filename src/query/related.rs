@@ -0,0 +1,109 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use crate::query::GraphQueryDirection;
+use crate::DatabaseRecord;
+
+/// A record along with its related documents, returned by the queries loading the related
+/// documents with [`Query::with_related`].
+///
+/// The related documents are deserialized into `R`, a struct with a field per
+/// [`Query::with_related`] alias.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::Related;
+/// # use aragog::{DatabaseRecord, Record};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// #[derive(Record, Clone, Serialize, Deserialize)]
+/// pub struct User {
+///     pub name: String,
+/// }
+///
+/// #[derive(Record, Clone, Serialize, Deserialize)]
+/// pub struct Post {
+///     pub title: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// pub struct UserPosts {
+///     pub posts: Vec<DatabaseRecord<Post>>,
+/// }
+///
+/// let user: Related<User, UserPosts> = serde_json::from_value(serde_json::json!({
+///     "_key": "123",
+///     "_id": "User/123",
+///     "_rev": "1",
+///     "name": "Robert",
+///     "posts": [{ "_key": "1", "_id": "Post/1", "_rev": "1", "title": "Hello" }]
+/// })).unwrap();
+/// assert_eq!(user.name, "Robert");
+/// assert_eq!(user.related.posts[0].title, "Hello");
+/// ```
+///
+/// [`Query::with_related`]: crate::query::Query::with_related
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Related<T, R> {
+    /// The related documents
+    #[serde(flatten)]
+    pub related: R,
+    /// The queried record
+    #[serde(flatten)]
+    pub record: DatabaseRecord<T>,
+}
+
+impl<T, R> Deref for Related<T, R> {
+    type Target = DatabaseRecord<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.record
+    }
+}
+
+impl<T, R> DerefMut for Related<T, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.record
+    }
+}
+
+/// A sub query of the `RETURN` projection of a [`Query`], loading the documents related to the
+/// queried ones through an edge collection
+///
+/// [`Query`]: crate::query::Query
+#[derive(Debug, Clone)]
+pub struct RelatedQuery {
+    /// The traversed edge collection
+    pub edge_collection: String,
+    /// The traversal direction
+    pub direction: GraphQueryDirection,
+    /// The projection attribute of the related documents
+    pub alias: String,
+}
+
+impl RelatedQuery {
+    /// Renders the `alias: (FOR ...)` projection attribute of the `collection_id` documents
+    pub fn render(&self, collection_id: &str, index: usize) -> String {
+        let related_id = format!("related_{}", index);
+        format!(
+            "{}: (FOR {related} in 1..1 {} {} {} return {related})",
+            self.alias,
+            self.direction,
+            collection_id,
+            self.edge_collection,
+            related = related_id
+        )
+    }
+}
+
+/// Renders the `MERGE` projection of the `collection_id` documents with their `related` documents
+pub fn render_projection(collection_id: &str, related: &[RelatedQuery]) -> String {
+    let attributes: Vec<String> = related
+        .iter()
+        .enumerate()
+        .map(|(index, related)| related.render(collection_id, index))
+        .collect();
+    format!("MERGE({}, {{ {} }})", collection_id, attributes.join(", "))
+}
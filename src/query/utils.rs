@@ -1,5 +1,39 @@
+use serde_json::Value;
 use std::fmt::Display;
 
+/// Values passed as bind parameters while rendering a query, named after their position so that
+/// queries of the same shape render the same AQL string
+#[derive(Clone, Debug, Default)]
+pub struct BoundValues {
+    values: Vec<Value>,
+    /// Whether the literal values are passed as bind parameters too
+    pub bind_literals: bool,
+}
+
+impl BoundValues {
+    pub const fn new(bind_literals: bool) -> Self {
+        Self {
+            values: Vec::new(),
+            bind_literals,
+        }
+    }
+
+    /// Registers `value` and returns the bind parameter to render in its place
+    pub fn bind(&mut self, value: Value) -> String {
+        let name = format!("@value_{}", self.values.len());
+        self.values.push(value);
+        name
+    }
+
+    /// The bind parameter names, without `@`, and their values
+    pub fn into_bind_vars(self) -> impl Iterator<Item = (String, Value)> {
+        self.values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| (format!("value_{}", i), value))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OptionalQueryString(pub Option<String>);
 
@@ -39,6 +73,17 @@ where
     array_str
 }
 
+/// Is `path` a document attribute path, like `address.city`, safe to render in a query string
+pub fn is_attribute_path(path: &str) -> bool {
+    path.split('.').all(|attribute| {
+        let mut chars = attribute.chars();
+        chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
 impl ToString for OptionalQueryString {
     fn to_string(&self) -> String {
         match &self.0 {
@@ -2,9 +2,24 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::db::transaction::Transaction;
-use crate::query::{Query, QueryCursor, QueryResult};
+use crate::query::{Filter, Query, QueryCursor, QueryResult};
+use crate::schema::IndexSchema;
 use crate::transaction::TransactionBuilder;
-use crate::{DatabaseAccess, DatabaseConnection, DatabaseRecord, Error};
+use crate::{
+    ConflictPolicy, CounterCache, DatabaseAccess, DatabaseConnection, DatabaseRecord, Error,
+    RawDocument, Repository, UpsertOutcome,
+};
+
+/// The [`Record`] write operations checked by [`Record::authorize_write`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordAction {
+    /// Document creation
+    Create,
+    /// Document save
+    Save,
+    /// Document deletion
+    Delete,
+}
 
 /// The main trait of the Aragog library.
 /// Trait for structures that can be stored in Database.
@@ -15,8 +30,193 @@ use crate::{DatabaseAccess, DatabaseConnection, DatabaseRecord, Error};
 pub trait Record: DeserializeOwned + Serialize + Clone {
     /// returns the associated Collection
     /// for read and write operations.
+    ///
+    /// The derive macro uses the type name, the `#[collection_name = "Name"]` attribute, or the
+    /// type name converted by the `#[collection_name(snake_case, pluralize)]` naming options, and
+    /// checks the `ArangoDB` naming rules at compile time: the name must start with a letter and
+    /// only contain letters, digits, `_` and `-`, up to 256 characters.
+    ///
+    /// ```rust compile_fail
+    /// # use aragog::Record;
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// #[collection_name = "_users"] // Reserved for system collections
+    /// pub struct User {}
+    /// ```
     const COLLECTION_NAME: &'static str;
 
+    /// Defines if the associated collection is an edge collection, `false` by default.
+    ///
+    /// Use the `#[edge_collection]` attribute along with the derive macro to set it for models
+    /// used as [`EdgeRecord`] data. [`EdgeRecord`] always sets it to `true`.
+    ///
+    /// [`EdgeRecord`]: crate::EdgeRecord
+    const IS_EDGE_COLLECTION: bool = false;
+
+    /// The counts of the edges linked to this vertex type, maintained by the hooks of the
+    /// [`EdgeRecord`] documents once registered on the connection. Empty by default.
+    ///
+    /// Use the `#[counter_cache(edge = "...", field = "...")]` attribute along with the derive
+    /// macro to set it, see [`CounterCache`].
+    ///
+    /// [`EdgeRecord`]: crate::EdgeRecord
+    /// [`CounterCache`]: crate::CounterCache
+    const COUNTER_CACHES: &'static [CounterCache] = &[];
+
+    /// The current version of the model, written in the `_model_version` field of the created and
+    /// saved documents. `0` by default, meaning the model is not versioned.
+    ///
+    /// Use the `#[model_version(3)]` attribute along with the derive macro to set it, and its
+    /// `migrate_from` option to implement [`migrate_from`] with a function of the model:
+    /// `#[model_version(3, migrate_from = "upgrade")]`.
+    ///
+    /// [`migrate_from`]: Self::migrate_from
+    const MODEL_VERSION: u32 = 0;
+
+    /// The field holding the tenant of the documents, `None` by default meaning the documents are
+    /// shared by every tenant.
+    ///
+    /// Use the `#[tenant_scoped(field = "org_id")]` attribute along with the derive macro to set
+    /// it. The queries and retrievals of the record are then restricted to the documents of the
+    /// [`DatabaseAccess::tenant`], and the writes of documents of an other tenant are forbidden.
+    /// Operations without tenant fail with [`Error::Forbidden`].
+    ///
+    /// [`DatabaseAccess::tenant`]: crate::DatabaseAccess::tenant
+    /// [`Error::Forbidden`]: crate::Error::Forbidden
+    const TENANT_FIELD: Option<&'static str> = None;
+
+    /// Builds the record from a document stored with an older model `version`, called on
+    /// retrieval when the document `_model_version` is lower than [`MODEL_VERSION`] (documents
+    /// without `_model_version` are of version `0`).
+    /// The document is upgraded lazily: it is written with the current version on its next save.
+    ///
+    /// By default the document is deserialized as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The stored model version
+    /// * `value` - The stored document, without its system attributes and `_model_version`
+    ///
+    /// # Errors
+    ///
+    /// Should fail if the document can't be upgraded, the retrieval fails accordingly
+    ///
+    /// [`MODEL_VERSION`]: Self::MODEL_VERSION
+    fn migrate_from(version: u32, value: serde_json::Value) -> Result<Self, Error> {
+        log::debug!(
+            "Deserializing {} document of model version {}",
+            Self::COLLECTION_NAME,
+            version
+        );
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// The condition applied to all the queries of the model, like excluding the archived or soft
+    /// deleted documents. `None` by default.
+    ///
+    /// Use the `#[default_scope("deleted == false")]` attribute along with the derive macro to set
+    /// it, with the `#[scope]` condition syntax. The default scope is applied by [`query`],
+    /// [`find`] and [`get`], use [`Query::unscoped`] or [`find_unscoped`] to skip it.
+    ///
+    /// [`query`]: Self::query
+    /// [`find`]: Self::find
+    /// [`get`]: Self::get
+    /// [`find_unscoped`]: Self::find_unscoped
+    #[must_use]
+    fn default_scope() -> Option<Filter> {
+        None
+    }
+
+    /// The indexes of the record collection, added to the schema by
+    /// [`DatabaseSchema::add_record`]. Empty by default.
+    ///
+    /// Use the `#[index(fields("name"), unique)]` attribute along with the derive macro to declare
+    /// them.
+    ///
+    /// [`DatabaseSchema::add_record`]: crate::schema::DatabaseSchema::add_record
+    #[must_use]
+    fn indexes() -> Vec<IndexSchema> {
+        Vec::new()
+    }
+
+    /// The top level document fields that the user supplied queries may filter and sort on,
+    /// checked by [`Query::validate_fields`]. `None` by default, meaning every field is allowed.
+    ///
+    /// The derive macro lists the serialized fields of structs, applying the `serde` renames and
+    /// leaving out the `#[serde(skip)]` and `#[db(redact)]` fields. Enums and structs with
+    /// flattened fields are not restricted. The `_key`, `_id` and `_rev` system fields are always
+    /// allowed.
+    ///
+    /// ```rust
+    /// # use aragog::Record;
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// #[serde(rename_all = "camelCase")]
+    /// pub struct User {
+    ///     pub first_name: String,
+    ///     #[db(redact)]
+    ///     pub password_hash: String,
+    /// }
+    ///
+    /// assert_eq!(User::allowed_query_fields(), Some(&["firstName"][..]));
+    /// ```
+    ///
+    /// [`Query::validate_fields`]: crate::query::Query::validate_fields
+    #[must_use]
+    fn allowed_query_fields() -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// The resolution of the revision conflicts of [`DatabaseRecord::save`], failing by default.
+    ///
+    /// Use the `#[conflict_policy(retry_with_reload)]` or
+    /// `#[conflict_policy(merge = "merge_fn", max_retries = 5)]` attribute along with the derive
+    /// macro to set it, `merge_fn` being an associated function of the record taking the local
+    /// and the stored records.
+    ///
+    /// [`DatabaseRecord::save`]: crate::DatabaseRecord::save
+    #[must_use]
+    fn conflict_policy() -> ConflictPolicy<Self> {
+        ConflictPolicy::Fail
+    }
+
+    /// Checks that the operation `actor`, set with [`OperationOptions::actor`], is allowed to
+    /// perform `action` on `target` (`None` on creation).
+    ///
+    /// Called by the [`DatabaseRecord`] write operations before the hooks, even if the operation
+    /// options ignore hooks. Operations without actor are not checked.
+    ///
+    /// By default every actor is allowed. Use the `#[authorize(actor = "User")]` attribute along
+    /// with the derive macro to delegate the check to the [`AuthorizeAction`] implementation of
+    /// `User`.
+    ///
+    /// # Warning
+    ///
+    /// The default implementation fails open: a record type without `#[authorize]` attribute nor
+    /// custom implementation accepts the writes of any actor, setting an actor on the operation
+    /// options doesn't restrict anything by itself.
+    ///
+    /// The `target` is the stored document for `Save` and `Delete`, including the document
+    /// replaced by [`DatabaseRecord::upsert_by`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Forbidden`] if the actor is not allowed to perform `action`
+    ///
+    /// [`OperationOptions::actor`]: crate::OperationOptions::actor
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`AuthorizeAction`]: crate::AuthorizeAction
+    /// [`Error::Forbidden`]: crate::Error::Forbidden
+    /// [`DatabaseRecord::upsert_by`]: crate::DatabaseRecord::upsert_by
+    fn authorize_write(
+        actor: &serde_json::Value,
+        action: RecordAction,
+        target: Option<&DatabaseRecord<Self>>,
+    ) -> Result<(), Error> {
+        let _ = (actor, action, target);
+        Ok(())
+    }
+
     /// Finds a document in database from its unique key.
     /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`find`]
     ///
@@ -29,6 +229,121 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
         DatabaseRecord::find(key, db_accessor).await
     }
 
+    /// Finds a document in database from its unique key, ignoring the [`default_scope`].
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`find_unscoped`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on invalid document key or data corruption, like [`find_unscoped`]
+    ///
+    /// [`default_scope`]: Self::default_scope
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`find_unscoped`]: crate::DatabaseRecord::find_unscoped
+    async fn find_unscoped<D>(key: &str, db_accessor: &D) -> Result<DatabaseRecord<Self>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::find_unscoped(key, db_accessor).await
+    }
+
+    /// Finds a document in database from its key if its revision differs from `known_rev`.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`find_if_modified`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on invalid document key or data corruption, like [`find_if_modified`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`find_if_modified`]: crate::DatabaseRecord::find_if_modified
+    async fn find_if_modified<D>(
+        key: &str,
+        known_rev: &str,
+        db_accessor: &D,
+    ) -> Result<Option<DatabaseRecord<Self>>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::find_if_modified(key, known_rev, db_accessor).await
+    }
+
+    /// Finds the only document in database whose `field` equals `value`.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`find_by_field`]
+    ///
+    /// # Errors
+    ///
+    /// Fails if no document or more than one matches, like [`find_by_field`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`find_by_field`]: crate::DatabaseRecord::find_by_field
+    async fn find_by_field<D, V>(
+        field: &str,
+        value: V,
+        db_accessor: &D,
+    ) -> Result<DatabaseRecord<Self>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Into<serde_json::Value> + Send,
+    {
+        DatabaseRecord::find_by_field(field, value, db_accessor).await
+    }
+
+    /// Finds all documents in database whose `field` is one of `values`, in chunks of
+    /// [`BOUND_ARRAY_CHUNK_SIZE`] values.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`get_where_in`]
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`get_where_in`]
+    ///
+    /// [`BOUND_ARRAY_CHUNK_SIZE`]: crate::query::BOUND_ARRAY_CHUNK_SIZE
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_where_in`]: crate::DatabaseRecord::get_where_in
+    async fn get_where_in<D, V>(
+        field: &str,
+        values: &[V],
+        db_accessor: &D,
+    ) -> Result<QueryResult<Self>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Serialize + Sync,
+    {
+        DatabaseRecord::get_where_in(field, values, db_accessor).await
+    }
+
+    /// Finds a document in database from its unique key as raw JSON, to be deserialized into a
+    /// record borrowing from it.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`find_borrowed`]
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`find_borrowed`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`find_borrowed`]: crate::DatabaseRecord::find_borrowed
+    async fn find_borrowed<D>(key: &str, db_accessor: &D) -> Result<RawDocument, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::<Self>::find_borrowed(key, db_accessor).await
+    }
+
+    /// Finds all documents in database matching a `Query` as raw JSON, to be deserialized into
+    /// records borrowing from them.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`get_borrowed`]
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`get_borrowed`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_borrowed`]: crate::DatabaseRecord::get_borrowed
+    async fn get_borrowed<D>(query: &Query, db_accessor: &D) -> Result<Vec<RawDocument>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::<Self>::get_borrowed(query, db_accessor).await
+    }
+
     /// Finds all documents in database matching a `Query`.
     /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`get`]
     ///
@@ -70,8 +385,27 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
         DatabaseRecord::<Self>::exists(query, db_accessor).await
     }
 
+    /// Returns true if there are any document in database whose `field` equals `value`.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`exists_by`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`exists_by`]: crate::DatabaseRecord::exists_by
+    #[must_use]
+    async fn exists_by<D, V>(field: &str, value: V, db_accessor: &D) -> bool
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Into<serde_json::Value> + Send,
+    {
+        DatabaseRecord::<Self>::exists_by(field, value, db_accessor).await
+    }
+
     /// Creates a new document in database.
-    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`create`]
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`create`], callable on the record itself:
+    /// `user.create(&db_connection)` or `User::create(user, &db_connection)`.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`create`]
     ///
     /// # Example
     ///
@@ -94,21 +428,89 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
     ///     # .unwrap();
     ///
     /// let user = User { name: "Patrick".to_owned() };
-    /// let created_user = User::create(user, &db_connection).await.unwrap();
+    /// let created_user = user.create(&db_connection).await.unwrap();
     ///
     /// assert_eq!(created_user.name, "Patrick".to_owned());
     /// # }
     /// ```
     /// [`DatabaseRecord`]: crate::DatabaseRecord
     /// [`create`]: crate::DatabaseRecord::create
-    async fn create<D>(record: Self, db_accessor: &D) -> Result<DatabaseRecord<Self>, Error>
+    async fn create<D>(self, db_accessor: &D) -> Result<DatabaseRecord<Self>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::create(self, db_accessor).await
+    }
+
+    /// Creates a new document in database for every record of `records`, in order.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`create`] called on each record, running
+    /// their hooks.
+    ///
+    /// # Errors
+    ///
+    /// Stops on the first failing creation, like [`create`]. The previous records stay created,
+    /// use a [`transaction`] to create all of them or none.
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`create`]: crate::DatabaseRecord::create
+    /// [`transaction`]: Self::transaction
+    async fn create_many<D>(
+        records: Vec<Self>,
+        db_accessor: &D,
+    ) -> Result<Vec<DatabaseRecord<Self>>, Error>
     where
         D: DatabaseAccess + ?Sized,
     {
-        DatabaseRecord::create(record, db_accessor).await
+        let mut res = Vec::with_capacity(records.len());
+        for record in records {
+            res.push(DatabaseRecord::create(record, db_accessor).await?);
+        }
+        Ok(res)
     }
 
-    /// Creates a new `Query` instance for `Self`.
+    /// Inserts `record` in database, or replaces the document having the same `field` value.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`upsert_by`]
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{Record, DatabaseConnection, UpsertOutcome};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {
+    ///     pub email: String,
+    ///     pub name: String,
+    /// }
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+    ///
+    /// let user = User { email: "patrick@mail.com".to_owned(), name: "Patrick".to_owned() };
+    /// let (user, outcome) = User::upsert_by("email", user, &db_connection).await.unwrap();
+    /// assert_eq!(outcome, UpsertOutcome::Inserted);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`upsert_by`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`upsert_by`]: crate::DatabaseRecord::upsert_by
+    async fn upsert_by<D>(
+        field: &str,
+        record: Self,
+        db_accessor: &D,
+    ) -> Result<(DatabaseRecord<Self>, UpsertOutcome), Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::upsert_by(field, record, db_accessor).await
+    }
+
+    /// Creates a new `Query` instance for `Self`, filtered by the [`default_scope`] if any.
     ///
     /// # Example
     /// ```rust
@@ -123,9 +525,11 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
     /// let q = Query::new(User::COLLECTION_NAME);
     /// let q = Query::new("User");
     /// ```
+    ///
+    /// [`default_scope`]: Self::default_scope
     #[must_use]
     fn query() -> Query {
-        Query::new(Self::COLLECTION_NAME)
+        Query::new(Self::COLLECTION_NAME).with_default_scope(Self::default_scope())
     }
 
     /// method called by [`DatabaseRecord`]::[`create`]
@@ -194,6 +598,18 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
     where
         D: DatabaseAccess + ?Sized;
 
+    /// Returns a [`Repository`] of this collection using `db_accessor`, exposing the record
+    /// operations without repeating the accessor.
+    ///
+    /// [`Repository`]: crate::Repository
+    #[must_use]
+    fn repo<D>(db_accessor: &D) -> Repository<'_, Self, D>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        Repository::new(db_accessor)
+    }
+
     /// Returns a transaction builder on this collection only.
     #[must_use]
     fn transaction_builder() -> TransactionBuilder {
@@ -0,0 +1,20 @@
+use crate::schema::DatabaseSchema;
+
+/// A [`Record`] type registered with the `#[register_record]` attribute, collected at compile time
+///
+/// [`Record`]: crate::Record
+#[derive(Debug)]
+pub struct RegisteredRecord {
+    /// The record collection name
+    pub collection_name: &'static str,
+    /// Adds the record collection and indexes to a schema, see [`DatabaseSchema::add_record`]
+    pub add_to_schema: fn(&mut DatabaseSchema),
+}
+
+inventory::collect!(RegisteredRecord);
+
+/// Iterates over the records registered with the `#[register_record]` attribute, in no particular
+/// order
+pub fn registered_records() -> impl Iterator<Item = &'static RegisteredRecord> {
+    inventory::iter::<RegisteredRecord>.into_iter()
+}
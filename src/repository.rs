@@ -0,0 +1,218 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::db::database_service;
+use crate::query::{Query, QueryCursor, QueryResult};
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+
+/// A collection-scoped repository bundling a database accessor and a [`Record`] type, exposing
+/// the record operations without repeating the accessor on every call.
+///
+/// Build it with [`Record::repo`] or [`Repository::new`], it is a cheap `Copy` handle for
+/// service-layer code.
+///
+/// # Example
+///
+/// ```rust no_run
+/// # use aragog::{DatabaseConnection, Record, Repository};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// pub struct User {
+///     pub name: String,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let db_connection = DatabaseConnection::builder().build().await.unwrap();
+/// let users = User::repo(&db_connection);
+/// let user = users.create(User { name: "Patrick".to_string() }).await.unwrap();
+/// let found = users.find(user.key()).await.unwrap();
+/// let count = users.count().await.unwrap();
+/// let patricks = users.get(&users.query()).await.unwrap();
+/// # }
+/// ```
+pub struct Repository<'a, T, D: ?Sized> {
+    db_accessor: &'a D,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, D: ?Sized> Repository<'a, T, D> {
+    /// Instantiates a repository of `T` records using `db_accessor`
+    #[must_use]
+    #[inline]
+    pub const fn new(db_accessor: &'a D) -> Self {
+        Self {
+            db_accessor,
+            record: PhantomData,
+        }
+    }
+
+    /// The database accessor of the repository
+    #[must_use]
+    #[inline]
+    pub const fn db_accessor(&self) -> &'a D {
+        self.db_accessor
+    }
+}
+
+impl<T, D: ?Sized> Clone for Repository<'_, T, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, D: ?Sized> Copy for Repository<'_, T, D> {}
+
+impl<T, D: ?Sized> Debug for Repository<'_, T, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Repository")
+            .field("record", &std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T, D> Repository<'_, T, D>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    /// Creates a new `Query` instance for the repository records, see [`Record::query`]
+    #[must_use]
+    #[inline]
+    pub fn query(&self) -> Query {
+        T::query()
+    }
+
+    /// Finds a document from its unique key, see [`DatabaseRecord::find`]
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document is not found or on data corruption
+    #[maybe_async::maybe_async]
+    pub async fn find(&self, key: &str) -> Result<DatabaseRecord<T>, Error> {
+        DatabaseRecord::find(key, self.db_accessor).await
+    }
+
+    /// Finds the only document whose `field` equals `value`, see [`DatabaseRecord::find_by_field`]
+    ///
+    /// # Errors
+    ///
+    /// Fails if no document or more than one matches, or on data corruption
+    #[maybe_async::maybe_async]
+    pub async fn find_by_field<V>(&self, field: &str, value: V) -> Result<DatabaseRecord<T>, Error>
+    where
+        V: Into<serde_json::Value> + Send,
+    {
+        DatabaseRecord::find_by_field(field, value, self.db_accessor).await
+    }
+
+    /// Retrieves the documents matching `query`, see [`DatabaseRecord::get`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on query failure or on data corruption
+    #[maybe_async::maybe_async]
+    pub async fn get(&self, query: &Query) -> Result<QueryResult<T>, Error> {
+        DatabaseRecord::get(query, self.db_accessor).await
+    }
+
+    /// Retrieves the documents matching `query` using batches, see
+    /// [`DatabaseRecord::get_in_batches`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on query failure or on data corruption
+    #[maybe_async::maybe_async]
+    pub async fn get_in_batches(
+        &self,
+        query: &Query,
+        batch_size: u32,
+    ) -> Result<QueryCursor<T>, Error> {
+        DatabaseRecord::get_in_batches(query, self.db_accessor, batch_size).await
+    }
+
+    /// Returns `true` if a document matches `query`, see [`DatabaseRecord::exists`]
+    #[must_use]
+    #[maybe_async::maybe_async]
+    pub async fn exists(&self, query: &Query) -> bool {
+        DatabaseRecord::<T>::exists(query, self.db_accessor).await
+    }
+
+    /// Returns `true` if a document whose `field` equals `value` exists, see
+    /// [`DatabaseRecord::exists_by`]
+    #[must_use]
+    #[maybe_async::maybe_async]
+    pub async fn exists_by<V>(&self, field: &str, value: V) -> bool
+    where
+        V: Into<serde_json::Value> + Send,
+    {
+        DatabaseRecord::<T>::exists_by(field, value, self.db_accessor).await
+    }
+
+    /// Retrieves the document count of the collection.
+    ///
+    /// If `T` has a [`default_scope`] or is tenant scoped only the matching documents are counted,
+    /// through a query.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the collection is not loaded by the accessor or on database request failure
+    ///
+    /// [`default_scope`]: crate::Record::default_scope
+    #[maybe_async::maybe_async]
+    pub async fn count(&self) -> Result<u32, Error> {
+        if T::default_scope().is_none() && T::TENANT_FIELD.is_none() {
+            return self
+                .db_accessor
+                .get_collection(T::COLLECTION_NAME)?
+                .record_count()
+                .await;
+        }
+        database_service::count_records::<T, D>(self.db_accessor, &T::query()).await
+    }
+
+    /// Creates a new document, see [`DatabaseRecord::create`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on hook or validation failure, or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn create(&self, record: T) -> Result<DatabaseRecord<T>, Error> {
+        DatabaseRecord::create(record, self.db_accessor).await
+    }
+
+    /// Creates a new document with a custom key, see [`DatabaseRecord::create_with_key`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on hook or validation failure, or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn create_with_key(
+        &self,
+        record: T,
+        key: String,
+    ) -> Result<DatabaseRecord<T>, Error> {
+        DatabaseRecord::create_with_key(record, key, self.db_accessor).await
+    }
+
+    /// Saves the changes of `record`, see [`DatabaseRecord::save`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on hook or validation failure, or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn save(&self, record: &mut DatabaseRecord<T>) -> Result<(), Error> {
+        record.save(self.db_accessor).await
+    }
+
+    /// Deletes `record`, see [`DatabaseRecord::delete`]
+    ///
+    /// # Errors
+    ///
+    /// Fails on hook failure or on database request failure
+    #[maybe_async::maybe_async]
+    pub async fn delete(&self, record: &mut DatabaseRecord<T>) -> Result<(), Error> {
+        record.delete(self.db_accessor).await
+    }
+}
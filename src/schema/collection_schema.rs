@@ -1,15 +1,25 @@
-use arangors_lite::collection::options::CreateParameters;
+use arangors_lite::error::HttpError;
 use arangors_lite::{
-    collection::{options::CreateOptions, Collection, CollectionType},
-    ClientError, Database,
+    collection::{Collection, CollectionType},
+    ArangoError, ClientError, Database,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::schema::SchemaDatabaseOperation;
+use crate::error::ArangoError as ArangoErrorCode;
+use crate::schema::{ComputedValue, ReplicationFactor, SchemaDatabaseOperation};
+use crate::Record;
 
 /// Aragog schema representation of an `ArangoDB` Collection.
 /// This struct is meant to load/generate the schema file.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+///
+/// The sharding options are only meaningful in a cluster deployment and are ignored by single
+/// server deployments.
+///
+/// When applying the schema on an existing collection, its [`computed_values`] are replaced by the
+/// schema ones if any.
+///
+/// [`computed_values`]: Self::computed_values
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CollectionSchema {
     /// Collection name
     pub name: String,
@@ -20,6 +30,56 @@ pub struct CollectionSchema {
     /// If set on `true` the requests might be slower. By default, `false` is used
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_for_sync: Option<bool>,
+    /// The number of shards of the collection (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub number_of_shards: Option<u32>,
+    /// The document attributes used to determine the target shard of the documents (cluster only).
+    ///
+    /// By default, `_key` is used
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub shard_keys: Option<Vec<String>>,
+    /// The replication factor of the collection shards (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub replication_factor: Option<ReplicationFactor>,
+    /// The number of in sync copies required for the collection shards to accept writes
+    /// (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub write_concern: Option<u32>,
+    /// The name of an existing collection whose sharding is followed by the collection
+    /// (Enterprise Edition cluster only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub distribute_shards_like: Option<String>,
+    /// The computed values of the collection (`ArangoDB` 3.10+)
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub computed_values: Vec<ComputedValue>,
+}
+
+/// The `ArangoDB` collection creation body
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionCreationBody<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    collection_type: u8,
+    wait_for_sync: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    number_of_shards: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shard_keys: Option<&'a [String]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replication_factor: Option<ReplicationFactor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    write_concern: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribute_shards_like: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    computed_values: &'a [ComputedValue],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ComputedValuesBody<'a> {
+    computed_values: &'a [ComputedValue],
 }
 
 impl CollectionSchema {
@@ -32,8 +92,107 @@ impl CollectionSchema {
             name: name.to_string(),
             is_edge_collection,
             wait_for_sync,
+            ..Self::default()
+        }
+    }
+
+    /// Initializes the collection schema of the `T` record, with its collection name and type
+    /// (see [`Record::IS_EDGE_COLLECTION`]).
+    ///
+    /// [`Record::IS_EDGE_COLLECTION`]: crate::Record::IS_EDGE_COLLECTION
+    #[must_use]
+    #[inline]
+    pub fn from_record<T: Record>() -> Self {
+        Self::new(T::COLLECTION_NAME, T::IS_EDGE_COLLECTION, None)
+    }
+
+    fn creation_body(&self) -> CollectionCreationBody<'_> {
+        CollectionCreationBody {
+            name: &self.name,
+            collection_type: if self.is_edge_collection {
+                CollectionType::Edge as u8
+            } else {
+                CollectionType::Document as u8
+            },
+            wait_for_sync: true,
+            number_of_shards: self.number_of_shards,
+            shard_keys: self.shard_keys.as_deref(),
+            replication_factor: self.replication_factor,
+            write_concern: self.write_concern,
+            distribute_shards_like: self.distribute_shards_like.as_deref(),
+            computed_values: &self.computed_values,
         }
     }
+
+    /// Replaces the computed values of the existing collection by [`computed_values`].
+    /// An empty list removes every computed value of the collection.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the collection doesn't exist or on invalid computed values expressions.
+    ///
+    /// [`computed_values`]: Self::computed_values
+    #[maybe_async::maybe_async]
+    pub async fn update_computed_values(&self, database: &Database) -> Result<(), ClientError> {
+        log::debug!("Updating Collection {} computed values", &self.name);
+        let body = ComputedValuesBody {
+            computed_values: &self.computed_values,
+        };
+        Self::collection_request(
+            database,
+            http::Method::PUT,
+            &format!("_api/collection/{}/properties", self.name),
+            serde_json::to_string(&body)?,
+        )
+        .await
+    }
+
+    /// Creates the collection through the raw API as `arangors_lite` only supports the sharding
+    /// options behind its own features, and doesn't support computed values
+    #[maybe_async::maybe_async]
+    async fn create_collection(&self, database: &Database) -> Result<Collection, ClientError> {
+        let body = serde_json::to_string(&self.creation_body())?;
+        let result =
+            Self::collection_request(database, http::Method::POST, "_api/collection", body).await;
+        match result {
+            // The computed values of existing collections are kept in sync with the schema
+            Err(ClientError::Arango(ref error))
+                if !self.computed_values.is_empty()
+                    && ArangoErrorCode::from_error_num(error.error_num())
+                        == ArangoErrorCode::ArangoDuplicateName =>
+            {
+                self.update_computed_values(database).await?;
+                result?;
+            }
+            result => result?,
+        }
+        database.collection(&self.name).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn collection_request(
+        database: &Database,
+        method: http::Method,
+        path: &str,
+        body: String,
+    ) -> Result<(), ClientError> {
+        let url = database
+            .url()
+            .join(path)
+            .map_err(|e| ClientError::HttpClient(HttpError::HttpClient(e.to_string())))?;
+        let request = http::Request::builder()
+            .method(method)
+            .uri(url.to_string())
+            .body(body)
+            .map_err(|e| ClientError::HttpClient(HttpError::HttpClient(e.to_string())))?;
+        let response = database.session().request(request).await?;
+        let value: serde_json::Value = serde_json::from_str(response.body())?;
+        if value.get("error").and_then(serde_json::Value::as_bool) == Some(true) {
+            let error: ArangoError = serde_json::from_value(value)?;
+            return Err(ClientError::Arango(error));
+        }
+        Ok(())
+    }
 }
 
 #[maybe_async::maybe_async]
@@ -46,18 +205,7 @@ impl SchemaDatabaseOperation for CollectionSchema {
         silent: bool,
     ) -> Result<Option<Self::PoolType>, ClientError> {
         log::debug!("Creating Collection {}", &self.name);
-        let creation_settings = CreateOptions::builder()
-            .name(&self.name)
-            .collection_type(if self.is_edge_collection {
-                CollectionType::Edge
-            } else {
-                CollectionType::Document
-            })
-            .wait_for_sync(true)
-            .build();
-        let res = database
-            .create_collection_with_options(creation_settings, CreateParameters::default())
-            .await;
+        let res = self.create_collection(database).await;
         Self::handle_pool_result(res, silent)
     }
 
@@ -71,3 +219,91 @@ impl SchemaDatabaseOperation for CollectionSchema {
         database.collection(&self.name).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::ComputeOn;
+
+    use super::*;
+
+    #[test]
+    fn sharding_options() {
+        let yaml = r#"
+name: Order
+is_edge_collection: false
+number_of_shards: 3
+shard_keys:
+  - customer
+replication_factor: 2
+write_concern: 2
+distribute_shards_like: Customer
+"#;
+        let schema: CollectionSchema = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(schema.number_of_shards, Some(3));
+        assert_eq!(
+            schema.replication_factor,
+            Some(ReplicationFactor::Factor(2))
+        );
+        let body = serde_json::to_value(&schema.creation_body()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "name": "Order",
+                "type": 2,
+                "waitForSync": true,
+                "numberOfShards": 3,
+                "shardKeys": ["customer"],
+                "replicationFactor": 2,
+                "writeConcern": 2,
+                "distributeShardsLike": "Customer"
+            })
+        );
+        let schema = CollectionSchema::new("Follows", true, None);
+        let body = serde_json::to_value(&schema.creation_body()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "name": "Follows", "type": 3, "waitForSync": true })
+        );
+    }
+
+    #[test]
+    fn computed_values() {
+        let yaml = r#"
+name: User
+is_edge_collection: false
+computed_values:
+  - name: fullName
+    expression: RETURN CONCAT(@doc.firstName, " ", @doc.lastName)
+    computeOn:
+      - insert
+      - replace
+    overwrite: true
+    keepNull: false
+"#;
+        let schema: CollectionSchema = serde_yaml::from_str(yaml).unwrap();
+        let computed_value = &schema.computed_values[0];
+        assert_eq!(
+            computed_value.compute_on,
+            vec![ComputeOn::Insert, ComputeOn::Replace]
+        );
+        assert_eq!(computed_value.fail_on_warning, None);
+        let body = serde_json::to_value(&schema.creation_body()).unwrap();
+        assert_eq!(
+            body["computedValues"],
+            serde_json::json!([{
+                "name": "fullName",
+                "expression": r#"RETURN CONCAT(@doc.firstName, " ", @doc.lastName)"#,
+                "computeOn": ["insert", "replace"],
+                "overwrite": true,
+                "keepNull": false
+            }])
+        );
+        let body = ComputedValuesBody {
+            computed_values: &[],
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({ "computedValues": [] })
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// Aragog schema representation of an `ArangoDB` computed value (`ArangoDB` 3.10+), an attribute
+/// derived on the server side from an AQL expression when writing documents.
+///
+/// # Example
+///
+/// ```yaml
+/// name: fullName
+/// expression: RETURN CONCAT(@doc.firstName, " ", @doc.lastName)
+/// computeOn:
+///   - insert
+///   - update
+/// overwrite: true
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputedValue {
+    /// The name of the computed attribute
+    pub name: String,
+    /// The AQL `RETURN` expression computing the value, the document is available as `@doc`
+    pub expression: String,
+    /// The write operations computing the value. By default, the value is computed on every
+    /// operation
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub compute_on: Vec<ComputeOn>,
+    /// Defines if the computed value overwrites the attribute if set in the document
+    pub overwrite: bool,
+    /// Defines if the attribute is kept when the expression evaluates to `null` (`true` by default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_null: Option<bool>,
+    /// Defines if the write operation fails when the expression produces a warning (`false` by
+    /// default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_on_warning: Option<bool>,
+}
+
+/// Write operation computing a [`ComputedValue`]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeOn {
+    /// The value is computed on document creation
+    Insert,
+    /// The value is computed on partial document update
+    Update,
+    /// The value is computed on document replacement
+    Replace,
+}
+
+impl ComputedValue {
+    /// Initializes a computed value overwriting the attribute on every write operation
+    #[must_use]
+    #[inline]
+    pub fn new(name: &str, expression: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            compute_on: Vec::new(),
+            overwrite: true,
+            keep_null: None,
+            fail_on_warning: None,
+        }
+    }
+}
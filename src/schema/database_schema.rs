@@ -4,7 +4,7 @@ use arangors_lite::{ClientError, Database};
 use serde::{Deserialize, Serialize};
 
 use crate::schema::{CollectionSchema, GraphSchema, IndexSchema, SchemaDatabaseOperation};
-use crate::Error;
+use crate::{Error, Record};
 
 /// Aragog schema representation of an `ArangoDB` Database.
 /// This struct is meant to load/generate the schema file.
@@ -35,6 +35,84 @@ impl DatabaseSchema {
         self.collections.iter().find(|c| c.name == name)
     }
 
+    /// Checks that the `T` record collection is in the schema with the right collection type
+    /// (see [`Record::IS_EDGE_COLLECTION`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if the collection is missing and [`Error::InitError`] if its type
+    /// doesn't match.
+    ///
+    /// [`Record::IS_EDGE_COLLECTION`]: crate::Record::IS_EDGE_COLLECTION
+    /// [`Error::NotFound`]: crate::Error::NotFound
+    /// [`Error::InitError`]: crate::Error::InitError
+    pub fn check_record<T: Record>(&self) -> Result<(), Error> {
+        let collection = self
+            .collection(T::COLLECTION_NAME)
+            .ok_or_else(|| Error::NotFound {
+                item: "Collection".to_string(),
+                id: T::COLLECTION_NAME.to_string(),
+                source: None,
+            })?;
+        if collection.is_edge_collection != T::IS_EDGE_COLLECTION {
+            return Err(Error::InitError {
+                item: T::COLLECTION_NAME.to_string(),
+                message: format!(
+                    "Collection is {}an edge collection in the schema",
+                    if collection.is_edge_collection {
+                        ""
+                    } else {
+                        "not "
+                    }
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds the `T` record collection to the schema, as an edge collection if
+    /// [`Record::IS_EDGE_COLLECTION`] is set. Does nothing if the collection already exists.
+    ///
+    /// [`Record::IS_EDGE_COLLECTION`]: crate::Record::IS_EDGE_COLLECTION
+    pub fn add_record_collection<T: Record>(&mut self) {
+        if self.collection_index(T::COLLECTION_NAME).is_none() {
+            self.collections.push(CollectionSchema::from_record::<T>());
+        }
+    }
+
+    /// Adds the `T` record collection and its [`Record::indexes`] to the schema, skipping the
+    /// existing ones.
+    ///
+    /// [`Record::indexes`]: crate::Record::indexes
+    pub fn add_record<T: Record>(&mut self) {
+        self.add_record_collection::<T>();
+        for index in T::indexes() {
+            if self.index_index(&index.collection, &index.name).is_none() {
+                self.indexes.push(index);
+            }
+        }
+    }
+
+    /// Builds the schema of every record registered with the `#[register_record]` attribute,
+    /// sorted by collection name (see [`add_record`]).
+    ///
+    /// Useful for simple applications without migrations, the schema can then be applied on
+    /// connection with [`DatabaseConnectionBuilder::with_schema`].
+    ///
+    /// [`add_record`]: Self::add_record
+    /// [`DatabaseConnectionBuilder::with_schema`]: crate::DatabaseConnectionBuilder::with_schema
+    #[cfg(feature = "registry")]
+    #[must_use]
+    pub fn from_registered_models() -> Self {
+        let mut records: Vec<&crate::RegisteredRecord> = crate::registered_records().collect();
+        records.sort_by_key(|record| record.collection_name);
+        let mut schema = Self::default();
+        for record in records {
+            (record.add_to_schema)(&mut schema);
+        }
+        schema
+    }
+
     /// Find an index index from the schema instance
     #[must_use]
     pub fn index_index(&self, collection: &str, name: &str) -> Option<usize> {
@@ -54,13 +132,73 @@ impl DatabaseSchema {
     /// Find an index index from the schema instance
     #[must_use]
     pub fn graph_index(&self, name: &str) -> Option<usize> {
-        self.graphs.iter().position(|c| c.0.name == name)
+        self.graphs.iter().position(|c| c.name == name)
     }
 
     /// Find an Index from the schema instance
     #[must_use]
     pub fn graph(&self, name: &str) -> Option<&GraphSchema> {
-        self.graphs.iter().find(|c| c.0.name == name)
+        self.graphs.iter().find(|c| c.name == name)
+    }
+
+    /// The collections of the graphs missing from the schema collections, as `(graph, collection)`
+    /// pairs. See [`GraphSchema::collection_names`]
+    #[must_use]
+    pub fn undeclared_graph_collections(&self) -> Vec<(&str, &str)> {
+        self.graphs
+            .iter()
+            .flat_map(|graph| {
+                graph
+                    .collection_names()
+                    .into_iter()
+                    .filter(|name| self.collection_index(name).is_none())
+                    .map(move |name| (graph.name.as_str(), name))
+            })
+            .collect()
+    }
+
+    /// Checks that the collections of every graph are declared in the schema or exist in
+    /// `database`, to fail on schema application instead of on the first graph traversal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InitError`] listing the missing collections of the first invalid graph,
+    /// or fails on database error.
+    ///
+    /// [`Error::InitError`]: crate::Error::InitError
+    #[maybe_async::maybe_async]
+    pub async fn check_graph_collections(&self, database: &Database) -> Result<(), Error> {
+        let undeclared = self.undeclared_graph_collections();
+        if undeclared.is_empty() {
+            return Ok(());
+        }
+        let existing: Vec<String> = database
+            .accessible_collections()
+            .await?
+            .into_iter()
+            .map(|info| info.name)
+            .collect();
+        let missing: Vec<(&str, &str)> = undeclared
+            .into_iter()
+            .filter(|(_, name)| !existing.iter().any(|existing| existing == name))
+            .collect();
+        match missing.first() {
+            None => Ok(()),
+            Some((graph, _)) => {
+                let names: Vec<&str> = missing
+                    .iter()
+                    .filter(|(missing_graph, _)| missing_graph == graph)
+                    .map(|(_, name)| *name)
+                    .collect();
+                Err(Error::InitError {
+                    item: format!("Graph {}", graph),
+                    message: format!(
+                        "The graph references collections missing from the schema and the database: {}",
+                        names.join(", ")
+                    ),
+                })
+            }
+        }
     }
 
     /// Loads the YAML schema from the give `path`
@@ -132,10 +270,10 @@ impl SchemaDatabaseOperation for DatabaseSchema {
 
 #[cfg(test)]
 mod tests {
-    use arangors_lite::graph::{EdgeDefinition, Graph, GraphOptions};
+    use arangors_lite::graph::EdgeDefinition;
     use arangors_lite::index::IndexSettings;
 
-    use crate::schema::IndexSchema;
+    use crate::schema::{GraphSchemaOptions, IndexSchema, ReplicationFactor};
 
     use super::*;
 
@@ -147,16 +285,19 @@ mod tests {
                     name: "collectionA".to_string(),
                     is_edge_collection: false,
                     wait_for_sync: None,
+                    ..CollectionSchema::default()
                 },
                 CollectionSchema {
                     name: "collectionB".to_string(),
                     is_edge_collection: false,
                     wait_for_sync: Some(true),
+                    ..CollectionSchema::default()
                 },
                 CollectionSchema {
                     name: "edgeCollectionA".to_string(),
                     is_edge_collection: true,
                     wait_for_sync: None,
+                    ..CollectionSchema::default()
                 },
             ],
             indexes: vec![
@@ -177,7 +318,7 @@ mod tests {
                     settings: IndexSettings::Ttl { expire_after: 3600 },
                 },
             ],
-            graphs: vec![GraphSchema(Graph {
+            graphs: vec![GraphSchema {
                 name: "namedGraph".to_string(),
                 edge_definitions: vec![EdgeDefinition {
                     collection: "edgeCollection1".to_string(),
@@ -187,19 +328,83 @@ mod tests {
                 orphan_collections: vec![],
                 is_smart: None,
                 is_disjoint: None,
-                options: Some(GraphOptions {
+                options: Some(GraphSchemaOptions {
                     smart_graph_attribute: None,
                     number_of_shards: None,
-                    replication_factor: Some(10),
+                    replication_factor: Some(ReplicationFactor::Factor(10)),
                     write_concern: None,
+                    satellites: vec![],
                 }),
-            })],
+            }],
         }
     }
 
+    #[test]
+    fn undeclared_graph_collections() {
+        let mut schema = schema();
+        assert_eq!(
+            schema.undeclared_graph_collections(),
+            vec![
+                ("namedGraph", "edgeCollection1"),
+                ("namedGraph", "collectionC")
+            ]
+        );
+        schema.graphs[0].edge_definitions[0].collection = "edgeCollectionA".to_string();
+        schema.graphs[0].edge_definitions[0].to.pop();
+        schema.graphs[0]
+            .orphan_collections
+            .push("collectionA".to_string());
+        assert!(schema.undeclared_graph_collections().is_empty());
+    }
+
     #[test]
     fn serialization_works() {
         let schema = schema();
         serde_yaml::to_string(&schema).unwrap();
     }
+
+    #[test]
+    fn enterprise_graph_options() {
+        let yaml = r#"
+version: 1
+collections: []
+graphs:
+  - name: smartGraph
+    edgeDefinitions: []
+    isSmart: true
+    options:
+      smartGraphAttribute: region
+      replicationFactor: 2
+      satellites:
+        - Country
+  - name: satelliteGraph
+    edgeDefinitions: []
+    options:
+      replicationFactor: satellite
+"#;
+        let schema: DatabaseSchema = serde_yaml::from_str(yaml).unwrap();
+        let options = schema.graph("smartGraph").unwrap().options.clone().unwrap();
+        assert_eq!(options.smart_graph_attribute.as_deref(), Some("region"));
+        assert_eq!(
+            options.replication_factor,
+            Some(ReplicationFactor::Factor(2))
+        );
+        assert_eq!(options.satellites, vec!["Country".to_string()]);
+        let options = schema
+            .graph("satelliteGraph")
+            .unwrap()
+            .options
+            .clone()
+            .unwrap();
+        assert_eq!(
+            options.replication_factor,
+            Some(ReplicationFactor::Satellite)
+        );
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "replicationFactor": "satellite" })
+        );
+        assert!(serde_yaml::from_str::<GraphSchemaOptions>("replicationFactor: other").is_err());
+    }
 }
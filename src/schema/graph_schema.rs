@@ -1,16 +1,215 @@
+use std::fmt::{self, Formatter};
+
 use crate::schema::SchemaDatabaseOperation;
-use arangors_lite::graph::Graph;
-use arangors_lite::{ClientError, Database};
-use serde::{Deserialize, Serialize};
+use arangors_lite::error::HttpError;
+use arangors_lite::graph::{EdgeDefinition, Graph, GraphOptions};
+use arangors_lite::{ArangoError, ClientError, Database};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Aragog schema representation of an `ArangoDB` Named Graph.
 /// This struct is meant to load/generate the schema file.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct GraphSchema(pub Graph);
+///
+/// The `ArangoDB` Enterprise Edition graphs are supported:
+/// * **Smart graph**: set `is_smart` and the `smart_graph_attribute` option
+/// * **Enterprise graph**: set `is_smart` without a `smart_graph_attribute` option
+/// * **Satellite graph**: set the `replication_factor` option to [`ReplicationFactor::Satellite`]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphSchema {
+    /// Name of the graph
+    pub name: String,
+    /// The definitions of the relations of the graph
+    pub edge_definitions: Vec<EdgeDefinition>,
+    /// Additional vertex collections, without edges in the graph
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub orphan_collections: Vec<String>,
+    /// Defines if the graph is a smart graph or an enterprise graph (Enterprise Edition only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_smart: Option<bool>,
+    /// Defines if the smart graph is disjoint (Enterprise Edition only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_disjoint: Option<bool>,
+    /// The options of the graph collections creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<GraphSchemaOptions>,
+}
+
+/// Options of the collections created for a [`GraphSchema`]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphSchemaOptions {
+    /// The attribute used to shard the vertices of a smart graph, required for smart graphs
+    /// (Enterprise Edition only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_graph_attribute: Option<String>,
+    /// The number of shards of every collection of the graph (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_shards: Option<u32>,
+    /// The replication factor of the graph collections (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication_factor: Option<ReplicationFactor>,
+    /// The number of in sync copies required for the graph collections shards to accept writes
+    /// (cluster only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_concern: Option<u32>,
+    /// The vertex collections of a smart graph or enterprise graph to create as satellite
+    /// collections (Enterprise Edition only)
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub satellites: Vec<String>,
+}
+
+/// Replication factor of the graph collections, serialized as a number or as `"satellite"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationFactor {
+    /// The number of copies of each shard
+    Factor(u32),
+    /// Creates a satellite graph, replicated on every database server (Enterprise Edition only)
+    Satellite,
+}
+
+impl GraphSchema {
+    /// The names of the collections of the graph: the edge collections, the vertex collections of
+    /// the edge definitions and the orphan collections, without duplicates
+    #[must_use]
+    pub fn collection_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        let definitions = self.edge_definitions.iter().flat_map(|definition| {
+            std::iter::once(&definition.collection)
+                .chain(&definition.from)
+                .chain(&definition.to)
+        });
+        for name in definitions.chain(&self.orphan_collections) {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
 
+#[derive(Deserialize)]
+struct GraphResponse {
+    graph: Graph,
+}
+
+impl From<Graph> for GraphSchema {
+    fn from(graph: Graph) -> Self {
+        Self {
+            name: graph.name,
+            edge_definitions: graph.edge_definitions,
+            orphan_collections: graph.orphan_collections,
+            is_smart: graph.is_smart,
+            is_disjoint: graph.is_disjoint,
+            options: graph.options.map(Into::into),
+        }
+    }
+}
+
+/// The satellite options are lost in the conversion
 impl From<GraphSchema> for Graph {
     fn from(schema: GraphSchema) -> Self {
-        schema.0
+        Self {
+            name: schema.name,
+            edge_definitions: schema.edge_definitions,
+            orphan_collections: schema.orphan_collections,
+            is_smart: schema.is_smart,
+            is_disjoint: schema.is_disjoint,
+            options: schema.options.map(Into::into),
+        }
+    }
+}
+
+impl From<GraphOptions> for GraphSchemaOptions {
+    fn from(options: GraphOptions) -> Self {
+        Self {
+            smart_graph_attribute: options.smart_graph_attribute,
+            number_of_shards: options.number_of_shards,
+            replication_factor: options.replication_factor.map(ReplicationFactor::Factor),
+            write_concern: options.write_concern,
+            satellites: Vec::new(),
+        }
+    }
+}
+
+impl From<GraphSchemaOptions> for GraphOptions {
+    fn from(options: GraphSchemaOptions) -> Self {
+        Self {
+            smart_graph_attribute: options.smart_graph_attribute,
+            number_of_shards: options.number_of_shards,
+            replication_factor: match options.replication_factor {
+                Some(ReplicationFactor::Factor(factor)) => Some(factor),
+                Some(ReplicationFactor::Satellite) | None => None,
+            },
+            write_concern: options.write_concern,
+        }
+    }
+}
+
+impl Serialize for ReplicationFactor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Factor(factor) => serializer.serialize_u32(*factor),
+            Self::Satellite => serializer.serialize_str("satellite"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ReplicationFactor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ReplicationFactorVisitor;
+
+        impl Visitor<'_> for ReplicationFactorVisitor {
+            type Value = ReplicationFactor;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str(r#"a positive integer or "satellite""#)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                u32::try_from(value)
+                    .map(ReplicationFactor::Factor)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                u32::try_from(value)
+                    .map(ReplicationFactor::Factor)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value == "satellite" {
+                    Ok(ReplicationFactor::Satellite)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(value), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ReplicationFactorVisitor)
+    }
+}
+
+impl GraphSchema {
+    /// Creates the graph through the raw API as `arangors_lite` doesn't support satellites
+    #[maybe_async::maybe_async]
+    async fn create_graph(&self, database: &Database) -> Result<Graph, ClientError> {
+        let url = database
+            .url()
+            .join("_api/gharial?waitForSync=true")
+            .map_err(|e| ClientError::HttpClient(HttpError::HttpClient(e.to_string())))?;
+        let request = http::Request::post(url.to_string())
+            .body(serde_json::to_string(self)?)
+            .map_err(|e| ClientError::HttpClient(HttpError::HttpClient(e.to_string())))?;
+        let response = database.session().request(request).await?;
+        let value: serde_json::Value = serde_json::from_str(response.body())?;
+        if value.get("error").and_then(serde_json::Value::as_bool) == Some(true) {
+            let error: ArangoError = serde_json::from_value(value)?;
+            return Err(ClientError::Arango(error));
+        }
+        let response: GraphResponse = serde_json::from_value(value)?;
+        Ok(response.graph)
     }
 }
 
@@ -23,18 +222,18 @@ impl SchemaDatabaseOperation for GraphSchema {
         database: &Database,
         silent: bool,
     ) -> Result<Option<Self::PoolType>, ClientError> {
-        log::debug!("Creating Graph {}", &self.0.name);
-        let graph = self.clone().into();
-        Self::handle_pool_result(database.create_graph(graph, true).await, silent)
+        log::debug!("Creating Graph {}", &self.name);
+        let result = self.create_graph(database).await;
+        Self::handle_pool_result(result, silent)
     }
 
     async fn drop(&self, database: &Database) -> Result<(), ClientError> {
-        log::debug!("Deleting Graph {}", &self.0.name);
-        database.drop_graph(&self.0.name, false).await?;
+        log::debug!("Deleting Graph {}", &self.name);
+        database.drop_graph(&self.name, false).await?;
         Ok(())
     }
 
     async fn get(&self, database: &Database) -> Result<Self::PoolType, ClientError> {
-        database.graph(&self.0.name).await
+        database.graph(&self.name).await
     }
 }
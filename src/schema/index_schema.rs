@@ -28,6 +28,18 @@ impl From<IndexSchema> for Index {
 }
 
 impl IndexSchema {
+    /// Builds the schema representation of an existing `arangors_lite` index of `collection`
+    #[must_use]
+    #[inline]
+    pub fn from_index(collection: &str, index: Index) -> Self {
+        Self {
+            name: index.name,
+            collection: collection.to_string(),
+            fields: index.fields,
+            settings: index.settings,
+        }
+    }
+
     /// Retrieve the index id
     #[must_use]
     #[inline]
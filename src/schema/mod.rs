@@ -1,19 +1,31 @@
 use arangors_lite::{ClientError, Database};
 
+pub use arangors_lite::index::IndexSettings;
+
 pub use {
-    collection_schema::CollectionSchema, database_schema::DatabaseSchema,
-    graph_schema::GraphSchema, index_schema::IndexSchema,
+    collection_schema::CollectionSchema,
+    computed_value::{ComputeOn, ComputedValue},
+    database_schema::DatabaseSchema,
+    graph_schema::{GraphSchema, GraphSchemaOptions, ReplicationFactor},
+    index_schema::IndexSchema,
+    schema_report::SchemaApplyReport,
 };
 
 mod collection_schema;
+mod computed_value;
 mod database_schema;
 mod graph_schema;
 mod index_schema;
+mod schema_report;
 
 /// Default schema path, can be overridden manually or set as `SCHEMA_PATH` env var
 pub const SCHEMA_DEFAULT_PATH: &str = "./src/config/db";
 /// Default schema file name, can be overridden manually
 pub const SCHEMA_DEFAULT_FILE_NAME: &str = "schema.yaml";
+/// Default name of the collection storing the database schema version, synchronized by `aragog_cli`
+pub const SCHEMA_DEFAULT_COLLECTION_NAME: &str = "AragogConfiguration";
+/// Key of the document storing the database schema version in the schema collection
+pub const SCHEMA_DOCUMENT_KEY: &str = "DatabaseSchema";
 
 /// Trait used for all schema elements allowing to synchronise schema changes.
 /// Used by `aragog_cli` for migrations and `DatabaseConnection`
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use arangors_lite::collection::CollectionType;
+use arangors_lite::graph::EdgeDefinition;
+use arangors_lite::index::IndexSettings;
+use arangors_lite::Database;
+
+use crate::schema::{DatabaseSchema, SchemaDatabaseOperation};
+use crate::Error;
+
+/// The schema elements created by [`DatabaseSchema::apply_strict`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaApplyReport {
+    /// The created collections names
+    pub created_collections: Vec<String>,
+    /// The created indexes identifiers (`collection/name`)
+    pub created_indexes: Vec<String>,
+    /// The created graphs names
+    pub created_graphs: Vec<String>,
+}
+
+impl SchemaApplyReport {
+    /// Returns `true` if the database already matched the schema
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.created_collections.is_empty()
+            && self.created_indexes.is_empty()
+            && self.created_graphs.is_empty()
+    }
+}
+
+impl Display for SchemaApplyReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "the database matches the schema");
+        }
+        let created = [
+            ("collections", &self.created_collections),
+            ("indexes", &self.created_indexes),
+            ("graphs", &self.created_graphs),
+        ];
+        let items: Vec<String> = created
+            .iter()
+            .filter(|(_, names)| !names.is_empty())
+            .map(|(kind, names)| format!("created {}: {}", kind, names.join(", ")))
+            .collect();
+        write!(f, "{}", items.join(", "))
+    }
+}
+
+fn incompatible(item: String, message: &str) -> Error {
+    Error::InitError {
+        item,
+        message: format!("Incompatible schema definition: {}", message),
+    }
+}
+
+/// The index settings as JSON, the `hash` and `skiplist` types being aliases of `persistent`
+fn comparable_settings(settings: &IndexSettings) -> serde_json::Value {
+    let mut value = serde_json::to_value(settings).unwrap_or_default();
+    if matches!(
+        settings,
+        IndexSettings::Hash { .. } | IndexSettings::Skiplist { .. }
+    ) {
+        value["type"] = serde_json::Value::from("persistent");
+    }
+    value
+}
+
+fn comparable_edge_definitions(
+    definitions: &[EdgeDefinition],
+) -> Vec<(String, Vec<String>, Vec<String>)> {
+    let mut definitions: Vec<_> = definitions
+        .iter()
+        .map(|definition| {
+            let mut from = definition.from.clone();
+            let mut to = definition.to.clone();
+            from.sort();
+            to.sort();
+            (definition.collection.clone(), from, to)
+        })
+        .collect();
+    definitions.sort();
+    definitions
+}
+
+impl DatabaseSchema {
+    /// Applies the schema by computing the difference with the live database: only the missing
+    /// collections, indexes and graphs are created, and the created elements are reported.
+    ///
+    /// Unlike the silent [`apply_to_database`], the errors are never ignored.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InitError`] if an existing element is incompatible with its schema
+    /// definition (collection type, index type or fields, graph edge definitions), or on any
+    /// database error.
+    ///
+    /// [`apply_to_database`]: SchemaDatabaseOperation::apply_to_database
+    /// [`Error::InitError`]: crate::Error::InitError
+    #[maybe_async::maybe_async]
+    pub async fn apply_strict(&self, database: &Database) -> Result<SchemaApplyReport, Error> {
+        let mut report = SchemaApplyReport::default();
+        let existing_collections: HashMap<String, CollectionType> = database
+            .accessible_collections()
+            .await?
+            .into_iter()
+            .map(|info| (info.name, info.collection_type))
+            .collect();
+        for collection in &self.collections {
+            let expected_type = if collection.is_edge_collection {
+                CollectionType::Edge
+            } else {
+                CollectionType::Document
+            };
+            match existing_collections.get(&collection.name) {
+                Some(collection_type) if *collection_type == expected_type => (),
+                Some(collection_type) => {
+                    return Err(incompatible(
+                        format!("Collection {}", collection.name),
+                        &format!(
+                            "the collection type is {:?} instead of {:?}",
+                            collection_type, expected_type
+                        ),
+                    ))
+                }
+                None => {
+                    collection.apply_to_database(database, false).await?;
+                    report.created_collections.push(collection.name.clone());
+                }
+            }
+        }
+        for index in &self.indexes {
+            let existing = database
+                .indexes(&index.collection)
+                .await?
+                .indexes
+                .into_iter()
+                .find(|existing| existing.name == index.name);
+            if let Some(existing) = existing {
+                let (expected, found) = (
+                    comparable_settings(&index.settings),
+                    comparable_settings(&existing.settings),
+                );
+                if expected != found || existing.fields != index.fields {
+                    return Err(incompatible(
+                        format!("Index {}", index.id()),
+                        &format!(
+                            "the index is {} on {:?} instead of {} on {:?}",
+                            found, existing.fields, expected, index.fields
+                        ),
+                    ));
+                }
+            } else {
+                index.apply_to_database(database, false).await?;
+                report.created_indexes.push(index.id());
+            }
+        }
+        let existing_graphs = database.graphs().await?.graphs;
+        for graph in &self.graphs {
+            let existing = existing_graphs
+                .iter()
+                .find(|existing| existing.name == graph.name);
+            if let Some(existing) = existing {
+                if comparable_edge_definitions(&existing.edge_definitions)
+                    != comparable_edge_definitions(&graph.edge_definitions)
+                {
+                    return Err(incompatible(
+                        format!("Graph {}", graph.name),
+                        "the edge definitions differ",
+                    ));
+                }
+            } else {
+                graph.apply_to_database(database, false).await?;
+                report.created_graphs.push(graph.name.clone());
+            }
+        }
+        log::info!("Applied the database schema: {}", report);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_settings_aliases() {
+        let hash = IndexSettings::Hash {
+            unique: true,
+            sparse: false,
+            deduplicate: false,
+        };
+        let persistent = IndexSettings::Persistent {
+            unique: true,
+            sparse: false,
+            deduplicate: false,
+        };
+        let non_unique = IndexSettings::Persistent {
+            unique: false,
+            sparse: false,
+            deduplicate: false,
+        };
+        assert_eq!(comparable_settings(&hash), comparable_settings(&persistent));
+        assert_ne!(
+            comparable_settings(&non_unique),
+            comparable_settings(&persistent)
+        );
+    }
+
+    #[test]
+    fn report_display() {
+        let mut report = SchemaApplyReport::default();
+        assert_eq!(report.to_string(), "the database matches the schema");
+        report.created_collections = vec!["User".to_string(), "Order".to_string()];
+        report.created_graphs = vec!["Shop".to_string()];
+        assert_eq!(
+            report.to_string(),
+            "created collections: User, Order, created graphs: Shop"
+        );
+    }
+}
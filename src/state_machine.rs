@@ -0,0 +1,267 @@
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Error, Validate};
+
+/// The `StateMachine` trait of the Aragog library.
+/// It declares the allowed transitions between the values of a status enum, stored in a record
+/// through a [`State`] field.
+///
+/// Use the [`state_machine!`] macro to implement it.
+///
+/// [`state_machine!`]: crate::state_machine!
+pub trait StateMachine: Clone + PartialEq + Debug {
+    /// Returns `true` if the state can change from `self` to `next`
+    fn can_transition_to(&self, next: &Self) -> bool;
+}
+
+/// A record field holding a [`StateMachine`] value, serialized as the inner value.
+///
+/// The field remembers the value it was deserialized with, allowing to check the pending
+/// transition before saving the record: the [`Validate`] implementation fails on illegal
+/// transitions, use it with the `call_validations` operation of the `Validate` derive macro and a
+/// `before_save` validation hook. Only one transition can be saved at a time.
+/// Transition callbacks are `before_save` hooks checking [`pending_transition`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate aragog;
+/// # use aragog::{Record, State, Validate};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// pub enum OrderStatus {
+///     Pending,
+///     Paid,
+///     Shipped,
+///     Cancelled,
+/// }
+///
+/// state_machine! {
+///     OrderStatus {
+///         Pending => [Paid, Cancelled],
+///         Paid => [Shipped, Cancelled],
+///     }
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize, Record, Validate)]
+/// #[before_save(func = "validate")]
+/// #[before_save(func = "notify_shipping")]
+/// pub struct Order {
+///     #[validate(call_validations)]
+///     pub status: State<OrderStatus>,
+/// }
+///
+/// impl Order {
+///     fn notify_shipping(&self) -> Result<(), aragog::Error> {
+///         if let Some((_, OrderStatus::Shipped)) = self.status.pending_transition() {
+///             // notify the customer
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut order = Order { status: State::new(OrderStatus::Pending) };
+/// order.status.transition_to(OrderStatus::Paid).unwrap();
+/// assert!(order.status.transition_to(OrderStatus::Pending).is_err());
+/// assert_eq!(*order.status, OrderStatus::Paid);
+/// # }
+/// ```
+///
+/// [`pending_transition`]: Self::pending_transition
+#[derive(Debug, Clone)]
+pub struct State<S> {
+    current: S,
+    persisted: Option<S>,
+}
+
+impl<S> State<S> {
+    /// Initializes a state without persisted value, any value is valid for new records
+    #[must_use]
+    #[inline]
+    pub const fn new(state: S) -> Self {
+        Self {
+            current: state,
+            persisted: None,
+        }
+    }
+
+    /// The current value
+    #[must_use]
+    #[inline]
+    pub const fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// The value the state was deserialized with, `None` for a state built with [`new`]
+    ///
+    /// [`new`]: Self::new
+    #[must_use]
+    #[inline]
+    pub const fn persisted(&self) -> Option<&S> {
+        self.persisted.as_ref()
+    }
+}
+
+impl<S: StateMachine> State<S> {
+    /// The transition not saved yet, as a `(from, to)` tuple
+    #[must_use]
+    pub fn pending_transition(&self) -> Option<(&S, &S)> {
+        self.persisted
+            .as_ref()
+            .filter(|persisted| **persisted != self.current)
+            .map(|persisted| (persisted, &self.current))
+    }
+
+    /// Changes the current value to `next`
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::ValidationError`] if the current value can't transition to `next`
+    ///
+    /// [`Error::ValidationError`]: crate::Error::ValidationError
+    pub fn transition_to(&mut self, next: S) -> Result<(), Error> {
+        if !self.current.can_transition_to(&next) {
+            return Err(Error::ValidationError(Self::illegal_transition_message(
+                &self.current,
+                &next,
+            )));
+        }
+        self.current = next;
+        Ok(())
+    }
+
+    fn illegal_transition_message(from: &S, to: &S) -> String {
+        format!("transition from {:?} to {:?} is not allowed", from, to)
+    }
+}
+
+impl<S: StateMachine> Validate for State<S> {
+    fn validations(&self, errors: &mut Vec<String>) {
+        if let Some((from, to)) = self.pending_transition() {
+            if !from.can_transition_to(to) {
+                errors.push(Self::illegal_transition_message(from, to));
+            }
+        }
+    }
+}
+
+impl<S> Deref for State<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.current
+    }
+}
+
+impl<S: Serialize> Serialize for State<S> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        self.current.serialize(serializer)
+    }
+}
+
+impl<'de, S: Deserialize<'de> + Clone> Deserialize<'de> for State<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let current = S::deserialize(deserializer)?;
+        Ok(Self {
+            persisted: Some(current.clone()),
+            current,
+        })
+    }
+}
+
+/// Implements [`StateMachine`] for an enum from its allowed transitions, states without
+/// transitions are final.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate aragog;
+/// # use aragog::StateMachine;
+/// #[derive(Clone, Debug, PartialEq)]
+/// pub enum Status {
+///     Draft,
+///     Published,
+///     Archived,
+/// }
+///
+/// state_machine! {
+///     Status {
+///         Draft => [Published],
+///         Published => [Draft, Archived],
+///     }
+/// }
+///
+/// # fn main() {
+/// assert!(Status::Draft.can_transition_to(&Status::Published));
+/// assert!(!Status::Archived.can_transition_to(&Status::Draft));
+/// # }
+/// ```
+///
+/// [`StateMachine`]: crate::StateMachine
+#[macro_export]
+macro_rules! state_machine {
+    ($state:ty { $($from:ident => [$($to:ident),* $(,)?]),* $(,)? }) => {
+        impl $crate::StateMachine for $state {
+            #[allow(unreachable_patterns)]
+            fn can_transition_to(&self, next: &Self) -> bool {
+                match self {
+                    $(Self::$from => false $(|| *next == Self::$to)*,)*
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum Status {
+        Pending,
+        Paid,
+        Shipped,
+    }
+
+    state_machine! {
+        Status {
+            Pending => [Paid],
+            Paid => [Shipped],
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let mut state = State::new(Status::Pending);
+        assert!(state.pending_transition().is_none());
+        assert!(state.transition_to(Status::Shipped).is_err());
+        state.transition_to(Status::Paid).unwrap();
+        state.transition_to(Status::Shipped).unwrap();
+        assert!(state.transition_to(Status::Pending).is_err());
+        assert_eq!(*state, Status::Shipped);
+    }
+
+    #[test]
+    fn persisted_state_validation() {
+        let mut state: State<Status> = serde_json::from_str(r#""Pending""#).unwrap();
+        assert_eq!(state.persisted(), Some(&Status::Pending));
+        assert!(state.is_valid());
+        state.current = Status::Paid;
+        assert_eq!(
+            state.pending_transition(),
+            Some((&Status::Pending, &Status::Paid))
+        );
+        assert!(state.is_valid());
+        state.current = Status::Shipped;
+        assert!(!state.is_valid());
+        assert_eq!(serde_json::to_string(&state).unwrap(), r#""Shipped""#);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{DatabaseAccess, Error, Record};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::ops::{Deref, DerefMut};
@@ -54,6 +55,26 @@ impl Record for UndefinedRecord {
     }
 }
 
+impl UndefinedRecord {
+    /// Deserializes the raw JSON into `T`, returning both the typed struct and the raw value.
+    ///
+    /// The raw value keeps every attribute, including the ones unknown to `T`, allowing to preserve
+    /// them on round trips.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`UnprocessableEntity`] if the JSON can't be deserialized into `T`
+    ///
+    /// [`UnprocessableEntity`]: crate::Error::UnprocessableEntity
+    pub fn into_parts<T>(self) -> Result<(T, Value), Error>
+    where
+        T: DeserializeOwned,
+    {
+        let typed = T::deserialize(&self.0)?;
+        Ok((typed, self.0))
+    }
+}
+
 impl From<Value> for UndefinedRecord {
     fn from(json: Value) -> Self {
         Self(json)
@@ -73,3 +94,27 @@ impl DerefMut for UndefinedRecord {
         &mut self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct Dish {
+        name: String,
+    }
+
+    #[test]
+    fn into_parts_keeps_unknown_attributes() {
+        let record = UndefinedRecord(json!({ "name": "Pizza", "origin": "Naples" }));
+        let (dish, raw): (Dish, Value) = record.into_parts().unwrap();
+        assert_eq!(dish.name, "Pizza");
+        assert_eq!(raw["origin"], "Naples");
+        let record = UndefinedRecord(json!({ "origin": "Naples" }));
+        assert!(matches!(
+            record.into_parts::<Dish>(),
+            Err(Error::UnprocessableEntity { .. })
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Error, FieldChange};
+
+/// Computes the changes between two serializable values, like two versions of a record, to build
+/// audit logs or "what changed" views.
+///
+/// The objects and arrays are compared recursively, every change targets a leaf value through its
+/// [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) as the [`FieldChange::field`]:
+/// `/address/city` or `/tags/1`. The changes are sorted by object key and array index.
+///
+/// # Errors
+///
+/// Fails with [`Error::UnprocessableEntity`] if `old` or `new` can't be serialized
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::util::diff;
+/// # use serde::Serialize;
+/// #
+/// #[derive(Serialize)]
+/// struct Address {
+///     city: String,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+///     address: Address,
+///     tags: Vec<String>,
+/// }
+///
+/// let old = User {
+///     name: String::from("Robert"),
+///     address: Address { city: String::from("Paris") },
+///     tags: vec![String::from("admin")],
+/// };
+/// let new = User {
+///     name: String::from("Robert"),
+///     address: Address { city: String::from("Lyon") },
+///     tags: vec![String::from("admin"), String::from("staff")],
+/// };
+/// let changes = diff(&old, &new).unwrap();
+/// let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+/// assert_eq!(fields, vec!["/address/city", "/tags/1"]);
+/// assert_eq!(changes[1].old, None);
+/// ```
+pub fn diff<T: Serialize + ?Sized>(old: &T, new: &T) -> Result<Vec<FieldChange>, Error> {
+    Ok(diff_values(
+        &serde_json::to_value(old)?,
+        &serde_json::to_value(new)?,
+    ))
+}
+
+/// Computes the changes between two JSON values, see [`diff`]
+#[must_use]
+pub fn diff_values(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    collect_changes(String::new(), Some(old), Some(new), &mut changes);
+    changes
+}
+
+/// The maximum length in bytes of a document key
+const MAX_KEY_LENGTH: usize = 254;
+/// The maximum length in bytes of a collection name
+const MAX_COLLECTION_NAME_LENGTH: usize = 256;
+
+/// Checks that `key` is a valid `ArangoDB` document key (`_key`): between 1 and 254 bytes of
+/// letters, digits and `_ - : . @ ( ) + , = ; $ ! * ' %` symbols.
+///
+/// # Errors
+///
+/// Fails with [`Error::ValidationError`] if `key` is not a valid document key
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::util::validate_document_key;
+/// assert!(validate_document_key("custom_key-1").is_ok());
+/// assert!(validate_document_key("Dish/123").is_err());
+/// ```
+pub fn validate_document_key(key: &str) -> Result<(), Error> {
+    if is_document_key(key) {
+        Ok(())
+    } else {
+        Err(Error::ValidationError(format!(
+            r#""{}" is not a valid document key"#,
+            key
+        )))
+    }
+}
+
+/// Checks that `id` is a valid `ArangoDB` document id (`_id`): a `collection/key` string with a
+/// valid collection name and a valid [document key](validate_document_key).
+///
+/// Collection names start with a letter or an underscore, followed by letters, digits, `_` or `-`.
+///
+/// # Errors
+///
+/// Fails with [`Error::ValidationError`] if `id` is not a valid document id
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::util::validate_document_id;
+/// assert!(validate_document_id("Dish/123").is_ok());
+/// assert!(validate_document_id("Dish/").is_err());
+/// assert!(validate_document_id("Dish/1/2").is_err());
+/// ```
+pub fn validate_document_id(id: &str) -> Result<(), Error> {
+    if is_document_id(id) {
+        Ok(())
+    } else {
+        Err(Error::ValidationError(format!(
+            r#""{}" is not a valid document id"#,
+            id
+        )))
+    }
+}
+
+fn is_document_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-:.@()+,=;$!*'%".contains(c))
+}
+
+/// Returns `true` if `name` is a valid collection name, see [`validate_document_id`]
+pub(crate) fn is_collection_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    name.len() <= MAX_COLLECTION_NAME_LENGTH
+        && chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Returns `true` if `id` is a valid document id, see [`validate_document_id`]
+pub(crate) fn is_document_id(id: &str) -> bool {
+    id.split_once('/').map_or(false, |(collection, key)| {
+        is_collection_name(collection) && is_document_key(key)
+    })
+}
+
+/// Escapes an object key as a JSON pointer reference token
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn collect_changes(
+    pointer: String,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                collect_changes(
+                    format!("{}/{}", pointer, escape_pointer_token(key)),
+                    old.get(key),
+                    new.get(key),
+                    changes,
+                );
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) => {
+            for i in 0..old.len().max(new.len()) {
+                collect_changes(
+                    format!("{}/{}", pointer, i),
+                    old.get(i),
+                    new.get(i),
+                    changes,
+                );
+            }
+        }
+        (old, new) if old != new => changes.push(FieldChange {
+            field: pointer,
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_changes() {
+        let old = json!({ "a/b": { "c~d": 1 }, "list": [1, 2, 3], "kept": true, "removed": "x" });
+        let new = json!({ "a/b": { "c~d": 2 }, "list": [1, 4], "kept": true, "added": null });
+        let changes = diff_values(&old, &new);
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["/a~1b/c~0d", "/added", "/list/1", "/list/2", "/removed"]
+        );
+        assert_eq!(changes[1].old, None);
+        assert_eq!(changes[1].new, Some(Value::Null));
+        assert_eq!(changes[3].new, None);
+        assert_eq!(
+            diff_values(&json!(1), &json!("1")),
+            vec![FieldChange {
+                field: String::new(),
+                old: Some(json!(1)),
+                new: Some(json!("1")),
+            }]
+        );
+        assert!(diff_values(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn document_ids_and_keys() {
+        for key in ["1", "custom_key", "a-b:c.d@e(f)+g,h=i;j$k!l*m'n%o"] {
+            assert!(validate_document_key(key).is_ok(), "{}", key);
+        }
+        for key in ["", "a/b", "a b", "é", &"a".repeat(255)] {
+            assert!(validate_document_key(key).is_err(), "{}", key);
+        }
+        for id in ["Dish/1", "_system_collection/key", "my-collection_2/a:b"] {
+            assert!(validate_document_id(id).is_ok(), "{}", id);
+        }
+        for id in [
+            "Dish",
+            "Dish/",
+            "/123",
+            "Dish//123",
+            "Dish/1/2",
+            "2Dish/1",
+            "Di sh/1",
+        ] {
+            assert!(validate_document_id(id).is_err(), "{}", id);
+        }
+        assert_eq!(
+            validate_document_id("Dish/").unwrap_err().to_string(),
+            r#"Validations failed: `"Dish/" is not a valid document id`"#
+        );
+    }
+}
@@ -528,6 +528,303 @@ pub trait Validate {
         true
     }
 
+    /// Validates that `value` is between `min_value` and `max_value` (inclusive). Usually used as a helper
+    /// function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `min_value` - The minimum allowed value
+    /// * `max_value` - The maximum allowed value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_range<T: PartialOrd + Display>(
+        field_path: &str,
+        value: T,
+        min_value: T,
+        max_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value < min_value || value > max_value {
+            errors.push(format!(
+                "{} '{}' must be between {} and {}",
+                field_path, value, min_value, max_value
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is equal to one of the `allowed` values. Usually used as a helper function for
+    /// implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `allowed` - The allowed values
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_one_of<T, V>(
+        field_path: &str,
+        value: &T,
+        allowed: &[V],
+        errors: &mut Vec<String>,
+    ) -> bool
+    where
+        T: PartialEq<V> + Display + ?Sized,
+        V: Display,
+    {
+        if !allowed.iter().any(|v| value == v) {
+            errors.push(format!(
+                "{} '{}' must be one of: {}",
+                field_path,
+                value,
+                allowed
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is not equal to any of the `forbidden` values. Usually used as a helper
+    /// function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `forbidden` - The forbidden values
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_not_one_of<T, V>(
+        field_path: &str,
+        value: &T,
+        forbidden: &[V],
+        errors: &mut Vec<String>,
+    ) -> bool
+    where
+        T: PartialEq<V> + Display + ?Sized,
+        V: Display,
+    {
+        if forbidden.iter().any(|v| value == v) {
+            errors.push(format!("{} '{}' is not allowed", field_path, value));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is greater than `other_value`, the value of another field. Usually used as a
+    /// helper function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `other_field_path` - the string slice representing the compared field name or path
+    /// * `other_value` - the compared field value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_greater_than_field<T: PartialOrd + Display>(
+        field_path: &str,
+        value: T,
+        other_field_path: &str,
+        other_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value <= other_value {
+            errors.push(format!(
+                "{} '{}' must be greater than {} '{}'",
+                field_path, value, other_field_path, other_value
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is greater or equal to `other_value`, the value of another field. Usually used as a
+    /// helper function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `other_field_path` - the string slice representing the compared field name or path
+    /// * `other_value` - the compared field value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_greater_or_equal_to_field<T: PartialOrd + Display>(
+        field_path: &str,
+        value: T,
+        other_field_path: &str,
+        other_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value < other_value {
+            errors.push(format!(
+                "{} '{}' must be greater or equal to {} '{}'",
+                field_path, value, other_field_path, other_value
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is lower than `other_value`, the value of another field. Usually used as a
+    /// helper function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `other_field_path` - the string slice representing the compared field name or path
+    /// * `other_value` - the compared field value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_lesser_than_field<T: PartialOrd + Display>(
+        field_path: &str,
+        value: T,
+        other_field_path: &str,
+        other_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value >= other_value {
+            errors.push(format!(
+                "{} '{}' must be lower than {} '{}'",
+                field_path, value, other_field_path, other_value
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is lower or equal to `other_value`, the value of another field. Usually used as a
+    /// helper function for implementations of [`Validate`] trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `other_field_path` - the string slice representing the compared field name or path
+    /// * `other_value` - the compared field value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_lesser_or_equal_to_field<T: PartialOrd + Display>(
+        field_path: &str,
+        value: T,
+        other_field_path: &str,
+        other_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value > other_value {
+            errors.push(format!(
+                "{} '{}' must be lower or equal to {} '{}'",
+                field_path, value, other_field_path, other_value
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Validates that `value` is equal to `other_value`, the value of another field. Usually used as a
+    /// helper function for implementations of [`Validate`] trait.
+    ///
+    /// The values are not displayed in the error message, allowing to compare sensitive fields like passwords.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - the string slice representing the field name or path for clear errors
+    /// * `value` - the field value to validate
+    /// * `other_field_path` - the string slice representing the compared field name or path
+    /// * `other_value` - the compared field value
+    /// * `errors` - a mutable reference to a vector of String to be filled with error messages like provided
+    ///   in [`Validate`]::[`validations`]
+    ///
+    /// # Returns
+    ///
+    /// On success `true` is returned and `errors` stays unchanged. On failure `false` is returned and a
+    /// new error message is added to `errors`
+    ///
+    /// [`validations`]: Self::validations
+    #[allow(dead_code)]
+    fn validate_fields_equal<T: PartialEq>(
+        field_path: &str,
+        value: T,
+        other_field_path: &str,
+        other_value: T,
+        errors: &mut Vec<String>,
+    ) -> bool {
+        if value != other_value {
+            errors.push(format!(
+                "{} must be equal to {}",
+                field_path, other_field_path
+            ));
+            return false;
+        }
+        true
+    }
+
     /// A simple and fast regular expression to validate email formats
     const SIMPLE_EMAIL_REGEX: &'static str = r#"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,4}"#;
 
@@ -722,4 +1019,145 @@ mod tests {
             }
         }
     }
+
+    mod field_comparison_validators {
+        use super::*;
+
+        #[test]
+        fn validates_ordered_fields() {
+            let mut errors = Vec::new();
+            assert!(TestElem::validate_greater_than_field(
+                "end",
+                2,
+                "start",
+                1,
+                &mut errors
+            ));
+            assert!(TestElem::validate_greater_or_equal_to_field(
+                "end",
+                1,
+                "start",
+                1,
+                &mut errors
+            ));
+            assert!(TestElem::validate_lesser_than_field(
+                "start",
+                1,
+                "end",
+                2,
+                &mut errors
+            ));
+            assert!(TestElem::validate_lesser_or_equal_to_field(
+                "start",
+                2,
+                "end",
+                2,
+                &mut errors
+            ));
+            assert!(errors.is_empty());
+            assert!(!TestElem::validate_greater_than_field(
+                "end",
+                1,
+                "start",
+                1,
+                &mut errors
+            ));
+            assert!(!TestElem::validate_lesser_or_equal_to_field(
+                "start",
+                3,
+                "end",
+                2,
+                &mut errors
+            ));
+            assert_eq!(
+                errors,
+                vec![
+                    "end '1' must be greater than start '1'".to_string(),
+                    "start '3' must be lower or equal to end '2'".to_string()
+                ]
+            );
+        }
+
+        #[test]
+        fn validates_equal_fields() {
+            let mut errors = Vec::new();
+            assert!(TestElem::validate_fields_equal(
+                "a",
+                "value",
+                "b",
+                "value",
+                &mut errors
+            ));
+            assert!(errors.is_empty());
+            assert!(!TestElem::validate_fields_equal(
+                "a",
+                "value",
+                "b",
+                "other",
+                &mut errors
+            ));
+            assert_eq!(errors, vec!["a must be equal to b".to_string()]);
+        }
+
+        #[test]
+        fn validates_range() {
+            let mut errors = Vec::new();
+            assert!(TestElem::validate_range("age", 1, 1, 10, &mut errors));
+            assert!(TestElem::validate_range("age", 10, 1, 10, &mut errors));
+            assert!(errors.is_empty());
+            assert!(!TestElem::validate_range("age", 0, 1, 10, &mut errors));
+            assert!(!TestElem::validate_range(
+                "age",
+                10.5,
+                1.0,
+                10.0,
+                &mut errors
+            ));
+            assert_eq!(
+                errors,
+                vec![
+                    "age '0' must be between 1 and 10".to_string(),
+                    "age '10.5' must be between 1 and 10".to_string()
+                ]
+            );
+        }
+
+        #[test]
+        fn validates_allowed_values() {
+            let mut errors = Vec::new();
+            let status = String::from("draft");
+            assert!(TestElem::validate_one_of(
+                "status",
+                &status,
+                &["draft", "published"],
+                &mut errors
+            ));
+            assert!(TestElem::validate_not_one_of(
+                "status",
+                &status,
+                &["deleted"],
+                &mut errors
+            ));
+            assert!(errors.is_empty());
+            assert!(!TestElem::validate_one_of(
+                "status",
+                &status,
+                &["published", "archived"],
+                &mut errors
+            ));
+            assert!(!TestElem::validate_not_one_of(
+                "status",
+                &status,
+                &["draft"],
+                &mut errors
+            ));
+            assert_eq!(
+                errors,
+                vec![
+                    "status 'draft' must be one of: published, archived".to_string(),
+                    "status 'draft' is not allowed".to_string()
+                ]
+            );
+        }
+    }
 }
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use aragog::{AuthorizeAction, DatabaseRecord, Record};
+use aragog::{
+    AuthorizeAction, DatabaseAccess, DatabaseRecord, Error, OperationOptions, Record, RecordAction,
+    UpsertOutcome,
+};
 
 pub mod common;
 
@@ -53,6 +56,55 @@ impl AuthorizeAction<Dish> for User {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Record)]
+#[collection_name = "Dish"]
+#[authorize(actor = "User")]
+pub struct GuardedDish {
+    pub name: String,
+    pub price: u16,
+    pub is_alcohol: bool,
+}
+
+impl AuthorizeAction<GuardedDish> for User {
+    type Action = RecordAction;
+
+    fn is_action_authorized(
+        &self,
+        action: Self::Action,
+        _target: Option<&DatabaseRecord<GuardedDish>>,
+    ) -> bool {
+        match action {
+            RecordAction::Create => true,
+            RecordAction::Save | RecordAction::Delete => self.is_cook,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Record)]
+#[collection_name = "Dish"]
+#[authorize(actor = "User")]
+pub struct OwnedDish {
+    pub name: String,
+    pub owner: String,
+}
+
+impl AuthorizeAction<OwnedDish> for User {
+    type Action = RecordAction;
+
+    fn is_action_authorized(
+        &self,
+        action: Self::Action,
+        target: Option<&DatabaseRecord<OwnedDish>>,
+    ) -> bool {
+        match action {
+            RecordAction::Create => true,
+            RecordAction::Save | RecordAction::Delete => {
+                target.map_or(false, |dish| dish.owner == self.name)
+            }
+        }
+    }
+}
+
 #[maybe_async::test(
     any(feature = "blocking"),
     async(all(not(feature = "blocking")), tokio::test)
@@ -153,3 +205,109 @@ async fn can_fail() -> Result<(), String> {
     )?;
     Ok(())
 }
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn writes_are_authorized_for_the_operation_actor() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let user = |name: &str, is_cook| User {
+        name: name.to_string(),
+        age: 30,
+        money: 20,
+        is_cook,
+    };
+    let cook = DatabaseRecord::create(user("Cook", true), &connection)
+        .await
+        .unwrap();
+    let client = DatabaseRecord::create(user("Client", false), &connection)
+        .await
+        .unwrap();
+    let as_cook = connection.operation_options().actor(&cook);
+    let as_client = connection.operation_options().actor(&client);
+
+    let dish = GuardedDish {
+        name: "Gratin".to_string(),
+        price: 12,
+        is_alcohol: false,
+    };
+    let mut dish_record = DatabaseRecord::create_with_options(dish, &connection, as_client.clone())
+        .await
+        .unwrap();
+    dish_record.price = 1;
+    let res = dish_record
+        .save_with_options(&connection, as_client.clone())
+        .await;
+    common::expect_assert(matches!(res, Err(Error::Forbidden(None))))?;
+    // The check doesn't depend on hooks
+    let res = dish_record
+        .delete_with_options(&connection, as_client.ignore_hooks(true))
+        .await;
+    common::expect_assert(matches!(res, Err(Error::Forbidden(None))))?;
+    // An actor of an other collection is refused
+    let res = dish_record
+        .save_with_options(&connection, OperationOptions::default().actor(&dish_record))
+        .await;
+    common::expect_assert(matches!(res, Err(Error::Forbidden(None))))?;
+
+    dish_record
+        .save_with_options(&connection, as_cook.clone())
+        .await
+        .unwrap();
+    common::expect_assert_eq(dish_record.price, 1)?;
+    // Without actor nothing is checked
+    dish_record.save(&connection).await.unwrap();
+    dish_record
+        .delete_with_options(&connection, as_cook)
+        .await
+        .unwrap();
+    Ok(())
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn upserts_authorize_the_replaced_document() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let user = |name: &str| User {
+        name: name.to_string(),
+        age: 30,
+        money: 20,
+        is_cook: true,
+    };
+    let alice = DatabaseRecord::create(user("Alice"), &connection)
+        .await
+        .unwrap();
+    let bob = DatabaseRecord::create(user("Bob"), &connection)
+        .await
+        .unwrap();
+    let dish = |owner: &str| OwnedDish {
+        name: "Gratin".to_string(),
+        owner: owner.to_string(),
+    };
+    let as_alice = connection.operation_options().actor(&alice);
+    let as_bob = connection.operation_options().actor(&bob);
+
+    let (_, outcome) = DatabaseRecord::upsert_by_with_options(
+        "name",
+        dish("Alice"),
+        &connection,
+        as_alice.clone(),
+    )
+    .await
+    .unwrap();
+    common::expect_assert_eq(outcome, UpsertOutcome::Inserted)?;
+    // Bob can't overwrite the dish of Alice
+    let res =
+        DatabaseRecord::upsert_by_with_options("name", dish("Bob"), &connection, as_bob).await;
+    common::expect_assert(matches!(res, Err(Error::Forbidden(None))))?;
+    let (record, outcome) =
+        DatabaseRecord::upsert_by_with_options("name", dish("Alice"), &connection, as_alice)
+            .await
+            .unwrap();
+    common::expect_assert_eq(outcome, UpsertOutcome::Updated)?;
+    common::expect_assert_eq(record.owner.as_str(), "Alice")?;
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 use std::fmt::Debug;
 
-use aragog::DatabaseConnection;
+use aragog::{DatabaseConnection, DatabaseConnectionBuilder};
 
 pub const DEFAULT_DB_HOST: &str = "http://localhost:8529";
 pub const DEFAULT_DB_NAME: &str = "aragog_test";
@@ -11,7 +11,12 @@ pub const DEFAULT_DB_PASSWORD: &str = "test";
 
 #[maybe_async::maybe_async]
 pub async fn setup_db() -> DatabaseConnection {
-    let connection = DatabaseConnection::builder()
+    setup_db_with(DatabaseConnection::builder()).await
+}
+
+#[maybe_async::maybe_async]
+pub async fn setup_db_with(builder: DatabaseConnectionBuilder) -> DatabaseConnection {
+    let connection = builder
         .with_credentials(
             &std::env::var("DB_HOST").unwrap_or_else(|_| DEFAULT_DB_HOST.to_string()),
             &std::env::var("DB_NAME").unwrap_or_else(|_| DEFAULT_DB_NAME.to_string()),
@@ -23,7 +28,7 @@ pub async fn setup_db() -> DatabaseConnection {
         .build()
         .await
         .unwrap();
-    connection.truncate().await;
+    connection.unsafe_truncate_all().await.unwrap();
     connection
 }
 
@@ -1,10 +1,19 @@
+#[macro_use]
 extern crate aragog;
 
 use serde::{Deserialize, Serialize};
 
+use std::sync::Arc;
+use std::time::Duration;
+
+use aragog::schema::{DatabaseSchema, IndexSchema};
+use aragog::transaction::Transaction;
 use aragog::{
-    AuthMode, DatabaseAccess, DatabaseConnection, DatabaseRecord, Error, OperationOptions, Record,
+    AccessLevel, AuthMode, CollectionKind, DatabaseAccess, DatabaseConnection,
+    DatabaseConnectionBuilder, DatabaseRecord, DeserializationMode, Error, ImportOptions,
+    OnDuplicate, OperationOptions, Record,
 };
+use arangors_lite::index::IndexSettings;
 use common::*;
 
 pub mod common;
@@ -149,3 +158,569 @@ async fn operation_options() {
         },
     }
 }
+
+fn connection_builder() -> DatabaseConnectionBuilder {
+    DatabaseConnection::builder()
+        .with_credentials(
+            &std::env::var("DB_HOST").unwrap_or_else(|_| DEFAULT_DB_HOST.to_string()),
+            &std::env::var("DB_NAME").unwrap_or_else(|_| DEFAULT_DB_NAME.to_string()),
+            &std::env::var("DB_USER").unwrap_or_else(|_| DEFAULT_DB_USER.to_string()),
+            &std::env::var("DB_PASSWORD").unwrap_or_else(|_| DEFAULT_DB_PASSWORD.to_string()),
+        )
+        .with_schema_path("./tests/schema.yaml")
+        .apply_schema()
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn collection_operation_options() {
+    let connection = connection_builder()
+        .with_collection_operation_options("Dish", OperationOptions::default().ignore_hooks(true))
+        .build()
+        .await
+        .unwrap();
+    assert!(!connection.operation_options().ignore_hooks);
+    assert!(connection.collection_operation_options("Dish").ignore_hooks);
+    assert!(!connection.collection_operation_options("User").ignore_hooks);
+    // the hook is not launched
+    DatabaseRecord::create(
+        Dish {
+            name: "Cordon Bleu".to_string(),
+            price: 7,
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    // Unknown collection
+    let res = connection_builder()
+        .with_collection_operation_options("Unknown", OperationOptions::default())
+        .build()
+        .await;
+    assert!(matches!(res, Err(Error::InitError { .. })));
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn strict_schema_application() {
+    let connection = connection_builder()
+        .apply_schema_strict()
+        .build()
+        .await
+        .unwrap();
+    let mut schema = DatabaseSchema::load("./tests/schema.yaml").unwrap();
+    // The schema is already applied
+    let report = schema.apply_strict(connection.database()).await.unwrap();
+    assert!(report.is_empty());
+    // Index type change
+    schema.indexes[0].settings = IndexSettings::Fulltext { min_length: 3 };
+    match schema.apply_strict(connection.database()).await {
+        Err(Error::InitError { item, .. }) => assert_eq!(item, "Index Dish/by_name"),
+        _ => panic!("The index change should be incompatible"),
+    }
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn collections_and_graphs_introspection() {
+    let connection = setup_db().await;
+    DatabaseRecord::create(
+        Dish {
+            name: "Quiche".to_string(),
+            price: 10,
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    let collections = connection.collections().await.unwrap();
+    let dish = collections
+        .iter()
+        .find(|collection| collection.name == "Dish")
+        .unwrap();
+    assert_eq!(dish.kind, CollectionKind::Document);
+    assert_eq!(dish.document_count, 1);
+    let part_of = collections
+        .iter()
+        .find(|collection| collection.name == "PartOf")
+        .unwrap();
+    assert_eq!(part_of.kind, CollectionKind::Edge);
+    assert!(collections
+        .iter()
+        .all(|collection| !collection.name.starts_with('_')));
+    let graphs = connection.graphs().await.unwrap();
+    let graph = graphs
+        .iter()
+        .find(|graph| graph.name == "DishGraph")
+        .unwrap();
+    assert_eq!(graph.edge_collections, vec!["PartOf".to_string()]);
+    assert_eq!(
+        graph.vertex_collections,
+        vec!["Dish".to_string(), "Order".to_string()]
+    );
+    assert_eq!(graph.vertex_count, 1);
+    assert_eq!(graph.edge_count, 0);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn graph_collections_are_checked() {
+    let connection = connection_builder().build().await.unwrap();
+    let mut schema = DatabaseSchema::load("./tests/schema.yaml").unwrap();
+    // The collections are declared
+    schema
+        .check_graph_collections(connection.database())
+        .await
+        .unwrap();
+    // Existing but undeclared collections are accepted
+    schema
+        .collections
+        .retain(|collection| collection.name != "Order");
+    schema
+        .check_graph_collections(connection.database())
+        .await
+        .unwrap();
+    schema.graphs[0].edge_definitions[0]
+        .to
+        .push("Restaurant".to_string());
+    schema.graphs[0]
+        .orphan_collections
+        .push("Kitchen".to_string());
+    let res = connection_builder()
+        .with_schema(schema)
+        .apply_schema()
+        .build()
+        .await;
+    match res {
+        Err(Error::InitError { item, message }) => {
+            assert_eq!(item, "Graph DishGraph");
+            assert!(message.ends_with("Restaurant, Kitchen"));
+        }
+        _ => panic!("The graph collections should be missing"),
+    }
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn slow_queries_logging() {
+    let connection = connection_builder()
+        .log_slow_queries(Duration::from_nanos(1))
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        connection.slow_query_threshold(),
+        Some(Duration::from_nanos(1))
+    );
+    let transaction = Transaction::new(&connection).await.unwrap();
+    assert_eq!(
+        transaction.database_connection().slow_query_threshold(),
+        Some(Duration::from_nanos(1))
+    );
+    transaction.abort().await.unwrap();
+    // The query is logged
+    let query = Dish::query().filter(compare!(field "name").equals_str("Pizza").into());
+    let dishes = Dish::get(&query, &connection).await.unwrap();
+    assert!(dishes.is_empty());
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn strict_deserialization() {
+    let lenient = setup_db().await;
+    let strict = connection_builder()
+        .with_deserialization_mode(DeserializationMode::Strict)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(strict.deserialization_mode(), DeserializationMode::Strict);
+    let _: Vec<serde_json::Value> = lenient
+        .database()
+        .aql_str(
+            r#"INSERT { _key: "pizza", name: "Pizza", price: 10, vegan: false } INTO Dish RETURN NEW"#,
+        )
+        .await
+        .unwrap();
+    let query = Dish::query();
+    // The unknown `vegan` field is ignored in lenient mode
+    let dish = Dish::find("pizza", &lenient).await.unwrap();
+    assert_eq!(&dish.name, "Pizza");
+    let dishes = Dish::get(&query, &lenient).await.unwrap();
+    assert_eq!(dishes.len(), 1);
+    // The unknown `vegan` field is rejected in strict mode
+    match Dish::find("pizza", &strict).await {
+        Err(Error::UnprocessableEntity { source }) => {
+            assert!(source.to_string().contains("vegan"));
+        }
+        _ => panic!("The unknown field should be rejected"),
+    }
+    let res = Dish::get(&query, &strict).await;
+    assert!(matches!(res, Err(Error::UnprocessableEntity { .. })));
+    let res = Dish::get_in_batches(&query, &strict, 10).await;
+    assert!(matches!(res, Err(Error::UnprocessableEntity { .. })));
+    let res = strict
+        .get_collection("Dish")
+        .unwrap()
+        .export_stream::<Dish, _>(&strict, 10)
+        .await;
+    assert!(matches!(res, Err(Error::UnprocessableEntity { .. })));
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn velocypack_transport() {
+    let json = setup_db().await;
+    let connection = connection_builder()
+        .with_velocypack_transport()
+        .build()
+        .await
+        .unwrap();
+    assert!(connection.velocypack_transport().is_some());
+    let _: Vec<serde_json::Value> = json
+        .database()
+        .aql_str(
+            r#"FOR i IN 1..25 INSERT { _key: TO_STRING(i), name: "Pizza", price: i } INTO Dish"#,
+        )
+        .await
+        .unwrap();
+    let dish = Dish::find("12", &connection).await.unwrap();
+    assert_eq!(dish.price, 12);
+    assert_eq!(dish.key(), "12");
+    let res = Dish::find("unknown", &connection).await;
+    assert!(matches!(res, Err(Error::NotFound { .. })));
+    let dishes = Dish::get(&Dish::query(), &connection).await.unwrap();
+    assert_eq!(dishes.len(), 25);
+    let mut cursor = Dish::get_in_batches(&Dish::query(), &connection, 10)
+        .await
+        .unwrap();
+    let mut count = cursor.result().len();
+    while let Some(batch) = cursor.next_batch().await {
+        count += batch.len();
+    }
+    assert_eq!(count, 25);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn required_schema_version() {
+    // The test database schema is not versioned by the CLI
+    let res = connection_builder()
+        .require_schema_version(1)
+        .with_schema_collection_name("Unversioned")
+        .build()
+        .await;
+    match res {
+        Err(Error::InitError { item, .. }) => assert_eq!(item, "Schema version"),
+        _ => panic!("Schema version should not match"),
+    }
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn collection_figures_and_checksum() {
+    let connection = setup_db().await;
+    let collection = connection.get_collection("Dish").unwrap();
+    let checksum = collection.checksum().await.unwrap();
+    let figures = collection.figures().await.unwrap();
+    assert_eq!(figures.document_count, 0);
+    assert!(figures.indexes_count >= 1);
+
+    connection
+        .database()
+        .aql_str::<serde_json::Value>(r#"INSERT { name: "Pizza", price: 10 } INTO Dish"#)
+        .await
+        .unwrap();
+    let figures = collection.figures().await.unwrap();
+    assert_eq!(figures.document_count, 1);
+    let new_checksum = collection.checksum().await.unwrap();
+    assert_ne!(new_checksum, checksum);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn collection_import() {
+    let connection = setup_db().await;
+    let collection = connection.get_collection("Dish").unwrap();
+    let jsonl = r#"{"_key":"1","name":"Pizza","price":10}
+
+{"_key":"2","name":"Pasta","price":8}
+{"_key":"3","name":"Salad","price":6}"#;
+    let options = ImportOptions::default().chunk_size(2);
+    let mut progress = Vec::new();
+    let report = collection
+        .import_jsonl_with_progress(jsonl.as_bytes(), &options, |report| {
+            progress.push(report.processed());
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.created, 3);
+    assert_eq!(progress, vec![2, 3]);
+
+    let csv = "_key,name,price\n1,Pizza,12\n4,\"Fish, chips\",9";
+    let report = collection
+        .import_csv(csv.as_bytes(), &options)
+        .await
+        .unwrap();
+    assert_eq!(report.created, 1);
+    assert_eq!(report.errors, 1);
+    assert_eq!(report.details.len(), 1);
+    let options = options.on_duplicate(OnDuplicate::Update);
+    let report = collection
+        .import_csv(csv.as_bytes(), &options)
+        .await
+        .unwrap();
+    assert_eq!(report.updated, 2);
+    let count = collection.record_count().await.unwrap();
+    assert_eq!(count, 4);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn collection_export() {
+    let connection = setup_db().await;
+    let collection = connection.get_collection("Dish").unwrap();
+    let jsonl: Vec<_> = (0..5)
+        .map(|i| format!(r#"{{"name":"Dish {}","price":{}}}"#, i, i))
+        .collect();
+    collection
+        .import_jsonl(jsonl.join("\n").as_bytes(), &ImportOptions::default())
+        .await
+        .unwrap();
+
+    let cursor = collection
+        .export_stream::<Dish, _>(&connection, 2)
+        .await
+        .unwrap();
+    assert!(cursor.has_more());
+    let tracker = connection.operation_tracker().unwrap();
+    assert_eq!(tracker.open_cursors(), 1);
+    #[cfg(not(feature = "blocking"))]
+    let dishes: Vec<_> = futures_util::StreamExt::collect(cursor.into_records()).await;
+    #[cfg(feature = "blocking")]
+    let dishes: Vec<_> = cursor.into_records().collect();
+    let mut prices: Vec<_> = dishes.into_iter().map(|dish| dish.unwrap().price).collect();
+    prices.sort_unstable();
+    assert_eq!(prices, vec![0, 1, 2, 3, 4]);
+    assert_eq!(tracker.open_cursors(), 0);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn runtime_index_management() {
+    let connection = setup_db().await;
+    let collection = connection.get_collection("User").unwrap();
+    let index = IndexSchema {
+        name: "by_runtime_field".to_string(),
+        collection: "User".to_string(),
+        fields: vec!["runtime_field".to_string()],
+        settings: IndexSettings::Persistent {
+            unique: false,
+            sparse: true,
+            deduplicate: false,
+        },
+    };
+    collection.ensure_index(index.clone()).await.unwrap();
+    // Idempotent
+    collection.ensure_index(index).await.unwrap();
+    let indexes = collection.list_indexes().await.unwrap();
+    assert!(indexes.iter().any(|i| i.name == "by_runtime_field"));
+
+    collection.drop_index("by_runtime_field").await.unwrap();
+    let indexes = collection.list_indexes().await.unwrap();
+    assert!(!indexes.iter().any(|i| i.name == "by_runtime_field"));
+    match collection.drop_index("by_runtime_field").await {
+        Err(Error::NotFound { item, .. }) => assert_eq!(item, "Index"),
+        _ => panic!("Index should not be found"),
+    }
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn selective_truncate() {
+    let connection = setup_db().await;
+    let database = connection.database();
+    database
+        .aql_str::<serde_json::Value>(r#"INSERT { name: "Pizza", price: 10 } INTO Dish"#)
+        .await
+        .unwrap();
+    database
+        .aql_str::<serde_json::Value>(r#"INSERT { dish_count: 1 } INTO Menu"#)
+        .await
+        .unwrap();
+    connection.truncate_collections(&["Dish"]).await.unwrap();
+    let dish_count = connection
+        .get_collection("Dish")
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    let menu_count = connection
+        .get_collection("Menu")
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    assert_eq!(dish_count, 0);
+    assert_eq!(menu_count, 1);
+
+    match connection.truncate_collections(&["Menu", "Unknown"]).await {
+        Err(Error::NotFound { id, .. }) => assert_eq!(id, "Unknown"),
+        _ => panic!("Collection should not be found"),
+    }
+    // No collection is truncated on unknown collection
+    let menu_count = connection
+        .get_collection("Menu")
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    assert_eq!(menu_count, 1);
+
+    connection.unsafe_truncate_all().await.unwrap();
+    let menu_count = connection
+        .get_collection("Menu")
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    assert_eq!(menu_count, 0);
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn without_hooks() {
+    let connection = setup_db().await;
+    let record = connection
+        .without_hooks(|connection| async move {
+            assert!(connection.operation_options().ignore_hooks);
+            // the hook is not launched
+            DatabaseRecord::create(
+                Dish {
+                    name: "Cordon Bleu".to_string(),
+                    price: 7,
+                },
+                &connection,
+            )
+            .await
+        })
+        .await
+        .unwrap();
+    assert_eq!(record.name, "Cordon Bleu");
+    // The original connection still launches hooks
+    assert!(!connection.operation_options().ignore_hooks);
+    match DatabaseRecord::create(record.record.clone(), &connection).await {
+        Err(Error::InternalError { message }) => {
+            assert_eq!(message.unwrap(), "Hook forbids creation".to_string())
+        }
+        _ => panic!("Hook should have launched failure"),
+    }
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn shared_connection() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DatabaseConnection>();
+    let connection = setup_db().await.into_shared();
+    let shared = connection.clone();
+    assert!(Arc::ptr_eq(&connection, &shared));
+    let record = DatabaseRecord::force_create(
+        Dish {
+            name: "Cordon Bleu".to_string(),
+            price: 7,
+        },
+        &shared,
+    )
+    .await
+    .unwrap();
+    let found: DatabaseRecord<Dish> = DatabaseRecord::find(record.key(), &connection)
+        .await
+        .unwrap();
+    assert_eq!(found.name, "Cordon Bleu");
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn accessor_holders() {
+    let connection = setup_db().await;
+    let boxed: Box<dyn DatabaseAccess> = Box::new(connection.clone());
+    let record = DatabaseRecord::force_create(
+        Dish {
+            name: "Cordon Bleu".to_string(),
+            price: 7,
+        },
+        &boxed,
+    )
+    .await
+    .unwrap();
+    let reference = &connection;
+    let found: DatabaseRecord<Dish> = DatabaseRecord::find(record.key(), &reference)
+        .await
+        .unwrap();
+    assert_eq!(found.name, "Cordon Bleu");
+}
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn user_management() {
+    let connection = setup_db().await;
+    // The name is percent-encoded in the user API paths
+    let name = "aragog test tenant#1";
+    // Cleans up a previous failed run
+    let _ = connection.remove_user(name).await;
+    let user = connection
+        .create_user(name, "password", true)
+        .await
+        .unwrap();
+    assert_eq!(user.user, name);
+    assert!(user.active);
+    let res = connection.create_user(name, "password", true).await;
+    assert!(res.unwrap_err().is_conflict());
+    connection
+        .grant_database_access(name, AccessLevel::ReadOnly)
+        .await
+        .unwrap();
+    connection
+        .grant_collection_access(name, "Dish", AccessLevel::ReadWrite)
+        .await
+        .unwrap();
+    let users = connection.list_users().await.unwrap();
+    assert!(users.iter().any(|u| u.user == name));
+    connection.remove_user(name).await.unwrap();
+    let users = connection.list_users().await.unwrap();
+    assert!(!users.iter().any(|u| u.user == name));
+}
@@ -3,6 +3,7 @@ extern crate aragog;
 use aragog::error::{ArangoError, ArangoHttpError};
 use aragog::{DatabaseRecord, Error, OperationOptions, Record};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub mod common;
 
@@ -70,13 +71,22 @@ mod custom_key {
             .await
             .unwrap();
         let res = DatabaseRecord::create_with_key(doc, "CustomKey".to_string(), &connection).await;
-        if let Err(Error::Conflict(db_error)) = res {
+        if let Err(Error::UniqueViolation {
+            collection,
+            index,
+            conflicting_key,
+            source,
+        }) = res
+        {
+            assert_eq!(collection.as_deref(), Some("Dish"));
+            assert_eq!(index.as_deref(), Some("primary"));
+            assert_eq!(conflicting_key.as_deref(), Some("CustomKey"));
             assert_eq!(
-                db_error.arango_error,
+                source.arango_error,
                 ArangoError::ArangoUniqueConstraintViolated
             );
-            assert_eq!(db_error.http_error, ArangoHttpError::Conflict);
-            assert_eq!(db_error.message, "unique constraint violated - in index primary of type primary over '_key'; conflicting key: CustomKey")
+            assert_eq!(source.http_error, ArangoHttpError::Conflict);
+            assert_eq!(source.message, "unique constraint violated - in index primary of type primary over '_key'; conflicting key: CustomKey")
         } else {
             panic!("Duplicate unique key should raise a conflict error")
         }
@@ -177,3 +187,113 @@ async fn revision_check_works() -> Result<(), String> {
 
     Ok(())
 }
+
+#[maybe_async::test(
+    feature = "blocking",
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn timeout_works() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let doc = Dish {
+        name: "Pizza".to_string(),
+        description: "Italian Dish".to_string(),
+        price: 13,
+    };
+    let mut record = DatabaseRecord::create(doc, &connection).await.unwrap();
+    record.price = 14;
+    // A generous deadline doesn't change the behavior
+    record
+        .save_with_options(
+            &connection,
+            OperationOptions::default().timeout(Duration::from_secs(10)),
+        )
+        .await
+        .unwrap();
+    // The deadline is only enforced on the client side in async mode
+    #[cfg(not(feature = "blocking"))]
+    {
+        let error = record
+            .save_with_options(
+                &connection,
+                OperationOptions::default().timeout(Duration::from_nanos(1)),
+            )
+            .await
+            .unwrap_err();
+        common::expect_assert(error.is_timeout())?;
+        common::expect_assert_eq(error.http_code(), 504)?;
+    }
+    Ok(())
+}
+
+mod conflict_policy {
+    use super::*;
+    use aragog::ConflictPolicy;
+
+    #[maybe_async::maybe_async]
+    async fn stale_record(
+        connection: &aragog::DatabaseConnection,
+    ) -> (DatabaseRecord<Dish>, DatabaseRecord<Dish>) {
+        let doc = Dish {
+            name: "Pizza".to_string(),
+            description: "Italian Dish".to_string(),
+            price: 13,
+        };
+        let record = DatabaseRecord::create(doc, connection).await.unwrap();
+        let stale = record.clone();
+        let mut current = record;
+        current.price = 15;
+        current.save(connection).await.unwrap();
+        (stale, current)
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn merge_resolves_conflicts() {
+        let connection = common::setup_db().await;
+        let (mut stale, _) = stale_record(&connection).await;
+        stale.name = String::from("Pizza Margherita");
+        let error = stale
+            .save_with_options(&connection, OperationOptions::default().ignore_revs(false))
+            .await
+            .unwrap_err();
+        assert!(error.is_revision_conflict());
+        let policy = ConflictPolicy::merge(|local: &Dish, stored: &Dish| Dish {
+            name: local.name.clone(),
+            ..stored.clone()
+        });
+        stale
+            .save_with_conflict_policy(&connection, &policy)
+            .await
+            .unwrap();
+        let stored = Dish::find(stale.key(), &connection).await.unwrap();
+        assert_eq!(stored.name, "Pizza Margherita");
+        assert_eq!(stored.price, 15);
+        assert_eq!(stored.rev(), stale.rev());
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn retry_with_reload_overwrites() {
+        let connection = common::setup_db().await;
+        let (mut stale, _) = stale_record(&connection).await;
+        stale
+            .save_with_conflict_policy(&connection, &ConflictPolicy::retry_with_reload())
+            .await
+            .unwrap();
+        let stored = Dish::find(stale.key(), &connection).await.unwrap();
+        assert_eq!(stored.price, 13);
+        let (mut stale, _) = stale_record(&connection).await;
+        let error = stale
+            .save_with_conflict_policy(
+                &connection,
+                &ConflictPolicy::retry_with_reload().max_retries(0),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.is_revision_conflict());
+    }
+}
@@ -1,15 +1,22 @@
 use serde::{Deserialize, Serialize};
 
-use aragog::{DatabaseConnection, DatabaseRecord, EdgeRecord, Error, Record, Validate};
+use aragog::query::GraphQueryDirection;
+use aragog::schema::DatabaseSchema;
+use aragog::{
+    CounterCache, DatabaseAccess, DatabaseConnection, DatabaseRecord, EdgeRecord, Error, GraphOps,
+    OperationOptions, Record, Validate,
+};
 
 mod common;
 
 #[derive(Clone, Serialize, Deserialize, Record)]
+#[counter_cache(edge = "Edge", field = "order_count", vertex = "from")]
 pub struct Dish {
     pub name: String,
 }
 
 #[derive(Clone, Serialize, Deserialize, Record)]
+#[counter_cache(edge = "Edge", field = "dish_count")]
 pub struct Order {
     pub name: String,
 }
@@ -40,11 +47,165 @@ async fn create_order(connection: &DatabaseConnection) -> DatabaseRecord<Order>
 
 #[derive(Clone, Record, Serialize, Deserialize, Validate)]
 #[before_write(func = "validate")]
+#[edge_collection]
 pub struct PartOf {
     #[validate(min_length = 5)]
     description: String,
 }
 
+#[derive(Clone, Record, Serialize, Deserialize)]
+#[collection_name = "Edge"]
+pub struct Contains {}
+
+#[derive(Clone, Serialize, Deserialize, Record)]
+#[collection_name = "Order"]
+#[counter_cache(edge = "PartOf", field = "part_count")]
+pub struct CountedOrder {
+    pub name: String,
+}
+
+#[test]
+fn counter_caches() {
+    assert!(Contains::COUNTER_CACHES.is_empty());
+    assert_eq!(
+        Order::COUNTER_CACHES,
+        &[CounterCache::new("Edge", "dish_count")]
+    );
+    assert_eq!(
+        Dish::COUNTER_CACHES,
+        &[CounterCache::new("Edge", "order_count").on_from_vertex()]
+    );
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn counter_caches_are_maintained() -> Result<(), String> {
+    let connection = common::setup_db_with(
+        DatabaseConnection::builder()
+            .with_counter_caches::<Dish>()
+            .with_counter_caches::<Order>(),
+    )
+    .await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    let mut first = DatabaseRecord::link(&dish, &order, &connection, Contains {})
+        .await
+        .unwrap();
+    DatabaseRecord::link(&dish, &order, &connection, Contains {})
+        .await
+        .unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["dish_count"].as_u64(), Some(2))?;
+    let (_, dish_value) = DatabaseRecord::<Dish>::find_raw(dish.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(dish_value["order_count"].as_u64(), Some(2))?;
+    first.delete(&connection).await.unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["dish_count"].as_u64(), Some(1))?;
+    Ok(())
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn counter_caches_are_maintained_without_hooks() -> Result<(), String> {
+    let connection = common::setup_db_with(
+        DatabaseConnection::builder()
+            .with_counter_caches::<Dish>()
+            .with_counter_caches::<Order>(),
+    )
+    .await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    let edge = EdgeRecord::new(dish.id().clone(), order.id().clone(), Contains {}).unwrap();
+    let mut edge = DatabaseRecord::force_create(edge, &connection)
+        .await
+        .unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["dish_count"].as_u64(), Some(1))?;
+    edge.force_delete(&connection).await.unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["dish_count"].as_u64(), Some(0))?;
+    let edges = DatabaseRecord::link_many(
+        &[(dish.id(), order.id()), (dish.id(), order.id())],
+        |_, _| Contains {},
+        &connection,
+    )
+    .await
+    .unwrap();
+    common::expect_assert_eq(edges.len(), 2)?;
+    let (_, dish_value) = DatabaseRecord::<Dish>::find_raw(dish.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(dish_value["order_count"].as_u64(), Some(2))?;
+    Ok(())
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn detach_delete_updates_counter_caches() -> Result<(), String> {
+    let connection =
+        common::setup_db_with(DatabaseConnection::builder().with_counter_caches::<CountedOrder>())
+            .await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    DatabaseRecord::link(
+        &dish,
+        &order,
+        &connection,
+        PartOf {
+            description: "Correct".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["part_count"].as_u64(), Some(1))?;
+    dish.detach_delete(&connection).await.unwrap();
+    let (_, order_value) = DatabaseRecord::<Order>::find_raw(order.key(), &connection)
+        .await
+        .unwrap();
+    common::expect_assert_eq(order_value["part_count"].as_u64(), Some(0))?;
+    Ok(())
+}
+
+#[test]
+fn edge_collection_type() {
+    assert!(PartOf::IS_EDGE_COLLECTION);
+    assert!(!Dish::IS_EDGE_COLLECTION);
+    assert!(EdgeRecord::<Dish>::IS_EDGE_COLLECTION);
+    let schema = DatabaseSchema::load("./tests/schema.yaml").unwrap();
+    schema.check_record::<PartOf>().unwrap();
+    schema.check_record::<Dish>().unwrap();
+    assert!(matches!(
+        schema.check_record::<EdgeRecord<Dish>>(),
+        Err(Error::InitError { .. })
+    ));
+    let mut schema = DatabaseSchema::default();
+    schema.add_record_collection::<PartOf>();
+    schema.add_record_collection::<Dish>();
+    schema.add_record_collection::<PartOf>();
+    assert_eq!(schema.collections.len(), 2);
+    assert!(schema.collection("PartOf").unwrap().is_edge_collection);
+    assert!(!schema.collection("Dish").unwrap().is_edge_collection);
+}
+
 #[maybe_async::test(
     any(feature = "blocking"),
     async(all(not(feature = "blocking")), tokio::test)
@@ -153,6 +314,62 @@ async fn edge_can_be_created_with_a_simple_link() -> Result<(), String> {
     Ok(())
 }
 
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn edges_can_be_created_in_bulk() {
+    let connection = common::setup_db().await;
+    let dish = create_dish(&connection).await;
+    let orders = [
+        create_order(&connection).await,
+        create_order(&connection).await,
+        create_order(&connection).await,
+    ];
+    let pairs: Vec<_> = orders.iter().map(|order| (dish.id(), order.id())).collect();
+
+    let records = DatabaseRecord::link_many(
+        &pairs,
+        |_, to| PartOf {
+            description: format!("Part of {}", to),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    assert_eq!(records.len(), 3);
+    for (record, order) in records.iter().zip(&orders) {
+        assert_eq!(record.id_from(), dish.id());
+        assert_eq!(record.id_to(), order.id());
+        assert_eq!(record.description, format!("Part of {}", order.id()));
+    }
+    let count = connection
+        .get_collection(PartOf::COLLECTION_NAME)
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+
+    // The hooks validation fails before any insertion
+    let res = DatabaseRecord::link_many(
+        &pairs,
+        |_, _| PartOf {
+            description: "Bad".to_string(),
+        },
+        &connection,
+    )
+    .await;
+    assert!(matches!(res, Err(Error::ValidationError(_))));
+    let count = connection
+        .get_collection(PartOf::COLLECTION_NAME)
+        .unwrap()
+        .record_count()
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+}
+
 #[maybe_async::test(
     any(feature = "blocking"),
     async(all(not(feature = "blocking")), tokio::test)
@@ -200,6 +417,65 @@ async fn link_launches_hooks() -> Result<(), String> {
     }
 }
 
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn link_checks_edge_endpoints() {
+    let connection = common::setup_db().await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    let missing_order = DatabaseRecord::from_parts(
+        "missing".parse().unwrap(),
+        "Order/missing".parse().unwrap(),
+        String::new(),
+        order.record.clone(),
+    );
+    let edge = || PartOf {
+        description: "Correct".to_string(),
+    };
+    // Dangling edges are created by default
+    DatabaseRecord::link(&dish, &missing_order, &connection, edge())
+        .await
+        .unwrap();
+    let connection = DatabaseConnection::builder()
+        .with_credentials(
+            &std::env::var("DB_HOST").unwrap_or_else(|_| common::DEFAULT_DB_HOST.to_string()),
+            &std::env::var("DB_NAME").unwrap_or_else(|_| common::DEFAULT_DB_NAME.to_string()),
+            &std::env::var("DB_USER").unwrap_or_else(|_| common::DEFAULT_DB_USER.to_string()),
+            &std::env::var("DB_PASSWORD")
+                .unwrap_or_else(|_| common::DEFAULT_DB_PASSWORD.to_string()),
+        )
+        .with_schema_path("./tests/schema.yaml")
+        .with_collection_operation_options(
+            PartOf::COLLECTION_NAME,
+            OperationOptions::default().check_edge_endpoints(true),
+        )
+        .build()
+        .await
+        .unwrap();
+    let error = DatabaseRecord::link(&dish, &missing_order, &connection, edge())
+        .await
+        .err()
+        .unwrap();
+    assert!(error.is_not_found());
+    assert_eq!(error.to_string(), "Order missing not found");
+    DatabaseRecord::link(&dish, &order, &connection, edge())
+        .await
+        .unwrap();
+    let edges = PartOf::get(&PartOf::query(), &connection).await.unwrap();
+    assert_eq!(edges.len(), 2);
+    // No edge is created in bulk if an endpoint is missing
+    let pairs = [(dish.id(), order.id()), (dish.id(), missing_order.id())];
+    let error = DatabaseRecord::link_many(&pairs, |_, _| edge(), &connection)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "Order missing not found");
+    let edges = PartOf::get(&PartOf::query(), &connection).await.unwrap();
+    assert_eq!(edges.len(), 2);
+}
+
 #[test]
 fn edge_validated_format() -> Result<(), String> {
     let edge = EdgeRecord::new(
@@ -244,3 +520,221 @@ fn edge_validated_format() -> Result<(), String> {
     assert!(edge.is_err());
     Ok(())
 }
+
+#[test]
+fn edge_from_keys() {
+    let edge = EdgeRecord::new_from_keys::<Dish, Order>(
+        "123",
+        "234",
+        PartOf {
+            description: "part of".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(edge.id_from(), "Dish/123");
+    assert_eq!(edge.id_to(), "Order/234");
+    assert_eq!(edge.key_from(), "123");
+    assert_eq!(edge.to_collection_name(), Order::COLLECTION_NAME);
+    // Keys can't be ids
+    let edge = EdgeRecord::new_from_keys::<Dish, Order>(
+        "Dish/123",
+        "234",
+        PartOf {
+            description: "part of".to_string(),
+        },
+    );
+    assert!(edge.is_err());
+    let edge = EdgeRecord::new_from_keys::<Dish, Order>(
+        "123",
+        "",
+        PartOf {
+            description: "part of".to_string(),
+        },
+    );
+    assert!(edge.is_err());
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn detach_delete_removes_incident_edges() {
+    let connection = common::setup_db().await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+
+    let edge = DatabaseRecord::link(
+        &dish,
+        &order,
+        &connection,
+        PartOf {
+            description: "Correct".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    dish.detach_delete(&connection).await.unwrap();
+
+    let rec = Dish::find(dish.key(), &connection).await;
+    assert!(rec.is_err());
+    let rec = EdgeRecord::<PartOf>::find(edge.key(), &connection).await;
+    assert!(rec.is_err());
+    let rec = Order::find(order.key(), &connection).await;
+    assert!(rec.is_ok());
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn detach_delete_requires_a_graph() {
+    let connection = common::setup_db().await;
+    let order = create_order(&connection).await;
+    // `Order` is a vertex collection of `DishGraph`
+    order.detach_delete(&connection).await.unwrap();
+
+    let edge = DatabaseRecord::create(
+        EdgeRecord::new(
+            "Dish/unknown".to_string(),
+            "Order/unknown".to_string(),
+            PartOf {
+                description: "Correct".to_string(),
+            },
+        )
+        .unwrap(),
+        &connection,
+    )
+    .await
+    .unwrap();
+    // `PartOf` is an edge collection
+    match edge.detach_delete(&connection).await {
+        Err(Error::NotFound { .. }) => (),
+        _ => panic!("No graph should be found"),
+    }
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn neighbor_helpers() {
+    let connection = common::setup_db().await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    DatabaseRecord::link(
+        &dish,
+        &order,
+        &connection,
+        PartOf {
+            description: "Correct".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let orders = dish
+        .out_neighbors::<Order, _>("PartOf", &connection)
+        .await
+        .unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].id(), order.id());
+    let dishes = order
+        .in_neighbors::<Dish, _>("PartOf", &connection)
+        .await
+        .unwrap();
+    assert_eq!(dishes.len(), 1);
+    assert_eq!(dishes[0].id(), dish.id());
+    // Neighbors from other collections are ignored
+    let dishes = dish
+        .out_neighbors::<Dish, _>("PartOf", &connection)
+        .await
+        .unwrap();
+    assert!(dishes.is_empty());
+
+    let degree = dish
+        .degree("PartOf", GraphQueryDirection::Outbound, &connection)
+        .await
+        .unwrap();
+    assert_eq!(degree, 1);
+    let degree = dish
+        .degree("PartOf", GraphQueryDirection::Inbound, &connection)
+        .await
+        .unwrap();
+    assert_eq!(degree, 0);
+    let degree = order
+        .degree("PartOf", GraphQueryDirection::Any, &connection)
+        .await
+        .unwrap();
+    assert_eq!(degree, 1);
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn graph_ops() {
+    let connection = common::setup_db().await;
+    let dish = create_dish(&connection).await;
+    let order = create_order(&connection).await;
+    let other_dish = create_dish(&connection).await;
+    let lonely_dish = create_dish(&connection).await;
+    for from in [&dish, &other_dish] {
+        DatabaseRecord::link(
+            from,
+            &order,
+            &connection,
+            PartOf {
+                description: "Correct".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let graph = GraphOps::new(&connection).edge_collections(&["PartOf"]);
+    // The other dish is reached once through the order
+    let dishes = graph.neighbors_within::<Dish>(dish.id(), 2).await.unwrap();
+    assert_eq!(dishes.len(), 1);
+    assert_eq!(dishes.first().unwrap().key(), other_dish.key());
+    let orders = graph.neighbors_within::<Order>(dish.id(), 2).await.unwrap();
+    assert_eq!(orders.len(), 1);
+    let reachable = graph
+        .clone()
+        .direction(GraphQueryDirection::Outbound)
+        .reachable_from(dish.id())
+        .await
+        .unwrap();
+    assert_eq!(&reachable, &[order.id().clone()]);
+    let mut components = graph.connected_components("Dish").await.unwrap();
+    components.sort_by_key(Vec::len);
+    assert_eq!(components.len(), 2);
+    assert_eq!(&components[0], &[lonely_dish.id().clone()]);
+    assert_eq!(components[1].len(), 3);
+    // The `PartOf` edges have no `distance`, weighing the default weight
+    let weighted = graph.clone().weight_attribute("distance", 2.5);
+    let path = weighted
+        .shortest_path(dish.id(), other_dish.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &path.vertices,
+        &[
+            dish.id().clone(),
+            order.id().clone(),
+            other_dish.id().clone()
+        ]
+    );
+    assert_eq!(path.edges.len(), 2);
+    assert!((path.weight - 5.0).abs() < f64::EPSILON);
+    let paths = graph
+        .k_shortest_paths(dish.id(), order.id(), 5)
+        .await
+        .unwrap();
+    assert_eq!(paths.len(), 2);
+    assert!((paths[0].weight - 1.0).abs() < f64::EPSILON);
+    let path = weighted
+        .shortest_path(dish.id(), lonely_dish.id())
+        .await
+        .unwrap();
+    assert!(path.is_none());
+}
@@ -12,11 +12,11 @@ fn error_default() {
 
 #[test]
 fn error_sources() {
-    let db_error = DatabaseError {
-        http_error: ArangoHttpError::BadParameter,
-        arango_error: ArangoError::ArangoIllegalState,
-        message: "".to_string(),
-    };
+    let db_error = DatabaseError::new(
+        ArangoHttpError::BadParameter,
+        ArangoError::ArangoIllegalState,
+        "".to_string(),
+    );
 
     assert!(Error::ValidationError(String::new()).source().is_none());
     assert!(Error::NotFound {
@@ -47,6 +47,11 @@ fn error_sources() {
     .source()
     .is_some());
     assert!(Error::InternalError { message: None }.source().is_none());
+    assert!(
+        Error::CheckpointFailed(Box::new(Error::InternalError { message: None }))
+            .source()
+            .is_some()
+    );
     assert!(Error::InitError {
         item: "".to_string(),
         message: "".to_string()
@@ -54,3 +59,107 @@ fn error_sources() {
     .source()
     .is_none());
 }
+
+#[test]
+fn error_predicates() {
+    let db_error =
+        |http_error, arango_error| DatabaseError::new(http_error, arango_error, "".to_string());
+    let driver_error: arangors_lite::ArangoError = serde_json::from_value(serde_json::json!({
+        "code": 409,
+        "errorNum": 1210,
+        "errorMessage": "unique constraint violated - in index by_name of type persistent over 'name'; conflicting key: 123",
+    }))
+    .unwrap();
+    let unique_error = Error::from(arangors_lite::ClientError::Arango(driver_error));
+    assert!(unique_error.is_conflict());
+    assert!(unique_error.is_unique_violation());
+    assert!(!unique_error.is_not_found());
+    assert_eq!(unique_error.http_code(), 409);
+    match unique_error.with_document("User", None) {
+        Error::UniqueViolation {
+            collection,
+            index,
+            conflicting_key,
+            source,
+        } => {
+            assert_eq!(collection.as_deref(), Some("User"));
+            assert_eq!(index.as_deref(), Some("by_name"));
+            assert_eq!(conflicting_key.as_deref(), Some("123"));
+            assert_eq!(
+                source.arango_error,
+                ArangoError::ArangoUniqueConstraintViolated
+            );
+        }
+        _ => panic!("Expected a unique violation error"),
+    }
+
+    let revision_error = Error::Conflict(db_error(
+        ArangoHttpError::Conflict,
+        ArangoError::ArangoConflict,
+    ));
+    assert!(revision_error.is_conflict());
+    assert!(!revision_error.is_unique_violation());
+
+    assert!(Error::NotFound {
+        item: "".to_string(),
+        id: "".to_string(),
+        source: None
+    }
+    .is_not_found());
+    assert!(Error::ArangoError(db_error(
+        ArangoHttpError::NotFound,
+        ArangoError::ArangoDocumentNotFound
+    ))
+    .is_not_found());
+    assert!(!Error::ValidationError(String::new()).is_not_found());
+    assert!(Error::ValidationError(String::new())
+        .database_error()
+        .is_none());
+}
+
+#[test]
+fn error_document_context() {
+    let error = Error::Conflict(DatabaseError::new(
+        ArangoHttpError::Conflict,
+        ArangoError::ArangoConflict,
+        "".to_string(),
+    ))
+    .with_document("User", Some("123"));
+    let db_error = error.database_error().unwrap();
+    assert_eq!(db_error.document(), Some("User/123"));
+    assert_eq!(db_error.collection_name(), Some("User"));
+    assert_eq!(db_error.document_key(), Some("123"));
+    assert!(db_error.to_string().ends_with("document: User/123"));
+    let error = Error::ArangoError(db_error.clone()).with_document("User", None);
+    let db_error = error.database_error().unwrap();
+    assert_eq!(db_error.collection_name(), Some("User"));
+    assert!(db_error.document_key().is_none());
+    // Errors without database source are unchanged
+    let error = Error::ValidationError("invalid".to_string()).with_document("User", None);
+    assert!(matches!(error, Error::ValidationError(msg) if msg == "invalid"));
+}
+
+#[test]
+fn error_context() {
+    let error = Error::Conflict(DatabaseError::new(
+        ArangoHttpError::Conflict,
+        ArangoError::ArangoConflict,
+        "".to_string(),
+    ))
+    .context("Failed to save the user")
+    .context("Failed to register");
+    assert_eq!(error.to_string(), "Failed to register");
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "Failed to save the user"
+    );
+    assert!(matches!(error.root(), Error::Conflict(_)));
+    assert_eq!(error.http_code(), 409);
+    assert!(error.is_conflict());
+    assert!(!error.is_not_found());
+    let error = error.with_document("User", Some("123"));
+    assert_eq!(error.database_error().unwrap().document(), Some("User/123"));
+    #[cfg(not(feature = "backtrace"))]
+    assert!(error.backtrace().is_none());
+    assert!(Error::ValidationError(String::new()).backtrace().is_none());
+}
@@ -0,0 +1,85 @@
+use aragog::{JsonSchema, JsonSchemaExporter, Record};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DishKind {
+    MainCourse,
+    Dessert,
+    #[serde(rename = "drink")]
+    Beverage,
+}
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Audit {
+    pub created_by: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Record, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Dish {
+    pub dish_name: String,
+    pub price: u16,
+    pub kind: DishKind,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(skip)]
+    pub cached_rating: f32,
+    #[serde(rename = "ingredients")]
+    #[json_schema(as = "Vec<String>")]
+    pub ingredient_ids: Vec<u64>,
+    #[serde(flatten)]
+    pub audit: Audit,
+}
+
+#[test]
+fn derived_schema_follows_serde_attributes() {
+    assert_eq!(
+        DishKind::json_schema(),
+        json!({ "type": "string", "enum": ["MAIN_COURSE", "DESSERT", "drink"] })
+    );
+    let schema = Dish::json_schema();
+    assert_eq!(
+        schema["properties"],
+        json!({
+            "dishName": { "type": "string" },
+            "price": { "type": "integer" },
+            "kind": { "type": "string", "enum": ["MAIN_COURSE", "DESSERT", "drink"] },
+            "description": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "ingredients": { "type": "array", "items": { "type": "string" } },
+            "created_by": { "type": "string" },
+        })
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["dishName", "price", "kind", "ingredients", "created_by"])
+    );
+}
+
+#[test]
+fn exporter_renders_record_models() {
+    let exporter = JsonSchemaExporter::new().model::<Dish>();
+    let schema = exporter.json_schema();
+    assert_eq!(
+        schema["definitions"]["Dish"]["properties"]["_key"],
+        json!({ "type": "string" })
+    );
+    assert_eq!(
+        exporter.typescript(),
+        "export interface Dish {\n  \
+           _id: string;\n  \
+           _key: string;\n  \
+           _rev: string;\n  \
+           created_by: string;\n  \
+           description?: string | null;\n  \
+           dishName: string;\n  \
+           ingredients: string[];\n  \
+           kind: \"MAIN_COURSE\" | \"DESSERT\" | \"drink\";\n  \
+           price: number;\n  \
+           tags?: string[];\n\
+         }\n"
+    );
+}
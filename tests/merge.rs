@@ -0,0 +1,98 @@
+use aragog::{ConflictPolicy, Merge, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Merge)]
+pub struct Stats {
+    #[merge(strategy = "max")]
+    pub views: u32,
+    #[merge(strategy = "min")]
+    pub first_seen: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Record, Merge)]
+#[conflict_policy(merge_fields, max_retries = 2)]
+pub struct Page {
+    pub title: String,
+    #[merge(strategy = "stored")]
+    pub published: bool,
+    #[merge(strategy = "union")]
+    pub editors: HashSet<String>,
+    #[merge(strategy = "merge")]
+    pub stats: Stats,
+    #[merge(with = "concat")]
+    pub body: String,
+}
+
+fn concat(local: &String, stored: &String) -> String {
+    format!("{}\n{}", stored, local)
+}
+
+#[derive(Clone, Debug, PartialEq, Merge)]
+pub struct Tagged<T> {
+    #[merge(strategy = "max")]
+    pub version: u32,
+    pub value: T,
+}
+
+#[test]
+fn fields_are_merged() {
+    let local = Page {
+        title: String::from("Local"),
+        published: false,
+        editors: HashSet::from([String::from("alice")]),
+        stats: Stats {
+            views: 3,
+            first_seen: 20,
+        },
+        body: String::from("local"),
+    };
+    let stored = Page {
+        title: String::from("Stored"),
+        published: true,
+        editors: HashSet::from([String::from("bob")]),
+        stats: Stats {
+            views: 5,
+            first_seen: 10,
+        },
+        body: String::from("stored"),
+    };
+    let merged = Page::merge(&local, &stored);
+    assert_eq!(merged.title, "Local");
+    assert!(merged.published);
+    assert_eq!(
+        merged.editors,
+        HashSet::from([String::from("alice"), String::from("bob")])
+    );
+    assert_eq!(
+        merged.stats,
+        Stats {
+            views: 5,
+            first_seen: 10
+        }
+    );
+    assert_eq!(merged.body, "stored\nlocal");
+    assert!(matches!(
+        Page::conflict_policy(),
+        ConflictPolicy::Merge { max_retries: 2, .. }
+    ));
+}
+
+#[test]
+fn generic_fields_are_merged() {
+    let local = Tagged {
+        version: 1,
+        value: "local",
+    };
+    let stored = Tagged {
+        version: 2,
+        value: "stored",
+    };
+    assert_eq!(
+        Tagged::merge(&local, &stored),
+        Tagged {
+            version: 2,
+            value: "local"
+        }
+    );
+}
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate aragog;
 
-use aragog::query::{Comparison, Filter, Query, SortDirection};
+use aragog::query::{Comparison, Filter, GraphQueryDirection, Query, SortDirection};
 
 pub mod common;
 
@@ -52,6 +52,18 @@ mod comparison {
         Ok(())
     }
 
+    #[test]
+    fn in_bound_array() -> Result<(), String> {
+        let item = Comparison::field("username").in_bound_array(&["felix", "gerard"]);
+        common::expect_assert_eq(
+            item.aql_str("i").as_str(),
+            r#"i.username IN ["felix","gerard"]"#,
+        )?;
+        let item = Comparison::field("age").not_in_bound_array(&[13, 14, 15]);
+        common::expect_assert_eq(item.aql_str("i").as_str(), r#"i.age NOT IN [13,14,15]"#)?;
+        Ok(())
+    }
+
     #[test]
     fn like() -> Result<(), String> {
         let item = Comparison::field("last_name").like("de %");
@@ -66,6 +78,16 @@ mod comparison {
         Ok(())
     }
 
+    #[test]
+    fn in_collection() -> Result<(), String> {
+        let item = Comparison::field("commentable").in_collection("Blog_Post");
+        common::expect_assert_eq(
+            item.aql_str("i").as_str(),
+            r#"i.commentable LIKE "Blog\\_Post/%""#,
+        )?;
+        Ok(())
+    }
+
     #[test]
     fn matches() -> Result<(), String> {
         let item = Comparison::field("last_name").matches(r#"^/[0.9]$"#);
@@ -232,6 +254,117 @@ mod filter {
     }
 }
 
+mod aql_macro {
+    use super::*;
+
+    #[test]
+    fn builds_query_with_bind_vars() -> Result<(), String> {
+        let min_age = 18;
+        let query = aql!(
+            "FOR u IN @@collection FILTER u.age >= @min && u.name != \"@admin\" RETURN u",
+            @collection = "User",
+            min = min_age,
+        );
+        common::expect_assert_eq(
+            query.query(),
+            "FOR u IN @@collection FILTER u.age >= @min && u.name != \"@admin\" RETURN u",
+        )?;
+        let value = serde_json::to_value(&query).unwrap();
+        common::expect_assert_eq(
+            value["bindVars"].clone(),
+            serde_json::json!({ "@collection": "User", "min": 18 }),
+        )?;
+        Ok(())
+    }
+}
+
+mod scope {
+    use aragog::Record;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    #[scope(active = "active == true", adults = "age >= 18")]
+    #[scope(
+        customers = r#"roles IN ["customer", "admin"] || email LIKE "%@mail.com""#,
+        rated = "rating.stars > 2.5 && rating.label != null"
+    )]
+    struct User {
+        active: bool,
+        age: u16,
+        email: String,
+        roles: Vec<String>,
+        rating: Option<serde_json::Value>,
+        #[db(redact)]
+        password: String,
+        #[serde(skip)]
+        tokens: Vec<String>,
+    }
+
+    #[test]
+    fn scopes_render_filters() -> Result<(), String> {
+        common::expect_assert_eq(
+            User::customers().aql_str("i"),
+            r#"i.roles IN ["customer", "admin"] || i.email LIKE "%@mail.com""#.to_string(),
+        )?;
+        common::expect_assert_eq(
+            User::rated().aql_str("i"),
+            "i.rating.stars > 2.5 && i.rating.label != null".to_string(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn scopes_are_composed() -> Result<(), String> {
+        let query = User::query()
+            .scope(User::active())
+            .scope(User::adults())
+            .sort("age", None);
+        common::expect_assert_eq(
+            query.aql_str(),
+            "FOR a in User FILTER a.active == true FILTER a.age >= 18 SORT a.age ASC return a"
+                .to_string(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn fields_are_validated() -> Result<(), String> {
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "comparisons": [
+                { "field": "age", "comparator": "greater_than", "value": 18 },
+                { "field": "rating.stars", "comparator": "greater_than", "value": 2 },
+                { "field": "roles", "comparator": "contains", "value": "admin" },
+                { "field": "_key", "comparator": "equals", "value": "1" }
+            ],
+            "operators": ["and", "and", "and"]
+        }))
+        .unwrap();
+        let query = User::query().filter(filter).sort("email", None);
+        common::expect_assert(query.validate_fields::<User>().is_ok())?;
+        let query = User::query().sort("password", None);
+        common::expect_assert(query.validate_fields::<User>().is_err())?;
+        let query = User::query().filter(Comparison::any("tokens").like("a%").into());
+        common::expect_assert(query.validate_fields::<User>().is_err())?;
+        // The skipped fields are never stored
+        let user: User = serde_json::from_value(serde_json::json!({
+            "active": true,
+            "age": 18,
+            "email": "felix@mail.com",
+            "roles": [],
+            "rating": null,
+            "password": "secret",
+            "tokens": ["token"]
+        }))
+        .unwrap();
+        common::expect_assert(user.tokens.is_empty())?;
+        let query = Query::new("Admin").filter(Comparison::field("age").equals(1).into());
+        common::expect_assert(query.validate_fields::<User>().is_err())?;
+        Ok(())
+    }
+}
+
 mod query {
     use super::*;
 
@@ -304,6 +437,30 @@ mod query {
             )?;
             Ok(())
         }
+
+        #[test]
+        fn edge_operations_reference_the_edge() -> Result<(), String> {
+            let query = Query::new("Companies").join_outbound(
+                1,
+                3,
+                false,
+                Query::new("MemberOf")
+                    .prune_edges(Comparison::field("weight").greater_than(10).into())
+                    .filter_edges(Comparison::field("kind").equals_str("employee").into())
+                    .filter(Comparison::field("active").equals(true).into()),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "\
+            FOR b in Companies \
+                FOR a, a_e in 1..3 OUTBOUND b MemberOf \
+                    PRUNE a_e.weight > 10 \
+                    FILTER a_e.kind == \"employee\" \
+                    FILTER a.active == true \
+                    return a",
+            )?;
+            Ok(())
+        }
     }
 
     mod named_graph {
@@ -458,6 +615,230 @@ mod query {
         Ok(())
     }
 
+    #[test]
+    fn bound_arrays_are_bind_vars() -> Result<(), String> {
+        let query = Query::new("Users")
+            .filter(Comparison::field("_key").in_bound_array(&["1", "2"]).into())
+            .join_outbound(
+                1,
+                1,
+                false,
+                Query::new("Follows")
+                    .filter(
+                        Comparison::field("age")
+                            .not_in_bound_array(&[18, 21])
+                            .into(),
+                    )
+                    .bind_var("active", true),
+            );
+        let (aql, bind_vars) = query.aql_with_bind_vars(false);
+        common::expect_assert_eq(
+            aql.as_str(),
+            "FOR b in Users \
+                FILTER b._key IN @value_0 \
+                FOR a in 1..1 OUTBOUND b Follows \
+                    FILTER a.age NOT IN @value_1 \
+                    return a",
+        )?;
+        common::expect_assert_eq(aql, query.aql_str())?;
+        common::expect_assert_eq(bind_vars.len(), 3)?;
+        common::expect_assert_eq(&bind_vars["value_0"], &serde_json::json!(["1", "2"]))?;
+        common::expect_assert_eq(&bind_vars["value_1"], &serde_json::json!([18, 21]))?;
+        common::expect_assert_eq(&bind_vars["active"], &serde_json::json!(true))?;
+        Ok(())
+    }
+
+    #[test]
+    fn literals_are_bind_vars() -> Result<(), String> {
+        let query = |username: &str, age: u32, page: u32| {
+            Query::new("Users")
+                .filter(
+                    Comparison::field("username")
+                        .equals_str(username)
+                        .and(Comparison::field("age").greater_than(age))
+                        .and(Comparison::field("role").in_str_array(&["admin", "editor"]))
+                        .and(Comparison::field("deleted").eq_false())
+                        .and(Comparison::field("parent").equals("@parent")),
+                )
+                .limit(10, Some(page * 10))
+        };
+        let (aql, bind_vars) = query("felix", 18, 2).aql_with_bind_vars(true);
+        common::expect_assert_eq(
+            aql.as_str(),
+            "FOR a in Users \
+                FILTER a.username == @value_0 && a.age > @value_1 && a.role IN @value_2 \
+                    && a.deleted == false && a.parent == @parent \
+                LIMIT @value_3, @value_4 \
+                return a",
+        )?;
+        common::expect_assert_eq(&bind_vars["value_0"], &serde_json::json!("felix"))?;
+        common::expect_assert_eq(&bind_vars["value_1"], &serde_json::json!(18))?;
+        common::expect_assert_eq(
+            &bind_vars["value_2"],
+            &serde_json::json!(["admin", "editor"]),
+        )?;
+        common::expect_assert_eq(&bind_vars["value_3"], &serde_json::json!(20))?;
+        common::expect_assert_eq(&bind_vars["value_4"], &serde_json::json!(10))?;
+        let (other_aql, _) = query("bianca \"b\"", 21, 3).aql_with_bind_vars(true);
+        common::expect_assert_eq(aql, other_aql)?;
+        let (aql, bind_vars) = query("felix", 18, 2).aql_with_bind_vars(false);
+        common::expect_assert_eq(aql, query("felix", 18, 2).aql_str())?;
+        common::expect_assert_eq(bind_vars.is_empty(), true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn collect_into_works() -> Result<(), String> {
+        let query = Query::new("Orders")
+            .filter(Filter::new(Comparison::field("total").greater_than(10)))
+            .distinct()
+            .collect_into("user", "user_id", "orders");
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR a in Orders \
+                FILTER a.total > 10 \
+                COLLECT user = a.user_id INTO orders = a \
+                return { key: user, documents: orders }",
+        )?;
+        let query = Query::new("User")
+            .join_outbound(1, 1, false, Query::new("Ordered"))
+            .collect_into("user", "user_id", "orders");
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR b in User \
+                FOR a in 1..1 OUTBOUND b Ordered \
+                COLLECT user = a.user_id INTO orders = a \
+                return { key: user, documents: orders }",
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn with_related_works() -> Result<(), String> {
+        let query = Query::new("User")
+            .join_outbound(1, 1, false, Query::new("MemberOf"))
+            .with_related("AuthoredBy", GraphQueryDirection::Inbound, "posts");
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR b in User \
+                FOR a in 1..1 OUTBOUND b MemberOf \
+                return MERGE(a, { posts: (FOR related_0 in 1..1 INBOUND a AuthoredBy return related_0) })",
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_works() -> Result<(), String> {
+        let query = Query::union(vec![
+            Query::new("Cats").filter(Comparison::field("name").equals_str("Tom").into()),
+            Query::new("Dogs").bind_var("color", "black"),
+        ])
+        .filter(Comparison::field("color").equals("@color").into())
+        .limit(5, None);
+        let (aql, bind_vars) = query.aql_with_bind_vars(true);
+        common::expect_assert_eq(
+            aql.as_str(),
+            "\
+            FOR b in UNION(\
+                (FOR a in Cats FILTER a.name == @value_0 return a), \
+                (FOR a in Dogs return a)\
+            ) \
+                FILTER b.color == @color \
+                LIMIT @value_1 \
+                return b",
+        )?;
+        common::expect_assert_eq(&bind_vars["value_0"], &serde_json::json!("Tom"))?;
+        common::expect_assert_eq(&bind_vars["color"], &serde_json::json!("black"))?;
+        let query = Query::union(vec![Query::new("User").join_outbound(
+            1,
+            1,
+            false,
+            Query::new("MemberOf"),
+        )]);
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "FOR c in (FOR b in User FOR a in 1..1 OUTBOUND b MemberOf return a) return c",
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn sample_works() -> Result<(), String> {
+        let query = Query::new("Products")
+            .filter(Comparison::field("stock").greater_than(0).into())
+            .sample(5)
+            .sort("price", None);
+        let (aql, bind_vars) = query.aql_with_bind_vars(true);
+        common::expect_assert_eq(
+            aql.as_str(),
+            "\
+            FOR a in Products \
+                FILTER a.stock > @value_0 \
+                SORT RAND() LIMIT @value_1 \
+                SORT a.price ASC \
+                return a",
+        )?;
+        common::expect_assert_eq(&bind_vars["value_1"], &serde_json::json!(5))?;
+        Ok(())
+    }
+
+    #[test]
+    fn array_membership_works() -> Result<(), String> {
+        let query = Query::new("Events")
+            .filter(Comparison::value(10).in_field("allowed_ages").into())
+            .filter(Comparison::field("tags").not_contains("private").into())
+            .filter(Comparison::statement("[1, 2]").contains(2).into());
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR a in Events \
+                FILTER 10 IN a.allowed_ages \
+                FILTER \"private\" NOT IN a.tags \
+                FILTER 2 IN [1, 2] \
+                return a",
+        )?;
+        let (aql, bind_vars) = query.aql_with_bind_vars(true);
+        common::expect_assert_eq(
+            aql.as_str(),
+            "\
+            FOR a in Events \
+                FILTER @value_0 IN a.allowed_ages \
+                FILTER @value_1 NOT IN a.tags \
+                FILTER @value_2 IN [1, 2] \
+                return a",
+        )?;
+        common::expect_assert_eq(&bind_vars["value_1"], &serde_json::json!("private"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn index_hint_works() -> Result<(), String> {
+        let query = Query::new("Products")
+            .index_hint(&["price_idx"])
+            .filter(Comparison::field("price").greater_than(10).into());
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR a in Products OPTIONS { indexHint: [\"price_idx\"] } \
+                FILTER a.price > 10 \
+                return a",
+        )?;
+        let query = query.force_index_hint();
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "\
+            FOR a in Products OPTIONS { indexHint: [\"price_idx\"], forceIndexHint: true } \
+                FILTER a.price > 10 \
+                return a",
+        )?;
+        let query = Query::new("Products").force_index_hint();
+        common::expect_assert_eq(query.aql_str().as_str(), "FOR a in Products return a")?;
+        Ok(())
+    }
+
     #[test]
     fn empty_query_works() -> Result<(), String> {
         let query = Query::new("Companies");
@@ -466,6 +847,136 @@ mod query {
     }
 }
 
+mod serialization {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn comparisons_round_trip() -> Result<(), String> {
+        let comparisons = vec![
+            Comparison::field("age").greater_or_equal(18),
+            Comparison::field("name").equals_str("felix"),
+            Comparison::any("emails").like("%gmail.com"),
+            Comparison::field("roles").in_str_array(&["admin"]),
+            Comparison::field("deleted_at").eq_null(),
+            Comparison::value(10).in_field("allowed_ages"),
+            Comparison::field("tags").not_contains("private"),
+        ];
+        for comparison in comparisons {
+            let value = serde_json::to_value(&comparison).map_err(|e| e.to_string())?;
+            let deserialized: Comparison =
+                serde_json::from_value(value).map_err(|e| e.to_string())?;
+            common::expect_assert_eq(deserialized.aql_str("a"), comparison.aql_str("a"))?;
+        }
+        common::expect_assert_eq(
+            serde_json::to_value(&Comparison::all("prices").lesser_than(10)).unwrap(),
+            json!({ "field": "prices", "quantifier": "all", "comparator": "lesser_than", "value": 10 }),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsafe_comparisons_are_rejected() -> Result<(), String> {
+        let invalid = [
+            json!({ "field": "age || true", "comparator": "equals", "value": 1 }),
+            json!({ "field": "age", "comparator": "==", "value": 1 }),
+            json!({ "field": "age", "comparator": "in_array", "value": 1 }),
+            json!({ "field": "name", "comparator": "like", "value": ["%a"] }),
+            json!({ "field": "tags", "quantifier": "any", "comparator": "contains", "value": 1 }),
+            json!({ "field": "age", "comparator": "equals", "value": 1, "statement": "1" }),
+        ];
+        for value in invalid {
+            common::expect_assert(serde_json::from_value::<Comparison>(value).is_err())?;
+        }
+        // Strings are rendered as escaped JSON literals
+        let comparison: Comparison = serde_json::from_value(
+            json!({ "field": "name", "comparator": "equals", "value": "\" || true || \"" }),
+        )
+        .unwrap();
+        common::expect_assert_eq(
+            comparison.aql_str("a"),
+            r#"a.name == "\" || true || \"""#.to_string(),
+        )?;
+        common::expect_assert(
+            serde_json::to_value(&Comparison::statement("1").equals(1)).is_err(),
+        )?;
+        common::expect_assert(
+            serde_json::to_value(&Comparison::field("a").equals("a.b")).is_err(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn filters_round_trip() -> Result<(), String> {
+        let filter = Comparison::field("age")
+            .greater_than(18)
+            .or(Comparison::field("vip").eq_true())
+            .and(Comparison::field("name").not_like("%bot%"));
+        let value = serde_json::to_value(&filter).map_err(|e| e.to_string())?;
+        common::expect_assert_eq(&value["operators"], &json!(["or", "and"]))?;
+        let deserialized: Filter = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        common::expect_assert_eq(deserialized.aql_str("a"), filter.aql_str("a"))?;
+        common::expect_assert(
+            serde_json::from_value::<Filter>(json!({ "comparisons": [], "operators": [] }))
+                .is_err(),
+        )?;
+        common::expect_assert(
+            serde_json::from_value::<Filter>(json!({
+                "comparisons": [{ "field": "age", "comparator": "equals", "value": 1 }],
+                "operators": ["and"]
+            }))
+            .is_err(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn queries_round_trip() -> Result<(), String> {
+        let query = Query::new("Users")
+            .filter(Comparison::field("age").greater_than(18).into())
+            .sort("name", Some(SortDirection::Desc))
+            .limit(10, Some(20))
+            .distinct()
+            .index_hint(&["age_idx"])
+            .force_index_hint()
+            .cursor_ttl(300)
+            .bind_var("role", "admin");
+        let value = serde_json::to_value(&query).map_err(|e| e.to_string())?;
+        common::expect_assert_eq(&value["operations"][1]["sort"]["direction"], &json!("desc"))?;
+        common::expect_assert_eq(&value["cursor_ttl"], &json!(300))?;
+        let deserialized: Query = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        common::expect_assert_eq(deserialized.aql_str(), query.aql_str())?;
+        common::expect_assert_eq(
+            deserialized.aql_with_bind_vars(false),
+            query.aql_with_bind_vars(false),
+        )?;
+        let legacy: Query = serde_json::from_value(json!({
+            "collection": "Users",
+            "operations": [{ "sort": { "field": "name", "direction": "Desc" } }]
+        }))
+        .map_err(|e| e.to_string())?;
+        common::expect_assert_eq(
+            legacy.aql_str(),
+            "FOR a in Users SORT a.name DESC return a".to_string(),
+        )?;
+        common::expect_assert(
+            serde_json::to_value(&Query::outbound(1, 2, "ChildOf", "User/123")).is_err(),
+        )?;
+        common::expect_assert(
+            serde_json::from_value::<Query>(json!({ "collection": "Users return 1 //" })).is_err(),
+        )?;
+        common::expect_assert(
+            serde_json::from_value::<Query>(json!({
+                "collection": "Users",
+                "operations": [{ "sort": { "field": "RAND()" } }]
+            }))
+            .is_err(),
+        )?;
+        Ok(())
+    }
+}
+
 mod call {
     use serde::{Deserialize, Serialize};
 
@@ -678,6 +1189,195 @@ mod call {
         Ok(())
     }
 
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn query_stats() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").limit(2, None);
+
+        let result: QueryResult<Dish> = query.call(&connection).await.unwrap();
+        common::expect_assert_eq(result.len(), 2)?;
+        common::expect_assert_eq(result.full_count(), Some(5))?;
+        common::expect_assert(result.stats().unwrap().scanned_full >= 2)?;
+        common::expect_assert(result.warnings().is_empty())?;
+
+        let cursor: QueryCursor<Dish> = query.call_in_batches(&connection, 1).await.unwrap();
+        common::expect_assert_eq(cursor.full_count(), Some(5))?;
+        common::expect_assert(cursor.warnings().is_empty())?;
+
+        let query =
+            Query::new("Dish").filter(Filter::new(Comparison::field("name").equals_str("Wine")));
+        let result: QueryResult<Dish> = query.call(&connection).await.unwrap();
+        common::expect_assert_eq(result.full_count(), None)?;
+        common::expect_assert_eq(result.stats().unwrap().scanned_full, 5)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn dropped_cursor() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").cursor_ttl(60);
+        let mut cursor: QueryCursor<Dish> = query.call_in_batches(&connection, 2).await.unwrap();
+        common::expect_assert(cursor.has_more())?;
+        common::expect_assert(cursor.next_batch().await.is_some())?;
+        // The server side cursor is removed in the background
+        drop(cursor);
+        let mut cursor: QueryCursor<Dish> = query.call_in_batches(&connection, 2).await.unwrap();
+        let mut count = cursor.result().len();
+        while let Some(result) = cursor.next_batch().await {
+            count += result.len();
+        }
+        common::expect_assert_eq(count, 5)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn grouped_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").collect_into("name", "name", "dishes");
+        let groups = query
+            .call_grouped::<_, String, Dish>(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(groups.len(), 5)?;
+        for group in groups {
+            common::expect_assert_eq(group.documents.len(), 1)?;
+            common::expect_assert_eq(&group.documents[0].name, &group.key)?;
+        }
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn sample_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let result = Dish::query()
+            .sample(3)
+            .call::<_, Dish>(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(result.len(), 3)?;
+        let result = Dish::query()
+            .sample(10)
+            .call::<_, Dish>(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(result.len(), 5)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn facets_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").filter(compare!(field "name").like("Pizza%").into());
+        let facets = query
+            .facets::<_, Dish>(&["name", "price"], &connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(
+            &facets["name"],
+            &vec![
+                (serde_json::json!("Pizza Mozarella"), 1),
+                (serde_json::json!("Pizza Regina"), 1),
+            ],
+        )?;
+        common::expect_assert_eq(&facets["price"], &vec![(serde_json::Value::Null, 2)])?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn union_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::union(vec![Dish::query(), Order::query()])
+            .filter(compare!(field "name").like("%Pizza%").into());
+        let result = query.raw_call(&connection).await.unwrap();
+        common::expect_assert_eq(result.len(), 4)?;
+        common::expect_assert_eq(
+            result
+                .iter()
+                .filter(|record| record.id().starts_with("Dish/"))
+                .count(),
+            2,
+        )?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn related_request() -> Result<(), String> {
+        #[derive(Deserialize)]
+        struct OrderDishes {
+            dishes: Vec<DatabaseRecord<Dish>>,
+        }
+
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query =
+            Query::new("Order").with_related("PartOf", GraphQueryDirection::Inbound, "dishes");
+        let orders = query
+            .call_with_related::<_, Order, OrderDishes>(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(orders.len(), 3)?;
+        for order in orders {
+            common::expect_assert_eq(order.related.dishes.len(), 3)?;
+            common::expect_assert(order.related.dishes.iter().any(|dish| dish.name == "Wine"))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "blocking"))]
+    #[tokio::test]
+    async fn concurrent_batches() -> Result<(), String> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let cursor: QueryCursor<Dish> = Query::new("Dish")
+            .call_in_batches(&connection, 2)
+            .await
+            .unwrap();
+        let batches = AtomicUsize::new(0);
+        let documents = AtomicUsize::new(0);
+        cursor
+            .for_each_concurrent(2, |batch| {
+                let (batches, documents) = (&batches, &documents);
+                async move {
+                    batches.fetch_add(1, Ordering::SeqCst);
+                    documents.fetch_add(batch.len(), Ordering::SeqCst);
+                }
+            })
+            .await
+            .unwrap();
+        common::expect_assert_eq(batches.into_inner(), 3)?;
+        common::expect_assert_eq(documents.into_inner(), 5)?;
+        Ok(())
+    }
+
     #[maybe_async::test(feature = "blocking")]
     fn cursor_iterator() -> Result<(), String> {
         let connection = common::setup_db();
@@ -3,7 +3,9 @@ extern crate aragog;
 
 use serde::{Deserialize, Serialize};
 
-use aragog::{DatabaseAccess, DatabaseConnection, DatabaseRecord, Error, Record, Validate};
+use aragog::{
+    DatabaseAccess, DatabaseConnection, DatabaseRecord, Error, Record, UpsertOutcome, Validate,
+};
 
 pub mod common;
 
@@ -116,6 +118,85 @@ mod write {
         DatabaseRecord::create(dish, &connection).await.unwrap();
     }
 
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn can_be_created_from_the_record() {
+        let connection = common::setup_db().await;
+        let mut menu = init_menu(&connection).await;
+        let dish = init_dish(menu.key()).create(&connection).await.unwrap();
+        assert_eq!(dish.name, "Quiche");
+        let mut pasta = init_dish(menu.key());
+        pasta.name = String::from("Pasta");
+        let mut salad = init_dish(menu.key());
+        salad.name = String::from("Salad");
+        let dishes = Dish::create_many(vec![pasta, salad], &connection)
+            .await
+            .unwrap();
+        let names: Vec<&str> = dishes.iter().map(|dish| dish.name.as_str()).collect();
+        assert_eq!(names, vec!["Pasta", "Salad"]);
+        // The hooks are called for every record
+        menu.reload_mut(&connection).await.unwrap();
+        assert_eq!(menu.dish_count, 3);
+        // Stops on the first conflict
+        let result = Dish::create_many(vec![init_dish(menu.key())], &connection).await;
+        assert!(result.is_err());
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn can_be_upserted_by_unique_field() {
+        let connection = common::setup_db().await;
+        let mut menu = init_menu(&connection).await;
+        let (dish, outcome) = Dish::upsert_by("name", init_dish(menu.key()), &connection)
+            .await
+            .unwrap();
+        assert_eq!(outcome, UpsertOutcome::Inserted);
+        // The insertion launches the create hooks
+        menu.reload_mut(&connection).await.unwrap();
+        assert_eq!(menu.dish_count, 1);
+        let mut quiche = init_dish(menu.key());
+        quiche.price = 9;
+        let (upserted, outcome) = Dish::upsert_by("name", quiche, &connection).await.unwrap();
+        assert_eq!(outcome, UpsertOutcome::Updated);
+        assert_eq!(upserted.key(), dish.key());
+        assert_eq!(upserted.price, 9);
+        menu.reload_mut(&connection).await.unwrap();
+        assert_eq!(menu.dish_count, 1);
+        assert_eq!(menu.last_dish_updated.as_ref().unwrap().price, 9);
+        let count = Dish::get(&Dish::query(), &connection).await.unwrap().len();
+        assert_eq!(count, 1);
+        // Only top level attributes are accepted
+        let result = Dish::upsert_by("menu.name", init_dish(menu.key()), &connection).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn save_and_diff() {
+        let connection = common::setup_db().await;
+        let menu = init_menu(&connection).await;
+        let mut dish = DatabaseRecord::create(init_dish(menu.key()), &connection)
+            .await
+            .unwrap();
+        let old_rev = dish.rev().clone();
+        dish.price = 20;
+        let change_set = dish.save_and_diff(&connection).await.unwrap();
+        assert_eq!(&change_set.old_rev, &old_rev);
+        assert_eq!(&change_set.new_rev, dish.rev());
+        assert_eq!(change_set.fields(), vec!["price"]);
+        let change = change_set.get("price").unwrap();
+        assert_eq!(change.old, Some(serde_json::Value::from(7)));
+        assert_eq!(change.new, Some(serde_json::Value::from(20)));
+        let change_set = dish.save_and_diff(&connection).await.unwrap();
+        assert!(change_set.is_empty());
+    }
+
     mod hooks {
         use super::*;
 
@@ -151,7 +232,7 @@ mod write {
                     name: "dish".to_string(),
                     description: "description".to_string(),
                     price: 0,
-                    menu_id: menu.key().clone(),
+                    menu_id: menu.key().to_string(),
                 };
                 if DatabaseRecord::create(dish, &connection).await.is_ok() {
                     return Err("Hook should have called validations".to_string());
@@ -173,7 +254,7 @@ mod write {
                     name: "dish".to_string(),
                     description: "description".to_string(),
                     price: 0,
-                    menu_id: menu.key().clone(),
+                    menu_id: menu.key().to_string(),
                 };
                 match DatabaseRecord::force_create(dish, &connection).await {
                     Ok(_) => (),
@@ -224,7 +305,7 @@ mod fmt {
                 name: "Pizza".to_string(),
                 description: "Tomato and Mozarella".to_string(),
                 price: 10,
-                menu_id: menu.key().clone(),
+                menu_id: menu.key().to_string(),
             },
             &connection,
         )
@@ -252,7 +333,7 @@ mod read {
                 name: "Pizza".to_string(),
                 description: "Tomato and Mozarella".to_string(),
                 price: 10,
-                menu_id: menu.key().clone(),
+                menu_id: menu.key().to_string(),
             },
             connection,
         )
@@ -263,7 +344,7 @@ mod read {
                 name: "Pasta".to_string(),
                 description: "Ham and cheese".to_string(),
                 price: 6,
-                menu_id: menu.key().clone(),
+                menu_id: menu.key().to_string(),
             },
             connection,
         )
@@ -274,7 +355,7 @@ mod read {
                 name: "Steak".to_string(),
                 description: "Served with fries".to_string(),
                 price: 10,
-                menu_id: menu.key().clone(),
+                menu_id: menu.key().to_string(),
             },
             connection,
         )
@@ -298,6 +379,94 @@ mod read {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn find_if_modified() {
+        let connection = common::setup_db().await;
+        let mut dish_record = create_dishes(&connection).await;
+        let rev = dish_record.rev().clone();
+
+        let found = Dish::find_if_modified(dish_record.key(), &rev, &connection)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+        dish_record.description = "Updated description".to_string();
+        dish_record.save(&connection).await.unwrap();
+        let found = Dish::find_if_modified(dish_record.key(), &rev, &connection)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.rev(), dish_record.rev());
+        assert_eq!(found.description, "Updated description");
+        match Dish::find_if_modified("wrong_key", &rev, &connection).await {
+            Err(Error::NotFound { .. }) => (),
+            _ => panic!("Document should not be found"),
+        }
+        // The key is percent-encoded in the document API path
+        let record = dish_record.record.clone();
+        let special = DatabaseRecord::create_with_key(record, "a+b%c@d".to_string(), &connection)
+            .await
+            .unwrap();
+        let found = Dish::find_if_modified(special.key(), "", &connection)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.key(), "a+b%c@d");
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn find_raw() {
+        let connection = common::setup_db().await;
+        let dish_record = create_dishes(&connection).await;
+        connection
+            .database()
+            .aql_str::<serde_json::Value>(&format!(
+                r#"UPDATE "{}" WITH {{ origin: "Naples" }} IN Dish"#,
+                dish_record.key()
+            ))
+            .await
+            .unwrap();
+
+        let (found, raw) = DatabaseRecord::<Dish>::find_raw(dish_record.key(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(found.key(), dish_record.key());
+        assert_eq!(found.record.name, dish_record.name);
+        assert_eq!(raw["origin"], "Naples");
+        assert_eq!(raw["name"], dish_record.name.as_str());
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn save_preserves_unknown_attributes() {
+        let connection = common::setup_db().await;
+        let dish_record = create_dishes(&connection).await;
+        connection
+            .database()
+            .aql_str::<serde_json::Value>(&format!(
+                r#"UPDATE "{}" WITH {{ origin: "Naples" }} IN Dish"#,
+                dish_record.key()
+            ))
+            .await
+            .unwrap();
+
+        let mut found = Dish::find(dish_record.key(), &connection).await.unwrap();
+        found.description = "Updated description".to_string();
+        found.save(&connection).await.unwrap();
+        let (_, raw) = DatabaseRecord::<Dish>::find_raw(dish_record.key(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(raw["description"], "Updated description");
+        assert_eq!(raw["origin"], "Naples");
+    }
+
     #[should_panic(expected = "NotFound")]
     #[maybe_async::test(
         feature = "blocking",
@@ -498,6 +667,58 @@ mod read {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn find_by_field() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        create_dishes(&connection).await;
+
+        let dish = Dish::find_by_field("name", "Pasta", &connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(dish.price, 6)?;
+        // Two dishes cost 10
+        let res = Dish::find_by_field("price", 10, &connection).await;
+        common::expect_assert(matches!(res, Err(Error::NotFound { .. })))?;
+        let res = Dish::find_by_field("name", "Burger", &connection).await;
+        common::expect_assert(matches!(res, Err(Error::NotFound { .. })))?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn exists_by() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        let dish = create_dishes(&connection).await;
+
+        common::expect_assert(Dish::exists_by("name", "Quiche", &connection).await)?;
+        common::expect_assert(!Dish::exists_by("name", "Burger", &connection).await)?;
+
+        let transaction = aragog::transaction::Transaction::new(&connection)
+            .await
+            .unwrap();
+        let transaction_connection = transaction.database_connection();
+        Dish::create(
+            Dish {
+                name: "Burger".to_string(),
+                ..dish.record.clone()
+            },
+            transaction_connection,
+        )
+        .await
+        .unwrap();
+        common::expect_assert(Dish::exists_by("name", "Burger", transaction_connection).await)?;
+        let burger = Dish::find_by_field("name", "Burger", transaction_connection).await;
+        common::expect_assert(burger.is_ok())?;
+        transaction.abort().await.unwrap();
+        common::expect_assert(!Dish::exists_by("name", "Burger", &connection).await)?;
+        Ok(())
+    }
+
     mod graph_querying {
         use aragog::query::Query;
 
@@ -577,63 +798,114 @@ mod read {
     }
 }
 
-// mod enum_record {
-//     use super::*;
-//
-//     #[derive(Clone, Debug, Serialize, Deserialize, Record)]
-//     enum Dish {
-//         Adult {
-//             price: u16,
-//             alcohol: bool,
-//             name: String,
-//         },
-//         Child {
-//             price: u16,
-//             name: String,
-//         },
-//     }
-//
-//     #[maybe_async::test(
-//         feature = "blocking",
-//         async(all(not(feature = "blocking")), tokio::test)
-//     )]
-//     async fn works() {
-//         let conn = common::setup_db().await;
-//         let dish = Dish::Adult {
-//             price: 20,
-//             alcohol: true,
-//             name: "Baba au rhum".to_string(),
-//         };
-//         // Store
-//         let db_record = DatabaseRecord::create(dish, &conn).await.unwrap();
-//         if let Dish::Adult {
-//             price,
-//             alcohol,
-//             name,
-//         } = &db_record.record
-//         {
-//             assert_eq!(*price, 20);
-//             assert!(*alcohol);
-//             assert_eq!(name, "Baba au rhum");
-//         } else {
-//             panic!()
-//         }
-//         // Query
-//         let queried: Dish = Dish::find(db_record.key(), &conn).await.unwrap().record;
-//         if let Dish::Adult {
-//             price,
-//             alcohol,
-//             name,
-//         } = queried
-//         {
-//             assert_eq!(price, 20);
-//             assert!(alcohol);
-//             assert_eq!(&name, "Baba au rhum");
-//         } else {
-//             panic!()
-//         }
-//     }
-// }
+mod enum_record {
+    use super::*;
+    use aragog::query::Comparison;
+
+    #[derive(Clone, Debug, Serialize, Deserialize, Record)]
+    enum Dish {
+        Adult {
+            price: u16,
+            alcohol: bool,
+            name: String,
+        },
+        Child {
+            price: u16,
+            name: String,
+        },
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn works() {
+        let conn = common::setup_db().await;
+        let dish = Dish::Adult {
+            price: 20,
+            alcohol: true,
+            name: "Baba au rhum".to_string(),
+        };
+        // Store
+        let db_record = DatabaseRecord::create(dish, &conn).await.unwrap();
+        if let Dish::Adult {
+            price,
+            alcohol,
+            name,
+        } = &db_record.record
+        {
+            assert_eq!(*price, 20);
+            assert!(*alcohol);
+            assert_eq!(name, "Baba au rhum");
+        } else {
+            panic!()
+        }
+        // Query
+        let queried: Dish = Dish::find(db_record.key(), &conn).await.unwrap().record;
+        if let Dish::Adult {
+            price,
+            alcohol,
+            name,
+        } = queried
+        {
+            assert_eq!(price, 20);
+            assert!(alcohol);
+            assert_eq!(&name, "Baba au rhum");
+        } else {
+            panic!()
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize, Record)]
+    #[serde(tag = "kind")]
+    #[collection_name = "Menu"]
+    #[before_create(func("before_create"))]
+    enum Menu {
+        Daily { dish_count: u16 },
+        Special { name: String, create_count: u16 },
+    }
+
+    impl Menu {
+        fn before_create(&mut self) -> Result<(), Error> {
+            if let Self::Special { create_count, .. } = self {
+                *create_count += 1;
+            }
+            Ok(())
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn tagged_enum_works_with_hooks() {
+        let conn = common::setup_db().await;
+        let menu = Menu::Special {
+            name: "Christmas".to_string(),
+            create_count: 0,
+        };
+        let db_record = DatabaseRecord::create(menu, &conn).await.unwrap();
+        let queried = Menu::find(db_record.key(), &conn).await.unwrap().record;
+        if let Menu::Special { name, create_count } = queried {
+            assert_eq!(&name, "Christmas");
+            assert_eq!(create_count, 1);
+        } else {
+            panic!()
+        }
+        DatabaseRecord::create(Menu::Daily { dish_count: 3 }, &conn)
+            .await
+            .unwrap();
+        let daily = Menu::get(
+            &Menu::query().filter(Comparison::field("kind").equals_str("Daily").into()),
+            &conn,
+        )
+        .await
+        .unwrap()
+        .uniq()
+        .unwrap();
+        assert!(matches!(daily.record, Menu::Daily { dish_count: 3 }));
+    }
+}
 
 mod collection_name {
     use super::*;
@@ -646,11 +918,80 @@ mod collection_name {
     #[collection_name = "Dish"]
     pub struct Dish2 {}
 
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name(snake_case, pluralize)]
+    pub struct User {}
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name(snake_case, pluralize)]
+    pub struct OrderItem {}
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name(pluralize)]
+    pub struct Category {}
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name(camel_case, pluralize)]
+    pub struct HTTPRequestBox {}
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name(snake_case)]
+    pub struct DayOfWeek {}
+
     #[test]
     fn has_correct_collection_name() {
         assert_eq!(Dish1::COLLECTION_NAME, "Dish");
         assert_eq!(Dish2::COLLECTION_NAME, "Dish");
     }
+
+    #[test]
+    fn applies_naming_options() {
+        assert_eq!(User::COLLECTION_NAME, "users");
+        assert_eq!(OrderItem::COLLECTION_NAME, "order_items");
+        assert_eq!(Category::COLLECTION_NAME, "Categories");
+        assert_eq!(HTTPRequestBox::COLLECTION_NAME, "httpRequestBoxes");
+        assert_eq!(DayOfWeek::COLLECTION_NAME, "day_of_week");
+    }
+}
+
+mod allowed_query_fields {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Account {
+        pub display_name: String,
+        #[serde(rename = "mail")]
+        pub email: String,
+        #[db(redact)]
+        pub password_hash: String,
+        #[serde(skip)]
+        pub session: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    pub struct Extended {
+        #[serde(flatten)]
+        pub extra: serde_json::Value,
+    }
+
+    #[test]
+    fn lists_serialized_fields() {
+        assert_eq!(
+            Account::allowed_query_fields(),
+            Some(&["displayName", "mail"][..])
+        );
+        assert_eq!(Extended::allowed_query_fields(), None);
+        // The skipped fields are never stored
+        let account: Account = serde_json::from_value(serde_json::json!({
+            "displayName": "Felix",
+            "mail": "felix@mail.com",
+            "passwordHash": "hash",
+            "session": "token"
+        }))
+        .unwrap();
+        assert_eq!(account.session, None);
+    }
 }
 
 mod all_hooks {
@@ -767,3 +1108,738 @@ mod all_hooks {
         assert_eq!(rec.after_all_count, 1);
     }
 }
+
+mod hooks_order {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Record, Default)]
+    #[before_write(func = "first")]
+    #[before_create(func = "second")]
+    #[before_create(func = "third")]
+    #[before_create(func = "prioritized", priority = 10)]
+    #[before_save(func = "last", priority = 0)]
+    pub struct Dish {
+        calls: Vec<String>,
+    }
+
+    impl Dish {
+        fn first(&mut self) -> Result<(), Error> {
+            self.calls.push("first".to_string());
+            Ok(())
+        }
+        fn second(&mut self) -> Result<(), Error> {
+            self.calls.push("second".to_string());
+            Ok(())
+        }
+        fn third(&mut self) -> Result<(), Error> {
+            self.calls.push("third".to_string());
+            Ok(())
+        }
+        fn prioritized(&mut self) -> Result<(), Error> {
+            self.calls.push("prioritized".to_string());
+            Ok(())
+        }
+        fn last(&mut self) -> Result<(), Error> {
+            self.calls.push("last".to_string());
+            Ok(())
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn hooks_follow_priority_and_declaration_order() {
+        let db = common::setup_db().await;
+        let mut rec = DatabaseRecord::create(Dish::default(), &db).await.unwrap();
+        assert_eq!(rec.calls, vec!["prioritized", "first", "second", "third"]);
+        rec.calls.clear();
+        rec.save(&db).await.unwrap();
+        assert_eq!(rec.calls, vec!["first", "last"]);
+    }
+}
+
+mod hook_modes {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Record, Default)]
+    #[before_create(func = "count_menus", is_async, db_access)]
+    #[before_create(func = "check", sync)]
+    #[before_create(func = "stamp", is_async)]
+    #[after_create(func = "mark_created", sync = true, db_access = true)]
+    pub struct Dish {
+        menu_count: usize,
+        checked: bool,
+        stamped: bool,
+        created: bool,
+    }
+
+    impl Dish {
+        // Awaited in async mode, called directly with the `blocking` feature
+        #[maybe_async::maybe_async]
+        async fn count_menus<D>(&mut self, db_access: &D) -> Result<(), Error>
+        where
+            D: DatabaseAccess + ?Sized,
+        {
+            let menus = Menu::get(&Menu::query(), db_access).await?;
+            self.menu_count = menus.len();
+            Ok(())
+        }
+
+        // Awaited in async mode, run to completion with the `blocking` feature
+        async fn stamp(&mut self) -> Result<(), Error> {
+            self.stamped = true;
+            Ok(())
+        }
+
+        // Called directly in both modes
+        fn check(&mut self) -> Result<(), Error> {
+            self.checked = true;
+            Ok(())
+        }
+
+        fn mark_created<D>(&mut self, _db_access: &D) -> Result<(), Error>
+        where
+            D: DatabaseAccess + ?Sized,
+        {
+            self.created = true;
+            Ok(())
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn sync_and_async_hooks_are_called() {
+        let db = common::setup_db().await;
+        init_menu(&db).await;
+        let rec = DatabaseRecord::create(Dish::default(), &db).await.unwrap();
+        assert_eq!(rec.menu_count, 1);
+        assert!(rec.checked);
+        assert!(rec.stamped);
+        assert!(rec.created);
+    }
+}
+
+mod redacted_fields {
+    use super::*;
+    use std::fmt::{Debug, Write};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[db(redacted_debug, redacted_display)]
+    pub struct User {
+        name: String,
+        #[db(redact)]
+        password: String,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[db(redacted_debug)]
+    pub struct Token(String, #[db(redact)] String);
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[db(redacted_debug)]
+    pub struct Secret<T> {
+        #[db(redact)]
+        value: T,
+        label: T,
+    }
+
+    // Without the opt-in the fmt traits are left to the user
+    #[derive(Serialize, Deserialize, Clone, Debug, Record)]
+    pub struct Unmasked {
+        #[db(redact)]
+        password: String,
+    }
+
+    /// Records the fields of the traced events
+    struct Recorder(Arc<Mutex<String>>);
+
+    struct Visitor<'a>(&'a mut String);
+
+    impl Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            write!(self.0, "{}={:?};", field.name(), value).unwrap();
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut Visitor(&mut self.0.lock().unwrap()));
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    fn user() -> User {
+        User {
+            name: "Felix".to_string(),
+            password: "password123".to_string(),
+        }
+    }
+
+    #[test]
+    fn debug_masks_redacted_fields() {
+        assert_eq!(
+            format!("{:?}", user()),
+            r#"User { name: "Felix", password: "[REDACTED]" }"#
+        );
+        let token = Token("bearer".to_string(), "secret_token".to_string());
+        assert_eq!(format!("{:?}", token), r#"Token("bearer", "[REDACTED]")"#);
+        let secret = Secret {
+            value: 42,
+            label: 1,
+        };
+        assert_eq!(
+            format!("{:#?}", secret),
+            "Secret {\n    value: \"[REDACTED]\",\n    label: 1,\n}"
+        );
+    }
+
+    #[test]
+    fn display_masks_redacted_fields() {
+        assert_eq!(
+            user().to_string(),
+            r#"User { name: "Felix", password: "[REDACTED]" }"#
+        );
+    }
+
+    #[test]
+    fn derived_debug_is_not_masked() {
+        let unmasked = Unmasked {
+            password: "password123".to_string(),
+        };
+        assert!(format!("{:?}", unmasked).contains("password123"));
+    }
+
+    #[test]
+    fn tracing_masks_redacted_fields() {
+        let logs = Arc::new(Mutex::new(String::new()));
+        tracing::subscriber::with_default(Recorder(Arc::clone(&logs)), || {
+            let user = user();
+            tracing::info!(debug = ?user, display = %user, "created {}", user);
+        });
+        let logs = logs.lock().unwrap();
+        assert!(logs.contains("debug=User"));
+        assert!(logs.contains("display=User"));
+        assert!(logs.contains("message=created User"));
+        assert!(!logs.contains("password123"));
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn database_record_debug_masks_redacted_fields() {
+        let db = common::setup_db().await;
+        let record = DatabaseRecord::create(user(), &db).await.unwrap();
+        let debug = format!("{:?}", record);
+        assert!(debug.contains("Felix"));
+        assert!(!debug.contains("password123"));
+    }
+}
+
+mod generic_record {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, Record)]
+    #[collection_name = "Menu"]
+    #[before_create(func = "check")]
+    pub struct Wrapper<T: Serialize + Clone> {
+        pub value: T,
+    }
+
+    impl<T: Serialize + Clone> Wrapper<T> {
+        fn check(&self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, Record)]
+    #[collection_name = "Menu"]
+    pub struct Pair<A, B>
+    where
+        A: Clone,
+        B: Clone,
+    {
+        pub left: A,
+        pub right: B,
+    }
+
+    #[test]
+    fn has_correct_collection_name() {
+        assert_eq!(Wrapper::<u16>::COLLECTION_NAME, "Menu");
+        assert_eq!(Pair::<String, bool>::COLLECTION_NAME, "Menu");
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn can_be_stored_and_retrieved() {
+        let db = common::setup_db().await;
+        let record = DatabaseRecord::create(
+            Wrapper {
+                value: "value".to_string(),
+            },
+            &db,
+        )
+        .await
+        .unwrap();
+        let found = Wrapper::<String>::find(record.key(), &db).await.unwrap();
+        assert_eq!(found.value, "value");
+
+        let record = DatabaseRecord::create(
+            Pair {
+                left: 10_u16,
+                right: true,
+            },
+            &db,
+        )
+        .await
+        .unwrap();
+        let found = Pair::<u16, bool>::find(record.key(), &db).await.unwrap();
+        assert_eq!(found.left, 10);
+        assert!(found.right);
+    }
+}
+
+mod repository {
+    use super::*;
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn operations_use_the_accessor() {
+        let connection = common::setup_db().await;
+        let menus = Menu::repo(&connection);
+        let mut menu = menus
+            .create(Menu {
+                dish_count: 0,
+                last_dish_updated: None,
+            })
+            .await
+            .unwrap();
+        let count = menus.count().await.unwrap();
+        assert_eq!(count, 1);
+        menu.dish_count = 2;
+        menus.save(&mut menu).await.unwrap();
+        let found = menus.find(menu.key()).await.unwrap();
+        assert_eq!(found.dish_count, 2);
+        let found = menus.find_by_field("dish_count", 2).await.unwrap();
+        assert_eq!(found.key(), menu.key());
+        let query = menus.query();
+        let menus_found = menus.get(&query).await.unwrap();
+        assert_eq!(menus_found.len(), 1);
+        menus.delete(&mut menu).await.unwrap();
+        let exists = menus.exists(&query).await;
+        assert!(!exists);
+    }
+}
+
+mod model_version {
+    use super::*;
+    use aragog::query::{Query, QueryResult};
+    use aragog::UndefinedRecord;
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    #[collection_name = "Menu"]
+    #[model_version(2, migrate_from = "upgrade")]
+    pub struct MenuV2 {
+        pub dishes: u16,
+        pub description: String,
+    }
+
+    impl MenuV2 {
+        fn upgrade(version: u32, value: serde_json::Value) -> Result<Self, Error> {
+            let dishes = match version {
+                0 => serde_json::from_value(value["dish_count"].clone())?,
+                _ => serde_json::from_value(value["dishes"].clone())?,
+            };
+            Ok(Self {
+                dishes,
+                description: String::from("No description"),
+            })
+        }
+    }
+
+    fn stored_documents(documents: serde_json::Value) -> QueryResult<UndefinedRecord> {
+        serde_json::from_value::<Vec<DatabaseRecord<UndefinedRecord>>>(documents)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn older_documents_are_migrated() {
+        let result = stored_documents(serde_json::json!([
+            { "_key": "1", "_id": "Menu/1", "_rev": "1", "dish_count": 3 },
+            { "_key": "2", "_id": "Menu/2", "_rev": "1", "dishes": 4, "_model_version": 1 },
+            { "_key": "3", "_id": "Menu/3", "_rev": "1", "dishes": 5, "description": "Fish", "_model_version": 2 },
+        ]));
+        let menus = result.get_records::<MenuV2>();
+        assert_eq!(menus.len(), 3);
+        assert_eq!(menus[0].dishes, 3);
+        assert_eq!(&menus[0].description, "No description");
+        assert_eq!(menus[1].dishes, 4);
+        assert_eq!(menus[2].dishes, 5);
+        assert_eq!(&menus[2].description, "Fish");
+    }
+
+    #[test]
+    fn deserialization_failures_are_reported() {
+        let result = stored_documents(serde_json::json!([
+            { "_key": "1", "_id": "Menu/1", "_rev": "1", "dishes": 4, "_model_version": 1 },
+            { "_key": "2", "_id": "Menu/2", "_rev": "1", "dishes": "four", "description": "Fish", "_model_version": 2 },
+            { "_key": "3", "_id": "Menu/3", "_rev": "1", "dish_count": null },
+        ]));
+        let (menus, errors) = result.get_records_with_errors::<MenuV2>();
+        assert_eq!(menus.len(), 1);
+        assert_eq!(menus[0].key(), "1");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(&errors[0].id, "Menu/2");
+        assert_eq!(&errors[0].path, "dishes");
+        assert_eq!(
+            &errors[0].message,
+            r#"invalid type: string "four", expected u16"#
+        );
+        assert_eq!(&errors[1].id, "Menu/3");
+        assert_eq!(&errors[1].path, ".");
+        assert_eq!(&errors[1].message, "invalid type: null, expected u16");
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn documents_are_upgraded_on_save() {
+        let connection = common::setup_db().await;
+        let menu = DatabaseRecord::create(
+            Menu {
+                dish_count: 3,
+                last_dish_updated: None,
+            },
+            &connection,
+        )
+        .await
+        .unwrap();
+        let mut found = MenuV2::find(menu.key(), &connection).await.unwrap();
+        assert_eq!(found.dishes, 3);
+        found.description = String::from("Fish");
+        found.save(&connection).await.unwrap();
+        let stored: QueryResult<UndefinedRecord> =
+            Query::new("Menu").call(&connection).await.unwrap();
+        assert_eq!(stored[0].0["_model_version"], 2);
+        let found = MenuV2::find(menu.key(), &connection).await.unwrap();
+        assert_eq!(&found.description, "Fish");
+    }
+}
+
+mod default_scope {
+    use aragog::query::{Comparison, Query};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name = "Dish"]
+    #[default_scope("price < 10")]
+    pub struct CheapDish {
+        name: String,
+        price: u16,
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn default_scope_is_applied() {
+        let connection = common::setup_db().await;
+        let menu = init_menu(&connection).await;
+        let mut dish = init_dish(menu.key());
+        dish.name = "Soup".to_string();
+        dish.price = 6;
+        let cheap = dish.create(&connection).await.unwrap();
+        let expensive = Dish::create(init_dish(menu.key()), &connection)
+            .await
+            .unwrap();
+
+        let found = CheapDish::find(cheap.key(), &connection).await.unwrap();
+        assert_eq!(found.price, 6);
+        let error = CheapDish::find(expensive.key(), &connection).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+        let found = CheapDish::find_unscoped(expensive.key(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(found.price, 7);
+        // Every find applies the default scope
+        let error = CheapDish::find_if_modified(expensive.key(), "", &connection).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+        let found = CheapDish::find_if_modified(cheap.key(), cheap.rev(), &connection)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+        let error = DatabaseRecord::<CheapDish>::find_raw(expensive.key(), &connection).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+
+        let query =
+            CheapDish::query().filter(Comparison::field("menu_id").equals_str(menu.key()).into());
+        let found = CheapDish::get(&query, &connection).await.unwrap();
+        assert_eq!(found.len(), 1);
+        // The default scope is applied to the record collection queries
+        let found = CheapDish::get(&Query::new("Dish"), &connection)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        let found = CheapDish::get(&CheapDish::query().unscoped(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+    }
+}
+
+mod tenant_scoped {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name = "Order"]
+    #[tenant_scoped(field = "org_id")]
+    pub struct Invoice {
+        org_id: String,
+        amount: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Record)]
+    #[collection_name = "PartOf"]
+    pub struct PartOf {}
+
+    fn invoice(org_id: &str) -> Invoice {
+        Invoice {
+            org_id: org_id.to_string(),
+            amount: 100,
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn operations_are_restricted_to_the_tenant() {
+        let connection = common::setup_db().await;
+        let acme = connection.with_tenant("acme");
+        let globex = connection.with_tenant("globex");
+        let mut acme_invoice = Invoice::create(invoice("acme"), &acme).await.unwrap();
+        let globex_invoice = Invoice::create(invoice("globex"), &globex).await.unwrap();
+        let error = Invoice::create(invoice("globex"), &acme).await;
+        assert!(matches!(error, Err(Error::Forbidden(None))));
+
+        let found = Invoice::get(&Invoice::query(), &acme).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.first().unwrap().key(), acme_invoice.key());
+        let exists = Invoice::exists(&Invoice::query(), &globex).await;
+        assert!(exists);
+        let error = Invoice::find(globex_invoice.key(), &acme).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+        let error = DatabaseRecord::<Invoice>::find_raw(globex_invoice.key(), &acme).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+        let count = Invoice::repo(&acme).count().await.unwrap();
+        assert_eq!(count, 1);
+        let cursor = acme
+            .get_collection("Order")
+            .unwrap()
+            .export_stream::<Invoice, _>(&acme, 10)
+            .await
+            .unwrap();
+        assert_eq!(cursor.result().len(), 1);
+        // Traversals and unions only return the tenant documents
+        let menu = init_menu(&connection).await;
+        let dish = Dish::create(init_dish(menu.key()), &connection)
+            .await
+            .unwrap();
+        for invoice in [&acme_invoice, &globex_invoice] {
+            DatabaseRecord::link(&dish, invoice, &connection, PartOf {})
+                .await
+                .unwrap();
+        }
+        let found = dish
+            .out_neighbors::<Invoice, _>("PartOf", &acme)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.first().unwrap().key(), acme_invoice.key());
+        let found = aragog::GraphOps::new(&acme)
+            .edge_collections(&["PartOf"])
+            .neighbors_within::<Invoice>(dish.id(), 1)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        let query = aragog::query::Query::union(vec![Invoice::query(), Invoice::query()]);
+        let found = Invoice::get(&query, &acme).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|record| record.org_id == "acme"));
+        let error = DatabaseRecord::<Invoice>::aql_get("FOR i in Order return i", &acme).await;
+        assert!(matches!(error, Err(Error::Forbidden(None))));
+        // Operations without tenant are forbidden
+        let error = Invoice::get(&Invoice::query(), &connection).await;
+        assert!(matches!(error, Err(Error::Forbidden(None))));
+        let error = Invoice::find(acme_invoice.key(), &connection).await;
+        assert!(matches!(error, Err(Error::Forbidden(None))));
+
+        acme_invoice.org_id = "globex".to_string();
+        let error = acme_invoice.save(&acme).await;
+        assert!(matches!(error, Err(Error::Forbidden(None))));
+        acme_invoice.org_id = "acme".to_string();
+        acme_invoice.amount = 50;
+        acme_invoice.save(&acme).await.unwrap();
+        acme_invoice.delete(&acme).await.unwrap();
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn registered_hooks_stamp_the_tenant() {
+        use aragog::HookEvent;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let connection = common::setup_db().await;
+        connection.on_before_create::<Invoice, _>(|invoice, context| {
+            invoice.org_id = context.tenant.unwrap_or_default().to_string();
+            Ok(())
+        });
+        let deletions = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&deletions);
+        connection.on_any_hook(HookEvent::AfterDelete, move |_, context| {
+            assert_eq!(context.collection, "Order");
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        });
+        let acme = connection.with_tenant("acme");
+        let mut acme_invoice = Invoice::create(invoice(""), &acme).await.unwrap();
+        assert_eq!(acme_invoice.org_id, "acme");
+        let found = Invoice::find(acme_invoice.key(), &acme).await.unwrap();
+        assert_eq!(found.org_id, "acme");
+        acme_invoice.delete(&acme).await.unwrap();
+        assert_eq!(deletions.load(Ordering::SeqCst), 1);
+    }
+}
+
+mod polymorphic_ref {
+    use aragog::PolymorphicRef;
+
+    use super::*;
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn references_are_resolved() {
+        let connection = common::setup_db().await;
+        let menu = init_menu(&connection).await;
+        let dish = Dish::create(init_dish(menu.key()), &connection)
+            .await
+            .unwrap();
+        let references = [PolymorphicRef::from(&menu), PolymorphicRef::from(&dish)];
+        assert!(references[0].is::<Menu>());
+        assert_eq!(references[1].collection(), "Dish");
+
+        let found: DatabaseRecord<Menu> = references[0].resolve(&connection).await.unwrap();
+        assert_eq!(found.key(), menu.key());
+        let found = references[1].resolve::<Dish, _>(&connection).await.unwrap();
+        assert_eq!(found.key(), dish.key());
+        let error = references[1].resolve::<Menu, _>(&connection).await;
+        assert!(matches!(error, Err(Error::ValidationError(_))));
+    }
+}
+
+mod attachments {
+    use aragog::Attachments;
+
+    use super::*;
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn attachments_are_stored_in_chunks() {
+        let connection = common::setup_db().await;
+        let menu = init_menu(&connection).await;
+        let attachments = Attachments::new(&connection).chunk_size(4);
+        let data: Vec<u8> = (0..10).collect();
+        let attachment = attachments
+            .upload(&menu, "menu.bin", "application/octet-stream", &data)
+            .await
+            .unwrap();
+        assert_eq!(attachment.size, 10);
+        assert_eq!(attachment.chunk_count, 3);
+        assert_eq!(&attachment.owner_id, menu.id());
+
+        let found = attachments.find(&attachment.key).await.unwrap();
+        assert_eq!(found, attachment);
+        let listed = attachments.list(menu.id()).await.unwrap();
+        assert_eq!(listed, vec![attachment.clone()]);
+        let downloaded = attachments.download(&attachment).await.unwrap();
+        assert_eq!(downloaded, data);
+
+        attachments.delete(&attachment).await.unwrap();
+        let listed = attachments.list(menu.id()).await.unwrap();
+        assert!(listed.is_empty());
+        let error = attachments.find(&attachment.key).await;
+        assert!(matches!(error, Err(Error::NotFound { .. })));
+    }
+}
+
+mod pagination {
+    use super::*;
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn pages_are_serialized_with_metadata() {
+        let connection = common::setup_db().await;
+        for dish_count in 0..5 {
+            DatabaseRecord::create(
+                Menu {
+                    dish_count,
+                    last_dish_updated: None,
+                },
+                &connection,
+            )
+            .await
+            .unwrap();
+        }
+        let query = Menu::query().sort("dish_count", None).paginate(2, 2);
+        let page = Menu::get(&query, &connection)
+            .await
+            .unwrap()
+            .into_page(2, 2);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.total_pages(), 3);
+        let value = serde_json::to_value(&page).unwrap();
+        assert_eq!(
+            value["meta"],
+            serde_json::json!({ "total": 5, "page": 2, "per_page": 2 })
+        );
+        assert_eq!(value["data"][0]["dish_count"], 2);
+        assert_eq!(value["data"].as_array().unwrap().len(), 2);
+    }
+}
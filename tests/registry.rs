@@ -0,0 +1,72 @@
+use aragog::schema::{DatabaseSchema, IndexSettings};
+use aragog::Record;
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "registry", aragog::register_record)]
+#[derive(Clone, Serialize, Deserialize, Record)]
+#[index(fields("name"), unique)]
+#[index(fields("category", "price"), sparse, name = "ByCategory")]
+pub struct Dish {
+    pub name: String,
+    pub category: Option<String>,
+    pub price: u16,
+}
+
+#[cfg_attr(feature = "registry", aragog::register_record)]
+#[derive(Clone, Serialize, Deserialize, Record)]
+#[edge_collection]
+#[collection_name = "PartOf"]
+#[index(fields("created_at"), ttl = 3600)]
+pub struct PartOf {
+    pub created_at: String,
+}
+
+#[test]
+fn records_declare_indexes() {
+    let mut schema = DatabaseSchema::default();
+    schema.add_record::<Dish>();
+    schema.add_record::<Dish>();
+    assert_eq!(schema.collections.len(), 1);
+    assert_eq!(schema.indexes.len(), 2);
+    let unique = schema.index("Dish", "unique_name").unwrap();
+    assert_eq!(unique.fields, ["name"]);
+    assert!(matches!(
+        unique.settings,
+        IndexSettings::Persistent {
+            unique: true,
+            sparse: false,
+            deduplicate: true
+        }
+    ));
+    let by_category = schema.index("Dish", "ByCategory").unwrap();
+    assert_eq!(by_category.fields, ["category", "price"]);
+    assert!(matches!(
+        by_category.settings,
+        IndexSettings::Persistent {
+            unique: false,
+            sparse: true,
+            ..
+        }
+    ));
+    let ttl = PartOf::indexes();
+    assert_eq!(ttl[0].name, "ttl_created_at");
+    assert!(matches!(
+        ttl[0].settings,
+        IndexSettings::Ttl { expire_after: 3600 }
+    ));
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn schema_is_generated_from_registered_models() {
+    let schema = DatabaseSchema::from_registered_models();
+    let names: Vec<&str> = schema
+        .collections
+        .iter()
+        .map(|collection| collection.name.as_str())
+        .collect();
+    assert_eq!(names, ["Dish", "PartOf"]);
+    assert!(schema.collections[1].is_edge_collection);
+    assert_eq!(schema.indexes.len(), 3);
+    assert!(schema.index("PartOf", "ttl_created_at").is_some());
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use aragog::integrity::{self, ForeignKey};
 use aragog::query::{Comparison, Query, QueryResult};
+use aragog::schema::DatabaseSchema;
 use aragog::{DatabaseRecord, ForeignLink, Link, Record};
 use std::borrow::Borrow;
 
@@ -31,6 +33,10 @@ impl ForeignLink<Order> for Dish {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Record)]
+#[edge_collection]
+pub struct PartOf {}
+
 impl Link<Dish> for Dish {
     fn link_query(&self) -> Query {
         Dish::query().filter(
@@ -60,7 +66,7 @@ async fn relation_work() -> Result<(), String> {
             name: "DishTest".to_string(),
             description: "relation Test".to_string(),
             price: 10,
-            order_id: order.key().clone(),
+            order_id: order.key().to_string(),
         },
         &connection,
     )
@@ -91,7 +97,7 @@ async fn foreign_key_relation_work() -> Result<(), String> {
             name: "DishTest".to_string(),
             description: "relation Test".to_string(),
             price: 10,
-            order_id: order.key().clone(),
+            order_id: order.key().to_string(),
         },
         &connection,
     )
@@ -102,3 +108,60 @@ async fn foreign_key_relation_work() -> Result<(), String> {
     common::expect_assert_eq(relation.key(), order.key())?;
     Ok(())
 }
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn integrity_check_works() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let schema = DatabaseSchema::load("./tests/schema.yaml").unwrap();
+    let foreign_keys = [ForeignKey::new::<Dish, Order>("order_id")];
+    let mut order = DatabaseRecord::create(
+        Order {
+            name: "Test".to_string(),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    let dish = DatabaseRecord::create(
+        Dish {
+            name: "DishTest".to_string(),
+            description: "integrity Test".to_string(),
+            price: 10,
+            order_id: order.key().to_string(),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    let edge = DatabaseRecord::link(&dish, &order, &connection, PartOf {})
+        .await
+        .unwrap();
+    let report = integrity::check_with_foreign_keys(&connection, &schema, &foreign_keys)
+        .await
+        .unwrap();
+    common::expect_assert(report.is_clean())?;
+
+    order.delete(&connection).await.unwrap();
+    let report = integrity::check_with_foreign_keys(&connection, &schema, &foreign_keys)
+        .await
+        .unwrap();
+    common::expect_assert_eq(report.dangling_edges.len(), 1)?;
+    common::expect_assert_eq(report.dangling_edges[0].id.as_str(), edge.id().as_ref())?;
+    common::expect_assert(report.dangling_edges[0].missing_to)?;
+    common::expect_assert(!report.dangling_edges[0].missing_from)?;
+    common::expect_assert_eq(report.broken_links.len(), 1)?;
+    common::expect_assert_eq(report.broken_links[0].id.as_str(), dish.id().as_ref())?;
+
+    let repaired = integrity::repair(&connection, &report.repair_actions())
+        .await
+        .unwrap();
+    common::expect_assert_eq(repaired, 2)?;
+    let report = integrity::check_with_foreign_keys(&connection, &schema, &foreign_keys)
+        .await
+        .unwrap();
+    common::expect_assert(report.is_clean())?;
+    Ok(())
+}
@@ -3,7 +3,7 @@ extern crate aragog;
 
 use serde::{Deserialize, Serialize};
 
-use aragog::transaction::{Transaction, TransactionOutput};
+use aragog::transaction::{Transaction, TransactionBuilder, TransactionOutput};
 use aragog::{DatabaseAccess, DatabaseRecord, Record};
 
 pub mod common;
@@ -243,6 +243,126 @@ mod safe_execute {
         }
     }
 
+    mod checkpoint {
+        use aragog::Error;
+
+        use super::*;
+
+        #[cfg(not(feature = "blocking"))]
+        async fn get_failing_result_after_checkpoint(
+            transaction: &Transaction,
+            doc: &User,
+        ) -> TransactionOutput<()> {
+            transaction
+                .safe_execute(|connection| async move {
+                    DatabaseRecord::create(doc.clone(), &connection).await?;
+                    DatabaseRecord::create(doc.clone(), &connection).await?;
+                    connection.checkpoint().await?;
+                    DatabaseRecord::create(doc.clone(), &connection).await?;
+                    Err(Error::default())
+                })
+                .await
+                .unwrap()
+        }
+
+        #[cfg(feature = "blocking")]
+        fn get_failing_result_after_checkpoint(
+            transaction: &Transaction,
+            doc: &User,
+        ) -> TransactionOutput<()> {
+            transaction
+                .safe_execute(|connection| {
+                    DatabaseRecord::create(doc.clone(), &connection)?;
+                    DatabaseRecord::create(doc.clone(), &connection)?;
+                    connection.checkpoint()?;
+                    DatabaseRecord::create(doc.clone(), &connection)?;
+                    Err(Error::default())
+                })
+                .unwrap()
+        }
+
+        #[cfg(not(feature = "blocking"))]
+        async fn get_result_with_clone_after_checkpoint(
+            transaction: &Transaction,
+            doc: &User,
+        ) -> TransactionOutput<()> {
+            transaction
+                .safe_execute(|connection| async move {
+                    let connection_clone = connection.clone();
+                    DatabaseRecord::create(doc.clone(), &connection).await?;
+                    connection.checkpoint().await?;
+                    DatabaseRecord::create(doc.clone(), &connection_clone).await?;
+                    Ok(())
+                })
+                .await
+                .unwrap()
+        }
+
+        #[cfg(feature = "blocking")]
+        fn get_result_with_clone_after_checkpoint(
+            transaction: &Transaction,
+            doc: &User,
+        ) -> TransactionOutput<()> {
+            transaction
+                .safe_execute(|connection| {
+                    let connection_clone = connection.clone();
+                    DatabaseRecord::create(doc.clone(), &connection)?;
+                    connection.checkpoint()?;
+                    DatabaseRecord::create(doc.clone(), &connection_clone)?;
+                    Ok(())
+                })
+                .unwrap()
+        }
+
+        #[maybe_async::test(
+            feature = "blocking",
+            async(all(not(feature = "blocking")), tokio::test)
+        )]
+        async fn checkpoint_continues_every_clone() {
+            let connection = common::setup_db().await;
+            let doc = User {
+                name: "Felix".to_string(),
+                description: "LM".to_string(),
+                email: "felix.maneville@qonfucius.team".to_string(),
+            };
+            let transaction = Transaction::new(&connection).await.unwrap();
+            let result = get_result_with_clone_after_checkpoint(&transaction, &doc).await;
+            assert!(result.is_committed());
+            let count = connection
+                .get_collection("User")
+                .unwrap()
+                .record_count()
+                .await
+                .unwrap();
+            assert_eq!(count, 2);
+        }
+
+        #[maybe_async::test(
+            feature = "blocking",
+            async(all(not(feature = "blocking")), tokio::test)
+        )]
+        async fn checkpoint_commits_previous_operations() {
+            let connection = common::setup_db().await;
+            let doc = User {
+                name: "Felix".to_string(),
+                description: "LM".to_string(),
+                email: "felix.maneville@qonfucius.team".to_string(),
+            };
+            let transaction = Transaction::new(&connection).await.unwrap();
+            let first_id = transaction.id().to_string();
+            let result = get_failing_result_after_checkpoint(&transaction, &doc).await;
+            assert!(result.is_aborted());
+            assert_ne!(transaction.id(), first_id);
+            let count = connection
+                .get_collection("User")
+                .unwrap()
+                .record_count()
+                .await
+                .unwrap();
+            assert_eq!(count, 2);
+        }
+    }
+
     mod query {
         use super::*;
 
@@ -276,5 +396,143 @@ mod safe_execute {
             assert_eq!(res.len(), 1);
             Ok(())
         }
+
+        #[cfg(not(feature = "blocking"))]
+        async fn find_created_user(
+            transaction: &Transaction,
+            doc: &User,
+        ) -> TransactionOutput<bool> {
+            transaction
+                .safe_execute(|connection| async move {
+                    let created = DatabaseRecord::create(doc.clone(), &connection).await?;
+                    let found =
+                        User::find_by_field("email", doc.email.as_str(), &connection).await?;
+                    assert_eq!(found.key(), created.key());
+                    Ok(User::exists_by("email", doc.email.as_str(), &connection).await)
+                })
+                .await
+                .unwrap()
+        }
+
+        #[cfg(feature = "blocking")]
+        fn find_created_user(transaction: &Transaction, doc: &User) -> TransactionOutput<bool> {
+            transaction
+                .safe_execute(|connection| {
+                    let created = DatabaseRecord::create(doc.clone(), &connection)?;
+                    let found = User::find_by_field("email", doc.email.as_str(), &connection)?;
+                    assert_eq!(found.key(), created.key());
+                    Ok(User::exists_by("email", doc.email.as_str(), &connection))
+                })
+                .unwrap()
+        }
+
+        #[maybe_async::test(
+            feature = "blocking",
+            async(all(not(feature = "blocking")), tokio::test)
+        )]
+        async fn field_lookups_run_in_the_transaction() {
+            let db_connection = common::setup_db().await;
+            let doc = User {
+                name: "Robert Surcouf".to_string(),
+                description: "Corsaire Français".to_string(),
+                email: "lerevenantmalouin@qonfucius.team".to_string(),
+            };
+            let transaction = Transaction::new(&db_connection).await.unwrap();
+            let result = find_created_user(&transaction, &doc).await;
+            assert!(result.is_committed());
+            assert!(result.unwrap());
+        }
+    }
+}
+
+mod manual {
+    use aragog::error::ArangoError;
+    use aragog::Error;
+
+    use super::*;
+
+    fn user() -> User {
+        User {
+            name: "Felix".to_string(),
+            description: "LM".to_string(),
+            email: "felix.maneville@qonfucius.team".to_string(),
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn commit_works() {
+        let connection = common::setup_db().await;
+        let transaction = Transaction::new(&connection).await.unwrap();
+        DatabaseRecord::create(user(), transaction.database_connection())
+            .await
+            .unwrap();
+        let collection = connection.get_collection("User").unwrap();
+        let count = collection.record_count().await.unwrap();
+        assert_eq!(count, 0);
+        transaction.commit().await.unwrap();
+        let count = collection.record_count().await.unwrap();
+        assert_eq!(count, 1);
+        // Transactions can be committed multiple times
+        transaction.commit().await.unwrap();
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn abort_works() {
+        let connection = common::setup_db().await;
+        let transaction = Transaction::new(&connection).await.unwrap();
+        DatabaseRecord::create(user(), transaction.database_connection())
+            .await
+            .unwrap();
+        transaction.abort().await.unwrap();
+        let collection = connection.get_collection("User").unwrap();
+        let count = collection.record_count().await.unwrap();
+        assert_eq!(count, 0);
+        // Aborted transactions can't be committed
+        let result = transaction.commit().await;
+        assert!(result.is_err());
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn restricted_collections_work() {
+        let connection = common::setup_db().await;
+        let transaction = TransactionBuilder::new()
+            .collections(vec!["User".to_string()])
+            .lock_timeout(1000)
+            .wait_for_sync()
+            .build(&connection)
+            .await
+            .unwrap();
+        DatabaseRecord::create(user(), transaction.database_connection())
+            .await
+            .unwrap();
+        let error = DatabaseRecord::create(
+            Dish {
+                name: "Pizza".to_string(),
+                price: 10,
+            },
+            transaction.database_connection(),
+        )
+        .await
+        .unwrap_err();
+        match error {
+            Error::ArangoError(db_error) => assert_eq!(
+                db_error.arango_error,
+                ArangoError::TransactionUnregisteredCollectionError
+            ),
+            _ => panic!("Wrong error retured"),
+        }
+        transaction.abort().await.unwrap();
+        let collection = connection.get_collection("User").unwrap();
+        let count = collection.record_count().await.unwrap();
+        assert_eq!(count, 0);
     }
 }
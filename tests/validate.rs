@@ -473,4 +473,206 @@ mod macros {
             }
         }
     }
+
+    mod cross_field_validations {
+        use super::*;
+
+        #[derive(Validate)]
+        #[validate(fields_equal("password", "password_confirmation"))]
+        struct Registration {
+            password: String,
+            password_confirmation: String,
+            #[validate(lesser_or_equal_field("max_age"))]
+            min_age: u8,
+            #[validate(greater_or_equal_field("min_age"))]
+            max_age: u8,
+            start_date: String,
+            #[validate(greater_than_field("start_date"))]
+            end_date: String,
+            #[validate(lesser_than_field("end_date"))]
+            checkpoint: String,
+            #[validate(equals_field("password"))]
+            password_repeat: String,
+        }
+
+        fn valid_registration() -> Registration {
+            Registration {
+                password: "secret".to_string(),
+                password_confirmation: "secret".to_string(),
+                min_age: 18,
+                max_age: 18,
+                start_date: "2021-01-01".to_string(),
+                end_date: "2021-02-01".to_string(),
+                checkpoint: "2021-01-15".to_string(),
+                password_repeat: "secret".to_string(),
+            }
+        }
+
+        #[test]
+        fn can_pass() {
+            valid_registration().validate().unwrap();
+        }
+
+        #[test]
+        fn can_fail_and_provide_message() {
+            let registration = Registration {
+                password_confirmation: "other".to_string(),
+                min_age: 20,
+                end_date: "2020-12-31".to_string(),
+                password_repeat: "other".to_string(),
+                ..valid_registration()
+            };
+            match registration.validate() {
+                Ok(()) => panic!("Validations should have failed"),
+                Err(Error::ValidationError(msg)) => {
+                    assert!(msg.contains("password must be equal to password_confirmation"));
+                    assert!(msg.contains("min_age '20' must be lower or equal to max_age '18'"));
+                    assert!(msg.contains("max_age '18' must be greater or equal to min_age '20'"));
+                    assert!(msg.contains(
+                        "end_date '2020-12-31' must be greater than start_date '2021-01-01'"
+                    ));
+                    assert!(msg.contains(
+                        "checkpoint '2021-01-15' must be lower than end_date '2020-12-31'"
+                    ));
+                    assert!(msg.contains("password_repeat must be equal to password"));
+                    assert!(!msg.contains("secret"));
+                }
+                Err(_) => panic!("Wrong error returned"),
+            }
+        }
+    }
+
+    mod custom_messages {
+        use super::*;
+
+        #[derive(Validate)]
+        #[validate(
+            fields_equal("password", "password_confirmation"),
+            message = "passwords don't match"
+        )]
+        struct User {
+            #[validate(min_length = 5)]
+            #[validate(min_length(8), message = "password is too weak")]
+            password: String,
+            password_confirmation: String,
+            #[validate_each(min_length = 3, message = "tags are too short")]
+            tags: Vec<String>,
+        }
+
+        #[test]
+        fn can_pass() {
+            User {
+                password: "a strong password".to_string(),
+                password_confirmation: "a strong password".to_string(),
+                tags: vec!["tag".to_string()],
+            }
+            .validate()
+            .unwrap();
+        }
+
+        #[test]
+        fn replaces_rule_messages() {
+            let user = User {
+                password: "weak".to_string(),
+                password_confirmation: "other".to_string(),
+                tags: vec!["a".to_string(), "b".to_string()],
+            };
+            match user.validate() {
+                Ok(()) => panic!("Validations should have failed"),
+                Err(Error::ValidationError(msg)) => {
+                    assert!(msg.contains("passwords don't match"));
+                    assert!(msg.contains("password is too weak"));
+                    // Rules without custom message keep the default message
+                    assert!(msg.contains("password 'weak' is too short, min length: 5"));
+                    assert!(!msg.contains("min length: 8"));
+                    assert_eq!(msg.matches("tags are too short").count(), 1);
+                }
+                Err(_) => panic!("Wrong error returned"),
+            }
+        }
+    }
+
+    mod enumeration_validations {
+        use super::*;
+
+        #[derive(Validate)]
+        struct Article {
+            #[validate(range(min = 1, max = 100))]
+            priority: i32,
+            #[validate(range(min = 0.0, max = 5.0))]
+            rating: f64,
+            #[validate(one_of("draft", "published"))]
+            status: String,
+            #[validate(not_one_of("admin", "root"))]
+            author: String,
+            #[validate_each(one_of(1, 2, 3))]
+            levels: Vec<u8>,
+            #[validate_each(not_one_of("spam"))]
+            tags: Vec<String>,
+        }
+
+        impl Default for Article {
+            fn default() -> Self {
+                Self {
+                    priority: 1,
+                    rating: 5.0,
+                    status: "draft".to_string(),
+                    author: "felix".to_string(),
+                    levels: vec![1, 3],
+                    tags: vec!["rust".to_string()],
+                }
+            }
+        }
+
+        #[test]
+        fn can_pass() {
+            Article::default().validate().unwrap();
+            Article {
+                priority: 100,
+                status: "published".to_string(),
+                ..Article::default()
+            }
+            .validate()
+            .unwrap();
+        }
+
+        #[test]
+        fn validates_range() {
+            let article = Article {
+                priority: 101,
+                rating: -0.5,
+                ..Article::default()
+            };
+            assert!(!article.is_valid());
+            let mut errors = Vec::new();
+            article.validations(&mut errors);
+            assert_eq!(
+                errors,
+                vec![
+                    "priority '101' must be between 1 and 100".to_string(),
+                    "rating '-0.5' must be between 0 and 5".to_string()
+                ]
+            );
+        }
+
+        #[test]
+        fn validates_allowed_values() {
+            let article = Article {
+                status: "deleted".to_string(),
+                author: "root".to_string(),
+                levels: vec![1, 4],
+                tags: vec!["rust".to_string(), "spam".to_string()],
+                ..Article::default()
+            };
+            let mut errors = Vec::new();
+            article.validations(&mut errors);
+            assert_eq!(errors.len(), 4);
+            assert!(
+                errors.contains(&"status 'deleted' must be one of: draft, published".to_string())
+            );
+            assert!(errors.contains(&"author 'root' is not allowed".to_string()));
+            assert!(errors.contains(&"levels '4' must be one of: 1, 2, 3".to_string()));
+            assert!(errors.contains(&"tags 'spam' is not allowed".to_string()));
+        }
+    }
 }